@@ -0,0 +1,32 @@
+//! Throws arbitrary templates at the parser and the synthesiser of the
+//! `pattern!` macro, which must return errors rather than panic or hang.
+//!
+//! Run with `cargo +nightly fuzz run pattern` in directory `spdlog-macros`.
+
+#![no_main]
+
+// A proc-macro crate cannot export its internals, so its modules are included
+// here instead.
+#[allow(dead_code)]
+#[path = "../../src/helper.rs"]
+mod helper;
+#[allow(dead_code)]
+#[path = "../../src/parse.rs"]
+mod parse;
+#[allow(dead_code)]
+#[path = "../../src/synthesis.rs"]
+mod synthesis;
+
+use libfuzzer_sys::fuzz_target;
+use proc_macro2::Span;
+use quote::ToTokens;
+use syn::LitStr;
+
+use crate::{parse::Pattern, synthesis::Synthesiser};
+
+fuzz_target!(|template: &str| {
+    let template = LitStr::new(template, Span::call_site());
+    if let Ok(pattern) = syn::parse2::<Pattern>(template.into_token_stream()) {
+        let _ = Synthesiser::with_builtin_formatters().synthesis(&pattern.template);
+    }
+});
@@ -12,40 +12,41 @@ use nom::{
 /// work inside the `nom::sequence::delimited()` parser.
 ///
 /// It skips nested brackets until it finds an extra unbalanced closing bracket.
-/// This function is very similar to `nom::bytes::complete::take_until(">")`,
-/// except it also takes nested brackets.
+/// Doubled brackets outside of nested brackets are escaped literals, which are
+/// skipped rather than counted.
+///
+/// For example, with `{` and `}`, it takes `{{a}}` from `{{a}}}`, as `{{` and
+/// `}}` are escaped literals, and the last `}` is the unbalanced one.
 pub(crate) fn take_until_unbalanced(
     opening_bracket: char,
     closing_bracket: char,
 ) -> impl Fn(&str) -> IResult<&str, &str> {
     move |i: &str| {
         let mut index = 0;
-        let mut bracket_counter = 0;
-        while let Some(n) = &i[index..].find(&[opening_bracket, closing_bracket][..]) {
+        let mut depth = 0_usize;
+        while let Some(n) = i[index..].find(&[opening_bracket, closing_bracket][..]) {
             index += n;
-            let mut it = i[index..].chars();
-            match it.next().unwrap_or_default() {
-                c if c == opening_bracket => {
-                    bracket_counter += 1;
-                    index += opening_bracket.len_utf8();
-                }
-                c if c == closing_bracket => {
-                    // Closing bracket.
-                    bracket_counter -= 1;
-                    index += closing_bracket.len_utf8();
-                }
-                // Can not happen.
-                _ => unreachable!(),
-            };
-            // We found the unmatched closing bracket.
-            if bracket_counter == -1 {
-                // We do not consume it.
-                index -= closing_bracket.len_utf8();
-                return Ok((&i[index..], &i[0..index]));
-            };
+            let rest = &i[index..];
+            let c = rest.chars().next().unwrap();
+            let len = c.len_utf8();
+
+            if depth == 0 && rest[len..].starts_with(c) {
+                // An escaped literal
+                index += len * 2;
+                continue;
+            }
+            if c == opening_bracket {
+                depth += 1;
+            } else if depth == 0 {
+                // We found the unmatched closing bracket, and we do not consume it.
+                return Ok((rest, &i[0..index]));
+            } else {
+                depth -= 1;
+            }
+            index += len;
         }
 
-        if bracket_counter == 0 {
+        if depth == 0 {
             Ok(("", i))
         } else {
             Err(Err::Error(Error::from_error_kind(i, ErrorKind::TakeUntil)))
@@ -95,6 +96,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn take_until_escaped() {
+        let parser = take_until_unbalanced('{', '}');
+        assert_eq!(parser("abc"), Ok(("", "abc")));
+        assert_eq!(parser("a{b}c}d"), Ok(("}d", "a{b}c")));
+        assert_eq!(parser("{{}d"), Ok(("}d", "{{")));
+        assert_eq!(parser("}}}d"), Ok(("}d", "}}")));
+        assert_eq!(parser("{a}}}"), Ok(("", "{a}}}")));
+        assert_eq!(parser("{{a}}}"), Ok(("}", "{{a}}")));
+        assert_eq!(parser("{a}}"), Ok(("}", "{a}")));
+        assert_eq!(
+            parser("{a"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "{a",
+                ErrorKind::TakeUntil
+            )))
+        );
+        assert_eq!(take_until_unbalanced('€', 'ü')("€€ü€x"), Ok(("ü€x", "€€")));
+        assert_eq!(
+            take_until_unbalanced('€', 'ü')("€€u€üüü"),
+            Ok(("", "€€u€üüü"))
+        );
+    }
+
     #[test]
     fn take_until_basic_usage() {
         let mut parser = delimited(tag("<"), take_until_unbalanced('<', '>'), tag(">"));
@@ -135,6 +135,9 @@ impl PatternTemplateLiteral {
 pub(crate) struct PatternTemplateFormatter {
     pub(crate) name: String,
     pub(crate) kind: PatternFormatterKind,
+    /// The argument following the name, e.g. `trunc(80)` in
+    /// `{payload:trunc(80)}`.
+    pub(crate) arg: Option<String>,
 }
 
 impl PatternTemplateFormatter {
@@ -155,18 +158,31 @@ impl PatternTemplateFormatter {
             ))),
         )));
 
-        nom::sequence::delimited(open_paren_parser, formatter_name_parser, close_paren_parser).map(
-            |name: &str| match name.strip_prefix('$') {
+        let formatter_arg_parser = nom::sequence::preceded(
+            nom::character::complete::char(':'),
+            nom::bytes::complete::is_not("{}"),
+        );
+
+        nom::sequence::delimited(
+            open_paren_parser,
+            formatter_name_parser.and(nom::combinator::opt(formatter_arg_parser)),
+            close_paren_parser,
+        )
+        .map(|(name, arg): (&str, Option<&str>)| {
+            let arg = arg.map(str::to_owned);
+            match name.strip_prefix('$') {
                 Some(custom_name) => Self {
                     name: custom_name.to_owned(),
                     kind: PatternFormatterKind::Custom,
+                    arg,
                 },
                 None => Self {
                     name: name.to_owned(),
                     kind: PatternFormatterKind::BuiltIn,
+                    arg,
                 },
-            },
-        )
+            }
+        })
     }
 }
 
@@ -319,14 +335,16 @@ mod tests {
                             }),
                             PatternTemplateToken::Formatter(PatternTemplateFormatter {
                                 name: String::from("name"),
-                                kind: PatternFormatterKind::BuiltIn
+                                kind: PatternFormatterKind::BuiltIn,
+                                arg: None
                             }),
                             PatternTemplateToken::Literal(PatternTemplateLiteral {
                                 literal: String::from("!"),
                             }),
                             PatternTemplateToken::Formatter(PatternTemplateFormatter {
                                 name: String::from("custom"),
-                                kind: PatternFormatterKind::Custom
+                                kind: PatternFormatterKind::Custom,
+                                arg: None
                             }),
                         ],
                     }
@@ -349,6 +367,36 @@ mod tests {
             );
         }
 
+        #[test]
+        fn test_parse_formatter_arg() {
+            assert_eq!(
+                parse_template_str(r#"{payload:trunc(80)} {$custom:a b}"#),
+                Ok((
+                    "",
+                    PatternTemplate {
+                        tokens: vec![
+                            PatternTemplateToken::Formatter(PatternTemplateFormatter {
+                                name: String::from("payload"),
+                                kind: PatternFormatterKind::BuiltIn,
+                                arg: Some(String::from("trunc(80)"))
+                            }),
+                            PatternTemplateToken::Literal(PatternTemplateLiteral {
+                                literal: String::from(" "),
+                            }),
+                            PatternTemplateToken::Formatter(PatternTemplateFormatter {
+                                name: String::from("custom"),
+                                kind: PatternFormatterKind::Custom,
+                                arg: Some(String::from("a b"))
+                            }),
+                        ],
+                    }
+                ))
+            );
+
+            assert!(parse_template_str(r#"{payload:}"#).is_err());
+            assert!(parse_template_str(r#"{payload:{level}}"#).is_err());
+        }
+
         #[test]
         fn test_parse_formatter_invalid_name() {
             assert!(parse_template_str(r#"hello {name{}!"#).is_err());
@@ -372,7 +420,8 @@ mod tests {
                             }),
                             PatternTemplateToken::Formatter(PatternTemplateFormatter {
                                 name: String::from("name"),
-                                kind: PatternFormatterKind::BuiltIn
+                                kind: PatternFormatterKind::BuiltIn,
+                                arg: None
                             }),
                             PatternTemplateToken::Literal(PatternTemplateLiteral {
                                 literal: String::from("}"),
@@ -425,14 +474,16 @@ mod tests {
                                         }),
                                         PatternTemplateToken::Formatter(PatternTemplateFormatter {
                                             name: String::from("b_pat"),
-                                            kind: PatternFormatterKind::BuiltIn
+                                            kind: PatternFormatterKind::BuiltIn,
+                                            arg: None
                                         }),
                                         PatternTemplateToken::Literal(PatternTemplateLiteral {
                                             literal: String::from(" "),
                                         }),
                                         PatternTemplateToken::Formatter(PatternTemplateFormatter {
                                             name: String::from("c_pat"),
-                                            kind: PatternFormatterKind::Custom
+                                            kind: PatternFormatterKind::Custom,
+                                            arg: None
                                         }),
                                         PatternTemplateToken::Literal(PatternTemplateLiteral {
                                             literal: String::from(" {escape}"),
@@ -450,5 +501,178 @@ mod tests {
         fn test_parse_style_range_nested() {
             assert!(parse_template_str(r#"hello {^ hello {^ world } }"#).is_err());
         }
+
+        #[test]
+        fn test_parse_style_range_unbalanced_escape() {
+            let style_range_of_literal = |literal: &str| PatternTemplate {
+                tokens: vec![PatternTemplateToken::StyleRange(
+                    PatternTemplateStyleRange {
+                        body: PatternTemplate {
+                            tokens: vec![PatternTemplateToken::Literal(PatternTemplateLiteral {
+                                literal: String::from(literal),
+                            })],
+                        },
+                    },
+                )],
+            };
+
+            assert_eq!(
+                parse_template_str(r#"{^{{}"#),
+                Ok(("", style_range_of_literal("{")))
+            );
+            assert_eq!(
+                parse_template_str(r#"{^}}}"#),
+                Ok(("", style_range_of_literal("}")))
+            );
+            assert_eq!(
+                parse_template_str(r#"{^a{{b}"#),
+                Ok(("", style_range_of_literal("a{b")))
+            );
+        }
+    }
+
+    // Deterministic randomized tests run with `cargo test`, complementing the
+    // `cargo-fuzz` target in `fuzz/fuzz_targets/pattern.rs`.
+    mod template_fuzzing {
+        use super::*;
+
+        const ITERATIONS: usize = 20000;
+
+        // A xorshift generator, deterministic so that failures are reproducible.
+        struct Rng(u64);
+
+        impl Rng {
+            fn next(&mut self) -> u64 {
+                self.0 ^= self.0 << 13;
+                self.0 ^= self.0 >> 7;
+                self.0 ^= self.0 << 17;
+                self.0
+            }
+
+            fn below(&mut self, n: usize) -> usize {
+                (self.next() % n as u64) as usize
+            }
+
+            fn pick(&mut self, chars: &[char]) -> char {
+                chars[self.below(chars.len())]
+            }
+
+            fn string(&mut self, chars: &[char], min_len: usize, max_len: usize) -> String {
+                let len = min_len + self.below(max_len - min_len + 1);
+                (0..len).map(|_| self.pick(chars)).collect()
+            }
+        }
+
+        const ARBITRARY_CHARS: &[char] = &[
+            '{', '{', '}', '}', '^', '$', ':', '=', '_', 'a', 'Z', '0', ' ', 'é', '€', '\n',
+        ];
+        const LITERAL_CHARS: &[char] = &['{', '}', '^', '$', ':', '_', 'a', '0', ' ', 'é', '€'];
+        const NAME_HEAD_CHARS: &[char] = &['a', 'z', 'A', '_'];
+        const NAME_TAIL_CHARS: &[char] = &['a', 'Z', '0', '9', '_'];
+        const ARG_CHARS: &[char] = &['a', '0', '=', ':', ' ', '^', '$', 'é'];
+
+        fn parse_template_str(template: &str) -> nom::IResult<&str, PatternTemplate> {
+            PatternTemplate::parser().parse(template)
+        }
+
+        fn gen_template(rng: &mut Rng, allow_style_range: bool) -> PatternTemplate {
+            let mut tokens: Vec<PatternTemplateToken> = vec![];
+            for _ in 0..rng.below(6) {
+                let token = match rng.below(if allow_style_range { 3 } else { 2 }) {
+                    0 => {
+                        let mut literal = rng.string(LITERAL_CHARS, 1, 6);
+                        // A `}` right after a style range is ambiguous with the end of it
+                        if let Some(PatternTemplateToken::StyleRange(_)) = tokens.last() {
+                            literal = literal.replace('}', "{");
+                        }
+                        PatternTemplateToken::Literal(PatternTemplateLiteral { literal })
+                    }
+                    1 => {
+                        let mut name = rng.pick(NAME_HEAD_CHARS).to_string();
+                        name.push_str(&rng.string(NAME_TAIL_CHARS, 0, 4));
+                        let kind = if rng.below(2) == 0 {
+                            PatternFormatterKind::BuiltIn
+                        } else {
+                            PatternFormatterKind::Custom
+                        };
+                        let arg = (rng.below(2) == 0).then(|| rng.string(ARG_CHARS, 1, 4));
+                        PatternTemplateToken::Formatter(PatternTemplateFormatter {
+                            name,
+                            kind,
+                            arg,
+                        })
+                    }
+                    _ => PatternTemplateToken::StyleRange(PatternTemplateStyleRange {
+                        body: gen_template(rng, false),
+                    }),
+                };
+
+                // Adjacent literals are parsed as a single one
+                match (tokens.last_mut(), token) {
+                    (
+                        Some(PatternTemplateToken::Literal(last)),
+                        PatternTemplateToken::Literal(PatternTemplateLiteral { literal }),
+                    ) => last.literal.push_str(&literal),
+                    (_, token) => tokens.push(token),
+                }
+            }
+            PatternTemplate { tokens }
+        }
+
+        fn render_template(template: &PatternTemplate) -> String {
+            let mut rendered = String::new();
+            for token in &template.tokens {
+                match token {
+                    PatternTemplateToken::Literal(literal) => {
+                        rendered.push_str(&literal.literal.replace('{', "{{").replace('}', "}}"))
+                    }
+                    PatternTemplateToken::Formatter(formatter) => {
+                        rendered.push('{');
+                        if formatter.kind == PatternFormatterKind::Custom {
+                            rendered.push('$');
+                        }
+                        rendered.push_str(&formatter.name);
+                        if let Some(arg) = &formatter.arg {
+                            rendered.push(':');
+                            rendered.push_str(arg);
+                        }
+                        rendered.push('}');
+                    }
+                    PatternTemplateToken::StyleRange(style_range) => {
+                        rendered.push_str("{^");
+                        rendered.push_str(&render_template(&style_range.body));
+                        rendered.push('}');
+                    }
+                }
+            }
+            rendered
+        }
+
+        #[test]
+        fn test_arbitrary_input() {
+            let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+            for _ in 0..ITERATIONS {
+                let input = rng.string(ARBITRARY_CHARS, 0, 24);
+                // Must not panic, and must consume the whole input if succeeded
+                if let Ok((remaining, _)) = parse_template_str(&input) {
+                    assert_eq!(remaining, "", "input: {:?}", input);
+                }
+            }
+        }
+
+        #[test]
+        fn test_round_trip() {
+            let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+            for _ in 0..ITERATIONS {
+                let template = gen_template(&mut rng, true);
+                let rendered = render_template(&template);
+                assert_eq!(
+                    parse_template_str(&rendered),
+                    Ok(("", template)),
+                    "rendered: {:?}",
+                    rendered
+                );
+            }
+        }
     }
 }
@@ -89,6 +89,7 @@ impl Synthesiser {
             ["module_path"] => SourceModulePath,
             ["logger"] => LoggerName,
             ["payload"] => Payload,
+            ["fields"] => Fields,
             ["pid"] => ProcessId,
             ["tid"] => ThreadId,
             ["eol"] => Eol,
@@ -199,13 +200,164 @@ impl Synthesiser {
                 formatter_token.kind,
             )),
         }?;
-        let formatter_factory_path = &formatter.factory_path;
 
-        let stream = quote::quote!( #formatter_factory_path () );
+        let stream = match &formatter_token.arg {
+            None => {
+                let formatter_factory_path = &formatter.factory_path;
+                quote::quote!( #formatter_factory_path () )
+            }
+            Some(arg) => match formatter.kind {
+                PatternFormatterKind::BuiltIn => {
+                    Self::build_builtin_formatter_with_arg_stream(&formatter_token.name, arg)?
+                }
+                PatternFormatterKind::Custom => {
+                    return Err(SynthesisError::UnexpectedPatternArgument(
+                        formatter_token.name.clone(),
+                        formatter.kind,
+                    ))
+                }
+            },
+        };
         let factory_call_expr = syn::parse2(stream).unwrap();
         Ok(Expr::Call(factory_call_expr))
     }
 
+    // Arguments of built-in patterns are validated here, so that invalid ones are
+    // compile errors.
+    fn build_builtin_formatter_with_arg_stream(
+        name: &str,
+        arg: &str,
+    ) -> Result<TokenStream, SynthesisError> {
+        match name {
+            "payload" if arg == "hex" => Ok(quote::quote!(
+                ::spdlog::formatter::__pattern::Payload::__hex()
+            )),
+            "payload" => {
+                let max_chars = arg
+                    .strip_prefix("trunc(")
+                    .and_then(|rest| rest.strip_suffix(')'))
+                    .and_then(|max_chars| max_chars.trim().parse::<usize>().ok())
+                    .ok_or_else(|| {
+                        SynthesisError::InvalidPatternArgument(
+                            name.to_owned(),
+                            arg.to_owned(),
+                            "`trunc(N)` or `hex`",
+                        )
+                    })?;
+                Ok(quote::quote!(
+                    ::spdlog::formatter::__pattern::Payload::__truncated(#max_chars)
+                ))
+            }
+            "level" => match arg {
+                "lower" => Ok(quote::quote!(
+                    ::spdlog::formatter::__pattern::Level::default()
+                )),
+                "upper" => Ok(quote::quote!(
+                    ::spdlog::formatter::__pattern::UpperLevel::default()
+                )),
+                "short" => Ok(quote::quote!(
+                    ::spdlog::formatter::__pattern::ShortLevel::default()
+                )),
+                _ => Err(SynthesisError::InvalidPatternArgument(
+                    name.to_owned(),
+                    arg.to_owned(),
+                    "`upper`, `lower` or `short`",
+                )),
+            },
+            "source" => match arg {
+                "full" => Ok(quote::quote!(
+                    ::spdlog::formatter::__pattern::Source::default()
+                )),
+                "basename" => Ok(quote::quote!(
+                    ::spdlog::formatter::__pattern::Source::__basename()
+                )),
+                _ => {
+                    let prefix = arg
+                        .strip_prefix("strip_prefix=")
+                        .filter(|prefix| !prefix.is_empty())
+                        .ok_or_else(|| {
+                            SynthesisError::InvalidPatternArgument(
+                                name.to_owned(),
+                                arg.to_owned(),
+                                "`full`, `basename` or `strip_prefix=PREFIX`",
+                            )
+                        })?;
+                    Ok(quote::quote!(
+                        ::spdlog::formatter::__pattern::Source::__strip_prefix(#prefix)
+                    ))
+                }
+            },
+            "logger" => {
+                let parse_count = |prefix: &str| {
+                    arg.strip_prefix(prefix)
+                        .and_then(|rest| rest.strip_suffix(')'))
+                        .and_then(|count| count.trim().parse::<usize>().ok())
+                };
+                if arg == "abbrev" {
+                    Ok(quote::quote!(
+                        ::spdlog::formatter::__pattern::LoggerName::__abbreviated(0)
+                    ))
+                } else if let Some(max_chars) = parse_count("abbrev(") {
+                    Ok(quote::quote!(
+                        ::spdlog::formatter::__pattern::LoggerName::__abbreviated(#max_chars)
+                    ))
+                } else if let Some(count) = parse_count("short(").filter(|count| *count != 0) {
+                    Ok(quote::quote!(
+                        ::spdlog::formatter::__pattern::LoggerName::__last_segments(#count)
+                    ))
+                } else {
+                    Err(SynthesisError::InvalidPatternArgument(
+                        name.to_owned(),
+                        arg.to_owned(),
+                        "`abbrev`, `abbrev(N)` or `short(N)` with N > 0",
+                    ))
+                }
+            }
+            "unix_timestamp" => {
+                let unit_nanos: u128 = match arg {
+                    "ms" => 1_000_000,
+                    "us" => 1_000,
+                    "ns" => 1,
+                    _ => {
+                        return Err(SynthesisError::InvalidPatternArgument(
+                            name.to_owned(),
+                            arg.to_owned(),
+                            "`ms`, `us` or `ns`",
+                        ))
+                    }
+                };
+                Ok(quote::quote!(
+                    ::spdlog::formatter::__pattern::UnixTimestamp::__with_unit_nanos(#unit_nanos)
+                ))
+            }
+            "fields" => {
+                let (mut mdc_only, mut json) = (false, false);
+                for option in arg.split(',') {
+                    match option.trim() {
+                        "all" => mdc_only = false,
+                        "mdc" => mdc_only = true,
+                        "logfmt" => json = false,
+                        "json" => json = true,
+                        _ => {
+                            return Err(SynthesisError::InvalidPatternArgument(
+                                name.to_owned(),
+                                arg.to_owned(),
+                                "`all`, `mdc`, `logfmt` or `json`",
+                            ))
+                        }
+                    }
+                }
+                Ok(quote::quote!(
+                    ::spdlog::formatter::__pattern::Fields::__new(#mdc_only, #json)
+                ))
+            }
+            _ => Err(SynthesisError::UnexpectedPatternArgument(
+                name.to_owned(),
+                PatternFormatterKind::BuiltIn,
+            )),
+        }
+    }
+
     fn build_style_range_pattern_creation_expr(&self, body: Expr) -> Result<Expr, SynthesisError> {
         let style_range_pattern_new_path: Path =
             syn::parse_str("::spdlog::formatter::__pattern::StyleRange::new").unwrap();
@@ -268,6 +420,8 @@ impl Error for ConflictFormatterError {}
 pub(crate) enum SynthesisError {
     BuiltinPatternUsedAsCustomPattern(String),
     UnknownFormatterName(String, PatternFormatterKind),
+    UnexpectedPatternArgument(String, PatternFormatterKind),
+    InvalidPatternArgument(String, String, &'static str),
     MultipleStyleRange,
 }
 
@@ -291,6 +445,17 @@ impl Display for SynthesisError {
                     name
                 ),
             },
+            Self::UnexpectedPatternArgument(name, kind) => match kind {
+                Kind::BuiltIn => write!(f, "built-in pattern '{}' does not take an argument", name),
+                Kind::Custom => write!(f, "custom pattern '{}' does not take an argument", name),
+            },
+            Self::InvalidPatternArgument(name, arg, expected) => {
+                write!(
+                    f,
+                    "invalid argument '{}' for pattern '{}', expected {}",
+                    arg, name, expected
+                )
+            }
             Self::MultipleStyleRange => {
                 write!(f, "multiple style ranges are not currently supported")
             }
@@ -0,0 +1,153 @@
+#![feature(test)]
+
+extern crate test;
+
+mod common;
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+use spdlog::{
+    formatter::{pattern, FullFormatter, PatternFormatter},
+    prelude::*,
+    sink::*,
+};
+use test::Bencher;
+
+include!(concat!(
+    env!("OUT_DIR"),
+    "/test_utils/common_for_integration_test.rs"
+));
+use test_utils::*;
+
+// Counts allocations and reallocations of all threads, including the ones done
+// by the benchmark harness itself, so the results are approximate.
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+// The harness can't report custom metrics, so the number of allocations per
+// iteration is printed to `stderr`.
+fn bench_allocations(bencher: &mut Bencher, name: &str, sink: Arc<dyn Sink>) {
+    bench_allocations_with(
+        bencher,
+        name,
+        sink,
+        |logger, _| info!(logger: logger, bench_log_message!()),
+    );
+}
+
+fn bench_allocations_with(
+    bencher: &mut Bencher,
+    name: &str,
+    sink: Arc<dyn Sink>,
+    log: impl Fn(&Logger, usize),
+) {
+    sink.set_error_handler(Some(|err| panic!("an error occurred: {err}")));
+    let logger = build_test_logger(|b| b.sink(sink));
+
+    let mut iters = 0_usize;
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    bencher.iter(|| {
+        iters += 1;
+        log(&logger, iters)
+    });
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+
+    eprintln!(
+        "{name}: {:.2} allocations/iter",
+        allocations as f64 / iters as f64
+    );
+}
+
+fn discarding_sink(formatter: Box<dyn spdlog::formatter::Formatter>) -> Arc<dyn Sink> {
+    Arc::new(
+        WriteSink::builder()
+            .target(std::io::sink())
+            .formatter(formatter)
+            .build()
+            .unwrap(),
+    )
+}
+
+#[bench]
+fn bench_1_null_sink(bencher: &mut Bencher) {
+    bench_allocations(bencher, "null_sink", Arc::new(NullSink::new()));
+}
+
+#[bench]
+fn bench_2_full_formatter(bencher: &mut Bencher) {
+    let sink = discarding_sink(Box::new(FullFormatter::new()));
+    bench_allocations(bencher, "full_formatter", sink);
+}
+
+#[bench]
+fn bench_3_pattern_formatter(bencher: &mut Bencher) {
+    let sink = discarding_sink(Box::new(PatternFormatter::new(pattern!(
+        "[{date} {time}.{millisecond}] [{level}] [{source}] {payload}{eol}"
+    ))));
+    bench_allocations(bencher, "pattern_formatter", sink);
+}
+
+#[cfg(feature = "multi-thread")]
+#[bench]
+fn bench_4_null_sink_async(bencher: &mut Bencher) {
+    let thread_pool = Arc::new(spdlog::ThreadPool::builder().build().unwrap());
+    let sink = Arc::new(
+        AsyncPoolSink::builder()
+            .thread_pool(thread_pool)
+            .sink(Arc::new(NullSink::new()))
+            .build()
+            .unwrap(),
+    );
+    bench_allocations(bencher, "null_sink_async", sink);
+}
+
+#[cfg(feature = "multi-thread")]
+#[bench]
+fn bench_5_null_sink_async_batch(bencher: &mut Bencher) {
+    let sink = Arc::new(
+        AsyncBatchSink::builder()
+            .sink(Arc::new(NullSink::new()))
+            .build()
+            .unwrap(),
+    );
+    bench_allocations(bencher, "null_sink_async_batch", sink);
+}
+
+// The payload is formatted into a buffer reused by the logging thread, without
+// allocating a `String` for each record.
+#[bench]
+fn bench_6_full_formatter_format_args(bencher: &mut Bencher) {
+    let sink = discarding_sink(Box::new(FullFormatter::new()));
+    bench_allocations_with(
+        bencher,
+        "full_formatter_format_args",
+        sink,
+        |logger, n| info!(logger: logger, "x={}", n),
+    );
+}
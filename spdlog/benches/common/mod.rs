@@ -2,6 +2,7 @@ use std::{env, fs, path::PathBuf};
 
 use once_cell::sync::Lazy;
 
+#[allow(dead_code)]
 pub static BENCH_LOGS_PATH: Lazy<PathBuf> = Lazy::new(|| {
     let path = env::current_exe()
         .unwrap()
@@ -5,7 +5,7 @@ extern crate test;
 use std::{cell::RefCell, sync::Arc};
 
 use spdlog::{
-    formatter::{pattern, Formatter, FullFormatter, Pattern, PatternFormatter},
+    formatter::{self, pattern, Formatter, FullFormatter, Pattern, PatternFormatter, TimeCache},
     prelude::*,
     sink::Sink,
     Record, StringBuf,
@@ -103,6 +103,76 @@ fn bench_2_full_pattern(bencher: &mut Bencher) {
     bench_pattern(bencher, pattern)
 }
 
+// The same pattern as `bench_2_full_pattern`, but each part is dispatched
+// dynamically, as a pattern built at runtime would be. `pattern!` expands to a
+// tuple of concrete pattern types instead, which is monomorphized.
+#[bench]
+fn bench_2_full_pattern_dyn(bencher: &mut Bencher) {
+    let pattern: Vec<Arc<dyn Pattern>> = vec![
+        Arc::new(pattern!("[")),
+        Arc::new(pattern!("{date}")),
+        Arc::new(pattern!(" ")),
+        Arc::new(pattern!("{time}")),
+        Arc::new(pattern!(".")),
+        Arc::new(pattern!("{millisecond}")),
+        Arc::new(pattern!("] [")),
+        Arc::new(pattern!("{level}")),
+        Arc::new(pattern!("] ")),
+        Arc::new(pattern!("{payload}")),
+        Arc::new(pattern!("{eol}")),
+    ];
+
+    bench_pattern(bencher, pattern)
+}
+
+fn bench_time_cache(bencher: &mut Bencher, policy: TimeCache) {
+    if policy == TimeCache::Manual {
+        formatter::refresh_time_cache();
+    }
+    bench_formatter(bencher, FullFormatter::new().time_cache(policy));
+}
+
+#[bench]
+fn bench_3_time_cache_per_second(bencher: &mut Bencher) {
+    bench_time_cache(bencher, TimeCache::PerSecond)
+}
+
+#[bench]
+fn bench_4_time_cache_none(bencher: &mut Bencher) {
+    bench_time_cache(bencher, TimeCache::None)
+}
+
+#[bench]
+fn bench_5_time_cache_manual(bencher: &mut Bencher) {
+    bench_time_cache(bencher, TimeCache::Manual)
+}
+
+// Representative patterns in real-world configurations.
+
+#[bench]
+fn bench_6_pattern_with_source(bencher: &mut Bencher) {
+    bench_pattern(
+        bencher,
+        pattern!("[{date} {time}.{millisecond}] [{level}] [{source}] {payload}{eol}"),
+    )
+}
+
+#[bench]
+fn bench_7_pattern_with_logger_and_tid(bencher: &mut Bencher) {
+    bench_pattern(
+        bencher,
+        pattern!("{datetime} {level_short} {logger} [{tid}] {payload}{eol}"),
+    )
+}
+
+#[bench]
+fn bench_8_pattern_styled(bencher: &mut Bencher) {
+    bench_pattern(
+        bencher,
+        pattern!("[{time}] [{^{level:upper}}] {module_path}: {payload}{eol}"),
+    )
+}
+
 #[bench]
 fn bench_weekday_name(bencher: &mut Bencher) {
     bench_pattern(bencher, pattern!("{weekday_name}"))
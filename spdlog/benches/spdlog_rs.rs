@@ -4,7 +4,15 @@ extern crate test;
 
 mod common;
 
-use std::{fs, path::PathBuf, sync::Arc};
+use std::{
+    fs,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+};
 
 use once_cell::sync::Lazy;
 use spdlog::{
@@ -99,6 +107,20 @@ fn bench_file_inner(bencher: &mut Bencher, mode: Mode) {
     bench_any(bencher, mode, sink);
 }
 
+// Writes to `stderr`, redirect it to a pipe or `/dev/null` to benchmark the
+// non-terminal case, e.g. `cargo +nightly bench --bench spdlog_rs std_stream
+// --features multi-thread 2>/dev/null`.
+fn bench_std_stream_inner(bencher: &mut Bencher, flush_policy: FlushPolicy) {
+    let sink = Arc::new(
+        StdStreamSink::builder()
+            .std_stream(StdStream::Stderr)
+            .flush_policy(flush_policy)
+            .build()
+            .unwrap(),
+    );
+    bench_any(bencher, Mode::Sync, sink);
+}
+
 fn bench_rotating_inner(bencher: &mut Bencher, rotation_policy: RotationPolicy) {
     let sink = Arc::new(
         RotatingFileSink::builder()
@@ -142,3 +164,104 @@ fn bench_5_level_off(bencher: &mut Bencher) {
 
     bencher.iter(|| info!(logger: logger, bench_log_message!()))
 }
+
+#[bench]
+fn bench_6_std_stream_flush_every_record(bencher: &mut Bencher) {
+    bench_std_stream_inner(bencher, FlushPolicy::EveryRecord);
+}
+
+#[bench]
+fn bench_7_std_stream_buffered(bencher: &mut Bencher) {
+    bench_std_stream_inner(bencher, FlushPolicy::Buffered);
+}
+
+// Measures the overhead of the logging macro, payload formatting and
+// dispatching to sinks, excluding the cost of any actual sink.
+#[bench]
+fn bench_8_null_sink(bencher: &mut Bencher) {
+    bench_any(bencher, Mode::Sync, Arc::new(NullSink::new()));
+}
+
+fn discarding_sinks() -> [Arc<dyn Sink>; 2] {
+    let sink = || -> Arc<dyn Sink> {
+        Arc::new(
+            WriteSink::builder()
+                .target(std::io::sink())
+                .error_handler(|err| panic!("an error occurred: {err}"))
+                .build()
+                .unwrap(),
+        )
+    };
+    [sink(), sink()]
+}
+
+// Each sink formats records by itself.
+#[bench]
+fn bench_9_two_sinks(bencher: &mut Bencher) {
+    let logger = build_test_logger(|b| b.sinks(discarding_sinks()));
+
+    bencher.iter(|| info!(logger: logger, bench_log_message!()))
+}
+
+// Records are formatted once and written to both sinks.
+#[bench]
+fn bench_10_tee_two_sinks(bencher: &mut Bencher) {
+    let sink = Arc::new(
+        TeeSink::builder()
+            .sinks(discarding_sinks())
+            .build()
+            .unwrap(),
+    );
+    bench_any(bencher, Mode::Sync, sink);
+}
+
+// Measures the cost of enqueuing a record to the thread pool, the worker
+// thread does nothing with it.
+#[bench]
+fn bench_11_null_sink_async(bencher: &mut Bencher) {
+    bench_any(bencher, Mode::Async, Arc::new(NullSink::new()));
+}
+
+// Measures the latency of logging to a file sink while other threads are
+// logging to the same sink at the same time.
+#[bench]
+fn bench_12_file_contention(bencher: &mut Bencher) {
+    const CONTENDING_THREADS: usize = 3;
+
+    let sink = Arc::new(
+        FileSink::builder()
+            .path(Mode::Sync.path("file_contention"))
+            .truncate(true)
+            .error_handler(|err| panic!("an error occurred: {err}"))
+            .build()
+            .unwrap(),
+    );
+    let logger = build_test_logger(|b| b.error_handler(Mode::Sync.error_handler()).sink(sink));
+    let stop = AtomicBool::new(false);
+
+    thread::scope(|scope| {
+        for _ in 0..CONTENDING_THREADS {
+            scope.spawn(|| {
+                while !stop.load(Ordering::Relaxed) {
+                    info!(logger: logger, bench_log_message!());
+                }
+            });
+        }
+
+        bencher.iter(|| info!(logger: logger, bench_log_message!()));
+        stop.store(true, Ordering::Relaxed);
+    });
+}
+
+// Compared with `bench_1_file`, measures the cost of locking the file.
+#[bench]
+fn bench_13_single_threaded_file(bencher: &mut Bencher) {
+    let sink = Arc::new(
+        SingleThreadedFileSink::builder()
+            .path(Mode::Sync.path("single_threaded_file"))
+            .truncate(true)
+            .build()
+            .unwrap(),
+    );
+    bench_any(bencher, Mode::Sync, sink);
+}
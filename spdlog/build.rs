@@ -11,6 +11,7 @@ use rustc_version::{version_meta, Channel};
 fn main() -> Result<(), Box<dyn Error>> {
     set_cfg_channel()?;
     generate_code_test_utils()?;
+    build_native_shims()?;
     Ok(())
 }
 
@@ -22,6 +23,9 @@ fn set_cfg_channel() -> Result<(), Box<dyn Error>> {
         Channel::Nightly => "CHANNEL_NIGHTLY",
         Channel::Dev => "CHANNEL_DEV",
     };
+    println!(
+        "cargo:rustc-check-cfg=cfg(CHANNEL_STABLE, CHANNEL_BETA, CHANNEL_NIGHTLY, CHANNEL_DEV)"
+    );
     println!("cargo:rustc-cfg={}", channel);
     Ok(())
 }
@@ -59,6 +63,23 @@ fn generate_code_test_utils() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+// Build C shims for platform APIs that are only available as C macros
+#[cfg(feature = "native")]
+fn build_native_shims() -> Result<(), Box<dyn Error>> {
+    if env::var("CARGO_CFG_TARGET_VENDOR")? == "apple" {
+        println!("cargo:rerun-if-changed=src/sink/os_log_sink.c");
+        cc::Build::new()
+            .file("src/sink/os_log_sink.c")
+            .compile("spdlog_rs_os_log");
+    }
+    Ok(())
+}
+
+#[cfg(not(feature = "native"))]
+fn build_native_shims() -> Result<(), Box<dyn Error>> {
+    Ok(())
+}
+
 fn read_code(path: impl AsRef<Path>) -> Result<String, Box<dyn Error>> {
     println!("cargo:rerun-if-changed={}", path.as_ref().display());
     Ok(fs::read_to_string(path)?)
@@ -0,0 +1,104 @@
+use std::{
+    io::{self, Write},
+    sync::{Arc, Mutex},
+};
+
+use spdlog::{
+    formatter::{FmtExtraInfo, Formatter, FullFormatter},
+    prelude::*,
+    sink::Sink,
+    ErrorHandler, Record, StringBuf,
+};
+
+// A sink writing to stdout, rendering the style range of the formatted text in
+// bold, regardless of the level.
+struct BoldSink {
+    level_filter: Mutex<LevelFilter>,
+    formatter: Mutex<Box<dyn Formatter>>,
+}
+
+impl BoldSink {
+    fn new() -> Self {
+        Self {
+            level_filter: Mutex::new(LevelFilter::All),
+            formatter: Mutex::new(Box::new(FullFormatter::new())),
+        }
+    }
+
+    // Shared by `log` and `write_formatted`.
+    fn write(&self, formatted: &StringBuf, extra_info: &FmtExtraInfo) -> spdlog::Result<()> {
+        let text = formatted.as_str();
+        let styled = match extra_info.style_range() {
+            Some(range) => format!(
+                "{}\x1b[1m{}\x1b[m{}",
+                &text[..range.start],
+                &text[range.clone()],
+                &text[range.end..]
+            ),
+            None => text.to_string(),
+        };
+        io::stdout()
+            .write_all(styled.as_bytes())
+            .map_err(spdlog::Error::WriteRecord)
+    }
+}
+
+impl Sink for BoldSink {
+    fn log(&self, record: &Record) -> spdlog::Result<()> {
+        if !self.should_log(record.level()) {
+            return Ok(());
+        }
+
+        // The formatter returns the `FmtExtraInfo` along with the formatted text
+        let mut formatted = StringBuf::new();
+        let extra_info = self
+            .formatter
+            .lock()
+            .unwrap()
+            .format(record, &mut formatted)?;
+        self.write(&formatted, &extra_info)
+    }
+
+    // Called by combined sinks (e.g. `TeeSink`) that have formatted the record
+    // already
+    fn write_formatted(
+        &self,
+        record: &Record,
+        formatted: &StringBuf,
+        extra_info: &FmtExtraInfo,
+    ) -> spdlog::Result<()> {
+        if !self.should_log(record.level()) {
+            return Ok(());
+        }
+
+        self.write(formatted, extra_info)
+    }
+
+    fn flush(&self) -> spdlog::Result<()> {
+        io::stdout().flush().map_err(spdlog::Error::FlushBuffer)
+    }
+
+    fn level_filter(&self) -> LevelFilter {
+        *self.level_filter.lock().unwrap()
+    }
+
+    fn set_level_filter(&self, level_filter: LevelFilter) {
+        *self.level_filter.lock().unwrap() = level_filter;
+    }
+
+    fn set_formatter(&self, formatter: Box<dyn Formatter>) {
+        *self.formatter.lock().unwrap() = formatter;
+    }
+
+    fn set_error_handler(&self, _handler: Option<ErrorHandler>) {}
+}
+
+fn main() -> Result<(), spdlog::Error> {
+    let bold_sink: Arc<BoldSink> = Arc::new(BoldSink::new());
+    let logger: Arc<Logger> = Arc::new(Logger::builder().sink(bold_sink).build()?);
+
+    // The level is rendered in bold, since it's the style range of `FullFormatter`
+    info!(logger: logger, "hello custom sink");
+
+    Ok(())
+}
@@ -0,0 +1,149 @@
+//! Provides stuff related to logging binary data.
+
+use std::fmt::{self, Write};
+
+/// Formats bytes as hexadecimal digits, two digits per byte.
+///
+/// Payloads of records are always valid UTF-8, as they are formatted into
+/// [`StringBuf`] and exposed as `&str` by [`Record::payload`], so raw bytes
+/// that may not be valid UTF-8 (e.g. a protocol frame) cannot be embedded in
+/// a formatted payload as they are. Wrap them in `Hex` to embed them
+/// losslessly.
+///
+/// To log the bytes alone as the payload, use [`log_bytes!`] instead, which
+/// keeps the raw bytes in the record for sinks and for the pattern
+/// `{payload:hex}`.
+///
+/// The output is lowercase for `{}` and `{:x}`, and uppercase for `{:X}`. The
+/// alternate flag (`{:#}`) separates bytes by spaces.
+///
+/// If the bytes are mostly text, [`String::from_utf8_lossy`] can be used
+/// instead, which replaces invalid sequences with `U+FFFD`.
+///
+/// # Examples
+///
+/// ```
+/// use spdlog::{info, Hex};
+///
+/// let frame = [0xde, 0xad, 0xbe, 0xef, 0xff];
+///
+/// info!("received frame: {}", Hex::new(&frame));
+/// // received frame: deadbeefff
+///
+/// info!("received frame: {:#X}", Hex::new(&frame));
+/// // received frame: DE AD BE EF FF
+///
+/// info!("received text: {}", String::from_utf8_lossy(b"ok\xff"));
+/// // received text: ok�
+/// ```
+///
+/// [`StringBuf`]: crate::StringBuf
+/// [`Record::payload`]: crate::Record::payload
+/// [`log_bytes!`]: crate::log_bytes
+#[derive(Clone, Copy, Debug)]
+pub struct Hex<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Hex<'a> {
+    /// Constructs a `Hex`.
+    #[must_use]
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+
+    fn fmt_impl(&self, f: &mut fmt::Formatter, digits: &[u8; 16]) -> fmt::Result {
+        for (i, byte) in self.bytes.iter().enumerate() {
+            if f.alternate() && i != 0 {
+                f.write_char(' ')?;
+            }
+            f.write_char(digits[(byte >> 4) as usize] as char)?;
+            f.write_char(digits[(byte & 0xf) as usize] as char)?;
+        }
+        Ok(())
+    }
+}
+
+const LOWER_DIGITS: &[u8; 16] = b"0123456789abcdef";
+const UPPER_DIGITS: &[u8; 16] = b"0123456789ABCDEF";
+
+impl fmt::Display for Hex<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_impl(f, LOWER_DIGITS)
+    }
+}
+
+impl fmt::LowerHex for Hex<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_impl(f, LOWER_DIGITS)
+    }
+}
+
+impl fmt::UpperHex for Hex<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_impl(f, UPPER_DIGITS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::{prelude::*, test_utils::*};
+
+    #[test]
+    fn format() {
+        let bytes = [0x00, 0x7f, 0x80, 0xc3, 0x28, 0xff];
+
+        assert_eq!(Hex::new(&bytes).to_string(), "007f80c328ff");
+        assert_eq!(format!("{:x}", Hex::new(&bytes)), "007f80c328ff");
+        assert_eq!(format!("{:X}", Hex::new(&bytes)), "007F80C328FF");
+        assert_eq!(format!("{:#x}", Hex::new(&bytes)), "00 7f 80 c3 28 ff");
+        assert_eq!(Hex::new(&[]).to_string(), "");
+    }
+
+    #[test]
+    fn invalid_utf8_payload() {
+        let test_sink = Arc::new(CounterSink::new());
+        let test_logger = build_test_logger(|b| b.sink(test_sink.clone()));
+
+        let frame = b"GET \xff\xfe /";
+        info!(logger: test_logger, "frame: {}", Hex::new(frame));
+        info!(logger: test_logger, "frame: {}", String::from_utf8_lossy(frame));
+
+        assert_eq!(
+            test_sink.payloads(),
+            vec![
+                "frame: 47455420fffe202f".to_string(),
+                "frame: GET \u{fffd}\u{fffd} /".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn log_bytes() {
+        let test_sink = Arc::new(CounterSink::new());
+        let test_logger = build_test_logger(|b| b.sink(test_sink.clone()));
+
+        let frame = b"GET \xff\xfe /";
+        crate::log_bytes!(logger: test_logger, Level::Info, frame);
+        crate::log_bytes!(logger: test_logger, Level::Info, "text");
+
+        let records = test_sink.records();
+        assert_eq!(records[0].payload(), "GET \u{fffd}\u{fffd} /");
+        assert_eq!(records[0].payload_bytes(), frame);
+        assert_eq!(records[1].payload(), "text");
+        assert_eq!(records[1].payload_bytes(), b"text");
+
+        // Records not logged by `log_bytes!` have the bytes of the payload
+        info!(logger: test_logger, "text");
+        assert_eq!(test_sink.records()[2].payload_bytes(), b"text");
+
+        // Rewriting the payload discards the raw bytes
+        let mut record = records[0].clone();
+        record.set_payload("redacted");
+        assert_eq!(record.payload_bytes(), b"redacted");
+        assert_eq!(records[0].payload_bytes(), frame);
+    }
+}
@@ -0,0 +1,607 @@
+//! Provides building loggers from declarative configurations.
+//!
+//! This module is only available with feature `config` enabled.
+//!
+//! A configuration describes a default logger and named loggers, with their
+//! level filters and sinks. It can be loaded from a JSON file with
+//! [`from_file`], which also sets the default logger, or with [`watch`], which
+//! additionally reloads it when the file changes, so that levels and outputs
+//! can be changed without redeploying.
+//!
+//! [`Config`] implements [`serde::Deserialize`], so it can also be
+//! deserialized from any other format supported by `serde` (e.g. TOML with
+//! crate `toml`), and applied with [`Config::apply`].
+//!
+//! # Schema
+//!
+//! ```json
+//! {
+//!     "default": {
+//!         "level": "info",
+//!         "flush_level": "warn",
+//!         "sinks": [
+//!             { "type": "std_stream", "stream": "stdout", "style_mode": "auto" },
+//!             { "type": "file", "path": "/path/to/app.log", "truncate": false },
+//!             { "type": "rotating_file", "base_path": "/path/to/rotating.log",
+//!               "policy": { "file_size": 10485760 }, "max_files": 10 },
+//!             { "type": "daily_file", "base_path": "/path/to/daily.log",
+//!               "hour": 2, "minute": 30, "max_files": 30 },
+//!             { "type": "hourly_file", "base_path": "/path/to/hourly.log", "level": "error" },
+//!             { "type": "null" }
+//!         ]
+//!     },
+//!     "loggers": {
+//!         "network": { "level": "debug", "sinks": [{ "type": "std_stream", "stream": "stderr" }] }
+//!     }
+//! }
+//! ```
+//!
+//! - Levels are `"off"`, `"all"`, or a level name (e.g. `"info"`) for that
+//!   level and more severe levels. `level` defaults to `"info"`, `flush_level`
+//!   defaults to `"critical"`, and the `level` of a sink defaults to `"all"`.
+//!
+//! - `policy` of `rotating_file` is one of the following:
+//!   - `{ "file_size": <bytes> }`
+//!   - `{ "daily": { "hour": <hour>, "minute": <minute> } }`
+//!   - `"hourly"`
+//!
+//! - `path` and `base_path` are used literally, unless `path_template` is
+//!   `true`, which makes them [path templates], e.g.
+//!   `"/var/log/${SERVICE}/app-{pid}.log"`.
+//!
+//! - Other fields of sinks are optional and default to the defaults of the
+//!   corresponding sink builders.
+//!
+//! - Formatters cannot be configured, sinks use their default formatters.
+//!   Patterns of [`PatternFormatter`] are built at compile-time, so they cannot
+//!   be loaded at runtime.
+//!
+//! # Note
+//!
+//! There is no logger registry in `spdlog-rs`, so named loggers are only built
+//! when requested with [`Config::build_logger`], and reloading only affects
+//! the default logger.
+//!
+//! [`PatternFormatter`]: crate::formatter::PatternFormatter
+//! [path templates]: crate::sink::FileSinkBuilder::path_template#path-templates
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use serde::{Deserialize, Deserializer};
+
+use crate::{
+    default_error_handler,
+    periodic_worker::PeriodicWorker,
+    sink::{
+        DailyFileSink, FileSink, HourlyFileSink, NullSink, RotatingFileSink, RotationPolicy, Sink,
+        StdStream, StdStreamSink,
+    },
+    sync::*,
+    terminal_style::StyleMode,
+    Error, Level, LevelFilter, Logger, Result,
+};
+
+/// A logging configuration.
+///
+/// See the [module level documentation](self) for the schema.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    #[serde(default)]
+    default: Option<LoggerConfig>,
+    #[serde(default)]
+    loggers: BTreeMap<String, LoggerConfig>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LoggerConfig {
+    #[serde(
+        default = "default_logger_level",
+        deserialize_with = "deserialize_level"
+    )]
+    level: LevelFilter,
+    #[serde(
+        default = "default_flush_level",
+        deserialize_with = "deserialize_level"
+    )]
+    flush_level: LevelFilter,
+    #[serde(default)]
+    sinks: Vec<SinkConfig>,
+}
+
+// `level` is repeated in each variant instead of flattening the variants into
+// a struct with it, since `deny_unknown_fields` does not work with `flatten`.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+enum SinkConfig {
+    StdStream {
+        #[serde(default = "default_sink_level", deserialize_with = "deserialize_level")]
+        level: LevelFilter,
+        stream: StdStreamConfig,
+        #[serde(default)]
+        style_mode: StyleModeConfig,
+    },
+    File {
+        #[serde(default = "default_sink_level", deserialize_with = "deserialize_level")]
+        level: LevelFilter,
+        path: PathBuf,
+        #[serde(default)]
+        path_template: bool,
+        #[serde(default)]
+        truncate: bool,
+    },
+    RotatingFile {
+        #[serde(default = "default_sink_level", deserialize_with = "deserialize_level")]
+        level: LevelFilter,
+        base_path: PathBuf,
+        #[serde(default)]
+        path_template: bool,
+        policy: RotationPolicyConfig,
+        #[serde(default)]
+        max_files: usize,
+        #[serde(default)]
+        rotate_on_open: bool,
+    },
+    DailyFile {
+        #[serde(default = "default_sink_level", deserialize_with = "deserialize_level")]
+        level: LevelFilter,
+        base_path: PathBuf,
+        #[serde(default)]
+        path_template: bool,
+        #[serde(default)]
+        hour: u32,
+        #[serde(default)]
+        minute: u32,
+        #[serde(default)]
+        max_files: usize,
+        #[serde(default)]
+        rotate_on_open: bool,
+    },
+    HourlyFile {
+        #[serde(default = "default_sink_level", deserialize_with = "deserialize_level")]
+        level: LevelFilter,
+        base_path: PathBuf,
+        #[serde(default)]
+        path_template: bool,
+        #[serde(default)]
+        max_files: usize,
+        #[serde(default)]
+        rotate_on_open: bool,
+    },
+    Null {
+        #[serde(default = "default_sink_level", deserialize_with = "deserialize_level")]
+        level: LevelFilter,
+    },
+}
+
+#[derive(Copy, Clone, Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum StdStreamConfig {
+    Stdout,
+    Stderr,
+}
+
+#[derive(Copy, Clone, Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum StyleModeConfig {
+    Always,
+    Auto,
+    Never,
+}
+
+#[derive(Copy, Clone, Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum RotationPolicyConfig {
+    FileSize(u64),
+    Daily { hour: u32, minute: u32 },
+    Hourly,
+}
+
+impl Default for StyleModeConfig {
+    fn default() -> Self {
+        Self::Auto
+    }
+}
+
+fn default_logger_level() -> LevelFilter {
+    LevelFilter::MoreSevereEqual(Level::Info)
+}
+
+// Same as the default of `LoggerBuilder`
+fn default_flush_level() -> LevelFilter {
+    LevelFilter::MoreSevereEqual(Level::Critical)
+}
+
+fn default_sink_level() -> LevelFilter {
+    LevelFilter::All
+}
+
+fn deserialize_level<'de, D>(deserializer: D) -> std::result::Result<LevelFilter, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let text = String::deserialize(deserializer)?;
+    LevelFilter::from_str_for_env(&text).ok_or_else(|| {
+        serde::de::Error::custom(format!(
+            "invalid level '{}', expected 'off', 'all' or a level name",
+            text
+        ))
+    })
+}
+
+impl Config {
+    /// Loads a configuration from a JSON file.
+    ///
+    /// # Errors
+    ///
+    /// If the file cannot be read, [`Error::ReadConfig`] will be returned. If
+    /// the content is invalid, [`Error::ParseConfig`] will be returned.
+    pub fn from_file<P>(path: P) -> Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let text = fs::read_to_string(path).map_err(Error::ReadConfig)?;
+        Self::from_json(&text)
+    }
+
+    /// Parses a configuration from a JSON string.
+    ///
+    /// # Errors
+    ///
+    /// If the content is invalid, [`Error::ParseConfig`] will be returned.
+    pub fn from_json(text: &str) -> Result<Self> {
+        serde_json::from_str(text).map_err(Error::ParseConfig)
+    }
+
+    /// Builds the default logger described by this configuration.
+    ///
+    /// Returns `Ok(None)` if the default logger is not configured.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of building the sinks.
+    pub fn build_default_logger(&self) -> Result<Option<Logger>> {
+        self.default
+            .as_ref()
+            .map(|config| config.build(None))
+            .transpose()
+    }
+
+    /// Builds the logger with the given name described by this configuration.
+    ///
+    /// Returns `Ok(None)` if there is no logger with the name.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of building the sinks, or
+    /// [`Error::InvalidArgument`] if the name is not a valid logger name.
+    pub fn build_logger(&self, name: &str) -> Result<Option<Logger>> {
+        self.loggers
+            .get(name)
+            .map(|config| config.build(Some(name)))
+            .transpose()
+    }
+
+    /// Builds the default logger described by this configuration, and sets it
+    /// as the default logger.
+    ///
+    /// The replacement is atomic, records being logged to the old default
+    /// logger concurrently are not lost, and the old default logger is flushed
+    /// after the replacement. If the default logger is not configured, nothing
+    /// happens.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of building the sinks, in which case the default
+    /// logger is not changed.
+    pub fn apply(&self) -> Result<()> {
+        if let Some(logger) = self.build_default_logger()? {
+            crate::swap_default_logger(Arc::new(logger)).flush();
+        }
+        Ok(())
+    }
+}
+
+impl LoggerConfig {
+    fn build(&self, name: Option<&str>) -> Result<Logger> {
+        let mut builder = Logger::builder();
+        if let Some(name) = name {
+            builder.name(name);
+        }
+        builder
+            .level_filter(self.level)
+            .flush_level_filter(self.flush_level);
+        for sink in &self.sinks {
+            builder.sink(sink.build()?);
+        }
+        builder.build()
+    }
+}
+
+impl SinkConfig {
+    fn build(&self) -> Result<Arc<dyn Sink>> {
+        let sink: Arc<dyn Sink> = match self.clone() {
+            SinkConfig::StdStream {
+                stream, style_mode, ..
+            } => Arc::new(
+                StdStreamSink::builder()
+                    .std_stream(match stream {
+                        StdStreamConfig::Stdout => StdStream::Stdout,
+                        StdStreamConfig::Stderr => StdStream::Stderr,
+                    })
+                    .style_mode(match style_mode {
+                        StyleModeConfig::Always => StyleMode::Always,
+                        StyleModeConfig::Auto => StyleMode::Auto,
+                        StyleModeConfig::Never => StyleMode::Never,
+                    })
+                    .build()?,
+            ),
+            SinkConfig::File {
+                path,
+                path_template,
+                truncate,
+                ..
+            } => Arc::new(
+                FileSink::builder()
+                    .path(path)
+                    .path_template(path_template)
+                    .truncate(truncate)
+                    .build()?,
+            ),
+            SinkConfig::RotatingFile {
+                base_path,
+                path_template,
+                policy,
+                max_files,
+                rotate_on_open,
+                ..
+            } => Arc::new(
+                RotatingFileSink::builder()
+                    .base_path(base_path)
+                    .path_template(path_template)
+                    .rotation_policy(match policy {
+                        RotationPolicyConfig::FileSize(max_size) => {
+                            RotationPolicy::FileSize(max_size)
+                        }
+                        RotationPolicyConfig::Daily { hour, minute } => {
+                            RotationPolicy::Daily { hour, minute }
+                        }
+                        RotationPolicyConfig::Hourly => RotationPolicy::Hourly,
+                    })
+                    .max_files(max_files)
+                    .rotate_on_open(rotate_on_open)
+                    .build()?,
+            ),
+            SinkConfig::DailyFile {
+                base_path,
+                path_template,
+                hour,
+                minute,
+                max_files,
+                rotate_on_open,
+                ..
+            } => Arc::new(
+                DailyFileSink::builder()
+                    .base_path(base_path)
+                    .path_template(path_template)
+                    .rotation_time(hour, minute)
+                    .max_files(max_files)
+                    .rotate_on_open(rotate_on_open)
+                    .build()?,
+            ),
+            SinkConfig::HourlyFile {
+                base_path,
+                path_template,
+                max_files,
+                rotate_on_open,
+                ..
+            } => Arc::new(
+                HourlyFileSink::builder()
+                    .base_path(base_path)
+                    .path_template(path_template)
+                    .max_files(max_files)
+                    .rotate_on_open(rotate_on_open)
+                    .build()?,
+            ),
+            SinkConfig::Null { .. } => Arc::new(NullSink::new()),
+        };
+        sink.set_level_filter(self.level());
+        Ok(sink)
+    }
+
+    #[must_use]
+    fn level(&self) -> LevelFilter {
+        match self {
+            SinkConfig::StdStream { level, .. }
+            | SinkConfig::File { level, .. }
+            | SinkConfig::RotatingFile { level, .. }
+            | SinkConfig::DailyFile { level, .. }
+            | SinkConfig::HourlyFile { level, .. }
+            | SinkConfig::Null { level } => *level,
+        }
+    }
+}
+
+/// Loads a configuration from a JSON file, and sets the default logger
+/// described by it.
+///
+/// The loaded configuration is returned, so that named loggers can be built
+/// with [`Config::build_logger`].
+///
+/// # Errors
+///
+/// Returns the errors of [`Config::from_file`] and [`Config::apply`].
+///
+/// # Examples
+///
+/// ```no_run
+/// # fn main() -> Result<(), spdlog::Error> {
+/// let config = spdlog::config::from_file("/path/to/logging.json")?;
+/// let network_logger = config.build_logger("network")?;
+/// # Ok(()) }
+/// ```
+pub fn from_file<P>(path: P) -> Result<Config>
+where
+    P: AsRef<Path>,
+{
+    let config = Config::from_file(path)?;
+    config.apply()?;
+    Ok(config)
+}
+
+/// Watches a configuration file, returned by [`watch`].
+///
+/// The file is no longer watched after the value is dropped.
+pub struct ConfigWatcher {
+    _worker: PeriodicWorker,
+}
+
+/// Loads a configuration from a JSON file like [`from_file`], and reloads it
+/// whenever the file changes.
+///
+/// The modification time of the file is checked every `interval`. When it
+/// changes, the configuration is loaded and applied again with
+/// [`Config::apply`]. If reloading fails, the error is printed to `stderr`,
+/// and the previous configuration stays in effect.
+///
+/// # Errors
+///
+/// Returns the errors of the initial [`from_file`].
+///
+/// # Panics
+///
+/// Panics if `interval` is zero.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// # fn main() -> Result<(), spdlog::Error> {
+/// let _watcher = spdlog::config::watch("/path/to/logging.json", Duration::from_secs(5))?;
+/// // Edit the file to change the default logger while the application is running.
+/// # Ok(()) }
+/// ```
+pub fn watch<P>(path: P, interval: Duration) -> Result<ConfigWatcher>
+where
+    P: Into<PathBuf>,
+{
+    let path = path.into();
+    let modified = Mutex::new(modified_time(&path));
+    from_file(&path)?;
+
+    let callback = move || {
+        let new_modified = modified_time(&path);
+        let mut modified = modified.lock_expect();
+        if new_modified != *modified {
+            *modified = new_modified;
+            if let Err(err) = Config::from_file(&path).and_then(|config| config.apply()) {
+                default_error_handler("config watcher", err);
+            }
+        }
+        true
+    };
+
+    Ok(ConfigWatcher {
+        _worker: PeriodicWorker::new(callback, interval),
+    })
+}
+
+#[must_use]
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::*;
+
+    static LOGS_PATH: Lazy<PathBuf> = Lazy::new(|| {
+        let path = TEST_LOGS_PATH.join("config");
+        fs::create_dir_all(&path).unwrap();
+        path
+    });
+
+    #[test]
+    fn parse_and_build() {
+        let config = Config::from_json(&format!(
+            r#"{{
+                "default": {{
+                    "level": "warn",
+                    "flush_level": "all",
+                    "sinks": [
+                        {{ "type": "null", "level": "error" }},
+                        {{ "type": "file", "path": {:?} }}
+                    ]
+                }},
+                "loggers": {{
+                    "network": {{ "sinks": [{{ "type": "std_stream", "stream": "stderr" }}] }}
+                }}
+            }}"#,
+            LOGS_PATH.join("parse_and_build.log")
+        ))
+        .unwrap();
+
+        let logger = config.build_default_logger().unwrap().unwrap();
+        assert_eq!(logger.name(), None);
+        assert_eq!(
+            logger.level_filter(),
+            LevelFilter::MoreSevereEqual(Level::Warn)
+        );
+        assert_eq!(logger.flush_level_filter(), LevelFilter::All);
+        assert_eq!(logger.sinks().len(), 2);
+        assert!(logger.sinks()[0].type_name().ends_with("::NullSink"));
+        assert_eq!(
+            logger.sinks()[0].level_filter(),
+            LevelFilter::MoreSevereEqual(Level::Error)
+        );
+        assert_eq!(logger.sinks()[1].level_filter(), LevelFilter::All);
+
+        let logger = config.build_logger("network").unwrap().unwrap();
+        assert_eq!(logger.name(), Some("network"));
+        assert_eq!(
+            logger.level_filter(),
+            LevelFilter::MoreSevereEqual(Level::Info)
+        );
+        assert_eq!(
+            logger.flush_level_filter(),
+            LevelFilter::MoreSevereEqual(Level::Critical)
+        );
+        assert!(config.build_logger("nonexistent").unwrap().is_none());
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(matches!(
+            Config::from_json(r#"{ "default": { "level": "loud" } }"#),
+            Err(Error::ParseConfig(_))
+        ));
+        assert!(matches!(
+            Config::from_json(r#"{ "default": { "sinks": [{ "type": "unknown" }] } }"#),
+            Err(Error::ParseConfig(_))
+        ));
+        assert!(matches!(
+            Config::from_json(
+                r#"{ "default": { "sinks": [{ "type": "file", "path": "a.log", "trunc": true }] } }"#
+            ),
+            Err(Error::ParseConfig(_))
+        ));
+        assert!(matches!(
+            Config::from_json(
+                r#"{ "default": { "sinks": [{ "type": "null", "levle": "off" }] } }"#
+            ),
+            Err(Error::ParseConfig(_))
+        ));
+        assert!(matches!(
+            Config::from_file(LOGS_PATH.join("nonexistent.json")),
+            Err(Error::ReadConfig(_))
+        ));
+    }
+}
@@ -15,6 +15,8 @@ static ENV_LEVEL: Lazy<RwLock<Option<EnvLevel>>> = Lazy::new(|| RwLock::new(None
 pub(crate) enum EnvLevelLogger {
     Default,
     Named(String),
+    // A logger name containing `*`, other than `*` itself
+    Pattern(String),
     Unnamed,
     AllExceptDefault,
 }
@@ -41,6 +43,8 @@ impl EnvLevelLogger {
             EnvLevelLogger::Unnamed
         } else if logger_name == "*" {
             EnvLevelLogger::AllExceptDefault
+        } else if logger_name.contains('*') {
+            EnvLevelLogger::Pattern(logger_name.into())
         } else {
             EnvLevelLogger::Named(logger_name.into())
         }
@@ -129,11 +133,66 @@ pub(crate) fn logger_level_inner(env_level: &EnvLevel, kind: LoggerKind) -> Opti
         LoggerKind::Default => env_level.get(&EnvLevelLogger::Default)?,
         LoggerKind::Other(logger_name) => env_level
             .get(&EnvLevelLogger::from_logger(logger_name))
+            .or_else(|| most_specific_pattern(env_level, logger_name?))
             .or_else(|| env_level.get(&EnvLevelLogger::AllExceptDefault))?,
     };
     Some(*level)
 }
 
+// The most specific pattern is the one with the most literal characters, then
+// the one with the longest literal prefix, then the lexicographically smallest
+// one, so that the result does not depend on the order of the hash map.
+#[must_use]
+fn most_specific_pattern<'a>(
+    env_level: &'a EnvLevel,
+    logger_name: &str,
+) -> Option<&'a LevelFilter> {
+    env_level
+        .iter()
+        .filter_map(|(logger, level)| match logger {
+            EnvLevelLogger::Pattern(pattern) if glob_matches(pattern, logger_name) => {
+                Some((pattern, level))
+            }
+            _ => None,
+        })
+        .max_by(|(a, _), (b, _)| {
+            let specificity = |pattern: &str| {
+                let literal_len = pattern.len() - pattern.matches('*').count();
+                let prefix_len = pattern.find('*').unwrap_or(pattern.len());
+                (literal_len, prefix_len)
+            };
+            specificity(a).cmp(&specificity(b)).then_with(|| b.cmp(a))
+        })
+        .map(|(_, level)| level)
+}
+
+// Matches a pattern where `*` matches any sequence of characters, including `.`
+// and the empty sequence.
+#[must_use]
+fn glob_matches(pattern: &str, name: &str) -> bool {
+    let mut parts = pattern.split('*').collect::<Vec<_>>();
+    // `split` always yields at least one part
+    let first = parts.remove(0);
+    let mut rest = match name.strip_prefix(first) {
+        Some(rest) => rest,
+        None => return false,
+    };
+    let last = match parts.pop() {
+        Some(last) => last,
+        // No `*` in the pattern
+        None => return rest.is_empty(),
+    };
+    // Matching each part at its first occurrence leaves the most room for the
+    // following parts
+    for part in parts {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -280,4 +339,71 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn glob_patterns() {
+        for (pattern, name, expected) in [
+            ("app.*", "app.db", true),
+            ("app.*", "app.db.pool", true),
+            ("app.*", "app.", true),
+            ("app.*", "app", false),
+            ("app.*", "myapp.db", false),
+            ("*.db", "app.db", true),
+            ("*.db", "app.db.pool", false),
+            ("app.*.pool", "app.db.pool", true),
+            ("app.*.pool", "app.db.cache", false),
+            ("a*b*c", "abbc", true),
+            ("a*b*c", "acb", false),
+            ("ab*ba", "aba", false),
+            ("**", "", true),
+        ] {
+            assert_eq!(
+                glob_matches(pattern, name),
+                expected,
+                "{} {}",
+                pattern,
+                name
+            );
+        }
+
+        let env_level = from_str_inner(
+            "*=off,app.*=info,app.db.*=debug,*.pool=warn,app.db.pool=trace,*.db.*=error",
+        )
+        .unwrap();
+        for (name, expected) in [
+            // Exact names take precedence over patterns
+            ("app.db.pool", Level::Trace),
+            // `app.db.*` is more specific than `*.db.*` and `app.*`
+            ("app.db.cache", Level::Debug),
+            // `*.pool` is more specific than `app.*` by its literal characters
+            ("app.net.pool", Level::Warn),
+            ("app.net", Level::Info),
+            ("web.db.cache", Level::Error),
+        ] {
+            assert_eq!(
+                logger_level_inner(&env_level, LoggerKind::Other(Some(name))),
+                Some(LevelFilter::MoreSevereEqual(expected)),
+                "{}",
+                name
+            );
+        }
+        assert_eq!(
+            logger_level_inner(&env_level, LoggerKind::Other(Some("web"))),
+            Some(LevelFilter::Off)
+        );
+        // Patterns match named loggers only
+        assert_eq!(
+            logger_level_inner(&env_level, LoggerKind::Other(None)),
+            Some(LevelFilter::Off)
+        );
+
+        // Equally specific patterns are resolved deterministically
+        let env_level = from_str_inner("a.*=info,*.a=warn").unwrap();
+        assert_eq!(
+            logger_level_inner(&env_level, LoggerKind::Other(Some("a.a"))),
+            Some(LevelFilter::MoreSevereEqual(Level::Info))
+        );
+
+        assert!(from_str_inner("app.*=info,app.*=warn").is_err());
+    }
 }
@@ -9,11 +9,25 @@ use atomic::Atomic;
 use thiserror::Error;
 
 pub use crate::env_level::EnvLevelError;
-use crate::utils::const_assert;
+use crate::{sink::CircuitState, sync::Arc, utils::const_assert};
 #[cfg(feature = "multi-thread")]
 use crate::{sink::Task, RecordOwned};
 
 /// The error type of this crate.
+///
+/// Each variant indicates the cause of the error, so that error handlers can
+/// match on it. If a variant wraps an underlying error (e.g. [`io::Error`]),
+/// the underlying error is returned by [`std::error::Error::source`] rather
+/// than included in the [`Display`] output, so that error chain printers (e.g.
+/// [`ErrorChain`]) show it once. Use [`ErrorChain`] to display an error
+/// together with its sources.
+///
+/// Errors returned by sinks are wrapped in [`Error::Sink`] by loggers, which
+/// identifies the failed sink. Error handlers that match on the cause of a
+/// sink error (e.g. [`Error::WriteRecord`]) should match on
+/// [`Error::strip_sink`] instead of the error itself.
+///
+/// [`ErrorChain`]: crate::ErrorChain
 #[derive(Error, Debug)]
 #[non_exhaustive]
 pub enum Error {
@@ -21,57 +35,102 @@ pub enum Error {
     /// formatting a record.
     ///
     /// [`Formatter`]: crate::formatter::Formatter
-    #[error("format record error: {0}")]
-    FormatRecord(fmt::Error),
+    #[error("format record error")]
+    FormatRecord(#[source] fmt::Error),
 
     /// The variant returned by [`Sink`]s when an error occurs in writing a
     /// record to the target.
     ///
     /// [`Sink`]: crate::sink::Sink
-    #[error("write record error: {0}")]
-    WriteRecord(io::Error),
+    #[error("write record error")]
+    WriteRecord(#[source] io::Error),
 
     /// The variant returned by [`Sink`]s when an error occurs in flushing the
     /// buffer.
     ///
     /// [`Sink`]: crate::sink::Sink
-    #[error("flush buffer error: {0}")]
-    FlushBuffer(io::Error),
+    #[error("flush buffer error")]
+    FlushBuffer(#[source] io::Error),
+
+    /// The variant returned by [`Sink`]s when an error occurs in syncing a
+    /// file or directory to disk.
+    ///
+    /// [`Sink`]: crate::sink::Sink
+    #[error("sync file error")]
+    SyncFile(#[source] io::Error),
 
     /// The variant returned by [`Sink`]s when an error occurs in creating a
     /// directory.
     ///
     /// [`Sink`]: crate::sink::Sink
-    #[error("create directory error: {0}")]
-    CreateDirectory(io::Error),
+    #[error("create directory error")]
+    CreateDirectory(#[source] io::Error),
 
     /// The variant returned by [`Sink`]s when an error occurs in opening a
     /// file.
     ///
     /// [`Sink`]: crate::sink::Sink
-    #[error("open file error: {0}")]
-    OpenFile(io::Error),
+    #[error("open file error")]
+    OpenFile(#[source] io::Error),
+
+    /// The variant returned by [`Sink`]s when an error occurs in locking a
+    /// file, e.g. the file is already locked by another process.
+    ///
+    /// [`Sink`]: crate::sink::Sink
+    #[error("lock file error")]
+    LockFile(#[source] io::Error),
 
     /// The variant returned by [`Sink`]s when an error occurs in querying the
     /// metadata of a file.
     ///
     /// [`Sink`]: crate::sink::Sink
-    #[error("query file metadata error: {0}")]
-    QueryFileMetadata(io::Error),
+    #[error("query file metadata error")]
+    QueryFileMetadata(#[source] io::Error),
 
     /// The variant returned by [`Sink`]s when an error occurs in renaming a
     /// file.
     ///
     /// [`Sink`]: crate::sink::Sink
-    #[error("rename file error: {0}")]
-    RenameFile(io::Error),
+    #[error("rename file error")]
+    RenameFile(#[source] io::Error),
 
     /// The variant returned by [`Sink`]s when an error occurs in removing a
     /// file.
     ///
     /// [`Sink`]: crate::sink::Sink
-    #[error("remove file error: {0}")]
-    RemoveFile(io::Error),
+    #[error("remove file error")]
+    RemoveFile(#[source] io::Error),
+
+    /// The variant returned by [`Sink`]s when an error occurs in binding or
+    /// connecting a socket.
+    ///
+    /// [`Sink`]: crate::sink::Sink
+    #[error("connect socket error")]
+    ConnectSocket(#[source] io::Error),
+
+    /// The variant passed to the error handler by [`CircuitBreakerSink`] when
+    /// its circuit changes state.
+    ///
+    /// [`CircuitBreakerSink`]: crate::sink::CircuitBreakerSink
+    #[error("circuit breaker state changed to {0}")]
+    CircuitStateChanged(CircuitState),
+
+    /// The variant passed to the error handler of a [`Logger`], and returned by
+    /// [`Logger::try_log`] and [`Logger::flush_all`], when a sink of the
+    /// logger failed.
+    ///
+    /// It identifies the failed sink, see [`SinkError`] for details. Use
+    /// [`Error::strip_sink`] to get the error returned by the sink.
+    ///
+    /// **This is a breaking change:** errors of sinks used to be passed to
+    /// error handlers as is, so handlers matching on e.g.
+    /// [`Error::WriteRecord`] must now match on [`Error::strip_sink`].
+    ///
+    /// [`Logger`]: crate::Logger
+    /// [`Logger::try_log`]: crate::Logger::try_log
+    /// [`Logger::flush_all`]: crate::Logger::flush_all
+    #[error(transparent)]
+    Sink(SinkError),
 
     /// The variant returned by [`from_str`] when the string doesn't match any
     /// of the log levels.
@@ -81,22 +140,47 @@ pub enum Error {
     ParseLevel(String),
 
     /// The variant returned if an invalid argument was passed in.
-    #[error("invalid argument {0}")]
+    #[error("invalid argument")]
     InvalidArgument(#[from] InvalidArgumentError),
 
+    /// The variant returned by [`LoggerBuilder::build`] when a sink added via
+    /// [`LoggerBuilder::try_sink`] failed to be built.
+    ///
+    /// [`LoggerBuilder::build`]: crate::LoggerBuilder::build
+    /// [`LoggerBuilder::try_sink`]: crate::LoggerBuilder::try_sink
+    #[error("build sink error")]
+    BuildSink(#[from] BuildSinkError),
+
+    /// The variant returned by [`config`] functions when an error occurs in
+    /// reading a configuration file.
+    ///
+    /// [`config`]: crate::config
+    #[cfg(feature = "config")]
+    #[error("read config error")]
+    ReadConfig(#[source] io::Error),
+
+    /// The variant returned by [`config`] functions when a configuration is
+    /// invalid.
+    ///
+    /// [`config`]: crate::config
+    #[cfg(feature = "config")]
+    #[error("parse config error")]
+    ParseConfig(#[source] serde_json::Error),
+
     /// The variant returned by [`Sink`]s when an error occurs in sending to the
     /// channel.
     ///
     /// [`Sink`]: crate::sink::Sink
     #[cfg(feature = "multi-thread")]
-    #[error("failed to send message to channel: {0}")]
-    SendToChannel(SendToChannelError, SendToChannelErrorDropped),
+    #[error("failed to send message to channel")]
+    SendToChannel(#[source] SendToChannelError, SendToChannelErrorDropped),
 
     /// This variant returned when multiple errors occurred.
     #[error("{0:?}")]
     Multiple(Vec<Error>),
 
     #[cfg(test)]
+    #[allow(missing_docs)]
     #[error("{0}")]
     __ForInternalTestsUseOnly(i32),
 }
@@ -111,7 +195,7 @@ pub enum InvalidArgumentError {
     /// requirements.
     ///
     /// [`LoggerBuilder::name`]: crate::LoggerBuilder::name
-    #[error("'logger name': {0}")]
+    #[error("'logger name'")]
     LoggerName(#[from] SetLoggerNameError),
 
     /// Invalid [`RotationPolicy`].
@@ -125,6 +209,47 @@ pub enum InvalidArgumentError {
     /// Invalid thread pool capacity.
     #[error("'thread pool capacity': {0}")]
     ThreadPoolCapacity(String),
+
+    /// Invalid chunk size of [`UdpSink`].
+    ///
+    /// See the documentation of [`UdpSinkBuilder::gelf_chunk_size`] for the
+    /// input requirements.
+    ///
+    /// [`UdpSink`]: crate::sink::UdpSink
+    /// [`UdpSinkBuilder::gelf_chunk_size`]: crate::sink::UdpSinkBuilder::gelf_chunk_size
+    #[error("'chunk size': {0}")]
+    ChunkSize(String),
+
+    /// Invalid batch limits of [`AsyncBatchSink`].
+    ///
+    /// See the documentation of [`AsyncBatchSinkBuilder::max_batch`] and
+    /// [`AsyncBatchSinkBuilder::max_bytes`] for the input requirements.
+    ///
+    /// [`AsyncBatchSink`]: crate::sink::AsyncBatchSink
+    /// [`AsyncBatchSinkBuilder::max_batch`]: crate::sink::AsyncBatchSinkBuilder::max_batch
+    /// [`AsyncBatchSinkBuilder::max_bytes`]: crate::sink::AsyncBatchSinkBuilder::max_bytes
+    #[error("'batch limit': {0}")]
+    BatchLimit(String),
+
+    /// Invalid path template of file sinks, or a variable in it cannot be
+    /// resolved.
+    ///
+    /// See the documentation of [`FileSinkBuilder::path`] for the grammar.
+    ///
+    /// [`FileSinkBuilder::path`]: crate::sink::FileSinkBuilder::path
+    #[error("'path template': {0}")]
+    PathTemplate(String),
+
+    /// Invalid failure threshold of [`CircuitBreakerSink`].
+    ///
+    /// See the documentation of
+    /// [`CircuitBreakerSinkBuilder::failure_threshold`] for the input
+    /// requirements.
+    ///
+    /// [`CircuitBreakerSink`]: crate::sink::CircuitBreakerSink
+    /// [`CircuitBreakerSinkBuilder::failure_threshold`]: crate::sink::CircuitBreakerSinkBuilder::failure_threshold
+    #[error("'failure threshold': {0}")]
+    FailureThreshold(String),
 }
 
 /// This error indicates that an invalid logger name was set.
@@ -156,6 +281,94 @@ impl Display for SetLoggerNameError {
     }
 }
 
+/// This error indicates that a sink failed to be built for a logger.
+///
+/// It identifies which sink failed, and the underlying error is returned by
+/// [`std::error::Error::source`].
+#[derive(Error, Clone, Debug)]
+#[error("failed to build sink #{index} ({sink_type})")]
+pub struct BuildSinkError {
+    index: usize,
+    sink_type: &'static str,
+    source: Arc<Error>,
+}
+
+impl BuildSinkError {
+    #[must_use]
+    pub(crate) fn new(index: usize, sink_type: &'static str, source: Arc<Error>) -> Self {
+        Self {
+            index,
+            sink_type,
+            source,
+        }
+    }
+
+    /// Gets the index of the failed sink, in the order in which sinks were
+    /// added to the logger builder.
+    #[must_use]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Gets the type name of the failed sink.
+    #[must_use]
+    pub fn sink_type(&self) -> &'static str {
+        self.sink_type
+    }
+}
+
+/// This error indicates that a sink of a logger failed, wrapping the error
+/// returned by the sink.
+///
+/// It identifies which sink failed, and the error returned by the sink is
+/// returned by [`SinkError::error`] and [`std::error::Error::source`].
+#[derive(Error, Debug)]
+#[error("sink #{index} ({sink_type}) failed")]
+pub struct SinkError {
+    index: usize,
+    sink_type: &'static str,
+    source: Box<Error>,
+}
+
+impl SinkError {
+    #[must_use]
+    pub(crate) fn new(index: usize, sink_type: &'static str, source: Error) -> Self {
+        Self {
+            index,
+            sink_type,
+            source: Box::new(source),
+        }
+    }
+
+    /// Gets the index of the failed sink, in the order in which sinks were
+    /// added to the logger.
+    #[must_use]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Gets the type name of the failed sink, as returned by
+    /// [`Sink::type_name`].
+    ///
+    /// [`Sink::type_name`]: crate::sink::Sink::type_name
+    #[must_use]
+    pub fn sink_type(&self) -> &'static str {
+        self.sink_type
+    }
+
+    /// Gets the error returned by the sink.
+    #[must_use]
+    pub fn error(&self) -> &Error {
+        &self.source
+    }
+
+    /// Converts into the error returned by the sink.
+    #[must_use]
+    pub fn into_error(self) -> Error {
+        *self.source
+    }
+}
+
 /// The more detailed error type of sending to channel.
 #[cfg(feature = "multi-thread")]
 #[derive(Error, Debug)]
@@ -189,6 +402,26 @@ pub enum SendToChannelErrorDropped {
 }
 
 impl Error {
+    /// Gets the error returned by the sink if this is an [`Error::Sink`],
+    /// otherwise returns `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spdlog::Error;
+    ///
+    /// fn is_write_failure(err: &Error) -> bool {
+    ///     matches!(err.strip_sink(), Error::WriteRecord(_))
+    /// }
+    /// ```
+    #[must_use]
+    pub fn strip_sink(&self) -> &Error {
+        match self {
+            Self::Sink(err) => err.error().strip_sink(),
+            _ => self,
+        }
+    }
+
     pub(crate) fn push_err<T>(result: Result<T>, new: Self) -> Result<T> {
         match result {
             Ok(_) => Err(new),
@@ -236,7 +469,7 @@ impl SendToChannelErrorDropped {
     #[must_use]
     pub(crate) fn from_task(task: Task) -> Self {
         match task {
-            Task::Log { record, .. } => Self::Record(record),
+            Task::Log { record, .. } => Self::Record(record.as_record().to_owned()),
             Task::Flush { .. } => Self::Flush,
         }
     }
@@ -253,8 +486,46 @@ const_assert!(Atomic::<Option<ErrorHandler>>::is_lock_free());
 
 #[cfg(test)]
 mod tests {
+    use std::error::Error as StdError;
+
     use super::*;
 
+    #[test]
+    fn source() {
+        let err = Error::WriteRecord(io::Error::new(io::ErrorKind::Other, "disk full"));
+        assert_eq!(err.to_string(), "write record error");
+        let source = err.source().unwrap().downcast_ref::<io::Error>().unwrap();
+        assert_eq!(source.kind(), io::ErrorKind::Other);
+
+        let err = Error::FormatRecord(fmt::Error);
+        assert!(err.source().unwrap().is::<fmt::Error>());
+
+        assert!(Error::ParseLevel("foo".to_string()).source().is_none());
+    }
+
+    #[test]
+    fn sink_context() {
+        let err = Error::Sink(SinkError::new(
+            1,
+            "spdlog::sink::FileSink",
+            Error::WriteRecord(io::Error::new(io::ErrorKind::Other, "disk full")),
+        ));
+        assert_eq!(
+            crate::ErrorChain::new(&err).to_string(),
+            "sink #1 (spdlog::sink::FileSink) failed: caused by: write record error: caused by: \
+             disk full"
+        );
+        assert!(matches!(err.strip_sink(), Error::WriteRecord(_)));
+        assert!(matches!(
+            Error::ParseLevel("foo".to_string()).strip_sink(),
+            Error::ParseLevel(_)
+        ));
+        match err {
+            Error::Sink(err) => assert!(matches!(err.into_error(), Error::WriteRecord(_))),
+            _ => unreachable!(),
+        }
+    }
+
     #[test]
     fn push_err() {
         macro_rules! make_err {
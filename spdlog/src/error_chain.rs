@@ -0,0 +1,194 @@
+//! Provides stuff related to logging errors with their sources.
+
+use std::{borrow::Cow, error::Error as StdError, fmt};
+
+use crate::record::KeyValue;
+
+/// Formats an error followed by its [sources], in the form of `error: caused
+/// by: source: caused by: source of source`.
+///
+/// It's used by macro [`error_chain!`], and can also be used as a format
+/// argument of log macros directly.
+///
+/// # Examples
+///
+/// ```
+/// use spdlog::{error, ErrorChain};
+///
+/// let err = std::fs::read("/path/to/nowhere").unwrap_err();
+///
+/// error!("failed to load config: {}", ErrorChain::new(&err));
+/// ```
+///
+/// [sources]: std::error::Error::source
+/// [`error_chain!`]: crate::error_chain
+#[derive(Clone, Copy)]
+pub struct ErrorChain<'a> {
+    error: &'a (dyn StdError + 'a),
+}
+
+impl<'a> ErrorChain<'a> {
+    /// Constructs an `ErrorChain`.
+    #[must_use]
+    pub fn new(error: &'a (dyn StdError + 'a)) -> Self {
+        Self { error }
+    }
+
+    // Converts to key-values `error`, `error.cause.0`, `error.cause.1`, ...
+    #[must_use]
+    pub(crate) fn key_values(&self) -> Vec<KeyValue<'static>> {
+        let mut key_values = vec![(Cow::Borrowed("error"), Cow::Owned(self.error.to_string()))];
+        let mut source = self.error.source();
+        while let Some(error) = source {
+            let key = format!("error.cause.{}", key_values.len() - 1);
+            key_values.push((Cow::Owned(key), Cow::Owned(error.to_string())));
+            source = error.source();
+        }
+        key_values
+    }
+}
+
+impl fmt::Display for ErrorChain<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.error)?;
+        let mut source = self.error.source();
+        while let Some(error) = source {
+            f.write_str(": caused by: ")?;
+            write!(f, "{}", error)?;
+            source = error.source();
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for ErrorChain<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::{prelude::*, test_utils::*};
+
+    #[derive(Debug)]
+    struct ChainedError {
+        message: &'static str,
+        source: Option<Box<ChainedError>>,
+    }
+
+    impl fmt::Display for ChainedError {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str(self.message)
+        }
+    }
+
+    impl StdError for ChainedError {
+        fn source(&self) -> Option<&(dyn StdError + 'static)> {
+            self.source.as_deref().map(|err| err as _)
+        }
+    }
+
+    #[must_use]
+    fn three_level_error() -> ChainedError {
+        ChainedError {
+            message: "failed to load config",
+            source: Some(Box::new(ChainedError {
+                message: "failed to read file",
+                source: Some(Box::new(ChainedError {
+                    message: "permission denied",
+                    source: None,
+                })),
+            })),
+        }
+    }
+
+    #[test]
+    fn display() {
+        let err = three_level_error();
+        assert_eq!(
+            ErrorChain::new(&err).to_string(),
+            "failed to load config: caused by: failed to read file: caused by: permission denied"
+        );
+        assert_eq!(
+            ErrorChain::new(err.source.as_deref().unwrap()).to_string(),
+            "failed to read file: caused by: permission denied"
+        );
+    }
+
+    #[test]
+    fn log_error_chain() {
+        let test_sink = Arc::new(CounterSink::new());
+        let test_logger = build_test_logger(|b| b.sink(test_sink.clone()));
+
+        let err = three_level_error();
+        crate::error_chain!(logger: test_logger, Level::Error, err);
+
+        let boxed: Box<dyn StdError> = Box::new(three_level_error());
+        crate::error_chain!(logger: test_logger, Level::Warn, *boxed);
+
+        let records = test_sink.records();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1].level(), Level::Warn);
+        for record in records {
+            assert_eq!(
+                record.payload(),
+                "failed to load config: caused by: failed to read file: caused by: permission denied"
+            );
+            assert_eq!(
+                record.key_values().collect::<Vec<_>>(),
+                [
+                    ("error", "failed to load config"),
+                    ("error.cause.0", "failed to read file"),
+                    ("error.cause.1", "permission denied")
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn log_error_context() {
+        let test_sink = Arc::new(CounterSink::new());
+        let test_logger = build_test_logger(|b| b.sink(test_sink.clone()));
+
+        let load = |fail| -> Result<u32, Box<dyn StdError>> {
+            let result = if fail {
+                Err(three_level_error())
+            } else {
+                Ok(42)
+            };
+            let value = crate::err_log!(logger: test_logger, result, "loading {}", "app")?;
+            Ok(value + 1)
+        };
+        assert_eq!(load(false).unwrap(), 43);
+        assert_eq!(test_sink.log_count(), 0);
+
+        let err = load(true).unwrap_err();
+        assert_eq!(err.to_string(), "failed to load config");
+        assert_eq!(test_sink.log_count(), 1);
+        let records = test_sink.records();
+        assert_eq!(records[0].level(), Level::Error);
+        assert_eq!(
+            records[0].payload(),
+            "loading app: failed to load config: caused by: failed to read file: caused by: permission denied"
+        );
+        assert_eq!(records[0].key_values().count(), 3);
+
+        test_sink.reset();
+        let value =
+            crate::warn_log!(logger: test_logger, Err::<u32, _>(three_level_error()), "ignored");
+        assert_eq!(value, None);
+        let value =
+            crate::warn_log!(logger: test_logger, level: Level::Info, Ok::<_, fmt::Error>(1), "ok");
+        assert_eq!(value, Some(1));
+        let records = test_sink.records();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].level(), Level::Warn);
+        assert!(records[0]
+            .payload()
+            .starts_with("ignored: failed to load config"));
+    }
+}
@@ -1,12 +1,18 @@
 //! Provides a full info formatter.
 
-use std::fmt::{self, Write};
+use std::{
+    borrow::Cow,
+    fmt::{self, Write},
+    io,
+};
 
 use cfg_if::cfg_if;
 
 use crate::{
-    formatter::{FmtExtraInfo, Formatter, LOCAL_TIME_CACHER},
-    Error, Record, StringBuf, EOL,
+    formatter::{
+        local_time_cacher, FmtExtraInfo, Formatter, Humanize, LevelLabels, StyleHint, TimeCache,
+    },
+    Error, LevelFilter, Record, StringBuf, EOL,
 };
 
 #[rustfmt::skip]
@@ -33,56 +39,265 @@ use crate::{
 ///    <pre>
 ///    [2022-11-02 09:23:12.263] [<font color="#11D116">info</font>] [mod::path, src/main.rs:4] hello, world!
 ///    </pre>
+///
+///  - If the record has key-values, with the default [`FieldStyle::Inline`]:
+///
+///    <pre>
+///    [2022-11-02 09:23:12.263] [<font color="#11D116">info</font>] hello, world! user=alice attempt=3
+///    </pre>
+///
+///  - If the record has key-values, with [`FieldStyle::Pretty`]:
+///
+///    <pre>
+///    [2022-11-02 09:23:12.263] [<font color="#11D116">info</font>] hello, world!
+///        user: alice
+///        attempt: 3
+///    </pre>
+///
+/// The sub-second part of the time is rendered in milliseconds with a fixed
+/// width by default, see [`FullFormatter::subsecond_style`] to trim it. Levels
+/// are labeled as [`Level::as_str`] returns by default, see
+/// [`FullFormatter::level_labels`] to customize them. The source location is
+/// included for all levels by default, see
+/// [`FullFormatter::source_location_filter`] to limit it. Values of key-values
+/// can be humanized with [`FieldStyle::Pretty`], see
+/// [`FullFormatter::humanize_field`].
+///
+/// [`Level::as_str`]: crate::Level::as_str
 #[derive(Clone)]
 pub struct FullFormatter {
     with_eol: bool,
+    field_style: FieldStyle,
+    subsecond_style: SubsecondStyle,
+    level_labels: LevelLabels,
+    source_location_filter: LevelFilter,
+    humanized_fields: Vec<(Cow<'static, str>, Humanize)>,
+    time_cache: TimeCache,
+}
+
+/// Layouts of the [key-values] of records for [`FullFormatter`].
+///
+/// [key-values]: crate::Record::key_values
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[non_exhaustive]
+pub enum FieldStyle {
+    /// Key-values are appended to the payload in the [logfmt] style, e.g.
+    /// `user=alice note="hello world"`. A value is quoted and escaped if it's
+    /// empty or contains spaces, quotes, `=` or control characters.
+    ///
+    /// It's compact and suitable for production.
+    ///
+    /// [logfmt]: https://brandur.org/logfmt
+    Inline,
+    /// Key-values are written as an indented block under the payload, one
+    /// key-value per line, e.g. `    user: alice`. Following lines of a
+    /// multi-line value are further indented to be distinguished from keys.
+    ///
+    /// It's readable for local development on the console.
+    Pretty,
+}
+
+/// Renderings of the sub-second part of the time for [`FullFormatter`].
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[non_exhaustive]
+pub enum SubsecondStyle {
+    /// Milliseconds are always rendered in 3 digits, e.g. `12:00:00.500`,
+    /// `12:00:00.050` and `12:00:00.000`.
+    Fixed,
+    /// Trailing zeros of milliseconds are trimmed, and the fraction is omitted
+    /// along with the `.` if it's zero, e.g. `12:00:00.5`, `12:00:00.05` and
+    /// `12:00:00`.
+    ///
+    /// The width of the time varies, so columns of log lines are not aligned.
+    Trimmed,
 }
 
 impl FullFormatter {
     /// Constructs a `FullFormatter`.
     #[must_use]
     pub fn new() -> FullFormatter {
-        FullFormatter { with_eol: true }
+        FullFormatter {
+            with_eol: true,
+            field_style: FieldStyle::Inline,
+            subsecond_style: SubsecondStyle::Fixed,
+            level_labels: LevelLabels::new(),
+            source_location_filter: LevelFilter::All,
+            humanized_fields: Vec::new(),
+            time_cache: TimeCache::PerSecond,
+        }
     }
 
+    /// Constructs a `FullFormatter` with the given layout of key-values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spdlog::{
+    ///     formatter::{FieldStyle, FullFormatter},
+    ///     sink::{StdStream, StdStreamSink},
+    /// };
+    ///
+    /// # fn main() -> Result<(), spdlog::Error> {
+    /// let sink = StdStreamSink::builder()
+    ///     .std_stream(StdStream::Stdout)
+    ///     .formatter(Box::new(FullFormatter::with_field_style(FieldStyle::Pretty)))
+    ///     .build()?;
+    /// # Ok(()) }
+    /// ```
     #[must_use]
-    pub(crate) fn without_eol() -> Self {
-        Self { with_eol: false }
+    pub fn with_field_style(field_style: FieldStyle) -> FullFormatter {
+        FullFormatter {
+            field_style,
+            ..Self::new()
+        }
     }
 
-    fn format_impl(
-        &self,
-        record: &Record,
-        dest: &mut StringBuf,
-    ) -> Result<FmtExtraInfo, fmt::Error> {
-        cfg_if! {
-            if #[cfg(not(feature = "flexible-string"))] {
-                dest.reserve(crate::string_buf::RESERVE_SIZE);
-            }
+    /// Sets the rendering of the sub-second part of the time.
+    ///
+    /// Defaults to [`SubsecondStyle::Fixed`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spdlog::formatter::{FullFormatter, SubsecondStyle};
+    ///
+    /// // [2022-11-02 09:23:12.5] [info] hello, world!
+    /// let formatter = FullFormatter::new().subsecond_style(SubsecondStyle::Trimmed);
+    /// ```
+    #[must_use]
+    pub fn subsecond_style(mut self, subsecond_style: SubsecondStyle) -> Self {
+        self.subsecond_style = subsecond_style;
+        self
+    }
+
+    /// Sets the caching policy of the local date and time.
+    ///
+    /// See [`TimeCache`] for details.
+    ///
+    /// Defaults to [`TimeCache::PerSecond`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spdlog::formatter::{FullFormatter, TimeCache};
+    ///
+    /// let formatter = FullFormatter::new().time_cache(TimeCache::None);
+    /// ```
+    #[must_use]
+    pub fn time_cache(mut self, policy: TimeCache) -> Self {
+        self.time_cache = policy;
+        self
+    }
+
+    /// Sets the labels of levels.
+    ///
+    /// The style range of the level brackets the custom label. See
+    /// [`LevelLabels`] for examples.
+    ///
+    /// Defaults to [`LevelLabels::new`].
+    #[must_use]
+    pub fn level_labels(mut self, level_labels: LevelLabels) -> Self {
+        self.level_labels = level_labels;
+        self
+    }
+
+    /// Sets the levels whose records include the source location.
+    ///
+    /// The source location of the records at other levels is omitted, e.g.
+    /// with `LevelFilter::MoreSevereEqual(Level::Error)`, it's only included
+    /// for errors and critical errors, keeping other records clean. It has no
+    /// effect if crate feature `source-location` is disabled.
+    ///
+    /// Defaults to [`LevelFilter::All`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spdlog::{formatter::FullFormatter, Level, LevelFilter};
+    ///
+    /// let formatter = FullFormatter::new()
+    ///     .source_location_filter(LevelFilter::MoreSevereEqual(Level::Error));
+    /// ```
+    #[must_use]
+    pub fn source_location_filter(mut self, level_filter: LevelFilter) -> Self {
+        self.source_location_filter = level_filter;
+        self
+    }
+
+    /// Humanizes the values of the key-values with the given key, e.g.
+    /// `bytes_sent: 1.5 MiB` and `elapsed: 2m3s`.
+    ///
+    /// It only takes effect with [`FieldStyle::Pretty`], which is intended for
+    /// humans. Values are kept raw with [`FieldStyle::Inline`], and by other
+    /// formatters, to be parsed by machines. Values that are not numbers of
+    /// the expected form are written as is. It can be called multiple times
+    /// for different keys.
+    ///
+    /// Defaults to no humanized key-values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spdlog::formatter::{FieldStyle, FullFormatter, Humanize};
+    ///
+    /// let formatter = FullFormatter::with_field_style(FieldStyle::Pretty)
+    ///     .humanize_field("bytes_sent", Humanize::BinaryBytes)
+    ///     .humanize_field("elapsed_ms", Humanize::Milliseconds);
+    /// ```
+    #[must_use]
+    pub fn humanize_field<K>(mut self, key: K, humanize: Humanize) -> Self
+    where
+        K: Into<Cow<'static, str>>,
+    {
+        let key = key.into();
+        self.humanized_fields.retain(|(k, _)| *k != key);
+        self.humanized_fields.push((key, humanize));
+        self
+    }
+
+    #[must_use]
+    pub(crate) fn without_eol() -> Self {
+        Self {
+            with_eol: false,
+            ..Self::new()
         }
+    }
 
+    fn format_impl<D>(&self, record: &Record, dest: &mut D) -> Result<FmtExtraInfo, fmt::Error>
+    where
+        D: Dest,
+    {
         {
-            let mut local_time_cacher = LOCAL_TIME_CACHER.lock();
+            let mut local_time_cacher = local_time_cacher(self.time_cache);
             let time = local_time_cacher.get(record.time());
             dest.write_str("[")?;
             dest.write_str(&time.full_second_str())?;
-            dest.write_str(".")?;
-            write!(dest, "{:03}", time.millisecond())?;
+            write_millisecond(dest, time.millisecond(), self.subsecond_style)?;
             dest.write_str("] [")?;
         }
 
+        let mut extra_info = FmtExtraInfo::builder();
+
         if let Some(logger_name) = record.logger_name() {
+            let logger_name_begin = dest.written_len();
             dest.write_str(logger_name)?;
+            extra_info = extra_info.style_range_with_hint(
+                logger_name_begin..dest.written_len(),
+                StyleHint::LoggerName,
+            );
             dest.write_str("] [")?;
         }
 
-        let style_range_begin = dest.len();
+        let style_range_begin = dest.written_len();
 
-        dest.write_str(record.level().as_str())?;
+        dest.write_str(self.level_labels.get(record.level()))?;
 
-        let style_range_end = dest.len();
+        let style_range_end = dest.written_len();
 
-        if let Some(srcloc) = record.source_location() {
+        if let Some(srcloc) = record
+            .source_location()
+            .filter(|_| self.source_location_filter.compare(record.level()))
+        {
             dest.write_str("] [")?;
             dest.write_str(srcloc.module_path())?;
             dest.write_str(", ")?;
@@ -94,21 +309,175 @@ impl FullFormatter {
         dest.write_str("] ")?;
         dest.write_str(record.payload())?;
 
+        for (key, value) in record.key_values() {
+            match self.field_style {
+                FieldStyle::Inline => dest.write_str(" ")?,
+                FieldStyle::Pretty => {
+                    dest.write_str(EOL)?;
+                    dest.write_str(PRETTY_INDENT)?;
+                }
+            }
+
+            let key_begin = dest.written_len();
+            dest.write_str(key)?;
+            extra_info =
+                extra_info.style_range_with_hint(key_begin..dest.written_len(), StyleHint::Key);
+
+            dest.write_str(match self.field_style {
+                FieldStyle::Inline => "=",
+                FieldStyle::Pretty => ": ",
+            })?;
+
+            let value_begin = dest.written_len();
+            match self.field_style {
+                FieldStyle::Inline => write_logfmt_value(dest, value)?,
+                FieldStyle::Pretty => {
+                    let humanize = self
+                        .humanized_fields
+                        .iter()
+                        .find(|(k, _)| k == key)
+                        .map(|(_, humanize)| *humanize);
+                    match humanize {
+                        Some(humanize) if humanize.write(dest, value)? => {}
+                        _ => write_pretty_value(dest, value)?,
+                    }
+                }
+            }
+            extra_info =
+                extra_info.style_range_with_hint(value_begin..dest.written_len(), StyleHint::Value);
+        }
+
         if self.with_eol {
             dest.write_str(EOL)?;
         }
 
-        Ok(FmtExtraInfo {
-            style_range: Some(style_range_begin..style_range_end),
-        })
+        Ok(extra_info
+            .style_range(style_range_begin..style_range_end)
+            .build())
+    }
+}
+
+const PRETTY_INDENT: &str = "    ";
+
+// A destination of `FullFormatter`, the written length locates style ranges.
+trait Dest: Write {
+    fn written_len(&self) -> usize;
+}
+
+impl Dest for StringBuf {
+    fn written_len(&self) -> usize {
+        self.len()
+    }
+}
+
+// Streams the formatted text to an `io::Write`, keeping the I/O error which
+// `fmt::Error` cannot carry.
+struct IoDest<'a> {
+    inner: &'a mut dyn io::Write,
+    written_len: usize,
+    error: Option<io::Error>,
+}
+
+impl Write for IoDest<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.inner.write_all(s.as_bytes()).map_err(|err| {
+            self.error = Some(err);
+            fmt::Error
+        })?;
+        self.written_len += s.len();
+        Ok(())
+    }
+}
+
+impl Dest for IoDest<'_> {
+    fn written_len(&self) -> usize {
+        self.written_len
+    }
+}
+
+fn write_millisecond(
+    dest: &mut impl Write,
+    millisecond: u32,
+    subsecond_style: SubsecondStyle,
+) -> fmt::Result {
+    match subsecond_style {
+        SubsecondStyle::Fixed => write!(dest, ".{:03}", millisecond),
+        SubsecondStyle::Trimmed if millisecond == 0 => Ok(()),
+        SubsecondStyle::Trimmed => {
+            let (mut value, mut width) = (millisecond, 3);
+            while value % 10 == 0 {
+                value /= 10;
+                width -= 1;
+            }
+            write!(dest, ".{:0width$}", value, width = width)
+        }
+    }
+}
+
+pub(super) fn write_logfmt_value(dest: &mut impl Write, value: &str) -> fmt::Result {
+    let needs_quote = value.is_empty()
+        || value
+            .chars()
+            .any(|ch| ch == ' ' || ch == '"' || ch == '=' || ch.is_control());
+    if !needs_quote {
+        return dest.write_str(value);
+    }
+
+    dest.write_char('"')?;
+    for ch in value.chars() {
+        match ch {
+            '"' => dest.write_str(r#"\""#)?,
+            '\\' => dest.write_str(r"\\")?,
+            '\n' => dest.write_str(r"\n")?,
+            '\r' => dest.write_str(r"\r")?,
+            '\t' => dest.write_str(r"\t")?,
+            ch if ch.is_control() => write!(dest, r"\u{:04x}", ch as u32)?,
+            ch => dest.write_char(ch)?,
+        }
+    }
+    dest.write_char('"')
+}
+
+// Following lines of a multi-line value are indented twice, the line breaks are
+// normalized to `EOL`.
+fn write_pretty_value(dest: &mut impl Write, value: &str) -> fmt::Result {
+    for (i, line) in value.lines().enumerate() {
+        if i != 0 {
+            dest.write_str(EOL)?;
+            dest.write_str(PRETTY_INDENT)?;
+            dest.write_str(PRETTY_INDENT)?;
+        }
+        dest.write_str(line)?;
     }
+    Ok(())
 }
 
 impl Formatter for FullFormatter {
     fn format(&self, record: &Record, dest: &mut StringBuf) -> crate::Result<FmtExtraInfo> {
+        cfg_if! {
+            if #[cfg(not(feature = "flexible-string"))] {
+                dest.reserve(crate::string_buf::RESERVE_SIZE);
+            }
+        }
+
         self.format_impl(record, dest).map_err(Error::FormatRecord)
     }
 
+    fn format_stream(&self, record: &Record, dest: &mut dyn io::Write) -> crate::Result<()> {
+        let mut dest = IoDest {
+            inner: dest,
+            written_len: 0,
+            error: None,
+        };
+        match self.format_impl(record, &mut dest) {
+            Ok(_) => Ok(()),
+            Err(err) => Err(match dest.error {
+                Some(err) => Error::WriteRecord(err),
+                None => Error::FormatRecord(err),
+            }),
+        }
+    }
+
     fn clone_box(&self) -> Box<dyn Formatter> {
         Box::new(self.clone())
     }
@@ -122,10 +491,12 @@ impl Default for FullFormatter {
 
 #[cfg(test)]
 mod tests {
+    use std::time::{Duration, SystemTime};
+
     use chrono::prelude::*;
 
     use super::*;
-    use crate::{Level, EOL};
+    use crate::{Level, SourceLocation, EOL};
 
     #[test]
     fn format() {
@@ -144,4 +515,210 @@ mod tests {
         );
         assert_eq!(Some(27..31), extra_info.style_range());
     }
+
+    #[test]
+    fn logger_name_style_range() {
+        let record = Record::builder(Level::Warn, "test log content")
+            .logger_name("my-logger")
+            .build();
+        let mut buf = StringBuf::new();
+        let extra_info = FullFormatter::new().format(&record, &mut buf).unwrap();
+
+        assert_eq!(&buf[27..36], "my-logger");
+        assert_eq!(&buf[39..43], "warn");
+        assert_eq!(
+            extra_info.style_ranges().collect::<Vec<_>>(),
+            [(27..36, StyleHint::LoggerName), (39..43, StyleHint::Level)]
+        );
+        assert_eq!(Some(39..43), extra_info.style_range());
+    }
+
+    #[test]
+    fn style_ranges() {
+        let info = FmtExtraInfo::builder()
+            .style_range_with_hint(20..25, StyleHint::Value)
+            .style_range(10..15)
+            .style_range_with_hint(0..5, StyleHint::LoggerName)
+            .style_range_with_hint(16..19, StyleHint::Key)
+            .build();
+        assert_eq!(info.style_range(), Some(10..15));
+        assert_eq!(
+            info.style_ranges().collect::<Vec<_>>(),
+            [
+                (0..5, StyleHint::LoggerName),
+                (10..15, StyleHint::Level),
+                (16..19, StyleHint::Key),
+                (20..25, StyleHint::Value)
+            ]
+        );
+
+        let info = FmtExtraInfo::builder()
+            .style_range_with_hint(0..5, StyleHint::Level)
+            .build();
+        assert_eq!(info.style_range(), Some(0..5));
+        assert_eq!(
+            info.style_ranges().collect::<Vec<_>>(),
+            [(0..5, StyleHint::Level)]
+        );
+        assert_eq!(FmtExtraInfo::new().style_ranges().count(), 0);
+    }
+
+    #[test]
+    fn subsecond_style() {
+        let format = |millisecond, style| {
+            let mut buf = StringBuf::new();
+            write_millisecond(&mut buf, millisecond, style).unwrap();
+            buf
+        };
+
+        for (millisecond, fixed, trimmed) in [
+            (500, ".500", ".5"),
+            (50, ".050", ".05"),
+            (5, ".005", ".005"),
+            (120, ".120", ".12"),
+            (0, ".000", ""),
+        ] {
+            assert_eq!(format(millisecond, SubsecondStyle::Fixed), fixed);
+            assert_eq!(format(millisecond, SubsecondStyle::Trimmed), trimmed);
+        }
+
+        let format_record = |millisecond: u64, formatter: FullFormatter| {
+            let time = SystemTime::UNIX_EPOCH + Duration::from_millis(1_000_000 + millisecond);
+            let record = Record::builder(Level::Info, "hi").time(time).build();
+            let mut buf = StringBuf::new();
+            formatter.format(&record, &mut buf).unwrap();
+            let local_time: DateTime<Local> = time.into();
+            (buf, local_time.format("[%Y-%m-%d %H:%M:%S").to_string())
+        };
+
+        let (buf, full_second) = format_record(500, FullFormatter::new());
+        assert!(buf.starts_with(&format!("{}.500] [info] hi", full_second)));
+        let trimmed = FullFormatter::new().subsecond_style(SubsecondStyle::Trimmed);
+        let (buf, full_second) = format_record(500, trimmed.clone());
+        assert!(buf.starts_with(&format!("{}.5] [info] hi", full_second)));
+        let (buf, full_second) = format_record(0, trimmed);
+        assert!(buf.starts_with(&format!("{}] [info] hi", full_second)));
+    }
+
+    #[test]
+    fn source_location_filter() {
+        let formatter =
+            FullFormatter::new().source_location_filter(LevelFilter::MoreSevereEqual(Level::Error));
+        let format = |level| {
+            let record = Record::builder(level, "hello")
+                .source_location(Some(SourceLocation::__new("my_mod", "src/my_mod.rs", 7, 1)))
+                .build();
+            let mut buf = StringBuf::new();
+            formatter.format(&record, &mut buf).unwrap();
+            buf
+        };
+
+        assert!(format(Level::Info).ends_with(&format!("] [info] hello{}", EOL)));
+        assert!(format(Level::Error)
+            .ends_with(&format!("] [error] [my_mod, src/my_mod.rs:7] hello{}", EOL)));
+        assert!(format(Level::Critical).contains("[my_mod, src/my_mod.rs:7]"));
+    }
+
+    #[must_use]
+    fn three_fields_record() -> Record<'static> {
+        Record::builder(Level::Info, "login failed")
+            .key_values(vec![
+                ("user".into(), "alice".into()),
+                ("attempt".into(), "3".into()),
+                ("query".into(), "SELECT *\nFROM \"users\"".into()),
+            ])
+            .build()
+    }
+
+    #[test]
+    fn key_values_inline() {
+        let record = three_fields_record();
+        let mut buf = StringBuf::new();
+        let extra_info = FullFormatter::new().format(&record, &mut buf).unwrap();
+
+        let expected = format!(
+            r#"login failed user=alice attempt=3 query="SELECT *\nFROM \"users\""{}"#,
+            EOL
+        );
+        assert!(buf.ends_with(&expected));
+
+        let styled = extra_info
+            .style_ranges()
+            .filter(|(_, hint)| *hint != StyleHint::Level)
+            .map(|(range, hint)| (&buf[range], hint))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            styled,
+            [
+                ("user", StyleHint::Key),
+                ("alice", StyleHint::Value),
+                ("attempt", StyleHint::Key),
+                ("3", StyleHint::Value),
+                ("query", StyleHint::Key),
+                (r#""SELECT *\nFROM \"users\"""#, StyleHint::Value)
+            ]
+        );
+    }
+
+    #[test]
+    fn key_values_pretty() {
+        let record = three_fields_record();
+        let mut buf = StringBuf::new();
+        FullFormatter::with_field_style(FieldStyle::Pretty)
+            .format(&record, &mut buf)
+            .unwrap();
+
+        let lines = buf.split(EOL).collect::<Vec<_>>();
+        assert!(lines[0].ends_with("] [info] login failed"));
+        assert_eq!(
+            lines[1..],
+            [
+                "    user: alice",
+                "    attempt: 3",
+                "    query: SELECT *",
+                "        FROM \"users\"",
+                ""
+            ]
+        );
+    }
+
+    #[test]
+    fn humanize_field() {
+        let record = Record::builder(Level::Info, "sent")
+            .key_values(vec![
+                ("bytes_sent".into(), "1572864".into()),
+                ("elapsed_ms".into(), "123000".into()),
+                ("retries".into(), "3".into()),
+                ("bytes_received".into(), "unknown".into()),
+            ])
+            .build();
+        let format = |field_style| {
+            let mut buf = StringBuf::new();
+            FullFormatter::with_field_style(field_style)
+                .humanize_field("bytes_sent", Humanize::BinaryBytes)
+                .humanize_field("elapsed_ms", Humanize::Milliseconds)
+                .humanize_field("bytes_received", Humanize::DecimalBytes)
+                .format(&record, &mut buf)
+                .unwrap();
+            buf
+        };
+
+        let pretty = format(FieldStyle::Pretty);
+        assert_eq!(
+            pretty.split(EOL).skip(1).collect::<Vec<_>>(),
+            [
+                "    bytes_sent: 1.5 MiB",
+                "    elapsed_ms: 2m3s",
+                "    retries: 3",
+                "    bytes_received: unknown",
+                ""
+            ]
+        );
+
+        let inline = format(FieldStyle::Inline);
+        assert!(inline.ends_with(&format!(
+            "sent bytes_sent=1572864 elapsed_ms=123000 retries=3 bytes_received=unknown{}",
+            EOL
+        )));
+    }
 }
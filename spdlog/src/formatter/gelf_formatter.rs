@@ -0,0 +1,268 @@
+//! Provides a GELF formatter.
+
+use std::{
+    fmt::{self, Write},
+    time::UNIX_EPOCH,
+};
+
+use crate::{
+    formatter::{FmtExtraInfo, Formatter},
+    Error, Record, StringBuf,
+};
+
+/// A formatter that formats records as [GELF] (Graylog Extended Log Format)
+/// 1.1 JSON objects.
+///
+/// A formatted record looks like (wrapped for readability):
+///
+/// ```json
+/// {"version":"1.1","host":"my-host","short_message":"first line",
+///  "full_message":"first line\nsecond line","timestamp":1668000000.123,
+///  "level":6,"_logger":"my-logger","_module_path":"my_crate::module",
+///  "_file":"src/main.rs","_line":4,"_thread_id":1234,"_user":"alice"}
+/// ```
+///
+/// - `short_message` is the first non-blank line of the payload, or `"(empty)"`
+///   if the payload is blank, since GELF requires it to be non-empty.
+///   `full_message` is the whole payload, which is only present if it's
+///   non-empty and differs from `short_message`.
+///
+/// - `timestamp` is the seconds since UNIX epoch with millisecond precision.
+///
+/// - `level` is the syslog severity of the record level, see
+///   [`Level::to_gelf`].
+///
+/// - `_logger` is present if the logger has a name, `_module_path`, `_file` and
+///   `_line` are present if the record has a source location.
+///
+/// - The [key-values] of the record are additional fields prefixed with `_`,
+///   their values are strings. Characters of keys other than ASCII
+///   alphanumerics, `_`, `.` and `-` are replaced with `_`, as required by
+///   GELF. The key `id`, which GELF forbids, and keys conflicting with the
+///   fields above are prefixed with another `_`, e.g. `line` is written as
+///   `__line`.
+///
+/// No delimiter is appended. For GELF over UDP, a datagram contains exactly one
+/// record, use it with [`UdpSink`] which supports chunking large messages.
+///
+/// [GELF]: https://go2docs.graylog.org/current/getting_in_log_data/gelf.html
+/// [`UdpSink`]: crate::sink::UdpSink
+/// [key-values]: crate::Record::key_values
+/// [`Level::to_gelf`]: crate::Level::to_gelf
+#[derive(Clone)]
+pub struct GelfFormatter {
+    host: String,
+}
+
+impl GelfFormatter {
+    /// Constructs a `GelfFormatter`.
+    ///
+    /// The parameter `host` is the name of the host sending the message, it's
+    /// written to the `host` field.
+    #[must_use]
+    pub fn new<S>(host: S) -> GelfFormatter
+    where
+        S: Into<String>,
+    {
+        GelfFormatter { host: host.into() }
+    }
+
+    fn format_impl(
+        &self,
+        record: &Record,
+        dest: &mut StringBuf,
+    ) -> Result<FmtExtraInfo, fmt::Error> {
+        let payload = record.payload();
+        let short_message = payload
+            .lines()
+            .find(|line| !line.trim().is_empty())
+            .unwrap_or(EMPTY_SHORT_MESSAGE);
+
+        dest.write_str(r#"{"version":"1.1","host":"#)?;
+        write_json_str(dest, &self.host)?;
+        dest.write_str(r#","short_message":"#)?;
+        write_json_str(dest, short_message)?;
+        if !payload.is_empty() && short_message != payload {
+            dest.write_str(r#","full_message":"#)?;
+            write_json_str(dest, payload)?;
+        }
+        dest.write_str(r#","timestamp":"#)?;
+        // Times before UNIX epoch are clamped to it, as GELF does not define them
+        let since_epoch = record.time().duration_since(UNIX_EPOCH).unwrap_or_default();
+        write!(
+            dest,
+            "{}.{:03}",
+            since_epoch.as_secs(),
+            since_epoch.subsec_millis()
+        )?;
+        write!(dest, r#","level":{}"#, record.level().to_gelf())?;
+
+        if let Some(logger_name) = record.logger_name() {
+            dest.write_str(r#","_logger":"#)?;
+            write_json_str(dest, logger_name)?;
+        }
+        if let Some(srcloc) = record.source_location() {
+            dest.write_str(r#","_module_path":"#)?;
+            write_json_str(dest, srcloc.module_path())?;
+            dest.write_str(r#","_file":"#)?;
+            write_json_str(dest, srcloc.file())?;
+            write!(dest, r#","_line":{}"#, srcloc.line())?;
+        }
+        write!(dest, r#","_thread_id":{}"#, record.tid())?;
+        for (key, value) in record.key_values() {
+            dest.write_str(r#","_"#)?;
+            write_field_name(dest, key)?;
+            dest.write_str(r#"":"#)?;
+            write_json_str(dest, value)?;
+        }
+        dest.write_str("}")?;
+
+        Ok(FmtExtraInfo::new())
+    }
+}
+
+// GELF rejects messages with an empty `short_message`
+const EMPTY_SHORT_MESSAGE: &str = "(empty)";
+
+// Keys of key-values that would be written as `_id`, which GELF forbids, or as
+// one of the additional fields written by `GelfFormatter`
+const RESERVED_KEYS: &[&str] = &["id", "logger", "module_path", "file", "line", "thread_id"];
+
+// Writes a key of key-values as an additional field name without the leading
+// `_`, which must match `^[\w\.\-]*$`
+fn write_field_name(dest: &mut StringBuf, key: &str) -> fmt::Result {
+    if RESERVED_KEYS.contains(&key) {
+        dest.write_char('_')?;
+    }
+    for ch in key.chars() {
+        if ch.is_ascii_alphanumeric() || matches!(ch, '_' | '.' | '-') {
+            dest.write_char(ch)?;
+        } else {
+            dest.write_char('_')?;
+        }
+    }
+    Ok(())
+}
+
+impl Formatter for GelfFormatter {
+    fn format(&self, record: &Record, dest: &mut StringBuf) -> crate::Result<FmtExtraInfo> {
+        self.format_impl(record, dest).map_err(Error::FormatRecord)
+    }
+
+    fn clone_box(&self) -> Box<dyn Formatter> {
+        Box::new(self.clone())
+    }
+}
+
+pub(super) fn write_json_str(dest: &mut StringBuf, value: &str) -> fmt::Result {
+    dest.write_char('"')?;
+    write_json_escaped(dest, value)?;
+    dest.write_char('"')
+}
+
+pub(super) fn write_json_escaped(dest: &mut StringBuf, value: &str) -> fmt::Result {
+    for ch in value.chars() {
+        match ch {
+            '"' => dest.write_str(r#"\""#)?,
+            '\\' => dest.write_str(r"\\")?,
+            '\n' => dest.write_str(r"\n")?,
+            '\r' => dest.write_str(r"\r")?,
+            '\t' => dest.write_str(r"\t")?,
+            ch if ch.is_control() => write!(dest, r"\u{:04x}", ch as u32)?,
+            ch => dest.write_char(ch)?,
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::Level;
+
+    #[test]
+    fn format() {
+        let mut record = Record::new(Level::Warn, "first \"line\"\r\nsecond\tline");
+        record.set_time(UNIX_EPOCH + Duration::from_millis(1_668_000_000_007));
+
+        let mut buf = StringBuf::new();
+        let extra_info = GelfFormatter::new("my-host")
+            .format(&record, &mut buf)
+            .unwrap();
+
+        assert_eq!(
+            buf,
+            format!(
+                r#"{{"version":"1.1","host":"my-host","short_message":"first \"line\"","full_message":"first \"line\"\r\nsecond\tline","timestamp":1668000000.007,"level":4,"_thread_id":{}}}"#,
+                record.tid()
+            )
+        );
+        assert!(extra_info.style_range().is_none());
+    }
+
+    #[test]
+    fn key_values() {
+        let record = Record::builder(Level::Info, "msg")
+            .key_values(vec![
+                ("user".into(), "alice".into()),
+                ("quote\"".into(), "a\nb".into()),
+                ("user name.é-1".into(), "bob".into()),
+                ("id".into(), "1".into()),
+                ("line".into(), "2".into()),
+                ("thread_id".into(), "3".into()),
+            ])
+            .build();
+
+        let mut buf = StringBuf::new();
+        GelfFormatter::new("my-host")
+            .format(&record, &mut buf)
+            .unwrap();
+        assert!(buf.ends_with(
+            r#","_user":"alice","_quote_":"a\nb","_user_name._-1":"bob","__id":"1","__line":"2","__thread_id":"3"}"#
+        ));
+    }
+
+    #[test]
+    fn short_message() {
+        let format_messages = |payload| {
+            let record = Record::new(Level::Info, payload);
+            let mut buf = StringBuf::new();
+            GelfFormatter::new("").format(&record, &mut buf).unwrap();
+            let short_start = buf.find(r#""short_message":"#).unwrap();
+            let end = buf.find(r#","timestamp":"#).unwrap();
+            buf[short_start..end].to_string()
+        };
+
+        assert_eq!(format_messages("msg"), r#""short_message":"msg""#);
+        assert_eq!(
+            format_messages("\n \r\nmsg\r\nmore"),
+            r#""short_message":"msg","full_message":"\n \r\nmsg\r\nmore""#
+        );
+        assert_eq!(format_messages(""), r#""short_message":"(empty)""#);
+        assert_eq!(
+            format_messages(" \n"),
+            r#""short_message":"(empty)","full_message":" \n""#
+        );
+    }
+
+    #[test]
+    fn timestamp() {
+        let format_time = |millis| {
+            let mut record = Record::new(Level::Info, "");
+            record.set_time(UNIX_EPOCH + Duration::from_millis(millis));
+
+            let mut buf = StringBuf::new();
+            GelfFormatter::new("").format(&record, &mut buf).unwrap();
+            let start = buf.find(r#""timestamp":"#).unwrap() + r#""timestamp":"#.len();
+            let end = start + buf[start..].find(',').unwrap();
+            buf[start..end].to_string()
+        };
+
+        assert_eq!(format_time(0), "0.000");
+        assert_eq!(format_time(1_500), "1.500");
+        assert_eq!(format_time(1_668_000_000_123), "1668000000.123");
+        assert_eq!(format_time(1_668_000_000_099), "1668000000.099");
+    }
+}
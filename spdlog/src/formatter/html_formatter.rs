@@ -0,0 +1,284 @@
+//! Provides an HTML formatter.
+
+use std::fmt::{self, Write};
+
+use cfg_if::cfg_if;
+
+use crate::{
+    formatter::{local_time_cacher, FmtExtraInfo, Formatter, TimeCache},
+    Error, Record, StringBuf, EOL,
+};
+
+/// A formatter that formats records as HTML fragments, for web log viewers.
+///
+/// A formatted record looks like (wrapped for readability):
+///
+/// ```html
+/// <div class="log info"><span class="time">2022-11-02 09:23:12.263</span>
+///  <span class="logger">logger-name</span> <span class="level">info</span>
+///  <span class="source">mod::path, src/main.rs:4</span>
+///  <span class="payload">hello, world!</span>
+///  <span class="kv"><span class="key">user</span>=<span class="value">alice</span></span></div>
+/// ```
+///
+/// - The `div` of each record has class `log` and the name of the record level
+///   as returned by [`Level::as_str`], i.e. one of `critical`, `error`, `warn`,
+///   `info`, `debug` and `trace`, so that levels can be styled and filtered
+///   with CSS.
+///
+/// - The `logger` span is present if the logger has a name, the `source` span
+///   is present if the record has a source location, and a `kv` span is present
+///   for each of the [key-values] of the record.
+///
+/// - `<`, `>`, `&`, `"` and `'` in the logger name, the payload and the
+///   key-values are escaped as character references, so that records cannot
+///   inject markup into the page.
+///
+/// Each record is on its own line. Line breaks in the payload are kept, and
+/// rendered as is with the `white-space: pre-wrap` rule of the
+/// [default stylesheet](HtmlFormatter::document_header).
+///
+/// # Examples
+///
+/// A sink writing to a new file can be preceded by the document header, which
+/// contains the default stylesheet, so that the file can be opened in a
+/// browser directly.
+///
+/// ```
+/// use std::{fs, sync::Arc};
+///
+/// use spdlog::{formatter::HtmlFormatter, prelude::*, sink::FileSink};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// # let path = std::env::temp_dir().join("html_formatter_example.html");
+/// # let _ = fs::remove_file(&path);
+/// if !path.exists() {
+///     fs::write(&path, HtmlFormatter::document_header())?;
+/// }
+/// let sink = FileSink::builder()
+///     .path(&path)
+///     .formatter(Box::new(HtmlFormatter::new()))
+///     .build()?;
+/// let logger = Logger::builder().sink(Arc::new(sink)).build()?;
+///
+/// info!(logger: logger, "hello, <b>world</b>!");
+/// # Ok(()) }
+/// ```
+///
+/// [`Level::as_str`]: crate::Level::as_str
+/// [key-values]: crate::Record::key_values
+#[derive(Clone, Default)]
+pub struct HtmlFormatter {
+    time_cache: TimeCache,
+}
+
+const DOCUMENT_HEADER: &str = concat!(
+    "<!DOCTYPE html>\n",
+    "<html>\n",
+    "<head>\n",
+    "<meta charset=\"utf-8\">\n",
+    "<title>Logs</title>\n",
+    "<style>\n",
+    "body { margin: 0; background: #1e1e1e; color: #d4d4d4; font: 13px monospace; }\n",
+    ".log { padding: 1px 8px; white-space: pre-wrap; word-break: break-all; }\n",
+    ".log:hover { background: #2a2d2e; }\n",
+    ".time, .source { color: #808080; }\n",
+    ".logger { color: #4ec9b0; }\n",
+    ".level { font-weight: bold; }\n",
+    ".key { color: #9cdcfe; }\n",
+    ".critical .level { color: #fff; background: #c50f1f; }\n",
+    ".error .level { color: #f14c4c; }\n",
+    ".warn .level { color: #cca700; }\n",
+    ".info .level { color: #23d18b; }\n",
+    ".debug .level { color: #3b8eea; }\n",
+    ".trace .level { color: #808080; }\n",
+    "</style>\n",
+    "</head>\n",
+    "<body>\n",
+);
+
+impl HtmlFormatter {
+    /// Constructs a `HtmlFormatter`.
+    #[must_use]
+    pub fn new() -> HtmlFormatter {
+        HtmlFormatter {
+            time_cache: TimeCache::PerSecond,
+        }
+    }
+
+    /// Sets the caching policy of the local date and time.
+    ///
+    /// See [`TimeCache`] for details.
+    ///
+    /// Defaults to [`TimeCache::PerSecond`].
+    #[must_use]
+    pub fn time_cache(mut self, policy: TimeCache) -> Self {
+        self.time_cache = policy;
+        self
+    }
+
+    /// Gets the opening boilerplate of an HTML document for the formatted
+    /// records, containing a default stylesheet for the class names.
+    ///
+    /// It leaves the `body` element open, as records are appended afterwards,
+    /// which browsers accept. Write it to the target before any records, see
+    /// the [type-level documentation](HtmlFormatter#examples) for an example.
+    #[must_use]
+    pub fn document_header() -> &'static str {
+        DOCUMENT_HEADER
+    }
+
+    fn format_impl(
+        &self,
+        record: &Record,
+        dest: &mut StringBuf,
+    ) -> Result<FmtExtraInfo, fmt::Error> {
+        cfg_if! {
+            if #[cfg(not(feature = "flexible-string"))] {
+                dest.reserve(crate::string_buf::RESERVE_SIZE);
+            }
+        }
+
+        let level = record.level().as_str();
+
+        dest.write_str(r#"<div class="log "#)?;
+        dest.write_str(level)?;
+        dest.write_str(r#""><span class="time">"#)?;
+        {
+            let mut local_time_cacher = local_time_cacher(self.time_cache);
+            let time = local_time_cacher.get(record.time());
+            dest.write_str(&time.full_second_str())?;
+            write!(dest, ".{:03}", time.millisecond())?;
+        }
+        dest.write_str("</span> ")?;
+
+        if let Some(logger_name) = record.logger_name() {
+            dest.write_str(r#"<span class="logger">"#)?;
+            write_html_escaped(dest, logger_name)?;
+            dest.write_str("</span> ")?;
+        }
+
+        dest.write_str(r#"<span class="level">"#)?;
+        dest.write_str(level)?;
+        dest.write_str("</span> ")?;
+
+        if let Some(srcloc) = record.source_location() {
+            dest.write_str(r#"<span class="source">"#)?;
+            write_html_escaped(dest, srcloc.module_path())?;
+            dest.write_str(", ")?;
+            write_html_escaped(dest, srcloc.file())?;
+            write!(dest, ":{}</span> ", srcloc.line())?;
+        }
+
+        dest.write_str(r#"<span class="payload">"#)?;
+        write_html_escaped(dest, record.payload())?;
+        dest.write_str("</span>")?;
+
+        for (key, value) in record.key_values() {
+            dest.write_str(r#" <span class="kv"><span class="key">"#)?;
+            write_html_escaped(dest, key)?;
+            dest.write_str(r#"</span>=<span class="value">"#)?;
+            write_html_escaped(dest, value)?;
+            dest.write_str("</span></span>")?;
+        }
+
+        dest.write_str("</div>")?;
+        dest.write_str(EOL)?;
+
+        Ok(FmtExtraInfo::new())
+    }
+}
+
+impl Formatter for HtmlFormatter {
+    fn format(&self, record: &Record, dest: &mut StringBuf) -> crate::Result<FmtExtraInfo> {
+        self.format_impl(record, dest).map_err(Error::FormatRecord)
+    }
+
+    fn clone_box(&self) -> Box<dyn Formatter> {
+        Box::new(self.clone())
+    }
+}
+
+fn write_html_escaped(dest: &mut StringBuf, value: &str) -> fmt::Result {
+    let mut rest = value;
+    while let Some(pos) = rest.find(|ch| matches!(ch, '<' | '>' | '&' | '"' | '\'')) {
+        dest.write_str(&rest[..pos])?;
+        dest.write_str(match rest.as_bytes()[pos] {
+            b'<' => "&lt;",
+            b'>' => "&gt;",
+            b'&' => "&amp;",
+            b'"' => "&quot;",
+            _ => "&#39;",
+        })?;
+        rest = &rest[pos + 1..];
+    }
+    dest.write_str(rest)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::prelude::*;
+
+    use super::*;
+    use crate::{Level, SourceLocation};
+
+    #[must_use]
+    fn format(record: &Record) -> StringBuf {
+        let mut buf = StringBuf::new();
+        HtmlFormatter::new().format(record, &mut buf).unwrap();
+        buf
+    }
+
+    #[test]
+    fn format_record() {
+        let record = Record::builder(Level::Warn, "hello")
+            .logger_name("net")
+            .source_location(Some(SourceLocation::__new("my_mod", "src/my_mod.rs", 7, 1)))
+            .key_values(vec![("user".into(), "alice".into())])
+            .build();
+        let local_time: DateTime<Local> = record.time().into();
+
+        assert_eq!(
+            format(&record),
+            format!(
+                concat!(
+                    r#"<div class="log warn"><span class="time">{}</span> "#,
+                    r#"<span class="logger">net</span> <span class="level">warn</span> "#,
+                    r#"<span class="source">my_mod, src/my_mod.rs:7</span> "#,
+                    r#"<span class="payload">hello</span> "#,
+                    r#"<span class="kv"><span class="key">user</span>=<span class="value">alice</span></span>"#,
+                    "</div>{}"
+                ),
+                local_time.format("%Y-%m-%d %H:%M:%S.%3f"),
+                EOL
+            )
+        );
+    }
+
+    #[test]
+    fn level_class_names() {
+        for level in Level::iter() {
+            let buf = format(&Record::new(level, "hi"));
+            assert!(buf.starts_with(&format!(r#"<div class="log {}">"#, level.as_str())));
+        }
+    }
+
+    #[test]
+    fn escape() {
+        let record = Record::builder(Level::Info, r#"<script>alert("x" & 'y')</script>"#)
+            .logger_name("a<b>")
+            .key_values(vec![("k&".into(), "\"v\"".into())])
+            .build();
+        let buf = format(&record);
+
+        assert!(buf.contains(r#"<span class="logger">a&lt;b&gt;</span>"#));
+        assert!(buf.contains(concat!(
+            r#"<span class="payload">&lt;script&gt;alert(&quot;x&quot; &amp; &#39;y&#39;)"#,
+            r#"&lt;/script&gt;</span>"#
+        )));
+        assert!(buf.contains(
+            r#"<span class="key">k&amp;</span>=<span class="value">&quot;v&quot;</span>"#
+        ));
+        assert!(!buf.contains("<script>"));
+    }
+}
@@ -0,0 +1,196 @@
+use std::fmt::{self, Write};
+
+/// Human-readable renderings of numeric key-value values.
+///
+/// Values are written as is if they are not numbers of the expected form, e.g.
+/// negative byte counts.
+///
+/// It's used by [`FullFormatter::humanize_field`].
+///
+/// [`FullFormatter::humanize_field`]: crate::formatter::FullFormatter::humanize_field
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[non_exhaustive]
+pub enum Humanize {
+    /// The value is a byte count, rendered in binary units, e.g. `1536` is
+    /// rendered as `1.5 KiB`.
+    BinaryBytes,
+    /// The value is a byte count, rendered in decimal units, e.g. `1500` is
+    /// rendered as `1.5 kB`.
+    DecimalBytes,
+    /// The value is a duration in seconds, possibly fractional, e.g. `123` is
+    /// rendered as `2m3s`, and `0.0005` is rendered as `500µs`.
+    Seconds,
+    /// The value is a duration in milliseconds, possibly fractional, e.g.
+    /// `123000` is rendered as `2m3s`, and `1.5` is rendered as `1.5ms`.
+    Milliseconds,
+}
+
+impl Humanize {
+    // Returns `false` without writing anything if the value cannot be humanized.
+    pub(crate) fn write(self, dest: &mut impl Write, value: &str) -> Result<bool, fmt::Error> {
+        match self {
+            Self::BinaryBytes | Self::DecimalBytes => match value.parse::<u64>() {
+                Ok(bytes) => write_bytes(dest, bytes, self == Self::BinaryBytes)?,
+                Err(_) => return Ok(false),
+            },
+            Self::Seconds | Self::Milliseconds => match value.parse::<f64>() {
+                Ok(value) if value.is_finite() && value >= 0.0 => {
+                    let secs = if self == Self::Seconds {
+                        value
+                    } else {
+                        value / 1000.0
+                    };
+                    write_duration(dest, secs)?
+                }
+                _ => return Ok(false),
+            },
+        }
+        Ok(true)
+    }
+}
+
+const BINARY_UNITS: [&str; 7] = ["B", "KiB", "MiB", "GiB", "TiB", "PiB", "EiB"];
+const DECIMAL_UNITS: [&str; 7] = ["B", "kB", "MB", "GB", "TB", "PB", "EB"];
+
+fn write_bytes(dest: &mut impl Write, bytes: u64, binary: bool) -> fmt::Result {
+    let (base, units) = if binary {
+        (1024.0, BINARY_UNITS)
+    } else {
+        (1000.0, DECIMAL_UNITS)
+    };
+
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    // Compare the rounded value, so that e.g. 1023.96 KiB is rendered as 1 MiB
+    while round_tenth(value) >= base && unit < units.len() - 1 {
+        value /= base;
+        unit += 1;
+    }
+    write_tenth(dest, value)?;
+    write!(dest, " {}", units[unit])
+}
+
+const SUBSECOND_UNITS: [(f64, &str); 3] = [(1e-3, "ms"), (1e-6, "µs"), (1e-9, "ns")];
+
+// Sub-minute durations are rendered in the largest fitting unit with at most
+// one decimal place, longer ones in whole seconds with hours and minutes.
+fn write_duration(dest: &mut impl Write, secs: f64) -> fmt::Result {
+    if secs == 0.0 {
+        return dest.write_str("0s");
+    }
+
+    if round_tenth(secs) < 1.0 {
+        let (scale, unit) = SUBSECOND_UNITS
+            .iter()
+            .find(|(scale, _)| round_tenth(secs / scale) >= 1.0)
+            .unwrap_or(&SUBSECOND_UNITS[SUBSECOND_UNITS.len() - 1]);
+        write_tenth(dest, secs / scale)?;
+        return dest.write_str(unit);
+    }
+    if round_tenth(secs) < 60.0 {
+        write_tenth(dest, secs)?;
+        return dest.write_str("s");
+    }
+
+    let total = secs.round() as u64;
+    let (hours, minutes, secs) = (total / 3600, total / 60 % 60, total % 60);
+    if hours != 0 {
+        write!(dest, "{}h", hours)?;
+    }
+    write!(dest, "{}m{}s", minutes, secs)
+}
+
+#[must_use]
+fn round_tenth(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
+// Writes the value with one decimal place, omitting it if it's zero.
+fn write_tenth(dest: &mut impl Write, value: f64) -> fmt::Result {
+    let value = round_tenth(value);
+    if value.fract() == 0.0 {
+        write!(dest, "{}", value as u64)
+    } else {
+        write!(dest, "{:.1}", value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[must_use]
+    fn humanize(humanize: Humanize, value: &str) -> Option<String> {
+        let mut dest = String::new();
+        humanize.write(&mut dest, value).unwrap().then(|| dest)
+    }
+
+    #[test]
+    fn bytes() {
+        for (value, binary, decimal) in [
+            ("0", "0 B", "0 B"),
+            ("999", "999 B", "999 B"),
+            ("1000", "1000 B", "1 kB"),
+            ("1024", "1 KiB", "1 kB"),
+            ("1536", "1.5 KiB", "1.5 kB"),
+            ("1572864", "1.5 MiB", "1.6 MB"),
+            ("1048575", "1 MiB", "1 MB"),
+            ("18446744073709551615", "16 EiB", "18.4 EB"),
+        ] {
+            assert_eq!(
+                humanize(Humanize::BinaryBytes, value).as_deref(),
+                Some(binary)
+            );
+            assert_eq!(
+                humanize(Humanize::DecimalBytes, value).as_deref(),
+                Some(decimal)
+            );
+        }
+
+        for value in ["-1", "1.5", "", "1 KiB"] {
+            assert_eq!(humanize(Humanize::BinaryBytes, value), None);
+            assert_eq!(humanize(Humanize::DecimalBytes, value), None);
+        }
+    }
+
+    #[test]
+    fn duration() {
+        for (secs, expected) in [
+            ("0", "0s"),
+            ("0.0", "0s"),
+            ("0.0000000004", "0.4ns"),
+            ("0.0000005", "500ns"),
+            ("0.0000015", "1.5µs"),
+            ("0.0005", "500µs"),
+            ("0.00099999", "1ms"),
+            ("0.25", "250ms"),
+            ("1", "1s"),
+            ("3.25", "3.3s"),
+            ("59.99", "1m0s"),
+            ("123", "2m3s"),
+            ("3600", "1h0m0s"),
+            ("3723.4", "1h2m3s"),
+            ("180000", "50h0m0s"),
+        ] {
+            assert_eq!(humanize(Humanize::Seconds, secs).as_deref(), Some(expected));
+        }
+
+        assert_eq!(
+            humanize(Humanize::Milliseconds, "123000").as_deref(),
+            Some("2m3s")
+        );
+        assert_eq!(
+            humanize(Humanize::Milliseconds, "1.5").as_deref(),
+            Some("1.5ms")
+        );
+        assert_eq!(
+            humanize(Humanize::Milliseconds, "0.2").as_deref(),
+            Some("200µs")
+        );
+
+        for value in ["-1", "NaN", "inf", "", "2m3s"] {
+            assert_eq!(humanize(Humanize::Seconds, value), None);
+            assert_eq!(humanize(Humanize::Milliseconds, value), None);
+        }
+    }
+}
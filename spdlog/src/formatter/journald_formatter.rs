@@ -49,9 +49,9 @@ impl JournaldFormatter {
         dest.write_str(record.payload())?;
         dest.write_str(EOL)?;
 
-        Ok(FmtExtraInfo {
-            style_range: Some(style_range_begin..style_range_end),
-        })
+        Ok(FmtExtraInfo::builder()
+            .style_range(style_range_begin..style_range_end)
+            .build())
     }
 }
 
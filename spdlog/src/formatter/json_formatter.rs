@@ -0,0 +1,165 @@
+//! Provides a JSON formatter.
+
+use std::{
+    fmt::{self, Write},
+    time::UNIX_EPOCH,
+};
+
+use crate::{
+    formatter::{
+        gelf_formatter::{write_json_escaped, write_json_str},
+        FmtExtraInfo, Formatter,
+    },
+    Error, Record, StringBuf,
+};
+
+/// A formatter that formats records as newline-delimited JSON ([NDJSON]), one
+/// object per line.
+///
+/// A formatted record looks like (wrapped for readability):
+///
+/// ```json
+/// {"timestamp":1668000000123,"level":"info","payload":"first line\nsecond line",
+///  "logger":"my-logger","module_path":"my_crate::module","file":"src/main.rs",
+///  "line":4,"tid":1234,"kv":{"user":"alice"}}
+/// ```
+///
+/// - `timestamp` is the milliseconds since UNIX epoch.
+///
+/// - `logger` is present if the logger has a name, `module_path`, `file` and
+///   `line` are present if the record has a source location, and `kv` is
+///   present if the record has [key-values].
+///
+/// Line breaks in strings are escaped, and a `\n` is appended to each object
+/// regardless of the platform, so a formatted record is always exactly one
+/// line. Combined with [`FileSink`] or [`RotatingFileSink`], every log file is
+/// valid NDJSON, since they write and rotate at record boundaries.
+///
+/// # Examples
+///
+/// ```no_run
+/// use spdlog::{
+///     formatter::JsonFormatter,
+///     sink::{RotatingFileSink, RotationPolicy},
+/// };
+///
+/// # fn main() -> Result<(), spdlog::Error> {
+/// let sink = RotatingFileSink::builder()
+///     .base_path("/path/to/app.ndjson")
+///     .rotation_policy(RotationPolicy::FileSize(10 * 1024 * 1024))
+///     .formatter(Box::new(JsonFormatter::new()))
+///     .build()?;
+/// # Ok(()) }
+/// ```
+///
+/// [NDJSON]: https://github.com/ndjson/ndjson-spec
+/// [key-values]: crate::Record::key_values
+/// [`FileSink`]: crate::sink::FileSink
+/// [`RotatingFileSink`]: crate::sink::RotatingFileSink
+#[derive(Clone, Default)]
+pub struct JsonFormatter {}
+
+impl JsonFormatter {
+    /// Constructs a `JsonFormatter`.
+    #[must_use]
+    pub fn new() -> JsonFormatter {
+        JsonFormatter {}
+    }
+
+    fn format_impl(
+        &self,
+        record: &Record,
+        dest: &mut StringBuf,
+    ) -> Result<FmtExtraInfo, fmt::Error> {
+        // Times before UNIX epoch are clamped to it
+        let since_epoch = record.time().duration_since(UNIX_EPOCH).unwrap_or_default();
+        write!(dest, r#"{{"timestamp":{}"#, since_epoch.as_millis())?;
+        write!(dest, r#","level":"{}""#, record.level().as_str())?;
+        dest.write_str(r#","payload":"#)?;
+        write_json_str(dest, record.payload())?;
+
+        if let Some(logger_name) = record.logger_name() {
+            dest.write_str(r#","logger":"#)?;
+            write_json_str(dest, logger_name)?;
+        }
+        if let Some(srcloc) = record.source_location() {
+            dest.write_str(r#","module_path":"#)?;
+            write_json_str(dest, srcloc.module_path())?;
+            dest.write_str(r#","file":"#)?;
+            write_json_str(dest, srcloc.file())?;
+            write!(dest, r#","line":{}"#, srcloc.line())?;
+        }
+        write!(dest, r#","tid":{}"#, record.tid())?;
+
+        let mut key_values = record.key_values().peekable();
+        if key_values.peek().is_some() {
+            dest.write_str(r#","kv":{"#)?;
+            for (i, (key, value)) in key_values.enumerate() {
+                if i != 0 {
+                    dest.write_char(',')?;
+                }
+                dest.write_char('"')?;
+                write_json_escaped(dest, key)?;
+                dest.write_str(r#"":"#)?;
+                write_json_str(dest, value)?;
+            }
+            dest.write_char('}')?;
+        }
+        dest.write_str("}\n")?;
+
+        Ok(FmtExtraInfo::new())
+    }
+}
+
+impl Formatter for JsonFormatter {
+    fn format(&self, record: &Record, dest: &mut StringBuf) -> crate::Result<FmtExtraInfo> {
+        self.format_impl(record, dest).map_err(Error::FormatRecord)
+    }
+
+    fn clone_box(&self) -> Box<dyn Formatter> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::Level;
+
+    #[test]
+    fn format() {
+        let mut record = Record::new(Level::Warn, "first \"line\"\r\nsecond\tline");
+        record.set_time(UNIX_EPOCH + Duration::from_millis(1_668_000_000_007));
+
+        let mut buf = StringBuf::new();
+        let extra_info = JsonFormatter::new().format(&record, &mut buf).unwrap();
+
+        assert_eq!(
+            buf,
+            format!(
+                "{{\"timestamp\":1668000000007,\"level\":\"warn\",\"payload\":\"first \\\"line\\\"\\r\\nsecond\\tline\",\"tid\":{}}}\n",
+                record.tid()
+            )
+        );
+        assert!(extra_info.style_range().is_none());
+    }
+
+    #[test]
+    fn key_values() {
+        let record = Record::builder(Level::Info, "msg")
+            .logger_name("my-logger")
+            .key_values(vec![
+                ("user".into(), "alice".into()),
+                ("quote\"".into(), "a\nb".into()),
+            ])
+            .build();
+
+        let mut buf = StringBuf::new();
+        JsonFormatter::new().format(&record, &mut buf).unwrap();
+        assert!(buf.contains(r#","logger":"my-logger","#));
+        assert!(buf.ends_with("\"kv\":{\"user\":\"alice\",\"quote\\\"\":\"a\\nb\"}}\n"));
+        assert_eq!(buf.lines().count(), 1);
+    }
+}
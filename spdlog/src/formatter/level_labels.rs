@@ -0,0 +1,140 @@
+use std::{borrow::Cow, fmt::Write};
+
+use crate::{
+    formatter::{Pattern, PatternContext},
+    level::LOG_LEVEL_NAMES,
+    Error, Level, Record, StringBuf,
+};
+
+/// Custom labels of log levels for formatters.
+///
+/// By default, levels are labeled as [`Level::as_str`] returns, e.g. `critical`
+/// and `trace`. Custom labels are useful to match an existing log schema, e.g.
+/// `FATAL` for [`Level::Critical`].
+///
+/// It's used by [`FullFormatter::level_labels`], and it is also a [`Pattern`]
+/// writing the label of the level of the record, to be used as a custom
+/// pattern for [`PatternFormatter`]. In both cases, the style range of the
+/// level brackets the custom label.
+///
+/// # Examples
+///
+/// ```
+/// use spdlog::{
+///     formatter::{pattern, FullFormatter, LevelLabels, PatternFormatter},
+///     Level,
+/// };
+///
+/// fn level_labels() -> LevelLabels {
+///     LevelLabels::new()
+///         .label(Level::Critical, "FATAL")
+///         .label(Level::Warn, "WARNING")
+/// }
+///
+/// let full_formatter = FullFormatter::new().level_labels(level_labels());
+///
+/// let pattern_formatter = PatternFormatter::new(pattern!(
+///     "[{^{$level_label}}] {payload}{eol}",
+///     {$level_label} => level_labels,
+/// ));
+/// ```
+///
+/// [`FullFormatter::level_labels`]: crate::formatter::FullFormatter::level_labels
+/// [`PatternFormatter`]: crate::formatter::PatternFormatter
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct LevelLabels {
+    labels: [Cow<'static, str>; Level::count()],
+}
+
+impl LevelLabels {
+    /// Constructs a `LevelLabels` with the default labels, i.e. those returned
+    /// by [`Level::as_str`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            labels: LOG_LEVEL_NAMES.map(Cow::Borrowed),
+        }
+    }
+
+    /// Sets the label of the specified level.
+    #[must_use]
+    pub fn label<L>(mut self, level: Level, label: L) -> Self
+    where
+        L: Into<Cow<'static, str>>,
+    {
+        self.labels[level as usize] = label.into();
+        self
+    }
+
+    /// Gets the label of the specified level.
+    #[must_use]
+    pub fn get(&self, level: Level) -> &str {
+        &self.labels[level as usize]
+    }
+}
+
+impl Default for LevelLabels {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Pattern for LevelLabels {
+    fn format(
+        &self,
+        record: &Record,
+        dest: &mut StringBuf,
+        _ctx: &mut PatternContext,
+    ) -> crate::Result<()> {
+        dest.write_str(self.get(record.level()))
+            .map_err(Error::FormatRecord)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formatter::{Formatter, FullFormatter};
+
+    fn custom_labels() -> LevelLabels {
+        LevelLabels::new()
+            .label(Level::Critical, "FATAL")
+            .label(Level::Error, "ERROR")
+            .label(Level::Warn, "WARNING")
+            .label(Level::Info, "INFO")
+            .label(Level::Debug, "DEBUG")
+            .label(Level::Trace, String::from("TRACE"))
+    }
+
+    #[test]
+    fn default_labels() {
+        let labels = LevelLabels::default();
+        for level in Level::iter() {
+            assert_eq!(labels.get(level), level.as_str());
+        }
+    }
+
+    #[test]
+    fn custom_labels_style_range() {
+        let expected = [
+            (Level::Critical, "FATAL"),
+            (Level::Error, "ERROR"),
+            (Level::Warn, "WARNING"),
+            (Level::Info, "INFO"),
+            (Level::Debug, "DEBUG"),
+            (Level::Trace, "TRACE"),
+        ];
+        let formatter = FullFormatter::new().level_labels(custom_labels());
+
+        for (level, label) in expected {
+            let record = Record::new(level, "hello");
+            let mut buf = StringBuf::new();
+            let extra_info = formatter.format(&record, &mut buf).unwrap();
+
+            let style_range = extra_info.style_range().unwrap();
+            assert_eq!(&buf[style_range.clone()], label);
+            assert_eq!(style_range.len(), label.len());
+            assert!(buf.contains(&format!("[{}] hello", label)));
+        }
+    }
+}
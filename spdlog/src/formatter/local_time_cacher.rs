@@ -9,9 +9,116 @@ use once_cell::sync::Lazy;
 
 use crate::sync::*;
 
-pub(crate) static LOCAL_TIME_CACHER: Lazy<SpinMutex<LocalTimeCacher>> =
+static PER_SECOND_TIME_CACHER: Lazy<SpinMutex<LocalTimeCacher>> =
     Lazy::new(|| SpinMutex::new(LocalTimeCacher::new()));
 
+static MANUAL_TIME_CACHER: Lazy<SpinMutex<LocalTimeCacher>> =
+    Lazy::new(|| SpinMutex::new(LocalTimeCacher::new()));
+
+/// Caching policies of the local date and time for built-in formatters.
+///
+/// Converting the time of a record to the local date and time is expensive, so
+/// [`FullFormatter`], [`HtmlFormatter`] and the date and time patterns of
+/// [`PatternFormatter`] cache the converted date and time, and the strings
+/// formatted from them.
+///
+/// The policy is set per formatter, e.g. with [`FullFormatter::time_cache`].
+/// Formatters with the same policy share a cache.
+///
+/// [`FullFormatter`]: crate::formatter::FullFormatter
+/// [`FullFormatter::time_cache`]: crate::formatter::FullFormatter::time_cache
+/// [`HtmlFormatter`]: crate::formatter::HtmlFormatter
+/// [`PatternFormatter`]: crate::formatter::PatternFormatter
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[non_exhaustive]
+pub enum TimeCache {
+    /// The cache is updated when a record of a different second than the
+    /// cached one is formatted.
+    ///
+    /// This is the default.
+    PerSecond,
+    /// The cache is not used, the local date and time are converted for each
+    /// record.
+    ///
+    /// This avoids the cache overhead and the shared lock for low-rate
+    /// logging.
+    None,
+    /// The cache is only updated by calling [`refresh_time_cache`], e.g. from a
+    /// timer of the application.
+    ///
+    /// This avoids the conversion on the formatting path completely for
+    /// extremely high-rate logging, at the cost of accuracy. The whole date and
+    /// time, including sub-second parts (e.g. `{millisecond}`), are those of
+    /// the last refresh (or of the first record formatted with this policy, if
+    /// the cache has not been refreshed), instead of those of the record. The
+    /// formatted time never goes backwards.
+    Manual,
+}
+
+impl Default for TimeCache {
+    fn default() -> Self {
+        TimeCache::PerSecond
+    }
+}
+
+/// Refreshes the cached local date and time with the current time.
+///
+/// This is intended for formatters using [`TimeCache::Manual`], it's called
+/// periodically by the application, typically once per second. Formatters with
+/// other policies are not affected.
+///
+/// # Examples
+///
+/// ```
+/// use std::{thread, time::Duration};
+///
+/// use spdlog::formatter::{self, FullFormatter, TimeCache};
+///
+/// let formatter = FullFormatter::new().time_cache(TimeCache::Manual);
+/// thread::spawn(|| loop {
+///     formatter::refresh_time_cache();
+///     thread::sleep(Duration::from_secs(1));
+/// });
+/// ```
+pub fn refresh_time_cache() {
+    MANUAL_TIME_CACHER.lock().refresh(SystemTime::now());
+}
+
+/// Gets the cacher of the local date and time for the given policy.
+///
+/// No shared lock is taken for [`TimeCache::None`].
+#[must_use]
+pub(crate) fn local_time_cacher(policy: TimeCache) -> LocalTimeCacherGuard {
+    match policy {
+        TimeCache::PerSecond => LocalTimeCacherGuard::PerSecond(PER_SECOND_TIME_CACHER.lock()),
+        TimeCache::Manual => LocalTimeCacherGuard::Manual(MANUAL_TIME_CACHER.lock()),
+        TimeCache::None => LocalTimeCacherGuard::Uncached(LocalTimeCacher::new()),
+    }
+}
+
+// The guard only lives on the stack while formatting a record, boxing the
+// uncached cacher would allocate for every record instead.
+#[allow(clippy::large_enum_variant)]
+pub(crate) enum LocalTimeCacherGuard {
+    PerSecond(SpinMutexGuard<'static, LocalTimeCacher>),
+    Manual(SpinMutexGuard<'static, LocalTimeCacher>),
+    Uncached(LocalTimeCacher),
+}
+
+impl LocalTimeCacherGuard {
+    #[must_use]
+    pub(crate) fn get(&mut self, system_time: SystemTime) -> TimeDate<'_> {
+        let utc_time = system_time.into();
+        match self {
+            Self::PerSecond(cacher) => cacher.get_inner(utc_time, TimeCache::PerSecond),
+            Self::Manual(cacher) => cacher.get_inner(utc_time, TimeCache::Manual),
+            Self::Uncached(cacher) => cacher.get_inner(utc_time, TimeCache::None),
+        }
+    }
+}
+
+const LEAP_BOUNDARY: u32 = 1_000_000_000;
+
 #[derive(Clone)]
 pub(crate) struct LocalTimeCacher {
     stored_key: CacheKey,
@@ -32,7 +139,7 @@ enum CacheKey {
 
 #[derive(Clone, Eq, PartialEq)]
 struct CacheValues {
-    local_time: DateTime<Local>,
+    local_time: DateTime<FixedOffset>,
     is_leap_second: bool,
     full_second_str: RefCell<Option<String>>,
     year: RefCell<Option<i32>>,
@@ -72,34 +179,46 @@ impl LocalTimeCacher {
         }
     }
 
-    #[must_use]
-    pub(crate) fn get(&mut self, system_time: SystemTime) -> TimeDate {
-        self.get_inner(system_time.into())
+    // Refreshing with an earlier time than the cached one is ignored, so that
+    // the time of `TimeCache::Manual` never goes backwards.
+    fn refresh(&mut self, system_time: SystemTime) {
+        let utc_time: DateTime<Utc> = system_time.into();
+        if let Some(cache_values) = &self.cache_values {
+            if cache_values.local_time.naive_utc() > utc_time.naive_utc() {
+                return;
+            }
+        }
+        let is_leap_second = utc_time.nanosecond() >= LEAP_BOUNDARY;
+        self.cache_values = Some(CacheValues::new(
+            to_local_time(&utc_time, &Local),
+            is_leap_second,
+        ));
+        self.stored_key = CacheKey::new(&utc_time, is_leap_second);
     }
 
-    fn get_inner(&mut self, utc_time: DateTime<Utc>) -> TimeDate {
-        const LEAP_BOUNDARY: u32 = 1_000_000_000;
-
-        let nanosecond = utc_time.nanosecond();
-        let is_leap_second = nanosecond >= LEAP_BOUNDARY;
-        let reduced_nanosecond = if is_leap_second {
-            nanosecond - LEAP_BOUNDARY
-        } else {
-            nanosecond
-        };
-        let millisecond = reduced_nanosecond / 1_000_000;
-
+    fn get_inner(&mut self, utc_time: DateTime<Utc>, policy: TimeCache) -> TimeDate<'_> {
+        let is_leap_second = utc_time.nanosecond() >= LEAP_BOUNDARY;
         let cache_key = CacheKey::new(&utc_time, is_leap_second);
-        if self.cache_values.is_none() || self.stored_key != cache_key {
-            self.cache_values = Some(CacheValues::new(utc_time, is_leap_second));
+        let outdated = match policy {
+            TimeCache::PerSecond => self.stored_key != cache_key,
+            TimeCache::None => true,
+            TimeCache::Manual => false,
+        };
+        if self.cache_values.is_none() || outdated {
+            self.cache_values = Some(CacheValues::new(
+                to_local_time(&utc_time, &Local),
+                is_leap_second,
+            ));
             self.stored_key = cache_key;
         }
 
-        TimeDate::new(
-            self.cache_values.as_mut().unwrap(),
-            reduced_nanosecond,
-            millisecond,
-        )
+        let cache_values = self.cache_values.as_mut().unwrap();
+        // The sub-second parts of `TimeCache::Manual` are also cached ones
+        let nanosecond = match policy {
+            TimeCache::Manual => cache_values.local_time.nanosecond(),
+            _ => utc_time.nanosecond(),
+        } % LEAP_BOUNDARY;
+        TimeDate::new(cache_values, nanosecond, nanosecond / 1_000_000)
     }
 }
 
@@ -323,11 +442,27 @@ impl CacheKey {
     }
 }
 
+// Converts a UTC time to the local time in the given time zone.
+//
+// The offset is looked up by the UTC time, for which a time zone always has
+// exactly one offset. Resolving the naive local time instead may have no result
+// (in the gap skipped by a DST transition) or two results (in the overlap
+// repeated by a DST transition), so it's never done here. The result is always
+// the local time of the same instant, with the offset in effect at that
+// instant.
+#[must_use]
+fn to_local_time<Tz: TimeZone>(utc_time: &DateTime<Utc>, tz: &Tz) -> DateTime<FixedOffset> {
+    let naive_utc = utc_time.naive_utc();
+    tz.offset_from_utc_datetime(&naive_utc)
+        .fix()
+        .from_utc_datetime(&naive_utc)
+}
+
 impl CacheValues {
     #[must_use]
-    fn new(utc_time: DateTime<Utc>, is_leap_second: bool) -> Self {
+    fn new(local_time: DateTime<FixedOffset>, is_leap_second: bool) -> Self {
         CacheValues {
-            local_time: utc_time.into(),
+            local_time,
             is_leap_second,
             full_second_str: RefCell::new(None),
             year: RefCell::new(None),
@@ -356,6 +491,8 @@ impl CacheValues {
 
 #[cfg(test)]
 mod tests {
+    use chrono::LocalResult;
+
     use super::*;
 
     #[test]
@@ -405,9 +542,147 @@ mod tests {
 
             println!(" => checking '{datetime}'");
 
-            let result = cacher.get_inner(datetime.and_local_timezone(Utc).unwrap());
+            let result = cacher.get_inner(
+                datetime.and_local_timezone(Utc).unwrap(),
+                TimeCache::PerSecond,
+            );
             assert_eq!(result.cached.is_leap_second, leap);
             assert_eq!(result.second(), if !leap { 59 } else { 60 });
         }
     }
+
+    #[test]
+    fn policies() {
+        let date = NaiveDate::from_ymd_opt(2022, 6, 30).unwrap();
+        let time_1 = date
+            .and_hms_milli_opt(10, 0, 0, 100)
+            .unwrap()
+            .and_local_timezone(Utc)
+            .unwrap();
+        let time_2 = date
+            .and_hms_milli_opt(10, 0, 5, 200)
+            .unwrap()
+            .and_local_timezone(Utc)
+            .unwrap();
+
+        for policy in [TimeCache::PerSecond, TimeCache::None] {
+            let mut cacher = LocalTimeCacher::new();
+            assert_eq!(cacher.get_inner(time_1, policy).second(), 0);
+            assert_eq!(cacher.get_inner(time_2, policy).second(), 5);
+        }
+
+        let mut cacher = LocalTimeCacher::new();
+        assert_eq!(cacher.get_inner(time_1, TimeCache::Manual).second(), 0);
+        let result = cacher.get_inner(time_2, TimeCache::Manual);
+        assert_eq!(result.second(), 0);
+        assert_eq!(result.millisecond(), 100);
+
+        cacher.refresh(time_2.into());
+        let result = cacher.get_inner(time_1, TimeCache::Manual);
+        assert_eq!((result.second(), result.millisecond()), (5, 200));
+
+        // Never goes backwards
+        cacher.refresh(time_1.into());
+        let result = cacher.get_inner(time_1, TimeCache::Manual);
+        assert_eq!((result.second(), result.millisecond()), (5, 200));
+    }
+
+    #[test]
+    fn uncached_policy() {
+        let _per_second = local_time_cacher(TimeCache::PerSecond);
+        let _manual = local_time_cacher(TimeCache::Manual);
+
+        // Doesn't deadlock on the shared caches locked above
+        let mut uncached = local_time_cacher(TimeCache::None);
+        assert!(matches!(uncached, LocalTimeCacherGuard::Uncached(_)));
+        let _ = uncached.get(SystemTime::now()).second();
+    }
+
+    // US Eastern Time in 2024, DST starts at 2024-03-10 07:00 UTC (02:00 local
+    // is skipped to 03:00), and ends at 2024-11-03 06:00 UTC (02:00 local goes
+    // back to 01:00).
+    #[derive(Clone)]
+    struct Eastern2024;
+
+    impl Eastern2024 {
+        fn utc(month: u32, day: u32, hour: u32, min: u32) -> NaiveDateTime {
+            NaiveDate::from_ymd_opt(2024, month, day)
+                .unwrap()
+                .and_hms_opt(hour, min, 0)
+                .unwrap()
+        }
+    }
+
+    impl TimeZone for Eastern2024 {
+        type Offset = FixedOffset;
+
+        fn from_offset(_offset: &FixedOffset) -> Self {
+            Eastern2024
+        }
+
+        fn offset_from_local_date(&self, local: &NaiveDate) -> LocalResult<FixedOffset> {
+            self.offset_from_local_datetime(&local.and_hms_opt(0, 0, 0).unwrap())
+        }
+
+        fn offset_from_local_datetime(&self, local: &NaiveDateTime) -> LocalResult<FixedOffset> {
+            let offsets = [4, 5]
+                .iter()
+                .map(|hours| FixedOffset::west_opt(hours * 3600).unwrap())
+                .filter(|offset| self.offset_from_utc_datetime(&(*local - *offset)) == *offset)
+                .collect::<Vec<_>>();
+            match offsets[..] {
+                [] => LocalResult::None,
+                [offset] => LocalResult::Single(offset),
+                [earliest, latest] => LocalResult::Ambiguous(earliest, latest),
+                _ => unreachable!(),
+            }
+        }
+
+        fn offset_from_utc_date(&self, utc: &NaiveDate) -> FixedOffset {
+            self.offset_from_utc_datetime(&utc.and_hms_opt(0, 0, 0).unwrap())
+        }
+
+        fn offset_from_utc_datetime(&self, utc: &NaiveDateTime) -> FixedOffset {
+            let is_dst = (Self::utc(3, 10, 7, 0)..Self::utc(11, 3, 6, 0)).contains(utc);
+            FixedOffset::west_opt(if is_dst { 4 } else { 5 } * 3600).unwrap()
+        }
+    }
+
+    #[test]
+    fn dst_transitions() {
+        let check = |utc: NaiveDateTime, (hour, minute), tz_offset: &str| {
+            let local_time = to_local_time(&utc.and_local_timezone(Utc).unwrap(), &Eastern2024);
+            let mut cache_values = CacheValues::new(local_time, false);
+            let time_date = TimeDate::new(&mut cache_values, 0, 0);
+            assert_eq!(
+                (
+                    time_date.hour(),
+                    time_date.minute(),
+                    time_date.tz_offset_str().as_str()
+                ),
+                (hour, minute, tz_offset),
+                "{}",
+                utc
+            );
+        };
+
+        // The local time 02:30 falls in the gap, it does not exist
+        let gap = Eastern2024::utc(3, 10, 2, 30);
+        assert_eq!(
+            Eastern2024.offset_from_local_datetime(&gap),
+            LocalResult::None
+        );
+        check(Eastern2024::utc(3, 10, 6, 59), (1, 59), "-05:00");
+        check(Eastern2024::utc(3, 10, 7, 0), (3, 0), "-04:00");
+        check(Eastern2024::utc(3, 10, 7, 30), (3, 30), "-04:00");
+
+        // The local time 01:30 falls in the overlap, it occurs twice
+        let overlap = Eastern2024::utc(11, 3, 1, 30);
+        assert!(matches!(
+            Eastern2024.offset_from_local_datetime(&overlap),
+            LocalResult::Ambiguous(_, _)
+        ));
+        check(Eastern2024::utc(11, 3, 5, 30), (1, 30), "-04:00");
+        check(Eastern2024::utc(11, 3, 6, 30), (1, 30), "-05:00");
+    }
 }
@@ -5,47 +5,146 @@
 //! [`Sink::set_formatter`]: crate::sink::Sink::set_formatter
 
 mod full_formatter;
+mod gelf_formatter;
+mod html_formatter;
+mod humanize;
 #[cfg(any(
     all(target_os = "linux", feature = "native", feature = "libsystemd"),
     all(doc, not(doctest))
 ))]
 mod journald_formatter;
+mod json_formatter;
+mod level_labels;
 mod local_time_cacher;
 mod pattern_formatter;
 
-use std::ops::Range;
+use std::{io, ops::Range};
 
 pub use full_formatter::*;
+pub use gelf_formatter::*;
+pub use html_formatter::*;
+pub use humanize::*;
 #[cfg(any(
     all(target_os = "linux", feature = "native", feature = "libsystemd"),
     all(doc, not(doctest))
 ))]
 pub(crate) use journald_formatter::*;
+pub use json_formatter::*;
+pub use level_labels::*;
 pub(crate) use local_time_cacher::*;
+pub use local_time_cacher::{refresh_time_cache, TimeCache};
 pub use pattern_formatter::*;
 
-use crate::{Record, Result, StringBuf};
+use crate::{Error, Record, Result, StringBuf};
 
 /// A trait for log records formatters.
 ///
+/// # Text and bytes
+///
+/// [`Formatter::format`] is the primary method, it formats a record into UTF-8
+/// text, along with [`FmtExtraInfo`] describing the style ranges of the text.
+/// All sinks in this crate call it, except those sending bytes to protocol
+/// targets, i.e. [`UdpSink`], which call [`Formatter::format_bytes`] in
+/// [`Sink::log`].
+///
+/// [`Formatter::format_bytes`] formats a record into bytes, which are not
+/// necessarily UTF-8 text. By default it's implemented in terms of
+/// [`Formatter::format`]. Formatters with a binary output (e.g. MessagePack)
+/// can override it to write bytes directly without a UTF-8 intermediate, and
+/// implement [`Formatter::format`] with a textual fallback, e.g. a hex dump,
+/// since sinks writing text still call it.
+///
+/// # Streaming
+///
+/// [`Formatter::format_stream`] formats a record straight into an
+/// [`io::Write`], without buffering the whole formatted text. It's used by
+/// [`FileSink`] with [`streaming`] enabled, to avoid holding another copy of
+/// very large payloads (e.g. dumped request bodies) in memory. Other sinks
+/// format records into a buffer, as they need the whole text, e.g. to render
+/// styles, or to write a record atomically.
+///
+/// By default it's implemented in terms of [`Formatter::format`], which still
+/// buffers. [`FullFormatter`] overrides it to write the payload directly,
+/// custom formatters can override it likewise.
+///
 /// # Examples
 ///
 /// See the implementation of [`FullFormatter`] and [./examples] directory.
 ///
 /// [./examples]: https://github.com/SpriteOvO/spdlog-rs/tree/main/spdlog/examples
+/// [`UdpSink`]: crate::sink::UdpSink
+/// [`Sink::log`]: crate::sink::Sink::log
+/// [`FileSink`]: crate::sink::FileSink
+/// [`streaming`]: crate::sink::FileSinkBuilder::streaming
 pub trait Formatter: Send + Sync {
     /// Formats a log record.
     fn format(&self, record: &Record, dest: &mut StringBuf) -> Result<FmtExtraInfo>;
 
+    /// Formats a log record into bytes, appending them to `dest`.
+    ///
+    /// Style ranges do not apply to bytes, so there is no [`FmtExtraInfo`]
+    /// returned. Sinks calling this method never render styles.
+    ///
+    /// The default implementation calls [`Formatter::format`] and appends the
+    /// UTF-8 bytes of the text, discarding the [`FmtExtraInfo`]. See the
+    /// [trait-level documentation] for when to override it.
+    ///
+    /// [trait-level documentation]: Formatter#text-and-bytes
+    fn format_bytes(&self, record: &Record, dest: &mut Vec<u8>) -> Result<()> {
+        let mut string_buf = StringBuf::new();
+        self.format(record, &mut string_buf)?;
+        dest.extend_from_slice(string_buf.as_bytes());
+        Ok(())
+    }
+
+    /// Formats a log record, writing the text to `dest` as it's formatted.
+    ///
+    /// Style ranges are not returned, as the written text cannot be styled
+    /// afterwards. If an error is returned, a part of the text may have been
+    /// written.
+    ///
+    /// The default implementation calls [`Formatter::format`] and writes the
+    /// whole text at once. See the [trait-level documentation] for when to
+    /// override it.
+    ///
+    /// [trait-level documentation]: Formatter#streaming
+    fn format_stream(&self, record: &Record, dest: &mut dyn io::Write) -> Result<()> {
+        let mut string_buf = StringBuf::new();
+        self.format(record, &mut string_buf)?;
+        dest.write_all(string_buf.as_bytes())
+            .map_err(Error::WriteRecord)
+    }
+
     /// Clones self into a boxed trait object.
     #[must_use]
     fn clone_box(&self) -> Box<dyn Formatter>;
 }
 
+/// The semantic of a styled span of formatted text, which determines the style
+/// a sink renders the span in.
+///
+/// See [`FmtExtraInfo::style_ranges`].
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[non_exhaustive]
+pub enum StyleHint {
+    /// The span is rendered in the style of the log level of the record.
+    ///
+    /// It's the span returned by [`FmtExtraInfo::style_range`].
+    Level,
+    /// The span is the logger name.
+    LoggerName,
+    /// The span is a key of the key-values.
+    Key,
+    /// The span is a value of the key-values.
+    Value,
+}
+
 /// Extra information for formatted text.
 #[derive(Clone, Eq, PartialEq, Hash, Debug, Default)]
 pub struct FmtExtraInfo {
     style_range: Option<Range<usize>>,
+    // Styled spans other than the level, sorted by their positions
+    hinted_ranges: Vec<(Range<usize>, StyleHint)>,
 }
 
 impl FmtExtraInfo {
@@ -67,12 +166,48 @@ impl FmtExtraInfo {
     /// rendered in the style corresponding to that log message level, otherwise
     /// it will be ignored.
     ///
+    /// It's the range with [`StyleHint::Level`] in
+    /// [`FmtExtraInfo::style_ranges`].
+    ///
+    /// Sinks, including custom ones, receive it from the formatter, see the
+    /// documentation of [`Sink`] for details.
+    ///
     /// Its indexes are guaranteed by the setter to be the correct UTF-8
     /// boundary.
+    ///
+    /// [`Sink`]: crate::sink::Sink#formatting
     #[must_use]
     pub fn style_range(&self) -> Option<Range<usize>> {
         self.style_range.clone() // This clone is cheap
     }
+
+    /// All style ranges (in bytes) of the formatted text, with hints of their
+    /// semantics, in the order of their positions.
+    ///
+    /// If style is available in the sink, the text in each range will be
+    /// rendered in the style corresponding to its [`StyleHint`], otherwise they
+    /// will be ignored.
+    ///
+    /// Sinks only supporting a single style range can use
+    /// [`FmtExtraInfo::style_range`] instead.
+    pub fn style_ranges(&self) -> impl Iterator<Item = (Range<usize>, StyleHint)> + '_ {
+        let level = self
+            .style_range
+            .clone()
+            .map(|range| (range, StyleHint::Level));
+        let position = match &self.style_range {
+            Some(level_range) => self
+                .hinted_ranges
+                .partition_point(|(range, _)| range.start < level_range.start),
+            None => 0,
+        };
+        let (before, after) = self.hinted_ranges.split_at(position);
+        before
+            .iter()
+            .cloned()
+            .chain(level)
+            .chain(after.iter().cloned())
+    }
 }
 
 /// The builder of [`FmtExtraInfo`].
@@ -99,6 +234,9 @@ impl FmtExtraInfoBuilder {
 
     /// Sets style range (in bytes) of the formatted text.
     ///
+    /// It's the same as calling [`FmtExtraInfoBuilder::style_range_with_hint`]
+    /// with [`StyleHint::Level`].
+    ///
     /// Users must ensure that indexes are correctly UTF-8 boundary.
     #[must_use]
     pub fn style_range(mut self, range: Range<usize>) -> Self {
@@ -106,6 +244,29 @@ impl FmtExtraInfoBuilder {
         self
     }
 
+    /// Adds a style range (in bytes) of the formatted text, with a hint of its
+    /// semantic.
+    ///
+    /// There can be only one range with [`StyleHint::Level`], setting it again
+    /// replaces the previous one.
+    ///
+    /// Users must ensure that indexes are correctly UTF-8 boundary, and ranges
+    /// do not overlap with each other.
+    #[must_use]
+    pub fn style_range_with_hint(mut self, range: Range<usize>, hint: StyleHint) -> Self {
+        match hint {
+            StyleHint::Level => self.info.style_range = Some(range),
+            hint => {
+                let position = self
+                    .info
+                    .hinted_ranges
+                    .partition_point(|(other, _)| other.start < range.start);
+                self.info.hinted_ranges.insert(position, (range, hint));
+            }
+        }
+        self
+    }
+
     /// Builds a [`FmtExtraInfo`].
     #[must_use]
     pub fn build(self) -> FmtExtraInfo {
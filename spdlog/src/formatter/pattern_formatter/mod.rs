@@ -13,11 +13,19 @@
 #[doc(hidden)]
 #[path = "pattern/mod.rs"]
 pub mod __pattern;
+mod preset;
 
-use std::{fmt::Write, ops::Range, sync::Arc};
+use std::{
+    any::Any,
+    fmt::{self, Write},
+    ops::Range,
+    sync::Arc,
+};
+
+pub use preset::*;
 
 use crate::{
-    formatter::{FmtExtraInfo, FmtExtraInfoBuilder, Formatter},
+    formatter::{FmtExtraInfo, FmtExtraInfoBuilder, Formatter, StyleHint, TimeCache},
     Error, Record, StringBuf,
 };
 
@@ -115,6 +123,38 @@ use crate::{
 /// placeholders at [Appendix](#appendix-a-full-list-of-built-in-patterns)
 /// below.
 ///
+/// Some built-in patterns take an argument after a `:`, for example
+/// `{payload:trunc(N)}` truncates the payload to at most `N` characters,
+/// `{source:basename}` omits the directories of the source file, and
+/// `{fields:mdc,json}` writes only the key-values of the [MDC] as JSON.
+/// Arguments only affect the placeholder they are attached to, and invalid
+/// arguments are compile errors:
+/// ```
+/// # use spdlog::{
+/// #     formatter::{pattern, PatternFormatter},
+/// #     info,
+/// # };
+#[doc = include_str!(concat!(env!("OUT_DIR"), "/test_utils/common_for_doc_test.rs"))]
+/// let formatter = PatternFormatter::new(pattern!("[{level}] {payload:trunc(12)}"));
+/// # let (doctest, sink) = test_utils::echo_logger_from_formatter(
+/// #     Box::new(formatter),
+/// #     None
+/// # );
+///
+/// info!(logger: doctest, "Interesting log message");
+/// # assert_eq!(
+/// #     sink.clone_string(),
+/// /* Output */ "[info] Interesting…"
+/// # );
+/// ```
+///
+/// ```compile_fail
+/// # use spdlog::formatter::pattern;
+/// #
+/// // Error: invalid argument for pattern 'payload'
+/// let pattern = pattern!("{payload:trunc(-1)}");
+/// ```
+///
 /// # Using Style Range
 ///
 /// A specific portion of a formatted log message can be specified as "style
@@ -305,57 +345,84 @@ use crate::{
 ///
 /// # Appendix: A Full List of Built-in Patterns
 ///
-/// | Placeholders          | Description                  | Example                                      |
-/// | --------------------- | ---------------------------- | -------------------------------------------- |
-/// | `{weekday_name}`      | Abbreviated weekday name     | `Mon`, `Tue`                                 |
-/// | `{weekday_name_full}` | Weekday name                 | `Monday`, `Tuesday`                          |
-/// | `{month_name}`        | Abbreviated month name       | `Jan`, `Feb`                                 |
-/// | `{month_name_full}`   | Month name                   | `January`, `February`                        |
-/// | `{datetime}`          | Full date time               | `Thu Aug 23 15:35:46 2014`                   |
-/// | `{year_short}`        | Short year                   | `22`, `20`                                   |
-/// | `{year}`              | Year                         | `2022`, `2021`                               |
-/// | `{date_short}`        | Short date                   | `04/01/22`, `12/31/21`                       |
-/// | `{date}`              | Date (ISO 8601)              | `2022-04-01`, `2021-12-31`                   |
-/// | `{month}`             | Month                        | `01`, `12`                                   |
-/// | `{day}`               | Day in month                 | `01`, `12`, `31`, `30`                       |
-/// | `{hour}`              | Hour in 24-hour              | `01`, `12`, `23`                             |
-/// | `{hour_12}`           | Hour in 12-hour              | `01`, `12`                                   |
-/// | `{minute}`            | Minute                       | `00`, `05`, `59`                             |
-/// | `{second}`            | Second                       | `00`, `05`, `59`                             |
-/// | `{millisecond}`       | Millisecond                  | `231`                                        |
-/// | `{microsecond}`       | Microseconds within a second | `372152`                                     |
-/// | `{nanosecond}`        | Nanoseconds within a second  | `482930154`                                  |
-/// | `{am_pm}`             | AM / PM                      | `AM`, `PM`                                   |
-/// | `{time_12}`           | Time in 12-hour format       | `02:55:02 PM`                                |
-/// | `{time_short}`        | Short time                   | `22:28`, `09:53`                             |
-/// | `{time}`              | Time                         | `22:28:02`, `09:53:41`                       |
-/// | `{tz_offset}`         | Timezone offset              | `+08:00`, `+00:00`, `-06:00`                 |
-/// | `{unix_timestamp}`    | Unix timestamp               | `1528834770`                                 |
-/// | `{full}`              | Full log message             | See [`FullFormatter`]                        |
-/// | `{level}`             | Log level                    | `critical`, `error`, `warn`                  |
-/// | `{level_short}`       | Short log level              | `C`, `E`, `W`                                |
-/// | `{source}`            | Source file and line         | `path/to/main.rs:30` [^1]                    |
-/// | `{file_name}`         | Source file name             | `main.rs` [^1]                               |
-/// | `{file}`              | Source file path             | `path/to/main.rs` [^1]                       |
-/// | `{line}`              | Source file line             | `30` [^1]                                    |
-/// | `{column}`            | Source file column           | `20` [^1]                                    |
-/// | `{module_path}`       | Source module path           | `mod::module` [^1]                           |
-/// | `{logger}`            | Logger name                  | `my-logger`                                  |
-/// | `{payload}`           | Log payload                  | `log message`                                |
-/// | `{pid}`               | Process ID                   | `3824`                                       |
-/// | `{tid}`               | Thread ID                    | `3132`                                       |
-/// | `{eol}`               | End of line                  | `\n` (on non-Windows) or `\r\n` (on Windows) |
+/// | Placeholders              | Description                  | Example                                      |
+/// | ------------------------- | ---------------------------- | -------------------------------------------- |
+/// | `{weekday_name}`          | Abbreviated weekday name     | `Mon`, `Tue`                                 |
+/// | `{weekday_name_full}`     | Weekday name                 | `Monday`, `Tuesday`                          |
+/// | `{month_name}`            | Abbreviated month name       | `Jan`, `Feb`                                 |
+/// | `{month_name_full}`       | Month name                   | `January`, `February`                        |
+/// | `{datetime}`              | Full date time               | `Thu Aug 23 15:35:46 2014`                   |
+/// | `{year_short}`            | Short year                   | `22`, `20`                                   |
+/// | `{year}`                  | Year                         | `2022`, `2021`                               |
+/// | `{date_short}`            | Short date                   | `04/01/22`, `12/31/21`                       |
+/// | `{date}`                  | Date (ISO 8601)              | `2022-04-01`, `2021-12-31`                   |
+/// | `{month}`                 | Month                        | `01`, `12`                                   |
+/// | `{day}`                   | Day in month                 | `01`, `12`, `31`, `30`                       |
+/// | `{hour}`                  | Hour in 24-hour              | `01`, `12`, `23`                             |
+/// | `{hour_12}`               | Hour in 12-hour              | `01`, `12`                                   |
+/// | `{minute}`                | Minute                       | `00`, `05`, `59`                             |
+/// | `{second}`                | Second                       | `00`, `05`, `59`                             |
+/// | `{millisecond}`           | Millisecond                  | `231`                                        |
+/// | `{microsecond}`           | Microseconds within a second | `372152`                                     |
+/// | `{nanosecond}`            | Nanoseconds within a second  | `482930154`                                  |
+/// | `{am_pm}`                 | AM / PM                      | `AM`, `PM`                                   |
+/// | `{time_12}`               | Time in 12-hour format       | `02:55:02 PM`                                |
+/// | `{time_short}`            | Short time                   | `22:28`, `09:53`                             |
+/// | `{time}`                  | Time                         | `22:28:02`, `09:53:41`                       |
+/// | `{tz_offset}`             | Timezone offset              | `+08:00`, `+00:00`, `-06:00`                 |
+/// | `{unix_timestamp}`        | Unix timestamp               | `1528834770`                                 |
+/// | `{unix_timestamp:ms}`     | Unix timestamp in ms         | `1528834770000`                              |
+/// | `{unix_timestamp:us}`     | Unix timestamp in µs         | `1528834770000000`                           |
+/// | `{unix_timestamp:ns}`     | Unix timestamp in ns         | `1528834770000000000`                        |
+/// | `{full}`                  | Full log message             | See [`FullFormatter`]                        |
+/// | `{level}`                 | Log level                    | `critical`, `error`, `warn`                  |
+/// | `{level_short}`           | Short log level              | `C`, `E`, `W`                                |
+/// | `{level:lower}`           | Log level, same as `{level}` | `critical`, `error`, `warn`                  |
+/// | `{level:upper}`           | Uppercase log level          | `CRITICAL`, `ERROR`, `WARN`                  |
+/// | `{level:short}`           | Short log level              | `C`, `E`, `W`                                |
+/// | `{source}`                | Source file and line         | `path/to/main.rs:30` [^1]                    |
+/// | `{source:basename}`       | Source file name and line    | `main.rs:30` [^1]                            |
+/// | `{source:strip_prefix=P}` | Source, prefix `P` removed   | `to/main.rs:30` [^1]                         |
+/// | `{file_name}`             | Source file name             | `main.rs` [^1]                               |
+/// | `{file}`                  | Source file path             | `path/to/main.rs` [^1]                       |
+/// | `{line}`                  | Source file line             | `30` [^1]                                    |
+/// | `{column}`                | Source file column           | `20` [^1]                                    |
+/// | `{module_path}`           | Source module path           | `mod::module` [^1]                           |
+/// | `{logger}`                | Logger name                  | `my-logger`                                  |
+/// | `{logger:abbrev}`         | Abbreviated logger name      | `c.e.a.s.d.pool` [^2]                        |
+/// | `{logger:abbrev(N)}`      | Logger name abbreviated to N | `c.e.a.s.db.pool` (N = 15) [^2]              |
+/// | `{logger:short(N)}`       | Last N logger name segments  | `db.pool` (N = 2) [^2]                       |
+/// | `{payload}`               | Log payload                  | `log message`                                |
+/// | `{payload:trunc(N)}`      | Truncated log payload        | `log mes…` (at most N characters)            |
+/// | `{payload:hex}`           | Log payload bytes in hex     | `6c6f67206d657373616765` [^3]                |
+/// | `{fields}`                | Key-values in logfmt         | `request_id=42 user=alice`                   |
+/// | `{fields:mdc}`            | MDC key-values in logfmt     | `request_id=42`                              |
+/// | `{fields:json}`           | Key-values in JSON           | `{"request_id":"42","user":"alice"}`         |
+/// | `{pid}`                   | Process ID                   | `3824`                                       |
+/// | `{tid}`                   | Thread ID                    | `3132`                                       |
+/// | `{eol}`                   | End of line                  | `\n` (on non-Windows) or `\r\n` (on Windows) |
 /// 
 /// [^1]: Patterns related to source location require that feature
 ///       `source-location` is enabled, otherwise the output is empty.
 ///
+/// [^2]: For the logger name `com.example.app.service.db.pool`. Names without
+///       dots are written as they are, see [`LoggerName`] for details.
+///
+/// [^3]: For records logged by [`log_bytes!`], the raw bytes, which may not
+///       be valid UTF-8.
+///
+/// [`LoggerName`]: crate::formatter::__pattern::LoggerName
+/// [`log_bytes!`]: crate::log_bytes
 /// [`FullFormatter`]: crate::formatter::FullFormatter
+/// [MDC]: crate::mdc
 pub use ::spdlog_macros::pattern;
 
 /// A formatter that formats log records according to a specified pattern.
 #[derive(Clone)]
 pub struct PatternFormatter<P> {
     pattern: P,
+    eol: &'static str,
+    time_cache: TimeCache,
 }
 
 impl<P> PatternFormatter<P>
@@ -365,10 +432,146 @@ where
     /// Creates a new `PatternFormatter` object with the given pattern.
     ///
     /// Currently users can only create a `pattern` object at compile-time by
-    /// calling [`pattern!`] macro.
+    /// calling [`pattern!`] macro. For commonly used patterns, see also
+    /// [`PatternFormatter::preset`].
     #[must_use]
     pub fn new(pattern: P) -> Self {
-        Self { pattern }
+        Self {
+            pattern,
+            eol: crate::EOL,
+            time_cache: TimeCache::PerSecond,
+        }
+    }
+
+    /// Makes the `{eol}` pattern write `\n` on all platforms, instead of
+    /// `\r\n` on Windows.
+    ///
+    /// It's intended for golden tests, so that the expected output of a
+    /// pattern containing `{eol}` is the same on Windows and Unix CI. Other
+    /// formatters are not affected.
+    ///
+    /// This parameter is **optional**, and defaults to `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use spdlog::{
+    ///     formatter::{pattern, PatternFormatter},
+    ///     prelude::*,
+    ///     sink::WriteSink,
+    /// };
+    ///
+    /// # fn main() -> Result<(), spdlog::Error> {
+    /// let formatter = PatternFormatter::new(pattern!("[{level}] {payload}{eol}")).force_lf_eol(true);
+    /// let sink = Arc::new(
+    ///     WriteSink::builder()
+    ///         .target(Vec::new())
+    ///         .formatter(Box::new(formatter))
+    ///         .build()?,
+    /// );
+    /// let logger = Logger::builder().sink(sink.clone()).build()?;
+    ///
+    /// info!(logger: logger, "hello");
+    /// assert_eq!(sink.clone_target(), b"[info] hello\n");
+    /// # Ok(()) }
+    /// ```
+    #[must_use]
+    pub fn force_lf_eol(mut self, force: bool) -> Self {
+        self.eol = if force { "\n" } else { crate::EOL };
+        self
+    }
+
+    /// Sets the caching policy of the local date and time for the date and
+    /// time patterns.
+    ///
+    /// See [`TimeCache`] for details.
+    ///
+    /// This parameter is **optional**, and defaults to
+    /// [`TimeCache::PerSecond`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spdlog::formatter::{pattern, PatternFormatter, TimeCache};
+    ///
+    /// let formatter = PatternFormatter::new(pattern!("[{time}] {payload}{eol}"))
+    ///     .time_cache(TimeCache::None);
+    /// ```
+    #[must_use]
+    pub fn time_cache(mut self, policy: TimeCache) -> Self {
+        self.time_cache = policy;
+        self
+    }
+
+    /// Formats a log record with a user-supplied context, which can be read by
+    /// patterns through [`PatternContext::user_context`].
+    ///
+    /// The context is only available within this call. Records logged through
+    /// [`Logger`]s are formatted by sinks calling [`Formatter::format`], which
+    /// has no per-call context, so `ctx.user_context()` returns `None` there.
+    /// This function is intended for using the formatter directly, e.g. in a
+    /// custom sink or in a service rendering records for a specific request.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fmt::Write;
+    ///
+    /// use spdlog::{
+    ///     formatter::{pattern, Pattern, PatternContext, PatternFormatter},
+    ///     Record, StringBuf,
+    /// };
+    ///
+    /// struct RequestInfo {
+    ///     id: u64,
+    /// }
+    ///
+    /// #[derive(Default, Clone)]
+    /// struct RequestId;
+    ///
+    /// impl Pattern for RequestId {
+    ///     fn format(
+    ///         &self,
+    ///         _record: &Record,
+    ///         dest: &mut StringBuf,
+    ///         ctx: &mut PatternContext,
+    ///     ) -> spdlog::Result<()> {
+    ///         match ctx.user_context::<RequestInfo>() {
+    ///             Some(info) => write!(dest, "{}", info.id),
+    ///             None => write!(dest, "-"),
+    ///         }
+    ///         .map_err(spdlog::Error::FormatRecord)
+    ///     }
+    /// }
+    ///
+    /// // Called with records received by a custom sink, for example.
+    /// fn render(record: &Record, request: &RequestInfo) -> spdlog::Result<StringBuf> {
+    ///     let formatter = PatternFormatter::new(pattern!(
+    ///         "[{$req_id}] {payload}",
+    ///         {$req_id} => RequestId::default,
+    ///     ));
+    ///
+    ///     let mut dest = StringBuf::new();
+    ///     formatter.format_with_context(record, request, &mut dest)?;
+    ///     Ok(dest)
+    /// }
+    /// ```
+    ///
+    /// [`Logger`]: crate::Logger
+    pub fn format_with_context(
+        &self,
+        record: &Record,
+        context: &dyn Any,
+        dest: &mut StringBuf,
+    ) -> crate::Result<FmtExtraInfo> {
+        let mut ctx = PatternContext::new(FmtExtraInfoBuilder::default());
+        ctx.user_context = Some(context);
+        ctx.eol = self.eol;
+        ctx.time_cache = self.time_cache;
+        self.pattern.format(record, dest, &mut ctx)?;
+        Ok(ctx.fmt_info_builder.build())
     }
 }
 
@@ -378,6 +581,8 @@ where
 {
     fn format(&self, record: &Record, dest: &mut StringBuf) -> crate::Result<FmtExtraInfo> {
         let mut ctx = PatternContext::new(FmtExtraInfoBuilder::default());
+        ctx.eol = self.eol;
+        ctx.time_cache = self.time_cache;
         self.pattern.format(record, dest, &mut ctx)?;
         Ok(ctx.fmt_info_builder.build())
     }
@@ -388,16 +593,37 @@ where
 }
 
 /// Provide context for patterns.
-#[derive(Clone, Debug)]
-pub struct PatternContext {
+#[derive(Clone)]
+pub struct PatternContext<'a> {
     fmt_info_builder: FmtExtraInfoBuilder,
+    user_context: Option<&'a dyn Any>,
+    // Written by the `{eol}` pattern
+    eol: &'static str,
+    // Read by the date and time patterns
+    time_cache: TimeCache,
 }
 
-impl PatternContext {
+impl<'a> PatternContext<'a> {
     /// Create a new `PatternContext` object.
     #[must_use]
     fn new(fmt_info_builder: FmtExtraInfoBuilder) -> Self {
-        Self { fmt_info_builder }
+        Self {
+            fmt_info_builder,
+            user_context: None,
+            eol: crate::EOL,
+            time_cache: TimeCache::PerSecond,
+        }
+    }
+
+    /// Gets the user-supplied context if it is of type `T`.
+    ///
+    /// The context is passed in by [`PatternFormatter::format_with_context`].
+    /// Returns `None` if the record is formatted without a context (e.g. by
+    /// [`Formatter::format`]), or the context is not of type `T`.
+    #[must_use]
+    pub fn user_context<T: Any>(&self) -> Option<&'a T> {
+        self.user_context
+            .and_then(|context| context.downcast_ref::<T>())
     }
 
     /// Set the style range of the log message written by the patterns.
@@ -409,6 +635,27 @@ impl PatternContext {
         let builder = std::mem::take(&mut self.fmt_info_builder);
         self.fmt_info_builder = builder.style_range(style_range);
     }
+
+    /// Add a style range with a hint of the log message written by the
+    /// patterns.
+    ///
+    /// This function is reserved for use by built-in patterns. User-defined
+    /// patterns cannot use this function due to type privacy.
+    fn set_style_range_with_hint(&mut self, style_range: Range<usize>, hint: StyleHint) {
+        let builder = std::mem::take(&mut self.fmt_info_builder);
+        self.fmt_info_builder = builder.style_range_with_hint(style_range, hint);
+    }
+}
+
+impl fmt::Debug for PatternContext<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PatternContext")
+            .field("fmt_info_builder", &self.fmt_info_builder)
+            .field("user_context", &self.user_context.map(|_| ..))
+            .field("eol", &self.eol)
+            .field("time_cache", &self.time_cache)
+            .finish()
+    }
 }
 
 /// A pattern.
@@ -432,8 +679,8 @@ pub trait Pattern: Send + Sync {
     /// Format this pattern against the given log record and write the formatted
     /// message into the output buffer.
     ///
-    /// **For implementors:** the `ctx` parameter is reserved for future use.
-    /// For now, please ignore it.
+    /// **For implementors:** the `ctx` parameter provides the user-supplied
+    /// context via [`PatternContext::user_context`], if any.
     fn format(
         &self,
         record: &Record,
@@ -464,7 +711,7 @@ impl Pattern for str {
     }
 }
 
-impl<'a, T> Pattern for &'a T
+impl<T> Pattern for &T
 where
     T: ?Sized + Pattern,
 {
@@ -478,7 +725,7 @@ where
     }
 }
 
-impl<'a, T> Pattern for &'a mut T
+impl<T> Pattern for &mut T
 where
     T: ?Sized + Pattern,
 {
@@ -1204,6 +1451,7 @@ tuple_pattern! {
 }
 
 #[cfg(test)]
+#[allow(missing_docs)]
 pub mod tests {
     use super::*;
     use crate::{Level, SourceLocation};
@@ -1237,6 +1485,131 @@ pub mod tests {
         assert_eq!(fmt_info.style_range(), style_range);
     }
 
+    #[test]
+    fn test_format_with_context() {
+        #[derive(Clone)]
+        struct ContextPattern;
+
+        impl Pattern for ContextPattern {
+            fn format(
+                &self,
+                _record: &Record,
+                dest: &mut StringBuf,
+                ctx: &mut PatternContext,
+            ) -> crate::Result<()> {
+                match ctx.user_context::<u32>() {
+                    Some(value) => write!(dest, "{}", value),
+                    None => write!(dest, "none"),
+                }
+                .map_err(crate::Error::FormatRecord)
+            }
+        }
+
+        let formatter = PatternFormatter::new(ContextPattern);
+        let record = get_mock_record();
+        let format = |context: Option<&dyn Any>| {
+            let mut dest = StringBuf::new();
+            match context {
+                Some(context) => formatter.format_with_context(&record, context, &mut dest),
+                None => formatter.format(&record, &mut dest),
+            }
+            .unwrap();
+            dest
+        };
+
+        assert_eq!(format(Some(&123_u32)), "123");
+        assert_eq!(format(Some(&"mismatched type")), "none");
+        assert_eq!(format(None), "none");
+    }
+
+    #[test]
+    fn test_source_path() {
+        use super::__pattern::Source;
+
+        let (file, prefix, prefix_with_separator) = if cfg!(windows) {
+            (
+                r"C:\ci\my-crate\src\net\conn.rs",
+                r"C:\ci\my-crate",
+                r"C:\ci\my-crate\",
+            )
+        } else {
+            (
+                "/home/ci/my-crate/src/net/conn.rs",
+                "/home/ci/my-crate",
+                "/home/ci/my-crate/",
+            )
+        };
+        let record = Record::builder(Level::Info, "")
+            .source_location(Some(SourceLocation::__new("module", file, 10, 20)))
+            .build();
+        let format = |pattern: Source| {
+            let mut dest = StringBuf::new();
+            let mut ctx = PatternContext::new(FmtExtraInfoBuilder::default());
+            pattern.format(&record, &mut dest, &mut ctx).unwrap();
+            dest
+        };
+
+        assert_eq!(format(Source::default()), format!("{}:10", file));
+        assert_eq!(format(Source::__basename()), "conn.rs:10");
+        assert_eq!(
+            format(Source::__strip_prefix(prefix)),
+            format!("{}:10", &file[prefix.len() + 1..])
+        );
+        assert_eq!(
+            format(Source::__strip_prefix(prefix_with_separator)),
+            format!("{}:10", &file[prefix.len() + 1..])
+        );
+        assert_eq!(
+            format(Source::__strip_prefix("unrelated")),
+            format!("{}:10", file)
+        );
+        // Only whole path components are stripped
+        let partial_prefix = &file[..file.len() - "et/conn.rs".len()];
+        assert!(partial_prefix.ends_with('n'));
+        assert_eq!(
+            format(Source::__strip_prefix(partial_prefix)),
+            format!("{}:10", file)
+        );
+    }
+
+    #[test]
+    fn test_logger_name_abbreviation() {
+        use super::__pattern::LoggerName;
+
+        let format = |name: &str, pattern: LoggerName| {
+            let record = Record::builder(Level::Info, "").logger_name(name).build();
+            let mut dest = StringBuf::new();
+            let mut ctx = PatternContext::new(FmtExtraInfoBuilder::default());
+            pattern.format(&record, &mut dest, &mut ctx).unwrap();
+            dest
+        };
+        let name = "com.example.app.service.db.pool";
+
+        assert_eq!(format(name, LoggerName::default()), name);
+        assert_eq!(format(name, LoggerName::__abbreviated(0)), "c.e.a.s.d.pool");
+        assert_eq!(
+            format(name, LoggerName::__abbreviated(15)),
+            "c.e.a.s.db.pool"
+        );
+        assert_eq!(format(name, LoggerName::__abbreviated(100)), name);
+        assert_eq!(format(name, LoggerName::__last_segments(2)), "db.pool");
+        assert_eq!(format(name, LoggerName::__last_segments(1)), "pool");
+        assert_eq!(format(name, LoggerName::__last_segments(100)), name);
+
+        // Names without dots, and empty segments
+        for pattern in [
+            LoggerName::__abbreviated(0),
+            LoggerName::__abbreviated(3),
+            LoggerName::__last_segments(1),
+            LoggerName::__last_segments(2),
+        ] {
+            assert_eq!(format("network", pattern.clone()), "network");
+            assert_eq!(format("", pattern), "");
+        }
+        assert_eq!(format("a..ccc.", LoggerName::__abbreviated(0)), "a..c.");
+        assert_eq!(format("a..ccc.", LoggerName::__last_segments(2)), "ccc.");
+    }
+
     #[test]
     fn test_string_as_pattern() {
         test_pattern(String::from("literal"), "literal", None);
@@ -1329,4 +1702,39 @@ pub mod tests {
     fn test_unit_as_pattern() {
         test_pattern((), "", None);
     }
+
+    #[test]
+    fn test_unix_timestamp() {
+        use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+        use crate::formatter::__pattern::UnixTimestamp;
+
+        let fmt = |pattern: UnixTimestamp, time: SystemTime| {
+            let record = Record::builder(Level::Info, "").time(time).build();
+            let mut dest = StringBuf::new();
+            PatternFormatter::new(pattern)
+                .format(&record, &mut dest)
+                .unwrap();
+            dest.to_string()
+        };
+        let (ms, us, ns) = (
+            UnixTimestamp::__with_unit_nanos(1_000_000),
+            UnixTimestamp::__with_unit_nanos(1_000),
+            UnixTimestamp::__with_unit_nanos(1),
+        );
+
+        let time = UNIX_EPOCH + Duration::new(1528834770, 123_456_789);
+        assert_eq!(fmt(UnixTimestamp::default(), time), "1528834770");
+        assert_eq!(fmt(ms.clone(), time), "1528834770123");
+        assert_eq!(fmt(us, time), "1528834770123456");
+        assert_eq!(fmt(ns.clone(), time), "1528834770123456789");
+
+        // Rounded down before the epoch
+        assert_eq!(
+            fmt(ms.clone(), UNIX_EPOCH - Duration::new(1, 500_000_000)),
+            "-1500"
+        );
+        assert_eq!(fmt(ms, UNIX_EPOCH - Duration::from_nanos(1)), "-1");
+        assert_eq!(fmt(ns, UNIX_EPOCH - Duration::from_nanos(1)), "-1");
+    }
 }
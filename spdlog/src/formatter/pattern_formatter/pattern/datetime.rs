@@ -1,8 +1,8 @@
-use std::{fmt::Write, marker::PhantomData};
+use std::{fmt::Write, marker::PhantomData, time::UNIX_EPOCH};
 
 use crate::{
     formatter::{
-        local_time_cacher::LOCAL_TIME_CACHER,
+        local_time_cacher::local_time_cacher,
         pattern_formatter::{Pattern, PatternContext},
     },
     Error, Record, StringBuf,
@@ -18,10 +18,9 @@ impl Pattern for AbbrWeekdayName {
         &self,
         record: &Record,
         dest: &mut StringBuf,
-        _ctx: &mut PatternContext,
+        ctx: &mut PatternContext,
     ) -> crate::Result<()> {
-        let name = LOCAL_TIME_CACHER
-            .lock()
+        let name = local_time_cacher(ctx.time_cache)
             .get(record.time())
             .weekday_name()
             .short;
@@ -40,10 +39,9 @@ impl Pattern for WeekdayName {
         &self,
         record: &Record,
         dest: &mut StringBuf,
-        _ctx: &mut PatternContext,
+        ctx: &mut PatternContext,
     ) -> crate::Result<()> {
-        let name = LOCAL_TIME_CACHER
-            .lock()
+        let name = local_time_cacher(ctx.time_cache)
             .get(record.time())
             .weekday_name()
             .full;
@@ -62,10 +60,9 @@ impl Pattern for AbbrMonthName {
         &self,
         record: &Record,
         dest: &mut StringBuf,
-        _ctx: &mut PatternContext,
+        ctx: &mut PatternContext,
     ) -> crate::Result<()> {
-        let name = LOCAL_TIME_CACHER
-            .lock()
+        let name = local_time_cacher(ctx.time_cache)
             .get(record.time())
             .month_name()
             .short;
@@ -84,10 +81,9 @@ impl Pattern for MonthName {
         &self,
         record: &Record,
         dest: &mut StringBuf,
-        _ctx: &mut PatternContext,
+        ctx: &mut PatternContext,
     ) -> crate::Result<()> {
-        let name = LOCAL_TIME_CACHER
-            .lock()
+        let name = local_time_cacher(ctx.time_cache)
             .get(record.time())
             .month_name()
             .full;
@@ -106,7 +102,7 @@ impl Pattern for FullDateTime {
         &self,
         record: &Record,
         dest: &mut StringBuf,
-        _ctx: &mut PatternContext,
+        ctx: &mut PatternContext,
     ) -> crate::Result<()> {
         let (
             abbr_weekday_name,
@@ -117,7 +113,7 @@ impl Pattern for FullDateTime {
             second_str,
             year_str,
         ) = {
-            let mut time_cacher_lock = LOCAL_TIME_CACHER.lock();
+            let mut time_cacher_lock = local_time_cacher(ctx.time_cache);
             let cached_time = time_cacher_lock.get(record.time());
             (
                 cached_time.weekday_name().short,
@@ -159,9 +155,11 @@ impl Pattern for ShortYear {
         &self,
         record: &Record,
         dest: &mut StringBuf,
-        _ctx: &mut PatternContext,
+        ctx: &mut PatternContext,
     ) -> crate::Result<()> {
-        let year_short_str = LOCAL_TIME_CACHER.lock().get(record.time()).year_short_str();
+        let year_short_str = local_time_cacher(ctx.time_cache)
+            .get(record.time())
+            .year_short_str();
         dest.write_str(&year_short_str).map_err(Error::FormatRecord)
     }
 }
@@ -176,9 +174,11 @@ impl Pattern for Year {
         &self,
         record: &Record,
         dest: &mut StringBuf,
-        _ctx: &mut PatternContext,
+        ctx: &mut PatternContext,
     ) -> crate::Result<()> {
-        let year_str = LOCAL_TIME_CACHER.lock().get(record.time()).year_str();
+        let year_str = local_time_cacher(ctx.time_cache)
+            .get(record.time())
+            .year_str();
         dest.write_str(&year_str).map_err(Error::FormatRecord)
     }
 }
@@ -193,10 +193,10 @@ impl Pattern for Date {
         &self,
         record: &Record,
         dest: &mut StringBuf,
-        _ctx: &mut PatternContext,
+        ctx: &mut PatternContext,
     ) -> crate::Result<()> {
         let (month_str, day_str, year_str) = {
-            let mut local_cacher_lock = LOCAL_TIME_CACHER.lock();
+            let mut local_cacher_lock = local_time_cacher(ctx.time_cache);
             let cached_time = local_cacher_lock.get(record.time());
             (
                 cached_time.month_str(),
@@ -226,10 +226,10 @@ impl Pattern for ShortDate {
         &self,
         record: &Record,
         dest: &mut StringBuf,
-        _ctx: &mut PatternContext,
+        ctx: &mut PatternContext,
     ) -> crate::Result<()> {
         let (month_str, day_str, year_short_str) = {
-            let mut local_cacher_lock = LOCAL_TIME_CACHER.lock();
+            let mut local_cacher_lock = local_time_cacher(ctx.time_cache);
             let cached_time = local_cacher_lock.get(record.time());
             (
                 cached_time.month_str(),
@@ -259,9 +259,11 @@ impl Pattern for Month {
         &self,
         record: &Record,
         dest: &mut StringBuf,
-        _ctx: &mut PatternContext,
+        ctx: &mut PatternContext,
     ) -> crate::Result<()> {
-        let month_str = LOCAL_TIME_CACHER.lock().get(record.time()).month_str();
+        let month_str = local_time_cacher(ctx.time_cache)
+            .get(record.time())
+            .month_str();
         dest.write_str(&month_str).map_err(Error::FormatRecord)
     }
 }
@@ -276,9 +278,11 @@ impl Pattern for Day {
         &self,
         record: &Record,
         dest: &mut StringBuf,
-        _ctx: &mut PatternContext,
+        ctx: &mut PatternContext,
     ) -> crate::Result<()> {
-        let day_str = LOCAL_TIME_CACHER.lock().get(record.time()).day_str();
+        let day_str = local_time_cacher(ctx.time_cache)
+            .get(record.time())
+            .day_str();
         dest.write_str(&day_str).map_err(Error::FormatRecord)
     }
 }
@@ -293,9 +297,11 @@ impl Pattern for Hour {
         &self,
         record: &Record,
         dest: &mut StringBuf,
-        _ctx: &mut PatternContext,
+        ctx: &mut PatternContext,
     ) -> crate::Result<()> {
-        let hour_str = LOCAL_TIME_CACHER.lock().get(record.time()).hour_str();
+        let hour_str = local_time_cacher(ctx.time_cache)
+            .get(record.time())
+            .hour_str();
         dest.write_str(&hour_str).map_err(Error::FormatRecord)
     }
 }
@@ -310,9 +316,11 @@ impl Pattern for Hour12 {
         &self,
         record: &Record,
         dest: &mut StringBuf,
-        _ctx: &mut PatternContext,
+        ctx: &mut PatternContext,
     ) -> crate::Result<()> {
-        let hour_12_str = LOCAL_TIME_CACHER.lock().get(record.time()).hour12_str();
+        let hour_12_str = local_time_cacher(ctx.time_cache)
+            .get(record.time())
+            .hour12_str();
         dest.write_str(&hour_12_str).map_err(Error::FormatRecord)
     }
 }
@@ -327,9 +335,11 @@ impl Pattern for Minute {
         &self,
         record: &Record,
         dest: &mut StringBuf,
-        _ctx: &mut PatternContext,
+        ctx: &mut PatternContext,
     ) -> crate::Result<()> {
-        let minute_str = LOCAL_TIME_CACHER.lock().get(record.time()).minute_str();
+        let minute_str = local_time_cacher(ctx.time_cache)
+            .get(record.time())
+            .minute_str();
         dest.write_str(&minute_str).map_err(Error::FormatRecord)
     }
 }
@@ -344,9 +354,11 @@ impl Pattern for Second {
         &self,
         record: &Record,
         dest: &mut StringBuf,
-        _ctx: &mut PatternContext,
+        ctx: &mut PatternContext,
     ) -> crate::Result<()> {
-        let second_str = LOCAL_TIME_CACHER.lock().get(record.time()).second_str();
+        let second_str = local_time_cacher(ctx.time_cache)
+            .get(record.time())
+            .second_str();
         dest.write_str(&second_str).map_err(Error::FormatRecord)
     }
 }
@@ -365,9 +377,11 @@ impl Pattern for Millisecond {
         &self,
         record: &Record,
         dest: &mut StringBuf,
-        _ctx: &mut PatternContext,
+        ctx: &mut PatternContext,
     ) -> crate::Result<()> {
-        let millisecond = LOCAL_TIME_CACHER.lock().get(record.time()).millisecond();
+        let millisecond = local_time_cacher(ctx.time_cache)
+            .get(record.time())
+            .millisecond();
         write!(dest, "{:03}", millisecond).map_err(Error::FormatRecord)
     }
 }
@@ -382,9 +396,11 @@ impl Pattern for Microsecond {
         &self,
         record: &Record,
         dest: &mut StringBuf,
-        _ctx: &mut PatternContext,
+        ctx: &mut PatternContext,
     ) -> crate::Result<()> {
-        let nanosecond = LOCAL_TIME_CACHER.lock().get(record.time()).nanosecond();
+        let nanosecond = local_time_cacher(ctx.time_cache)
+            .get(record.time())
+            .nanosecond();
         write!(dest, "{:06}", nanosecond / 1_000).map_err(Error::FormatRecord)
     }
 }
@@ -399,9 +415,11 @@ impl Pattern for Nanosecond {
         &self,
         record: &Record,
         dest: &mut StringBuf,
-        _ctx: &mut PatternContext,
+        ctx: &mut PatternContext,
     ) -> crate::Result<()> {
-        let nanosecond = LOCAL_TIME_CACHER.lock().get(record.time()).nanosecond();
+        let nanosecond = local_time_cacher(ctx.time_cache)
+            .get(record.time())
+            .nanosecond();
         write!(dest, "{:09}", nanosecond).map_err(Error::FormatRecord)
     }
 }
@@ -416,9 +434,11 @@ impl Pattern for AmPm {
         &self,
         record: &Record,
         dest: &mut StringBuf,
-        _ctx: &mut PatternContext,
+        ctx: &mut PatternContext,
     ) -> crate::Result<()> {
-        let am_pm_str = LOCAL_TIME_CACHER.lock().get(record.time()).am_pm_str();
+        let am_pm_str = local_time_cacher(ctx.time_cache)
+            .get(record.time())
+            .am_pm_str();
         dest.write_str(am_pm_str).map_err(Error::FormatRecord)
     }
 }
@@ -433,10 +453,10 @@ impl Pattern for Time12 {
         &self,
         record: &Record,
         dest: &mut StringBuf,
-        _ctx: &mut PatternContext,
+        ctx: &mut PatternContext,
     ) -> crate::Result<()> {
         let (hour_str, minute_str, second_str, am_pm_str) = {
-            let mut time_cacher_lock = LOCAL_TIME_CACHER.lock();
+            let mut time_cacher_lock = local_time_cacher(ctx.time_cache);
             let cached_time = time_cacher_lock.get(record.time());
             (
                 cached_time.hour12_str(),
@@ -469,10 +489,10 @@ impl Pattern for ShortTime {
         &self,
         record: &Record,
         dest: &mut StringBuf,
-        _ctx: &mut PatternContext,
+        ctx: &mut PatternContext,
     ) -> crate::Result<()> {
         let (hour_str, minute_str) = {
-            let mut time_cacher_lock = LOCAL_TIME_CACHER.lock();
+            let mut time_cacher_lock = local_time_cacher(ctx.time_cache);
             let cached_time = time_cacher_lock.get(record.time());
             (cached_time.hour_str(), cached_time.minute_str())
         };
@@ -496,10 +516,10 @@ impl Pattern for Time {
         &self,
         record: &Record,
         dest: &mut StringBuf,
-        _ctx: &mut PatternContext,
+        ctx: &mut PatternContext,
     ) -> crate::Result<()> {
         let (hour_str, minute_str, second_str) = {
-            let mut time_cacher_lock = LOCAL_TIME_CACHER.lock();
+            let mut time_cacher_lock = local_time_cacher(ctx.time_cache);
             let cached_time = time_cacher_lock.get(record.time());
             (
                 cached_time.hour_str(),
@@ -529,30 +549,61 @@ impl Pattern for TzOffset {
         &self,
         record: &Record,
         dest: &mut StringBuf,
-        _ctx: &mut PatternContext,
+        ctx: &mut PatternContext,
     ) -> crate::Result<()> {
-        let tz_offset_str = LOCAL_TIME_CACHER.lock().get(record.time()).tz_offset_str();
+        let tz_offset_str = local_time_cacher(ctx.time_cache)
+            .get(record.time())
+            .tz_offset_str();
         dest.write_str(&tz_offset_str).map_err(Error::FormatRecord)
     }
 }
 
 /// A pattern that writes the unix timestamp of log records into the output.
-/// Examples: `1528834770`.
+/// Examples: `1528834770`, or `1528834770000` in milliseconds.
 #[derive(Clone, Default)]
-pub struct UnixTimestamp;
+pub struct UnixTimestamp {
+    // The number of nanoseconds per unit, or `None` for seconds
+    unit_nanos: Option<u128>,
+}
+
+impl UnixTimestamp {
+    #[doc(hidden)]
+    #[must_use]
+    pub fn __with_unit_nanos(unit_nanos: u128) -> Self {
+        Self {
+            unit_nanos: Some(unit_nanos),
+        }
+    }
+}
 
 impl Pattern for UnixTimestamp {
     fn format(
         &self,
         record: &Record,
         dest: &mut StringBuf,
-        _ctx: &mut PatternContext,
+        ctx: &mut PatternContext,
     ) -> crate::Result<()> {
-        let unix_timestamp_str = LOCAL_TIME_CACHER
-            .lock()
-            .get(record.time())
-            .unix_timestamp_str();
-        dest.write_str(&unix_timestamp_str)
-            .map_err(Error::FormatRecord)
+        let unit_nanos = match self.unit_nanos {
+            Some(unit_nanos) => unit_nanos,
+            None => {
+                let unix_timestamp_str = local_time_cacher(ctx.time_cache)
+                    .get(record.time())
+                    .unix_timestamp_str();
+                return dest
+                    .write_str(&unix_timestamp_str)
+                    .map_err(Error::FormatRecord);
+            }
+        };
+
+        // No time zone is involved, so it's computed directly instead of cached.
+        // Times before the epoch are rounded down, as the seconds are.
+        match record.time().duration_since(UNIX_EPOCH) {
+            Ok(since) => write!(dest, "{}", since.as_nanos() / unit_nanos),
+            Err(err) => {
+                let before = (err.duration().as_nanos() + unit_nanos - 1) / unit_nanos;
+                write!(dest, "-{}", before)
+            }
+        }
+        .map_err(Error::FormatRecord)
     }
 }
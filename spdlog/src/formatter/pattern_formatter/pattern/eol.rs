@@ -11,7 +11,10 @@ use crate::{
 ///
 /// On non-Windows systems, this pattern writes a `\n` to the output.
 ///
-/// On Windows, this pattern writes a `\r\n` to the output.
+/// On Windows, this pattern writes a `\r\n` to the output, unless
+/// [`PatternFormatter::force_lf_eol`] is enabled.
+///
+/// [`PatternFormatter::force_lf_eol`]: crate::formatter::PatternFormatter::force_lf_eol
 #[derive(Clone, Default)]
 pub struct Eol;
 
@@ -20,8 +23,8 @@ impl Pattern for Eol {
         &self,
         _record: &Record,
         dest: &mut StringBuf,
-        _ctx: &mut PatternContext,
+        ctx: &mut PatternContext,
     ) -> crate::Result<()> {
-        dest.write_str(crate::EOL).map_err(Error::FormatRecord)
+        dest.write_str(ctx.eol).map_err(Error::FormatRecord)
     }
 }
@@ -0,0 +1,87 @@
+use std::fmt::{self, Write};
+
+use crate::{
+    formatter::{
+        full_formatter::write_logfmt_value,
+        gelf_formatter::write_json_str,
+        pattern_formatter::{Pattern, PatternContext},
+    },
+    Error, Record, StringBuf,
+};
+
+/// A pattern that writes the key-values of a log record into output. Example:
+/// `request_id=42 user=alice`.
+///
+/// By default, all [key-values of the record] are written in logfmt. The
+/// argument is a comma-separated list of options:
+///
+/// - `all` (default) or `mdc`, writing only the key-values captured from the
+///   [MDC], see [`Record::mdc_key_values`].
+/// - `logfmt` (default) or `json`, writing them as a JSON object, e.g.
+///   `{"request_id":"42","user":"alice"}`.
+///
+/// Nothing is written in logfmt if there are no key-values, while `{}` is
+/// written in JSON.
+///
+/// [key-values of the record]: crate::Record::key_values
+/// [MDC]: crate::mdc
+/// [`Record::mdc_key_values`]: crate::Record::mdc_key_values
+#[derive(Clone, Default)]
+pub struct Fields {
+    mdc_only: bool,
+    json: bool,
+}
+
+impl Fields {
+    // Used by the `pattern!` macro for `{fields:OPTIONS}`.
+    #[doc(hidden)]
+    #[must_use]
+    pub fn __new(mdc_only: bool, json: bool) -> Self {
+        Self { mdc_only, json }
+    }
+
+    fn write<'a>(
+        &self,
+        dest: &mut StringBuf,
+        key_values: impl Iterator<Item = (&'a str, &'a str)>,
+    ) -> fmt::Result {
+        if self.json {
+            dest.write_char('{')?;
+            for (i, (key, value)) in key_values.enumerate() {
+                if i != 0 {
+                    dest.write_char(',')?;
+                }
+                write_json_str(dest, key)?;
+                dest.write_char(':')?;
+                write_json_str(dest, value)?;
+            }
+            dest.write_char('}')
+        } else {
+            for (i, (key, value)) in key_values.enumerate() {
+                if i != 0 {
+                    dest.write_char(' ')?;
+                }
+                dest.write_str(key)?;
+                dest.write_char('=')?;
+                write_logfmt_value(dest, value)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+impl Pattern for Fields {
+    fn format(
+        &self,
+        record: &Record,
+        dest: &mut StringBuf,
+        _ctx: &mut PatternContext,
+    ) -> crate::Result<()> {
+        let result = if self.mdc_only {
+            self.write(dest, record.mdc_key_values())
+        } else {
+            self.write(dest, record.key_values())
+        };
+        result.map_err(Error::FormatRecord)
+    }
+}
@@ -1,7 +1,7 @@
 use crate::{
     formatter::{
         pattern_formatter::{Pattern, PatternContext},
-        Formatter, FullFormatter,
+        Formatter, FullFormatter, StyleHint,
     },
     Record, StringBuf,
 };
@@ -31,11 +31,10 @@ impl Pattern for Full {
         ctx: &mut PatternContext,
     ) -> crate::Result<()> {
         let extra_info = self.full_formatter.format(record, dest)?;
-        if let Some(style_range) = extra_info.style_range {
-            // Before we support multiple style ranges, if there is already a style range
-            // set, we don't override it.
-            if ctx.fmt_info_builder.info.style_range.is_none() {
-                ctx.set_style_range(style_range)
+        for (style_range, hint) in extra_info.style_ranges() {
+            // If there is already a level style range set, we don't override it.
+            if hint != StyleHint::Level || ctx.fmt_info_builder.info.style_range.is_none() {
+                ctx.set_style_range_with_hint(style_range, hint)
             }
         }
         Ok(())
@@ -38,3 +38,20 @@ impl Pattern for ShortLevel {
             .map_err(Error::FormatRecord)
     }
 }
+
+/// A pattern that writes the level in uppercase of a log record into the
+/// output. Examples: `CRITICAL`, `ERROR`, `WARN`.
+#[derive(Clone, Default)]
+pub struct UpperLevel;
+
+impl Pattern for UpperLevel {
+    fn format(
+        &self,
+        record: &Record,
+        dest: &mut StringBuf,
+        _ctx: &mut PatternContext,
+    ) -> crate::Result<()> {
+        dest.write_str(record.level().as_upper_str())
+            .map_err(Error::FormatRecord)
+    }
+}
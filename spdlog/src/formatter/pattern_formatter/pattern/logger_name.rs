@@ -1,23 +1,111 @@
 use std::fmt::Write;
 
 use crate::{
-    formatter::pattern_formatter::{Pattern, PatternContext},
+    formatter::{
+        pattern_formatter::{Pattern, PatternContext},
+        StyleHint,
+    },
     Error, Record, StringBuf,
 };
 
 /// A pattern that writes the logger's name into the output. Example:
 /// `my-logger`.
+///
+/// Dotted names, e.g. `com.example.app.service.db.pool`, can be shortened
+/// depending on the argument:
+///
+/// - `{logger}` writes the name as it is.
+/// - `{logger:abbrev}` abbreviates each segment except the last one to its
+///   first character, e.g. `c.e.a.s.d.pool`.
+/// - `{logger:abbrev(N)}` abbreviates segments from the left until the name is
+///   at most `N` characters, in the style of Logback, e.g.
+///   `{logger:abbrev(15)}` writes `c.e.a.s.db.pool`. The last segment is never
+///   abbreviated, so the name may still be longer than `N`.
+/// - `{logger:short(N)}` writes only the last `N` segments, e.g.
+///   `{logger:short(2)}` writes `db.pool`.
+///
+/// Names without dots are always written as they are.
 #[derive(Clone, Default)]
-pub struct LoggerName;
+pub struct LoggerName {
+    style: NameStyle,
+}
+
+#[derive(Clone)]
+enum NameStyle {
+    Full,
+    Abbreviated { max_chars: usize },
+    LastSegments(usize),
+}
+
+impl Default for NameStyle {
+    fn default() -> Self {
+        Self::Full
+    }
+}
+
+impl LoggerName {
+    // Used by the `pattern!` macro for `{logger:abbrev}` (`max_chars` is 0) and
+    // `{logger:abbrev(N)}`.
+    #[doc(hidden)]
+    #[must_use]
+    pub fn __abbreviated(max_chars: usize) -> Self {
+        Self {
+            style: NameStyle::Abbreviated { max_chars },
+        }
+    }
+
+    // Used by the `pattern!` macro for `{logger:short(N)}`.
+    #[doc(hidden)]
+    #[must_use]
+    pub fn __last_segments(count: usize) -> Self {
+        Self {
+            style: NameStyle::LastSegments(count),
+        }
+    }
+}
 
 impl Pattern for LoggerName {
     fn format(
         &self,
         record: &Record,
         dest: &mut StringBuf,
-        _ctx: &mut PatternContext,
+        ctx: &mut PatternContext,
     ) -> crate::Result<()> {
-        dest.write_str(record.logger_name().unwrap_or(""))
-            .map_err(Error::FormatRecord)
+        let name = record.logger_name().unwrap_or("");
+        let begin = dest.len();
+        match self.style {
+            NameStyle::Full => dest.write_str(name),
+            NameStyle::Abbreviated { max_chars } => write_abbreviated(dest, name, max_chars),
+            NameStyle::LastSegments(count) => {
+                let tail = match name.rmatch_indices('.').nth(count.saturating_sub(1)) {
+                    Some((pos, _)) => &name[pos + 1..],
+                    None => name,
+                };
+                dest.write_str(tail)
+            }
+        }
+        .map_err(Error::FormatRecord)?;
+        if dest.len() != begin {
+            ctx.set_style_range_with_hint(begin..dest.len(), StyleHint::LoggerName);
+        }
+        Ok(())
+    }
+}
+
+fn write_abbreviated(dest: &mut StringBuf, name: &str, max_chars: usize) -> std::fmt::Result {
+    let last = name.matches('.').count();
+    let mut chars = name.chars().count();
+    for (i, segment) in name.split('.').enumerate() {
+        if i != 0 {
+            dest.write_char('.')?;
+        }
+        match segment.chars().next() {
+            Some(first) if i != last && chars > max_chars => {
+                dest.write_char(first)?;
+                chars -= segment.chars().count() - 1;
+            }
+            _ => dest.write_str(segment)?,
+        }
     }
+    Ok(())
 }
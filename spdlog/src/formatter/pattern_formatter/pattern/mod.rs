@@ -2,6 +2,7 @@
 
 mod datetime;
 mod eol;
+mod fields;
 mod full;
 mod level;
 mod logger_name;
@@ -13,6 +14,7 @@ mod thread_id;
 
 pub use datetime::*;
 pub use eol::*;
+pub use fields::*;
 pub use full::*;
 pub use level::*;
 pub use logger_name::*;
@@ -2,13 +2,49 @@ use std::fmt::Write;
 
 use crate::{
     formatter::pattern_formatter::{Pattern, PatternContext},
-    Error, Record, StringBuf,
+    Error, Hex, Record, StringBuf,
 };
 
 /// A pattern that writes the payload of a log record into output. Example: `log
 /// message`.
+///
+/// With `{payload:trunc(N)}`, payloads longer than `N` characters are
+/// truncated to `N - 1` characters followed by `…`, so the output is at most
+/// `N` characters.
+///
+/// With `{payload:hex}`, the bytes of the payload are written as lowercase
+/// hexadecimal digits, two digits per byte. For records logged by
+/// [`log_bytes!`], they are the raw bytes (see [`Record::payload_bytes`]), so
+/// payloads that are not valid UTF-8 are written losslessly.
+///
+/// [`log_bytes!`]: crate::log_bytes
 #[derive(Clone, Default)]
-pub struct Payload;
+pub struct Payload {
+    max_chars: Option<usize>,
+    hex: bool,
+}
+
+impl Payload {
+    // Used by the `pattern!` macro for `{payload:trunc(N)}`.
+    #[doc(hidden)]
+    #[must_use]
+    pub fn __truncated(max_chars: usize) -> Self {
+        Self {
+            max_chars: Some(max_chars),
+            hex: false,
+        }
+    }
+
+    // Used by the `pattern!` macro for `{payload:hex}`.
+    #[doc(hidden)]
+    #[must_use]
+    pub fn __hex() -> Self {
+        Self {
+            max_chars: None,
+            hex: true,
+        }
+    }
+}
 
 impl Pattern for Payload {
     fn format(
@@ -17,7 +53,26 @@ impl Pattern for Payload {
         dest: &mut StringBuf,
         _ctx: &mut PatternContext,
     ) -> crate::Result<()> {
-        dest.write_str(record.payload())
-            .map_err(Error::FormatRecord)
+        if self.hex {
+            return write!(dest, "{}", Hex::new(record.payload_bytes()))
+                .map_err(Error::FormatRecord);
+        }
+
+        let payload = record.payload();
+        let result = match self.max_chars {
+            None => dest.write_str(payload),
+            Some(max_chars) => match payload.char_indices().nth(max_chars) {
+                // Fits in `max_chars` characters
+                None => dest.write_str(payload),
+                // Not even the ellipsis fits
+                Some(_) if max_chars == 0 => Ok(()),
+                Some(_) => {
+                    let (end, _) = payload.char_indices().nth(max_chars - 1).unwrap();
+                    dest.write_str(&payload[..end])
+                        .and_then(|_| dest.write_char('…'))
+                }
+            },
+        };
+        result.map_err(Error::FormatRecord)
     }
 }
@@ -7,8 +7,53 @@ use crate::{
 
 /// A pattern that writes the source file, line and column of a log record into
 /// the output. Example: `path/to/main.rs:30`.
+///
+/// How the file path is rendered depends on the argument:
+///
+/// - `{source}` and `{source:full}` write the path as it is.
+/// - `{source:basename}` writes only the file name, e.g. `main.rs:30`.
+/// - `{source:strip_prefix=PREFIX}` removes `PREFIX` and the path separators
+///   following it from the path, e.g. `{source:strip_prefix=path/to}` writes
+///   `main.rs:30`. `PREFIX` only matches whole path components, so paths not
+///   starting with the directory `PREFIX` (e.g. `path/too/main.rs`) are written
+///   as they are.
 #[derive(Clone, Default)]
-pub struct Source;
+pub struct Source {
+    path: SourcePath,
+}
+
+#[derive(Clone)]
+enum SourcePath {
+    Full,
+    Basename,
+    StripPrefix(&'static str),
+}
+
+impl Default for SourcePath {
+    fn default() -> Self {
+        Self::Full
+    }
+}
+
+impl Source {
+    // Used by the `pattern!` macro for `{source:basename}`.
+    #[doc(hidden)]
+    #[must_use]
+    pub fn __basename() -> Self {
+        Self {
+            path: SourcePath::Basename,
+        }
+    }
+
+    // Used by the `pattern!` macro for `{source:strip_prefix=PREFIX}`.
+    #[doc(hidden)]
+    #[must_use]
+    pub fn __strip_prefix(prefix: &'static str) -> Self {
+        Self {
+            path: SourcePath::StripPrefix(prefix),
+        }
+    }
+}
 
 impl Pattern for Source {
     fn format(
@@ -18,8 +63,13 @@ impl Pattern for Source {
         _ctx: &mut PatternContext,
     ) -> crate::Result<()> {
         if let Some(loc) = record.source_location() {
+            let file = match self.path {
+                SourcePath::Full => loc.file(),
+                SourcePath::Basename => loc.file_name(),
+                SourcePath::StripPrefix(prefix) => strip_path_prefix(loc.file(), prefix),
+            };
             (|| {
-                dest.write_str(loc.file())?;
+                dest.write_str(file)?;
                 dest.write_char(':')?;
                 write!(dest, "{}", loc.line())
             })()
@@ -29,6 +79,20 @@ impl Pattern for Source {
     }
 }
 
+// Strips `prefix` from `path` only if it ends at a path separator, so that
+// `src/a` does not strip `src/ab.rs` into `b.rs`.
+#[must_use]
+fn strip_path_prefix<'a>(path: &'a str, prefix: &str) -> &'a str {
+    const SEPARATORS: &[char] = &['/', '\\'];
+
+    match path.strip_prefix(prefix) {
+        Some(rest) if prefix.ends_with(SEPARATORS) || rest.starts_with(SEPARATORS) => {
+            rest.trim_start_matches(SEPARATORS)
+        }
+        _ => path,
+    }
+}
+
 /// A pattern that writes the source file basename into the output. Example:
 /// `main.rs`.
 #[derive(Clone, Default)]
@@ -0,0 +1,193 @@
+use crate::{
+    formatter::pattern_formatter::{__pattern::*, Pattern, PatternContext, PatternFormatter},
+    Record, StringBuf,
+};
+
+/// Predefined patterns, for setting up a [`PatternFormatter`] without writing
+/// a template string.
+///
+/// Each preset is equivalent to a pattern built by the [`pattern!`] macro with
+/// the template string listed in the documentation of the variant. Example
+/// outputs are for a logger named `my-app`.
+///
+/// # Examples
+///
+/// ```
+/// use spdlog::{
+///     formatter::{PatternFormatter, Preset},
+///     sink::{StdStream, StdStreamSink},
+/// };
+///
+/// # fn main() -> Result<(), spdlog::Error> {
+/// let sink = StdStreamSink::builder()
+///     .std_stream(StdStream::Stdout)
+///     .formatter(Box::new(PatternFormatter::preset(Preset::Compact)))
+///     .build()?;
+/// # Ok(()) }
+/// ```
+///
+/// [`pattern!`]: crate::formatter::pattern
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[non_exhaustive]
+pub enum Preset {
+    /// A syslog-like format, with the logger name as the tag.
+    ///
+    /// ```text
+    /// {month_name} {day} {time} {logger}[{pid}]: [{level}] {payload}{eol}
+    /// ```
+    ///
+    /// ```text
+    /// Apr 01 22:28:02 my-app[3824]: [info] log message
+    /// ```
+    Syslog,
+    /// A format like the error log of the Apache HTTP server.
+    ///
+    /// ```text
+    /// [{weekday_name} {month_name} {day} {time}.{microsecond} {year}] [{logger}:{level}] [pid {pid}:tid {tid}] {payload}{eol}
+    /// ```
+    ///
+    /// ```text
+    /// [Fri Apr 01 22:28:02.372152 2022] [my-app:info] [pid 3824:tid 3132] log message
+    /// ```
+    Apache,
+    /// A short format with the time, the short level and the payload only.
+    ///
+    /// ```text
+    /// {time} {^{level_short}} {payload}{eol}
+    /// ```
+    ///
+    /// ```text
+    /// 22:28:02 I log message
+    /// ```
+    Compact,
+    /// A format with all information available, including the source
+    /// location.
+    ///
+    /// ```text
+    /// [{date} {time}.{microsecond} {tz_offset}] [{logger}] [{^{level}}] [pid {pid} tid {tid}] [{module_path}, {source}] {payload}{eol}
+    /// ```
+    ///
+    /// ```text
+    /// [2022-04-01 22:28:02.372152 +08:00] [my-app] [info] [pid 3824 tid 3132] [my_app::net, src/net.rs:30] log message
+    /// ```
+    Verbose,
+}
+
+impl Pattern for Preset {
+    fn format(
+        &self,
+        record: &Record,
+        dest: &mut StringBuf,
+        ctx: &mut PatternContext,
+    ) -> crate::Result<()> {
+        match self {
+            Preset::Syslog => syslog().format(record, dest, ctx),
+            Preset::Apache => apache().format(record, dest, ctx),
+            Preset::Compact => compact().format(record, dest, ctx),
+            Preset::Verbose => verbose().format(record, dest, ctx),
+        }
+    }
+}
+
+impl PatternFormatter<Preset> {
+    /// Creates a new `PatternFormatter` object with the given preset pattern.
+    ///
+    /// For the patterns of presets, see the documentation of [`Preset`].
+    #[must_use]
+    pub fn preset(preset: Preset) -> Self {
+        Self::new(preset)
+    }
+}
+
+// The functions below must be kept in sync with the template strings in the
+// documentation of `Preset`, which are asserted in tests/pattern.rs.
+
+#[must_use]
+fn syslog() -> impl Pattern {
+    (
+        AbbrMonthName,
+        " ",
+        Day,
+        " ",
+        Time,
+        " ",
+        LoggerName::default(),
+        "[",
+        ProcessId,
+        "]: [",
+        Level,
+        "] ",
+        Payload::default(),
+        Eol,
+    )
+}
+
+#[must_use]
+fn apache() -> impl Pattern {
+    (
+        "[",
+        AbbrWeekdayName,
+        " ",
+        AbbrMonthName,
+        " ",
+        Day,
+        " ",
+        Time,
+        ".",
+        Microsecond,
+        " ",
+        Year,
+        "] [",
+        LoggerName::default(),
+        ":",
+        Level,
+        "] [pid ",
+        ProcessId,
+        ":tid ",
+        ThreadId,
+        "] ",
+        Payload::default(),
+        Eol,
+    )
+}
+
+#[must_use]
+fn compact() -> impl Pattern {
+    (
+        Time,
+        " ",
+        StyleRange::new((ShortLevel,)),
+        " ",
+        Payload::default(),
+        Eol,
+    )
+}
+
+#[must_use]
+fn verbose() -> impl Pattern {
+    (
+        "[",
+        Date,
+        " ",
+        Time,
+        ".",
+        Microsecond,
+        " ",
+        TzOffset,
+        "] [",
+        LoggerName::default(),
+        "] [",
+        StyleRange::new((Level,)),
+        "] [pid ",
+        ProcessId,
+        " tid ",
+        ThreadId,
+        "] [",
+        SourceModulePath,
+        ", ",
+        Source::default(),
+        "] ",
+        Payload::default(),
+        Eol,
+    )
+}
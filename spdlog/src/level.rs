@@ -1,6 +1,6 @@
 //! Provides stuff related to log levels
 
-use std::{fmt, str::FromStr};
+use std::{cmp::Ordering, fmt, str::FromStr};
 
 use cfg_if::cfg_if;
 
@@ -11,11 +11,32 @@ pub(crate) const LOG_LEVEL_NAMES: [&str; Level::count()] =
 
 const LOG_LEVEL_SHORT_NAMES: [&str; Level::count()] = ["C", "E", "W", "I", "D", "T"];
 
+const LOG_LEVEL_UPPER_NAMES: [&str; Level::count()] =
+    ["CRITICAL", "ERROR", "WARN", "INFO", "DEBUG", "TRACE"];
+
 /// An enum representing log levels.
 ///
 /// Typical usage includes: specifying the `Level` of [`log!`], and comparing a
 /// `Level` to a [`LevelFilter`] through [`LevelFilter::compare`].
 ///
+/// # Ordering
+///
+/// A more severe `Level` is **greater** than a more verbose one, i.e.
+/// `Level::Critical > Level::Error > ... > Level::Trace`. Note that this is
+/// the opposite of `log::Level`, where `Error` is the least.
+///
+/// To avoid confusion about the direction, prefer
+/// [`Level::is_more_severe_than`] and [`Level::is_more_verbose_than`] over
+/// comparison operators.
+///
+/// ```
+/// use spdlog::Level;
+///
+/// assert!(Level::Error > Level::Info);
+/// assert!(Level::Error.is_more_severe_than(Level::Info));
+/// assert!(Level::Info.is_more_verbose_than(Level::Error));
+/// ```
+///
 /// # Note
 ///
 /// Users should never convert variants of this enum to integers for persistent
@@ -117,9 +138,27 @@ impl Level {
         Level::Trace
     }
 
+    /// Returns `true` if the `Level` is more severe than the given one.
+    ///
+    /// A `Level` is not more severe than itself.
+    #[must_use]
+    pub const fn is_more_severe_than(self, other: Level) -> bool {
+        (self as u16) < (other as u16)
+    }
+
+    /// Returns `true` if the `Level` is more verbose than the given one.
+    ///
+    /// A `Level` is not more verbose than itself.
+    #[must_use]
+    pub const fn is_more_verbose_than(self, other: Level) -> bool {
+        other.is_more_severe_than(self)
+    }
+
     /// Returns the string representation of the `Level`.
     ///
-    /// This returns the same string as the `fmt::Display` implementation.
+    /// This returns the same string as the `fmt::Display` implementation and
+    /// the built-in formatters, and can be parsed back by the `FromStr`
+    /// implementation.
     #[must_use]
     pub fn as_str(&self) -> &'static str {
         LOG_LEVEL_NAMES[*self as usize]
@@ -130,6 +169,76 @@ impl Level {
         LOG_LEVEL_SHORT_NAMES[*self as usize]
     }
 
+    #[must_use]
+    pub(crate) fn as_upper_str(&self) -> &'static str {
+        LOG_LEVEL_UPPER_NAMES[*self as usize]
+    }
+
+    /// Returns the syslog severity of the `Level`, as defined in [RFC 5424].
+    ///
+    /// | `Level`    | Severity          |
+    /// |------------|-------------------|
+    /// | `Critical` | `2` (critical)    |
+    /// | `Error`    | `3` (error)       |
+    /// | `Warn`     | `4` (warning)     |
+    /// | `Info`     | `6` (informational) |
+    /// | `Debug`    | `7` (debug)       |
+    /// | `Trace`    | `7` (debug)       |
+    ///
+    /// Severities `0` (emergency), `1` (alert) and `5` (notice) are never
+    /// returned.
+    ///
+    /// [RFC 5424]: https://datatracker.ietf.org/doc/html/rfc5424#section-6.2.1
+    #[must_use]
+    pub const fn to_syslog_severity(self) -> u8 {
+        match self {
+            Level::Critical => 2,
+            Level::Error => 3,
+            Level::Warn => 4,
+            Level::Info => 6,
+            Level::Debug | Level::Trace => 7,
+        }
+    }
+
+    /// Returns the `level` field of the `Level` in [GELF].
+    ///
+    /// GELF levels are syslog severities, so it's the same as
+    /// [`Level::to_syslog_severity`].
+    ///
+    /// [GELF]: https://go2docs.graylog.org/current/getting_in_log_data/gelf.html
+    #[must_use]
+    pub const fn to_gelf(self) -> u8 {
+        self.to_syslog_severity()
+    }
+
+    /// Returns the `SeverityNumber` of the `Level` in the [OpenTelemetry log
+    /// data model].
+    ///
+    /// | `Level`    | `SeverityNumber` |
+    /// |------------|------------------|
+    /// | `Critical` | `21` (FATAL)     |
+    /// | `Error`    | `17` (ERROR)     |
+    /// | `Warn`     | `13` (WARN)      |
+    /// | `Info`     | `9` (INFO)       |
+    /// | `Debug`    | `5` (DEBUG)      |
+    /// | `Trace`    | `1` (TRACE)      |
+    ///
+    /// The returned numbers are the first of their ranges, e.g. `Info` maps
+    /// to `9` (INFO) rather than `10` (INFO2).
+    ///
+    /// [OpenTelemetry log data model]: https://opentelemetry.io/docs/specs/otel/logs/data-model/#field-severitynumber
+    #[must_use]
+    pub const fn to_otel_severity_number(self) -> u8 {
+        match self {
+            Level::Critical => 21,
+            Level::Error => 17,
+            Level::Warn => 13,
+            Level::Info => 9,
+            Level::Debug => 5,
+            Level::Trace => 1,
+        }
+    }
+
     /// Iterate through all supported logging levels.
     ///
     /// The order of iteration is from more severe to more verbose.
@@ -162,6 +271,34 @@ impl From<log::Level> for Level {
     }
 }
 
+#[cfg(feature = "slog")]
+impl From<slog::Level> for Level {
+    fn from(level: slog::Level) -> Self {
+        match level {
+            slog::Level::Critical => Self::Critical,
+            slog::Level::Error => Self::Error,
+            slog::Level::Warning => Self::Warn,
+            slog::Level::Info => Self::Info,
+            slog::Level::Debug => Self::Debug,
+            slog::Level::Trace => Self::Trace,
+        }
+    }
+}
+
+impl PartialOrd for Level {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// Not derived, as the discriminants are in the order from more severe to more
+// verbose, while a more severe level is greater.
+impl Ord for Level {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (*other as u16).cmp(&(*self as u16))
+    }
+}
+
 impl fmt::Display for Level {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.write_str(self.as_str())
@@ -185,7 +322,8 @@ impl FromStr for Level {
 /// An enum representing log level logical filter conditions.
 ///
 /// A `LevelFilter` may be compared to a [`Level`] through
-/// [`LevelFilter::compare`].
+/// [`LevelFilter::compare`], and the enabled levels can be enumerated through
+/// [`LevelFilter::levels`].
 ///
 /// # Examples
 ///
@@ -248,20 +386,37 @@ impl LevelFilter {
     #[doc(hidden)]
     #[must_use]
     pub const fn __compare_const(&self, level: Level) -> bool {
-        let level_num: u16 = level as u16;
-
         match *self {
             Self::Off => false,
-            Self::Equal(stored) => level_num == stored as u16,
-            Self::NotEqual(stored) => level_num != stored as u16,
-            Self::MoreSevere(stored) => level_num < stored as u16,
-            Self::MoreSevereEqual(stored) => level_num <= stored as u16,
-            Self::MoreVerbose(stored) => level_num > stored as u16,
-            Self::MoreVerboseEqual(stored) => level_num >= stored as u16,
+            Self::Equal(stored) => level as u16 == stored as u16,
+            Self::NotEqual(stored) => level as u16 != stored as u16,
+            Self::MoreSevere(stored) => level.is_more_severe_than(stored),
+            Self::MoreSevereEqual(stored) => !level.is_more_verbose_than(stored),
+            Self::MoreVerbose(stored) => level.is_more_verbose_than(stored),
+            Self::MoreVerboseEqual(stored) => !level.is_more_severe_than(stored),
             Self::All => true,
         }
     }
 
+    /// Iterate through the logging levels enabled by the filter.
+    ///
+    /// The order of iteration is from more severe to more verbose, the same as
+    /// [`Level::iter`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spdlog::prelude::*;
+    ///
+    /// let levels: Vec<Level> = LevelFilter::MoreSevere(Level::Warn).levels().collect();
+    ///
+    /// assert_eq!(levels, [Level::Critical, Level::Error]);
+    /// ```
+    pub fn levels(&self) -> impl Iterator<Item = Level> {
+        let level_filter = *self;
+        Level::iter().filter(move |&level| level_filter.compare(level))
+    }
+
     #[must_use]
     pub(crate) fn from_str_for_env(text: &str) -> Option<LevelFilter> {
         if let Ok(level) = Level::from_str(text) {
@@ -334,6 +489,14 @@ mod tests {
         assert!(Level::from_str("notexist").is_err());
     }
 
+    #[test]
+    fn as_str() {
+        for level in Level::iter() {
+            assert_eq!(level.as_str(), level.to_string());
+            assert_eq!(Level::from_str(level.as_str()).unwrap(), level);
+        }
+    }
+
     #[test]
     fn as_short_str() {
         for (&name, &short_name) in LOG_LEVEL_NAMES.iter().zip(LOG_LEVEL_SHORT_NAMES.iter()) {
@@ -348,6 +511,33 @@ mod tests {
         }
     }
 
+    #[test]
+    fn as_upper_str() {
+        for (&name, &upper_name) in LOG_LEVEL_NAMES.iter().zip(LOG_LEVEL_UPPER_NAMES.iter()) {
+            assert_eq!(name.to_ascii_uppercase(), upper_name);
+        }
+    }
+
+    #[test]
+    fn numeric_severities() {
+        assert_eq!(
+            Level::iter()
+                .map(Level::to_syslog_severity)
+                .collect::<Vec<_>>(),
+            [2, 3, 4, 6, 7, 7]
+        );
+        assert_eq!(
+            Level::iter().map(Level::to_gelf).collect::<Vec<_>>(),
+            [2, 3, 4, 6, 7, 7]
+        );
+        assert_eq!(
+            Level::iter()
+                .map(Level::to_otel_severity_number)
+                .collect::<Vec<_>>(),
+            [21, 17, 13, 9, 5, 1]
+        );
+    }
+
     #[test]
     fn level_filter_from_str_for_env() {
         assert_eq!(
@@ -383,6 +573,46 @@ mod tests {
         assert_eq!(iter.next(), None);
     }
 
+    #[test]
+    fn severity_order() {
+        assert!(Level::Critical > Level::Error);
+        assert!(Level::Error > Level::Warn);
+        assert!(Level::Warn > Level::Info);
+        assert!(Level::Info > Level::Debug);
+        assert!(Level::Debug > Level::Trace);
+        assert_eq!(Level::iter().max(), Some(Level::most_severe()));
+        assert_eq!(Level::iter().min(), Some(Level::most_verbose()));
+
+        assert!(Level::Error.is_more_severe_than(Level::Info));
+        assert!(!Level::Info.is_more_severe_than(Level::Error));
+        assert!(!Level::Info.is_more_severe_than(Level::Info));
+        assert!(Level::Trace.is_more_verbose_than(Level::Debug));
+        assert!(!Level::Debug.is_more_verbose_than(Level::Trace));
+        assert!(!Level::Debug.is_more_verbose_than(Level::Debug));
+
+        // Filters agree with the ordering
+        for filter_level in Level::iter() {
+            for level in Level::iter() {
+                assert_eq!(
+                    LevelFilter::MoreSevere(filter_level).compare(level),
+                    level > filter_level
+                );
+                assert_eq!(
+                    LevelFilter::MoreSevereEqual(filter_level).compare(level),
+                    level >= filter_level
+                );
+                assert_eq!(
+                    LevelFilter::MoreVerbose(filter_level).compare(level),
+                    level < filter_level
+                );
+                assert_eq!(
+                    LevelFilter::MoreVerboseEqual(filter_level).compare(level),
+                    level <= filter_level
+                );
+            }
+        }
+    }
+
     #[test]
     fn filter() {
         assert!(!LevelFilter::Off.compare(Level::Trace));
@@ -418,6 +648,30 @@ mod tests {
         assert!(LevelFilter::All.compare(Level::Error));
     }
 
+    #[test]
+    fn filter_levels() {
+        assert_eq!(LevelFilter::Off.levels().count(), 0);
+        assert!(LevelFilter::All.levels().eq(Level::iter()));
+        assert_eq!(
+            LevelFilter::NotEqual(Level::Info)
+                .levels()
+                .collect::<Vec<_>>(),
+            [
+                Level::Critical,
+                Level::Error,
+                Level::Warn,
+                Level::Debug,
+                Level::Trace
+            ]
+        );
+        assert_eq!(
+            LevelFilter::MoreVerboseEqual(Level::Debug)
+                .levels()
+                .collect::<Vec<_>>(),
+            [Level::Debug, Level::Trace]
+        );
+    }
+
     #[cfg(feature = "log")]
     #[test]
     fn filter_from_log() {
@@ -74,10 +74,21 @@
 //!
 //! See [./examples] directory for examples.
 //!
+//! # Compatible with slog crate
+//!
+//! This is optional and is controlled by crate feature `slog`.
+//!
+//! [`SlogDrain`] is a [`slog::Drain`] forwarding records logged via [slog
+//! crate] to [`default_logger`] or a specified logger, to migrate from [slog
+//! crate] for your projects gradually.
+//!
 //! # Asynchronous support
 //!
 //! See [Asynchronous combined sink].
 //!
+//! Call [`shutdown`] at the end of `main` to ensure that the records queued in
+//! asynchronous sinks are written before the program exits.
+//!
 //! # Configured via environment variable
 //!
 //! Users can optionally configure the level filter of loggers via the
@@ -115,6 +126,24 @@
 //! debug, and info level logs in release builds with
 //! `features = ["level-debug", "release-level-warn"]`.
 //!
+//! The expressions passed to a log macro at a disabled level, including the
+//! `logger:` expression, are not evaluated. The exception is the [`Result`]
+//! passed to [`err_log!`] and [`warn_log!`], which they evaluate to.
+//!
+//! ## Stripping all logging
+//!
+//! For builds where logging must be entirely absent (e.g. for binary size or
+//! security reasons), the `logging-off` feature disables all levels in both
+//! debug and release builds, taking precedence over the features above. All
+//! log macros, including [`error_chain!`], [`log_at!`] and [`trace_scope!`],
+//! then expand to code that is never executed, so no records are built and
+//! the arguments are not evaluated, while they are still type-checked.
+//!
+//! It only affects macros. Explicit calls such as [`Logger::log`] with a
+//! manually built record still log, as do records forwarded from the `log`
+//! crate by [`LogCrateProxy`], which is configured with the `log` crate's own
+//! `max_level_off` feature.
+//!
 //! # Crate Feature Flags
 //!
 //! The following crate feature flags are available in addition to the filters.
@@ -134,10 +163,26 @@
 //!  - `log` see [Compatible with log crate](#compatible-with-log-crate) above.
 //!
 //!  - `native` enables platform-specific components, such as
-//!    [`sink::WinDebugSink`], [`sink::JournaldSink`], etc. Note If the
-//!    component requires additional system dependencies, then more granular
-//!    features need to be enabled as well. See the documentation of the
-//!    component for these details.
+//!    [`sink::WinDebugSink`], [`sink::JournaldSink`], [`sink::LogcatSink`],
+//!    [`sink::OsLogSink`], etc. Note If the component requires additional
+//!    system dependencies, then more granular features need to be enabled as
+//!    well. See the documentation of the component for these details.
+//!
+//!  - `config` enables building loggers from declarative configuration files,
+//!    and reloading them at runtime. See module [`config`].
+//!
+//!  - `metrics` enables emitting counters of logged records to the [`metrics`]
+//!    facade. See [`LoggerBuilder::emit_metrics`]. Note that the [`metrics`]
+//!    crate requires Rust 1.71.1 or later, which is above the minimum supported
+//!    Rust version of `spdlog-rs`.
+//!
+//!  - `slog` see [Compatible with slog crate](#compatible-with-slog-crate)
+//!    above.
+//!
+//!  - `test-util` enables helpers for capturing and asserting logs in tests.
+//!    See module [`test`].
+//!
+//! [`metrics`]: https://docs.rs/metrics
 //!
 //! # Supported Rust Versions
 //!
@@ -148,7 +193,8 @@
 //! - README.md
 //! -->
 //!
-//! The current minimum supported Rust version is 1.56.
+//! The current minimum supported Rust version is 1.56, except for the
+//! `metrics` feature, which requires 1.71.1.
 //!
 //! `spdlog-rs` is built against the latest Rust stable release, it is not
 //! guaranteed to build on Rust versions earlier than the minimum supported
@@ -176,7 +222,8 @@
 //!
 //!  - `daily_file_sink` and `hourly_file_sink` in C++ `spdlog` are merged into
 //!    [`RotatingFileSink`] in `spdlog-rs`. They correspond to rotation policies
-//!    [`RotationPolicy::Daily`] and [`RotationPolicy::Hourly`].
+//!    [`RotationPolicy::Daily`] and [`RotationPolicy::Hourly`]. For
+//!    convenience, [`DailyFileSink`] and [`HourlyFileSink`] are also provided.
 //!
 //!  - `async_logger` in C++ `spdlog` is [`AsyncPoolSink`] in `spdlog-rs`. This
 //!    allows it to be used with synchronous sinks.
@@ -195,9 +242,12 @@
 //! [open a discussion]: https://github.com/SpriteOvO/spdlog-rs/discussions/new
 //! [open an issue]: https://github.com/SpriteOvO/spdlog-rs/issues/new/choose
 //! [log crate]: https://crates.io/crates/log
+//! [slog crate]: https://crates.io/crates/slog
 //! [Asynchronous combined sink]: sink/index.html#asynchronous-combined-sink
 //! [`FullFormatter`]: crate::formatter::FullFormatter
 //! [`RotatingFileSink`]: crate::sink::RotatingFileSink
+//! [`DailyFileSink`]: crate::sink::DailyFileSink
+//! [`HourlyFileSink`]: crate::sink::HourlyFileSink
 //! [`Formatter`]: crate::formatter::Formatter
 //! [`RotationPolicy::Daily`]: crate::sink::RotationPolicy::Daily
 //! [`RotationPolicy::Hourly`]: crate::sink::RotationPolicy::Hourly
@@ -207,38 +257,62 @@
 #![cfg_attr(all(doc, CHANNEL_NIGHTLY), feature(doc_auto_cfg))]
 #![warn(missing_docs)]
 
+mod binary;
+#[cfg(feature = "config")]
+pub mod config;
 mod env_level;
 pub mod error;
+mod error_chain;
 pub mod formatter;
 mod level;
 #[cfg(feature = "log")]
 mod log_crate_proxy;
 mod log_macros;
+mod log_writer;
 mod logger;
+pub mod mdc;
 mod periodic_worker;
 mod record;
+mod shutdown;
+#[cfg(unix)]
+mod signal;
 pub mod sink;
+#[cfg(feature = "slog")]
+mod slog_drain;
 mod source_location;
 #[doc(hidden)]
 pub mod string_buf;
 mod sync;
 pub mod terminal_style;
+#[cfg(feature = "test-util")]
+pub mod test;
 #[cfg(test)]
 mod test_utils;
 #[cfg(feature = "multi-thread")]
 mod thread_pool;
+mod trace_scope;
 mod utils;
 
+pub use binary::*;
 pub use error::{Error, ErrorHandler, Result};
+pub use error_chain::*;
 pub use level::*;
 #[cfg(feature = "log")]
 pub use log_crate_proxy::*;
+pub use log_writer::*;
 pub use logger::*;
 pub use record::*;
+pub use shutdown::*;
+#[cfg(unix)]
+pub use signal::*;
+#[cfg(feature = "slog")]
+pub use slog_drain::*;
 pub use source_location::*;
 pub use string_buf::StringBuf;
+pub use terminal_style::strip_ansi;
 #[cfg(feature = "multi-thread")]
 pub use thread_pool::*;
+pub use trace_scope::*;
 
 /// Contains all log macros and common types.
 pub mod prelude {
@@ -272,7 +346,9 @@ use sync::*;
 pub const STATIC_LEVEL_FILTER: LevelFilter = STATIC_LEVEL_FILTER_INNER;
 
 cfg_if! {
-    if #[cfg(all(not(debug_assertions), feature = "release-level-off"))] {
+    if #[cfg(feature = "logging-off")] {
+        const STATIC_LEVEL_FILTER_INNER: LevelFilter = LevelFilter::Off;
+    } else if #[cfg(all(not(debug_assertions), feature = "release-level-off"))] {
         const STATIC_LEVEL_FILTER_INNER: LevelFilter = LevelFilter::Off;
     } else if #[cfg(all(not(debug_assertions), feature = "release-level-critical"))] {
         const STATIC_LEVEL_FILTER_INNER: LevelFilter = LevelFilter::MoreSevereEqual(Level::Critical);
@@ -357,7 +433,7 @@ fn default_logger_ref() -> &'static ArcSwap<Logger> {
 ///    ```
 ///
 /// Users can replace the default logger with [`set_default_logger`] or
-/// [`swap_default_logger`].
+/// [`swap_default_logger`], or temporarily with [`with_default_logger`].
 ///
 /// # Examples
 ///
@@ -401,7 +477,14 @@ pub fn swap_default_logger(logger: Arc<Logger>) -> Arc<Logger> {
     default_logger_ref().swap(logger)
 }
 
-/// Sets the given logger as the default logger.
+/// Sets the given logger as the default logger, and returns the old default
+/// logger so that it can be restored later.
+///
+/// The replacement is atomic, concurrent logging macros see either the old or
+/// the new default logger, never anything in between.
+///
+/// This is equivalent to [`swap_default_logger`], the returned old logger can
+/// simply be ignored if it is not needed.
 ///
 /// # Examples
 ///
@@ -410,12 +493,89 @@ pub fn swap_default_logger(logger: Arc<Logger>) -> Arc<Logger> {
 /// use spdlog::prelude::*;
 ///
 /// # let new_logger = spdlog::default_logger();
-/// spdlog::set_default_logger(new_logger);
+/// let old_logger: Arc<Logger> = spdlog::set_default_logger(new_logger);
 ///
 /// info!("this log will be handled by `new_logger`");
+///
+/// spdlog::set_default_logger(old_logger);
+/// info!("this log will be handled by `old_logger`");
+/// ```
+pub fn set_default_logger(logger: Arc<Logger>) -> Arc<Logger> {
+    swap_default_logger(logger)
+}
+
+/// Temporarily sets the given logger as the default logger while the given
+/// closure is running.
+///
+/// The previous default logger will be restored when the closure returns, even
+/// if it panics.
+///
+/// Note that the default logger is global, other threads will also see the
+/// temporary default logger until it is restored.
+///
+/// # Examples
+///
+/// ```
+/// # use std::sync::Arc;
+/// use spdlog::prelude::*;
+///
+/// # let new_logger = spdlog::default_logger();
+/// spdlog::with_default_logger(new_logger, || {
+///     info!("this log will be handled by `new_logger`");
+/// });
+///
+/// info!("this log will be handled by the previous default logger");
 /// ```
-pub fn set_default_logger(logger: Arc<Logger>) {
-    swap_default_logger(logger);
+pub fn with_default_logger<F, R>(logger: Arc<Logger>, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    struct RestoreGuard(Option<Arc<Logger>>);
+
+    impl Drop for RestoreGuard {
+        fn drop(&mut self) {
+            if let Some(previous) = self.0.take() {
+                set_default_logger(previous);
+            }
+        }
+    }
+
+    let _guard = RestoreGuard(Some(swap_default_logger(logger)));
+    f()
+}
+
+/// Flushes the default logger, and returns the errors instead of calling the
+/// error handler.
+///
+/// This is a shorthand for calling [`Logger::flush_all`] on the default logger,
+/// intended to make a clean shutdown a one-liner. Sinks are flushed in the
+/// order in which they were added to the default logger.
+///
+/// `spdlog-rs` does not have a registry of loggers (see [Significant
+/// differences from C++ spdlog]), other loggers owned by users need to be
+/// flushed by themselves.
+///
+/// This function does not initialize the default logger if it has not been
+/// used yet, and it does not allocate unless errors occur. However, it is not
+/// async-signal-safe, since sinks may lock or allocate while flushing.
+///
+/// # Examples
+///
+/// ```
+/// use spdlog::prelude::*;
+///
+/// # fn main() -> Result<(), spdlog::Error> {
+/// info!("the last log before exiting");
+/// spdlog::flush_default()?;
+/// # Ok(()) }
+/// ```
+///
+/// [Significant differences from C++ spdlog]: index.html#significant-differences-from-c-spdlog
+pub fn flush_default() -> Result<()> {
+    match DEFAULT_LOGGER.get() {
+        Some(default_logger) => default_logger.load().flush_all(),
+        None => Ok(()),
+    }
 }
 
 /// Initialize environment variable level filters from environment variable
@@ -433,6 +593,12 @@ pub fn set_default_logger(logger: Arc<Logger>) {
 /// Users should call this function early, the level filter of loggers built
 /// before calling this function will not be configured by environment variable.
 ///
+/// The rules, including patterns, are resolved once for each logger when it's
+/// built, with its name at that time, or when it's forked by
+/// [`Logger::fork_with_name`]. They are not re-applied to existing loggers
+/// when this function is called again, nor when a logger is renamed by
+/// [`Logger::set_name`], and [`Logger::set_level_filter`] overrides them.
+///
 /// ## Formats of the environment variable value
 ///
 /// The levels contained in the environment variable mean
@@ -455,7 +621,20 @@ pub fn set_default_logger(logger: Arc<Logger>) {
 /// - Specifies the level filter for ***loggers with the specified name***.
 ///
 ///   Possible inputs: `logger-name=info`, `network=warn`, `core=info`,
-/// `gui=critical`, etc.
+///   `gui=critical`, etc.
+///
+/// ---
+///
+/// - Specifies the level filter for ***loggers with names matching the
+///   specified pattern*** (respect the above rule first if it's matched).
+///
+///   A `*` in the pattern matches any sequence of characters, including `.`
+///   and nothing, e.g. `app.db.*` matches `app.db.pool` and
+///   `app.db.pool.conn`, but not `app.db`. If multiple patterns match, the
+///   most specific one wins, i.e. the one with the most characters other than
+///   `*`, then the one with the longest prefix before the first `*`.
+///
+///   Possible inputs: `app.db.*=debug`, `net.*=warn`, `*.pool=trace`, etc.
 ///
 /// ---
 ///
@@ -482,15 +661,25 @@ pub fn set_default_logger(logger: Arc<Logger>) {
 /// - `off,*=ERROR`
 ///
 ///   Specifies the level filter for the default logger as `LevelFilter::Off`,
-/// the rest of loggers as `LevelFilter::MoreSevereEqual(Level::Error)`.
+///   the rest of loggers as `LevelFilter::MoreSevereEqual(Level::Error)`.
 ///
 /// ---
 ///
 /// - `gui=warn,network=trace`
 ///
 ///   Specifies the level filter for loggers with name "gui" as
-/// `LevelFilter::MoreSevereEqual(Level::Warn)`, loggers with name "network" as
-/// `LevelFilter::MoreSevereEqual(Level::Trace)`.
+///   `LevelFilter::MoreSevereEqual(Level::Warn)`, loggers with name "network"
+///   as `LevelFilter::MoreSevereEqual(Level::Trace)`.
+///
+/// ---
+///
+/// - `net.*=warn,net.http.*=debug,*=error`
+///
+///   Specifies the level filter for loggers with names starting with
+///   "net.http." as `LevelFilter::MoreSevereEqual(Level::Debug)`, the other
+///   loggers with names starting with "net." as
+///   `LevelFilter::MoreSevereEqual(Level::Warn)`, and the rest of loggers
+///   except the default logger as `LevelFilter::MoreSevereEqual(Level::Error)`.
 ///
 /// ---
 ///
@@ -557,6 +746,32 @@ pub fn set_default_logger(logger: Arc<Logger>) {
 ///
 /// ---
 ///
+/// - `SPDLOG_RS_LEVEL="net.*=warn,net.http.*=debug"`:
+///
+///   ```
+///   use spdlog::prelude::*;
+///
+///   # fn main() -> Result<(), Box<dyn std::error::Error>> {
+///   # std::env::set_var("SPDLOG_RS_LEVEL", "net.*=warn,net.http.*=debug");
+///   assert_eq!(spdlog::init_env_level()?, true);
+///
+///   assert_eq!(
+///       Logger::builder().name("net.http.client").build()?.level_filter(),
+///       LevelFilter::MoreSevereEqual(Level::Debug)
+///   );
+///   assert_eq!(
+///       Logger::builder().name("net.dns").build()?.level_filter(),
+///       LevelFilter::MoreSevereEqual(Level::Warn)
+///   );
+///   assert_eq!(
+///       Logger::builder().name("gui").build()?.level_filter(),
+///       LevelFilter::MoreSevereEqual(Level::Info) // default level filter
+///   );
+///   # Ok(()) }
+///   ```
+///
+/// ---
+///
 /// - `SPDLOG_RS_LEVEL="network=Warn,network=Warn"` will fail, as the same rule
 ///   is specified multiple times.
 ///
@@ -691,31 +906,134 @@ fn default_error_handler(from: impl AsRef<str>, error: Error) {
         "[*** SPDLOG-RS UNHANDLED ERROR ***] [{}] [{}] {}",
         date,
         from.as_ref(),
-        error
+        ErrorChain::new(&error)
     );
 }
 
 // Used at log macros
 #[doc(hidden)]
-pub fn __log(
+pub fn __log_impl(
     logger: &Logger,
     level: Level,
     srcloc: Option<SourceLocation>,
+    verbosity: u8,
+    tags: &'static [&'static str],
+    key_values: &[(&'static str, &dyn std::fmt::Display)],
     fmt_args: std::fmt::Arguments,
 ) {
-    // use `Cow` to avoid allocation as much as we can
-    let payload: std::borrow::Cow<str> = match fmt_args.as_str() {
-        Some(literal_str) => literal_str.into(), // no format arguments, so it is a `&'static str`
-        None => fmt_args.to_string().into(),
-    };
+    let key_values = key_values
+        .iter()
+        .map(|(key, value)| ((*key).into(), value.to_string().into()))
+        .collect();
 
-    let mut builder = Record::builder(level, payload).source_location(srcloc);
+    record::with_formatted_payload(fmt_args, |payload| {
+        let mut builder = Record::builder(level, payload)
+            .source_location(srcloc)
+            .verbosity(verbosity)
+            .tags(tags)
+            .key_values(key_values);
+        if let Some(logger_name) = logger.name() {
+            builder = builder.logger_name(logger_name);
+        }
+        logger.log(&builder.build());
+    });
+}
+
+// Used at log macros
+#[doc(hidden)]
+pub fn __log_at(
+    logger: &Logger,
+    level: Level,
+    srcloc: Option<SourceLocation>,
+    time: std::time::SystemTime,
+    fmt_args: std::fmt::Arguments,
+) {
+    record::with_formatted_payload(fmt_args, |payload| {
+        let mut builder = Record::builder(level, payload)
+            .source_location(srcloc)
+            .time(time);
+        if let Some(logger_name) = logger.name() {
+            builder = builder.logger_name(logger_name);
+        }
+        logger.log(&builder.build());
+    });
+}
+
+// Used at log macros
+#[doc(hidden)]
+pub fn __log_error_chain(
+    logger: &Logger,
+    level: Level,
+    srcloc: Option<SourceLocation>,
+    error: &dyn std::error::Error,
+) {
+    let error_chain = ErrorChain::new(error);
+    let mut builder = Record::builder(level, error_chain.to_string())
+        .source_location(srcloc)
+        .key_values(error_chain.key_values());
     if let Some(logger_name) = logger.name() {
         builder = builder.logger_name(logger_name);
     }
     logger.log(&builder.build());
 }
 
+// Used at log macros
+#[doc(hidden)]
+pub fn __log_bytes(logger: &Logger, level: Level, srcloc: Option<SourceLocation>, bytes: &[u8]) {
+    let mut builder = Record::builder(level, String::from_utf8_lossy(bytes))
+        .source_location(srcloc)
+        .raw_payload(bytes);
+    if let Some(logger_name) = logger.name() {
+        builder = builder.logger_name(logger_name);
+    }
+    logger.log(&builder.build());
+}
+
+// Used at log macros
+#[doc(hidden)]
+pub fn __log_error_context(
+    logger: &Logger,
+    level: Level,
+    srcloc: Option<SourceLocation>,
+    context: std::fmt::Arguments,
+    error: &dyn std::error::Error,
+) {
+    let error_chain = ErrorChain::new(error);
+    let mut builder = Record::builder(level, format!("{}: {}", context, error_chain))
+        .source_location(srcloc)
+        .key_values(error_chain.key_values());
+    if let Some(logger_name) = logger.name() {
+        builder = builder.logger_name(logger_name);
+    }
+    logger.log(&builder.build());
+}
+
+// Used at log macros
+#[doc(hidden)]
+pub fn __log_lazy(
+    logger: &Logger,
+    level: Level,
+    srcloc: Option<SourceLocation>,
+    write_payload: impl FnOnce(&mut String) -> std::fmt::Result,
+) {
+    if !logger.sinks().iter().any(|sink| sink.should_log(level)) {
+        return;
+    }
+
+    record::with_payload_buf(|payload| {
+        if let Err(err) = write_payload(payload) {
+            logger.handle_error(Error::FormatRecord(err));
+            return;
+        }
+
+        let mut builder = Record::builder(level, payload.as_str()).source_location(srcloc);
+        if let Some(logger_name) = logger.name() {
+            builder = builder.logger_name(logger_name);
+        }
+        logger.log(&builder.build());
+    });
+}
+
 #[cfg(test)]
 mod tests {
     use test_utils::*;
@@ -733,11 +1051,12 @@ mod tests {
         info!("hello");
         error!("world");
 
-        set_default_logger(test_logger);
+        set_default_logger(test_logger.clone());
         warn!("hello");
         error!("rust");
 
-        set_default_logger(empty_logger);
+        let old_logger = set_default_logger(empty_logger.clone());
+        assert!(Arc::ptr_eq(&old_logger, &test_logger));
         info!("hello");
         error!("spdlog");
 
@@ -746,5 +1065,110 @@ mod tests {
             test_sink.payloads(),
             vec!["hello".to_string(), "rust".to_string()]
         );
+
+        // Tests for `with_default_logger` are here to avoid racing with the above
+        // assertions, as the default logger is global.
+        test_sink.reset();
+        let scoped_logger = Arc::new(build_test_logger(|b| b.sink(test_sink.clone())));
+
+        let ret = with_default_logger(scoped_logger.clone(), || {
+            assert!(Arc::ptr_eq(&default_logger(), &scoped_logger));
+            info!("scoped");
+            42
+        });
+        assert_eq!(ret, 42);
+        assert!(Arc::ptr_eq(&default_logger(), &empty_logger));
+        info!("restored");
+
+        let res = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+            with_default_logger(scoped_logger.clone(), || panic!("oops"));
+        }));
+        assert!(res.is_err());
+        assert!(Arc::ptr_eq(&default_logger(), &empty_logger));
+
+        assert_eq!(test_sink.payloads(), vec!["scoped".to_string()]);
+    }
+
+    #[test]
+    fn explicit_logger() {
+        let sink_a = Arc::new(CounterSink::new());
+        let sink_b = Arc::new(CounterSink::new());
+        let logger_a = build_test_logger(|b| b.sink(sink_a.clone()).level_filter(LevelFilter::All));
+        let logger_b = Arc::new(build_test_logger(|b| {
+            b.sink(sink_b.clone())
+                .level_filter(LevelFilter::MoreSevereEqual(Level::Warn))
+        }));
+
+        debug!(logger: logger_a, "a debug");
+        debug!(logger: logger_b, "b debug");
+        warn!(logger: &logger_a, "a warn");
+        warn!(logger: logger_b, "b {}", "warn");
+        log!(logger: logger_b.as_ref(), Level::Error, "b error");
+
+        assert_eq!(sink_a.payloads(), vec!["a debug", "a warn"]);
+        assert_eq!(sink_b.payloads(), vec!["b warn", "b error"]);
+
+        #[cfg(feature = "source-location")]
+        for record in sink_a.records().iter().chain(&sink_b.records()) {
+            assert_eq!(record.source_location().unwrap().file(), file!());
+        }
+    }
+
+    #[test]
+    fn lazy_message() {
+        let test_sink = Arc::new(CounterSink::new());
+        let test_logger = build_test_logger(|b| b.sink(test_sink.clone()));
+        test_logger.set_level_filter(LevelFilter::MoreSevereEqual(Level::Info));
+
+        let called = AtomicUsize::new(0);
+        let compute = || {
+            called.fetch_add(1, Ordering::SeqCst);
+            42
+        };
+
+        debug!(logger: test_logger, |f| write!(f, "expensive {}", compute()));
+        debug!(logger: test_logger, "expensive {}", compute());
+        assert_eq!(called.load(Ordering::SeqCst), 0);
+
+        // Filtered out by all sinks
+        test_sink.set_level_filter(LevelFilter::MoreSevereEqual(Level::Error));
+        info!(logger: test_logger, |f| write!(f, "expensive {}", compute()));
+        assert_eq!(called.load(Ordering::SeqCst), 0);
+
+        test_sink.set_level_filter(LevelFilter::All);
+        info!(logger: test_logger, |f| write!(f, "expensive {}", compute()));
+        assert_eq!(called.load(Ordering::SeqCst), 1);
+
+        static ERROR_COUNT: AtomicUsize = AtomicUsize::new(0);
+        test_logger.set_error_handler(Some(|_| {
+            ERROR_COUNT.fetch_add(1, Ordering::SeqCst);
+        }));
+        warn!(logger: test_logger, |f| {
+            write!(f, "discarded")?;
+            Err(std::fmt::Error)
+        });
+        assert_eq!(ERROR_COUNT.load(Ordering::SeqCst), 1);
+
+        assert_eq!(test_sink.payloads(), vec!["expensive 42".to_string()]);
+    }
+
+    #[test]
+    fn log_at() {
+        use std::time::{Duration, UNIX_EPOCH};
+
+        let string_sink = Arc::new(StringSink::with(|b| {
+            b.formatter(Box::new(formatter::FullFormatter::new()))
+        }));
+        let test_logger = build_test_logger(|b| b.sink(string_sink.clone()));
+
+        let time = UNIX_EPOCH + Duration::from_millis(1_000_000_000_123);
+        log_at!(logger: test_logger, time, Level::Info, "imported {}", 1);
+
+        let expected_time = chrono::DateTime::<chrono::Local>::from(time)
+            .format("[%Y-%m-%d %H:%M:%S.123]")
+            .to_string();
+        let output = string_sink.clone_string();
+        assert!(output.starts_with(&expected_time), "{}", output);
+        assert!(output.contains("[info]") && output.ends_with("imported 1\n"));
     }
 }
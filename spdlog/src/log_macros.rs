@@ -19,15 +19,269 @@
 ///     data.0, data.1, private_data);
 /// ```
 ///
+/// Without `logger:`, records are logged to the [default logger]. With
+/// `logger: <expr>`, they are logged to the given logger only, where `<expr>`
+/// can be a `Logger`, a `&Logger` or an `Arc<Logger>`, and the level filter of
+/// that logger applies. Libraries should prefer logging to their own loggers
+/// in this way rather than replacing the default logger of the application.
+///
+/// # Key-values
+///
+/// Key-values can be attached to the record with `kv: { key = value, ... }`
+/// before the format string, where keys are identifiers and values implement
+/// [`Display`]. They are merged with the default fields of the logger, see
+/// [`LoggerBuilder::with_field`]. [`FullFormatter`] outputs them in the
+/// style of [`FieldStyle`], and structured formatters such as [`GelfFormatter`]
+/// output them as fields.
+///
+/// ```
+/// use spdlog::{info, log, Level};
+///
+/// # let app_events = spdlog::default_logger();
+/// let user = "alice";
+///
+/// log!(Level::Info, kv: { user = user, attempt = 3 }, "login failed");
+/// info!(logger: app_events, kv: { user = user }, "login succeeded");
+/// ```
+///
+/// # Verbosity
+///
+/// A numeric [verbosity] can be attached to the record with `v = <u8>` before
+/// the key-values and the format string. It's a sub-level mostly useful for
+/// trace and debug records, records with a verbosity greater than
+/// [`Logger::max_verbosity`] are discarded.
+///
+/// ```
+/// use spdlog::{log, trace, Level};
+///
+/// # let app_events = spdlog::default_logger();
+/// # let packet = [0u8; 4];
+/// log!(Level::Debug, v = 1, "received a packet");
+/// trace!(logger: app_events, v = 3, kv: { len = packet.len() }, "{:?}", packet);
+/// ```
+///
+/// # Tags
+///
+/// Tags can be attached to the record with `tags = [...]` after the verbosity
+/// and before the key-values and the format string. A tag is a `&'static str`
+/// constant, e.g. a string literal, so tagging a record never allocates. They
+/// are coarser than key-values, intended for filtering and routing records by
+/// sinks, e.g. sending records tagged `"audit"` to a dedicated file with
+/// [`TagRoutingSink`]. See [`Record::tags`].
+///
+/// ```
+/// use spdlog::{info, log, Level};
+///
+/// # let app_events = spdlog::default_logger();
+/// # let user = "alice";
+/// info!(tags = ["audit", "security"], "login");
+/// log!(logger: app_events, Level::Debug, v = 1, tags = ["audit"], kv: { user = user }, "logout");
+/// ```
+///
+/// # Lazy messages
+///
+/// The format arguments are only evaluated if the level passes the level
+/// filter of the logger, so `log!(Level::Debug, "{}", compute())` does not
+/// call `compute()` when debug records are filtered out.
+///
+/// For messages that are expensive to build in other ways (e.g. with loops),
+/// a closure `|f| ...` can be passed instead of the format string. It writes
+/// the message into `f`, a [`String`] on which [`write!`] can be used, and
+/// it's only called if the level passes the level filters of the logger and
+/// of at least one of its sinks. Errors returned by the closure are passed to
+/// the error handler of the logger, and the record is discarded.
+///
+/// ```
+/// use spdlog::{log, Level};
+///
+/// # let app_events = spdlog::default_logger();
+/// # let items = vec![1, 2, 3];
+/// log!(Level::Debug, |f| {
+///     for item in &items {
+///         write!(f, "{} ", item)?;
+///     }
+///     Ok(())
+/// });
+/// log!(logger: app_events, Level::Trace, |f| write!(f, "{} items", items.len()));
+/// ```
+///
 /// [`Level`]: crate::Level
+/// [default logger]: crate::default_logger
+/// [verbosity]: crate::Record::verbosity
+/// [`Logger::max_verbosity`]: crate::Logger::max_verbosity
+/// [`Display`]: std::fmt::Display
+/// [`LoggerBuilder::with_field`]: crate::LoggerBuilder::with_field
+/// [`FullFormatter`]: crate::formatter::FullFormatter
+/// [`FieldStyle`]: crate::formatter::FieldStyle
+/// [`GelfFormatter`]: crate::formatter::GelfFormatter
+/// [`TagRoutingSink`]: crate::sink::TagRoutingSink
+/// [`Record::tags`]: crate::Record::tags
 #[macro_export]
 macro_rules! log {
+    (logger: $logger:expr, $level:expr, |$f:ident| $body:expr) => ({
+        const LEVEL: $crate::Level = $level;
+        const SHOULD_LOG: bool = $crate::STATIC_LEVEL_FILTER.__compare_const(LEVEL);
+        if SHOULD_LOG {
+            let logger = &$logger;
+            if logger.should_log(LEVEL) {
+                $crate::__log_lazy(logger, LEVEL, $crate::source_location_current!(), |$f: &mut ::std::string::String| {
+                    #[allow(unused_imports)]
+                    use ::std::fmt::Write as _;
+                    $body
+                });
+            }
+        }
+    });
+    (logger: $logger:expr, $level:expr, kv: { $($key:ident = $value:expr),+ $(,)? }, $($arg:tt)+) => ({
+        const LEVEL: $crate::Level = $level;
+        const SHOULD_LOG: bool = $crate::STATIC_LEVEL_FILTER.__compare_const(LEVEL);
+        if SHOULD_LOG {
+            let logger = &$logger;
+            if logger.should_log(LEVEL) {
+                $crate::__log_impl(
+                    logger,
+                    LEVEL,
+                    $crate::source_location_current!(),
+                    0,
+                    &[],
+                    &[$((stringify!($key), &$value as &dyn ::std::fmt::Display)),+],
+                    format_args!($($arg)+),
+                );
+            }
+        }
+    });
+    (logger: $logger:expr, $level:expr, tags = [$($tag:expr),+ $(,)?], kv: { $($key:ident = $value:expr),+ $(,)? }, $($arg:tt)+) => ({
+        const LEVEL: $crate::Level = $level;
+        const SHOULD_LOG: bool = $crate::STATIC_LEVEL_FILTER.__compare_const(LEVEL);
+        if SHOULD_LOG {
+            let logger = &$logger;
+            if logger.should_log(LEVEL) {
+                const TAGS: &[&str] = &[$($tag),+];
+                $crate::__log_impl(
+                    logger,
+                    LEVEL,
+                    $crate::source_location_current!(),
+                    0,
+                    TAGS,
+                    &[$((stringify!($key), &$value as &dyn ::std::fmt::Display)),+],
+                    format_args!($($arg)+),
+                );
+            }
+        }
+    });
+    (logger: $logger:expr, $level:expr, tags = [$($tag:expr),+ $(,)?], $($arg:tt)+) => ({
+        const LEVEL: $crate::Level = $level;
+        const SHOULD_LOG: bool = $crate::STATIC_LEVEL_FILTER.__compare_const(LEVEL);
+        if SHOULD_LOG {
+            let logger = &$logger;
+            if logger.should_log(LEVEL) {
+                const TAGS: &[&str] = &[$($tag),+];
+                $crate::__log_impl(
+                    logger,
+                    LEVEL,
+                    $crate::source_location_current!(),
+                    0,
+                    TAGS,
+                    &[],
+                    format_args!($($arg)+),
+                );
+            }
+        }
+    });
+    (logger: $logger:expr, $level:expr, v = $verbosity:expr, tags = [$($tag:expr),+ $(,)?], kv: { $($key:ident = $value:expr),+ $(,)? }, $($arg:tt)+) => ({
+        const LEVEL: $crate::Level = $level;
+        const SHOULD_LOG: bool = $crate::STATIC_LEVEL_FILTER.__compare_const(LEVEL);
+        if SHOULD_LOG {
+            let logger = &$logger;
+            let verbosity: u8 = $verbosity;
+            if logger.should_log(LEVEL) && logger.should_log_verbosity(verbosity) {
+                const TAGS: &[&str] = &[$($tag),+];
+                $crate::__log_impl(
+                    logger,
+                    LEVEL,
+                    $crate::source_location_current!(),
+                    verbosity,
+                    TAGS,
+                    &[$((stringify!($key), &$value as &dyn ::std::fmt::Display)),+],
+                    format_args!($($arg)+),
+                );
+            }
+        }
+    });
+    (logger: $logger:expr, $level:expr, v = $verbosity:expr, tags = [$($tag:expr),+ $(,)?], $($arg:tt)+) => ({
+        const LEVEL: $crate::Level = $level;
+        const SHOULD_LOG: bool = $crate::STATIC_LEVEL_FILTER.__compare_const(LEVEL);
+        if SHOULD_LOG {
+            let logger = &$logger;
+            let verbosity: u8 = $verbosity;
+            if logger.should_log(LEVEL) && logger.should_log_verbosity(verbosity) {
+                const TAGS: &[&str] = &[$($tag),+];
+                $crate::__log_impl(
+                    logger,
+                    LEVEL,
+                    $crate::source_location_current!(),
+                    verbosity,
+                    TAGS,
+                    &[],
+                    format_args!($($arg)+),
+                );
+            }
+        }
+    });
+    (logger: $logger:expr, $level:expr, v = $verbosity:expr, kv: { $($key:ident = $value:expr),+ $(,)? }, $($arg:tt)+) => ({
+        const LEVEL: $crate::Level = $level;
+        const SHOULD_LOG: bool = $crate::STATIC_LEVEL_FILTER.__compare_const(LEVEL);
+        if SHOULD_LOG {
+            let logger = &$logger;
+            let verbosity: u8 = $verbosity;
+            if logger.should_log(LEVEL) && logger.should_log_verbosity(verbosity) {
+                $crate::__log_impl(
+                    logger,
+                    LEVEL,
+                    $crate::source_location_current!(),
+                    verbosity,
+                    &[],
+                    &[$((stringify!($key), &$value as &dyn ::std::fmt::Display)),+],
+                    format_args!($($arg)+),
+                );
+            }
+        }
+    });
+    (logger: $logger:expr, $level:expr, v = $verbosity:expr, $($arg:tt)+) => ({
+        const LEVEL: $crate::Level = $level;
+        const SHOULD_LOG: bool = $crate::STATIC_LEVEL_FILTER.__compare_const(LEVEL);
+        if SHOULD_LOG {
+            let logger = &$logger;
+            let verbosity: u8 = $verbosity;
+            if logger.should_log(LEVEL) && logger.should_log_verbosity(verbosity) {
+                $crate::__log_impl(
+                    logger,
+                    LEVEL,
+                    $crate::source_location_current!(),
+                    verbosity,
+                    &[],
+                    &[],
+                    format_args!($($arg)+),
+                );
+            }
+        }
+    });
     (logger: $logger:expr, $level:expr, $($arg:tt)+) => ({
-        let logger = &$logger;
         const LEVEL: $crate::Level = $level;
         const SHOULD_LOG: bool = $crate::STATIC_LEVEL_FILTER.__compare_const(LEVEL);
-        if SHOULD_LOG && logger.should_log(LEVEL) {
-            $crate::__log(logger, LEVEL, $crate::source_location_current!(), format_args!($($arg)+));
+        if SHOULD_LOG {
+            let logger = &$logger;
+            if logger.should_log(LEVEL) {
+                $crate::__log_impl(
+                    logger,
+                    LEVEL,
+                    $crate::source_location_current!(),
+                    0,
+                    &[],
+                    &[],
+                    format_args!($($arg)+),
+                );
+            }
         }
     });
     ($level:expr, $($arg:tt)+) => ($crate::log!(logger: $crate::default_logger(), $level, $($arg)+))
@@ -176,3 +430,329 @@ macro_rules! trace {
         $crate::log!($crate::Level::Trace, $($arg)+)
     )
 }
+
+/// Logs a message with an explicit time, instead of the current time.
+///
+/// It's intended for importing or replaying historical logs, and for tests
+/// that need deterministic output. The time is a [`SystemTime`], which is what
+/// formatters read from [`Record::time`]. Otherwise it's the same as
+/// [`log!`].
+///
+/// Note that [`RotatingFileSink`] with a time-based policy rotates by the time
+/// of records, so replaying old records may rotate files.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::{Duration, UNIX_EPOCH};
+///
+/// use spdlog::{log_at, Level};
+///
+/// # let app_events = spdlog::default_logger();
+/// let time = UNIX_EPOCH + Duration::from_secs(1_600_000_000);
+///
+/// log_at!(time, Level::Info, "imported record");
+/// log_at!(logger: app_events, time, Level::Warn, "imported {}", "warning");
+/// ```
+///
+/// [`SystemTime`]: std::time::SystemTime
+/// [`Record::time`]: crate::Record::time
+/// [`RotatingFileSink`]: crate::sink::RotatingFileSink
+#[macro_export]
+macro_rules! log_at {
+    (logger: $logger:expr, $time:expr, $level:expr, $($arg:tt)+) => ({
+        const LEVEL: $crate::Level = $level;
+        const SHOULD_LOG: bool = $crate::STATIC_LEVEL_FILTER.__compare_const(LEVEL);
+        if SHOULD_LOG {
+            let logger = &$logger;
+            if logger.should_log(LEVEL) {
+                $crate::__log_at(
+                    logger,
+                    LEVEL,
+                    $crate::source_location_current!(),
+                    $time,
+                    format_args!($($arg)+),
+                );
+            }
+        }
+    });
+    ($time:expr, $level:expr, $($arg:tt)+) => (
+        $crate::log_at!(logger: $crate::default_logger(), $time, $level, $($arg)+)
+    )
+}
+
+/// Logs an error followed by its [sources].
+///
+/// The payload is the error chain formatted by [`ErrorChain`], in the form of
+/// `error: caused by: source: caused by: source of source`. The errors in the
+/// chain are also attached to the record as [key-values] `error`,
+/// `error.cause.0`, `error.cause.1`, ..., for structured formatters such as
+/// [`GelfFormatter`].
+///
+/// The error is borrowed, it can be any type implementing
+/// [`std::error::Error`]. For boxed errors, pass `*boxed`.
+///
+/// # Examples
+///
+/// ```
+/// use spdlog::{error_chain, Level};
+///
+/// # let app_events = spdlog::default_logger();
+/// let err = std::fs::read("/path/to/nowhere").unwrap_err();
+///
+/// error_chain!(Level::Error, err);
+///
+/// let boxed: Box<dyn std::error::Error> = Box::new(err);
+/// error_chain!(logger: app_events, Level::Warn, *boxed);
+/// ```
+///
+/// [sources]: std::error::Error::source
+/// [`ErrorChain`]: crate::ErrorChain
+/// [key-values]: crate::Record::key_values
+/// [`GelfFormatter`]: crate::formatter::GelfFormatter
+#[macro_export]
+macro_rules! error_chain {
+    (logger: $logger:expr, $level:expr, $error:expr $(,)?) => ({
+        const LEVEL: $crate::Level = $level;
+        const SHOULD_LOG: bool = $crate::STATIC_LEVEL_FILTER.__compare_const(LEVEL);
+        if SHOULD_LOG {
+            let logger = &$logger;
+            if logger.should_log(LEVEL) {
+                $crate::__log_error_chain(logger, LEVEL, $crate::source_location_current!(), &$error);
+            }
+        }
+    });
+    ($level:expr, $error:expr $(,)?) => (
+        $crate::error_chain!(logger: $crate::default_logger(), $level, $error)
+    )
+}
+
+/// Logs raw bytes as the payload.
+///
+/// The bytes can be anything implementing `AsRef<[u8]>`, and don't have to be
+/// valid UTF-8, e.g. a protocol frame. They are borrowed, not copied, unless a
+/// sink queues the record.
+///
+/// Since [`Record::payload`] is always valid UTF-8, it's the bytes converted
+/// with [`String::from_utf8_lossy`], which replaces invalid sequences with
+/// `U+FFFD`, so text formatters output them lossily. The bytes as they are can
+/// be obtained from [`Record::payload_bytes`], for sinks writing bytes rather
+/// than text, and for the pattern `{payload:hex}` of [`PatternFormatter`],
+/// which outputs them as hexadecimal digits losslessly.
+///
+/// To embed bytes in a formatted payload instead, see [`Hex`].
+///
+/// # Examples
+///
+/// ```
+/// use spdlog::{log_bytes, Level};
+///
+/// # let app_events = spdlog::default_logger();
+/// let frame = b"GET \xff\xfe /";
+///
+/// log_bytes!(Level::Info, frame);
+/// // GET �� /
+///
+/// log_bytes!(logger: app_events, Level::Debug, vec![0xde, 0xad]);
+/// ```
+///
+/// [`Record::payload`]: crate::Record::payload
+/// [`Record::payload_bytes`]: crate::Record::payload_bytes
+/// [`PatternFormatter`]: crate::formatter::PatternFormatter
+/// [`Hex`]: crate::Hex
+#[macro_export]
+macro_rules! log_bytes {
+    (logger: $logger:expr, $level:expr, $bytes:expr $(,)?) => ({
+        const LEVEL: $crate::Level = $level;
+        const SHOULD_LOG: bool = $crate::STATIC_LEVEL_FILTER.__compare_const(LEVEL);
+        if SHOULD_LOG {
+            let logger = &$logger;
+            if logger.should_log(LEVEL) {
+                $crate::__log_bytes(
+                    logger,
+                    LEVEL,
+                    $crate::source_location_current!(),
+                    ::std::convert::AsRef::<[u8]>::as_ref(&$bytes),
+                );
+            }
+        }
+    });
+    ($level:expr, $bytes:expr $(,)?) => (
+        $crate::log_bytes!(logger: $crate::default_logger(), $level, $bytes)
+    )
+}
+
+/// Logs the error of a [`Result`] with a context message, and evaluates to the
+/// [`Result`] unchanged.
+///
+/// It's intended to be followed by `?`, to log an error where it occurs with
+/// the context of the failed operation, and then propagate it. Nothing is
+/// logged if the [`Result`] is `Ok`.
+///
+/// The payload is the context message formatted by the `format!` based
+/// argument list, followed by `: ` and the error chain formatted by
+/// [`ErrorChain`]. The errors in the chain are also attached to the record as
+/// [key-values], as [`error_chain!`] does. The error type must implement
+/// [`std::error::Error`].
+///
+/// Records are logged at [`Level::Error`] by default, a different level can be
+/// specified with `level: <level>` before the [`Result`]. See [`warn_log!`] for
+/// logging an error without propagating it.
+///
+/// # Examples
+///
+/// ```
+/// use spdlog::{err_log, Level};
+///
+/// # let app_events = spdlog::default_logger();
+/// fn load_config(path: &str) -> std::io::Result<Vec<u8>> {
+///     // Logs "failed to read config /path/to/nowhere: No such file or directory (os error 2)"
+///     let content = err_log!(std::fs::read(path), "failed to read config {}", path)?;
+///     Ok(content)
+/// }
+///
+/// fn parse_port(app_events: &spdlog::Logger, port: &str) -> Result<u16, std::num::ParseIntError> {
+///     err_log!(logger: app_events, level: Level::Warn, port.parse(), "invalid port")
+/// }
+/// # assert!(load_config("/path/to/nowhere").is_err());
+/// # assert!(parse_port(&app_events, "abc").is_err());
+/// ```
+///
+/// [`ErrorChain`]: crate::ErrorChain
+/// [key-values]: crate::Record::key_values
+/// [`Level::Error`]: crate::Level::Error
+#[macro_export]
+macro_rules! err_log {
+    (logger: $logger:expr, level: $level:expr, $result:expr, $($arg:tt)+) => ({
+        let result = $result;
+        if let Err(err) = &result {
+            const LEVEL: $crate::Level = $level;
+            const SHOULD_LOG: bool = $crate::STATIC_LEVEL_FILTER.__compare_const(LEVEL);
+            if SHOULD_LOG {
+                let logger = &$logger;
+                if logger.should_log(LEVEL) {
+                    $crate::__log_error_context(
+                        logger,
+                        LEVEL,
+                        $crate::source_location_current!(),
+                        format_args!($($arg)+),
+                        err,
+                    );
+                }
+            }
+        }
+        result
+    });
+    (logger: $logger:expr, $result:expr, $($arg:tt)+) => (
+        $crate::err_log!(logger: $logger, level: $crate::Level::Error, $result, $($arg)+)
+    );
+    (level: $level:expr, $result:expr, $($arg:tt)+) => (
+        $crate::err_log!(logger: $crate::default_logger(), level: $level, $result, $($arg)+)
+    );
+    ($result:expr, $($arg:tt)+) => (
+        $crate::err_log!(logger: $crate::default_logger(), level: $crate::Level::Error, $result, $($arg)+)
+    );
+}
+
+/// Logs the error of a [`Result`] with a context message, and evaluates to the
+/// value of the [`Result`] as an [`Option`].
+///
+/// It's [`err_log!`] for errors that are not propagated, e.g. a failure to
+/// clean up a temporary file, which is worth a warning but does not fail the
+/// operation. The record is the same as [`err_log!`] logs, but at
+/// [`Level::Warn`] by default, a different level can be specified with
+/// `level: <level>` before the [`Result`].
+///
+/// # Examples
+///
+/// ```
+/// use spdlog::{warn_log, Level};
+///
+/// # let app_events = spdlog::default_logger();
+/// warn_log!(std::fs::remove_file("/path/to/app.lock"), "failed to remove lock file");
+///
+/// let port = warn_log!(logger: app_events, level: Level::Info, "abc".parse::<u16>(), "invalid port")
+///     .unwrap_or(8080);
+/// # assert_eq!(port, 8080);
+/// ```
+///
+/// [`Level::Warn`]: crate::Level::Warn
+#[macro_export]
+macro_rules! warn_log {
+    (logger: $logger:expr, level: $level:expr, $result:expr, $($arg:tt)+) => (
+        $crate::err_log!(logger: $logger, level: $level, $result, $($arg)+).ok()
+    );
+    (logger: $logger:expr, $result:expr, $($arg:tt)+) => (
+        $crate::warn_log!(logger: $logger, level: $crate::Level::Warn, $result, $($arg)+)
+    );
+    (level: $level:expr, $result:expr, $($arg:tt)+) => (
+        $crate::warn_log!(logger: $crate::default_logger(), level: $level, $result, $($arg)+)
+    );
+    ($result:expr, $($arg:tt)+) => (
+        $crate::warn_log!(logger: $crate::default_logger(), level: $crate::Level::Warn, $result, $($arg)+)
+    );
+}
+
+/// Logs the entry and exit of a scope, with the elapsed time.
+///
+/// It logs `enter <name>` when invoked, and `exit <name> (<elapsed>µs)` when
+/// the enclosing scope ends, at [`Level::Trace`] by default. A different level
+/// can be specified before the name. Both records have the source location of
+/// the macro invocation.
+///
+/// Like other log macros, nothing is logged if the level is filtered out, the
+/// filters are checked again on exit.
+///
+/// # Examples
+///
+/// ```
+/// use spdlog::{trace_scope, Level};
+///
+/// # let app_events = spdlog::default_logger();
+/// fn parse_config() {
+///     trace_scope!("parse_config");
+///     // ...
+/// } // logs "exit parse_config (123µs)"
+///
+/// fn load_plugins(app_events: &spdlog::Logger) {
+///     trace_scope!(logger: app_events, Level::Debug, "load_plugins");
+///     // ...
+/// }
+/// # parse_config();
+/// # load_plugins(&app_events);
+/// ```
+///
+/// [`Level::Trace`]: crate::Level::Trace
+#[macro_export]
+macro_rules! trace_scope {
+    (logger: $logger:expr, $level:expr, $name:expr $(,)?) => (
+        let _trace_scope = {
+            const LEVEL: $crate::Level = $level;
+            const SHOULD_LOG: bool = $crate::STATIC_LEVEL_FILTER.__compare_const(LEVEL);
+            if SHOULD_LOG {
+                Some($crate::TraceScope::__new(&$logger, LEVEL, $name, $crate::source_location_current!()))
+            } else {
+                None
+            }
+        };
+    );
+    (logger: $logger:expr, $name:expr $(,)?) => (
+        $crate::trace_scope!(logger: $logger, $crate::Level::Trace, $name)
+    );
+    ($level:expr, $name:expr $(,)?) => (
+        let default_logger = {
+            const SHOULD_LOG: bool = $crate::STATIC_LEVEL_FILTER.__compare_const($level);
+            if SHOULD_LOG {
+                Some($crate::default_logger())
+            } else {
+                None
+            }
+        };
+        let _trace_scope = default_logger.as_ref().map(|default_logger| {
+            $crate::TraceScope::__new(default_logger, $level, $name, $crate::source_location_current!())
+        });
+    );
+    ($name:expr $(,)?) => (
+        $crate::trace_scope!($crate::Level::Trace, $name)
+    )
+}
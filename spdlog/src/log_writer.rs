@@ -0,0 +1,234 @@
+use std::io::{self, Write};
+
+use crate::{sync::*, Level, Logger, Record};
+
+/// An [`io::Write`] adapter logging each line written to it as a record.
+///
+/// It's intended for capturing output of third-party code that writes to a
+/// writer, e.g. diagnostics of a library or the output of a subprocess, into
+/// the logger.
+///
+/// The incoming bytes are buffered until a newline (`\n`, optionally preceded
+/// by `\r`), then the line is logged as the payload of a record at the
+/// configured level, without the newline. Lines may be split across writes,
+/// and a write may contain several lines. Invalid UTF-8 sequences are replaced
+/// with `U+FFFD`, and empty lines are skipped. An incomplete last line is
+/// logged when the writer is dropped.
+///
+/// Lines longer than the maximum line length (64 KiB by default, see
+/// [`LogWriter::set_max_line_len`]) are split into several records, so that
+/// output without newlines does not buffer unboundedly. A line is split at a
+/// character boundary if it's valid UTF-8 there.
+///
+/// [`Write::flush`] flushes the logger, an incomplete line is kept buffered.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Write;
+///
+/// use spdlog::{Level, LogWriter};
+///
+/// let mut writer = LogWriter::new(spdlog::default_logger(), Level::Warn);
+///
+/// // Logs records "first line" and "second line"
+/// writer.write_all(b"first line\nsecond ")?;
+/// writer.write_all(b"line\n")?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub struct LogWriter {
+    logger: Arc<Logger>,
+    level: Level,
+    // The incomplete line
+    buf: Vec<u8>,
+    max_line_len: usize,
+}
+
+const DEFAULT_MAX_LINE_LEN: usize = 64 * 1024;
+
+impl LogWriter {
+    /// Constructs a `LogWriter` logging lines to the given logger at the
+    /// given level.
+    #[must_use]
+    pub fn new(logger: Arc<Logger>, level: Level) -> Self {
+        Self {
+            logger,
+            level,
+            buf: Vec::new(),
+            max_line_len: DEFAULT_MAX_LINE_LEN,
+        }
+    }
+
+    /// Gets a reference to the logger.
+    #[must_use]
+    pub fn logger(&self) -> &Arc<Logger> {
+        &self.logger
+    }
+
+    /// Gets the level of the logged records.
+    #[must_use]
+    pub fn level(&self) -> Level {
+        self.level
+    }
+
+    /// Gets the maximum line length in bytes.
+    #[must_use]
+    pub fn max_line_len(&self) -> usize {
+        self.max_line_len
+    }
+
+    /// Sets the maximum line length in bytes.
+    ///
+    /// Longer lines are split into several records, the part exceeding the
+    /// length is logged as soon as it's written, without waiting for the
+    /// newline. Values less than `4` are treated as `4`, so that characters
+    /// are never split. Defaults to 64 KiB.
+    pub fn set_max_line_len(&mut self, max_line_len: usize) {
+        self.max_line_len = max_line_len.max(4);
+    }
+
+    // Splits before `max_line_len`, or before the character crossing it
+    #[must_use]
+    fn split_point(&self, line: &[u8]) -> usize {
+        let is_char_boundary = |i: usize| line[i] & 0b1100_0000 != 0b1000_0000;
+        (self.max_line_len - 3..=self.max_line_len)
+            .rev()
+            .find(|&i| is_char_boundary(i))
+            .unwrap_or(self.max_line_len)
+    }
+
+    fn log_line(&self, line: &[u8]) {
+        let mut line = line.strip_suffix(b"\r").unwrap_or(line);
+        while line.len() > self.max_line_len {
+            let (part, rest) = line.split_at(self.split_point(line));
+            self.log_part(part);
+            line = rest;
+        }
+        self.log_part(line);
+    }
+
+    fn log_part(&self, line: &[u8]) {
+        if line.is_empty() || !self.logger.should_log(self.level) {
+            return;
+        }
+
+        let mut builder = Record::builder(self.level, String::from_utf8_lossy(line));
+        if let Some(logger_name) = self.logger.name() {
+            builder = builder.logger_name(logger_name);
+        }
+        self.logger.log(&builder.build());
+    }
+}
+
+impl Write for LogWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut rest = buf;
+        while let Some(pos) = rest.iter().position(|&b| b == b'\n') {
+            let (line, next) = (&rest[..pos], &rest[pos + 1..]);
+            if self.buf.is_empty() {
+                self.log_line(line);
+            } else {
+                let mut joined = std::mem::take(&mut self.buf);
+                joined.extend_from_slice(line);
+                self.log_line(&joined);
+                // Reuse the capacity
+                joined.clear();
+                self.buf = joined;
+            }
+            rest = next;
+        }
+        self.buf.extend_from_slice(rest);
+
+        let mut logged = 0;
+        while self.buf.len() - logged > self.max_line_len {
+            let part = &self.buf[logged..];
+            let split_point = self.split_point(part);
+            self.log_part(&part[..split_point]);
+            logged += split_point;
+        }
+        self.buf.drain(..logged);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.logger.flush();
+        Ok(())
+    }
+}
+
+impl Drop for LogWriter {
+    fn drop(&mut self) {
+        let line = std::mem::take(&mut self.buf);
+        self.log_line(&line);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::*;
+
+    #[test]
+    fn split_lines() {
+        let test_sink = Arc::new(CounterSink::new());
+        let logger = Arc::new(build_test_logger(|b| b.sink(test_sink.clone()).name("lib")));
+
+        let mut writer = LogWriter::new(logger, Level::Warn);
+        writer.write_all(b"hel").unwrap();
+        writer.write_all(b"lo\nwor").unwrap();
+        assert_eq!(test_sink.payloads(), ["hello"]);
+
+        writer.write_all(b"ld\r\n\nmulti\nline\npart").unwrap();
+        writer.write_all(b"ial \xF0\x9F").unwrap();
+        assert_eq!(test_sink.payloads(), ["hello", "world", "multi", "line"]);
+
+        drop(writer);
+        let records = test_sink.records();
+        assert_eq!(
+            test_sink.payloads(),
+            ["hello", "world", "multi", "line", "partial \u{FFFD}"]
+        );
+        assert!(records.iter().all(|record| record.level() == Level::Warn));
+        assert!(records
+            .iter()
+            .all(|record| record.logger_name() == Some("lib")));
+    }
+
+    #[test]
+    fn max_line_len() {
+        let test_sink = Arc::new(CounterSink::new());
+        let logger = Arc::new(build_test_logger(|b| b.sink(test_sink.clone())));
+
+        let mut writer = LogWriter::new(logger, Level::Info);
+        assert_eq!(writer.max_line_len(), 64 * 1024);
+        writer.set_max_line_len(5);
+
+        writer.write_all(b"0123456789ab\nabcde\n").unwrap();
+        assert_eq!(test_sink.payloads(), ["01234", "56789", "ab", "abcde"]);
+        test_sink.reset();
+
+        // Forced out without a newline
+        writer.write_all(b"abc").unwrap();
+        writer.write_all(b"defgh").unwrap();
+        assert_eq!(test_sink.payloads(), ["abcde"]);
+        writer.write_all(b"ijkl").unwrap();
+        assert_eq!(test_sink.payloads(), ["abcde", "fghij"]);
+        writer.write_all(b"\n").unwrap();
+        assert_eq!(test_sink.payloads(), ["abcde", "fghij", "kl"]);
+        test_sink.reset();
+
+        // Never splits a character, `é` is 2 bytes and `😀` is 4 bytes
+        writer.write_all("abcdé😀é\n".as_bytes()).unwrap();
+        assert_eq!(test_sink.payloads(), ["abcd", "é", "😀", "é"]);
+        test_sink.reset();
+
+        writer.set_max_line_len(0);
+        assert_eq!(writer.max_line_len(), 4);
+        writer.write_all(b"\xff\xff\xff\xff\xff").unwrap();
+        drop(writer);
+        assert_eq!(
+            test_sink.payloads(),
+            ["\u{FFFD}".repeat(4), "\u{FFFD}".to_string()]
+        );
+    }
+}
@@ -4,11 +4,13 @@ use std::{result::Result as StdResult, time::Duration};
 
 use crate::{
     env_level,
-    error::{Error, ErrorHandler, InvalidArgumentError, SetLoggerNameError},
+    error::{
+        BuildSinkError, Error, ErrorHandler, InvalidArgumentError, SetLoggerNameError, SinkError,
+    },
     periodic_worker::PeriodicWorker,
     sink::{Sink, Sinks},
     sync::*,
-    Level, LevelFilter, Record, Result,
+    Level, LevelFilter, Record, RecordOwned, Result,
 };
 
 fn check_logger_name(name: impl AsRef<str>) -> StdResult<(), SetLoggerNameError> {
@@ -34,6 +36,12 @@ fn check_logger_name(name: impl AsRef<str>) -> StdResult<(), SetLoggerNameError>
     }
 }
 
+/// A function that rewrites or drops records before they are dispatched to
+/// sinks.
+///
+/// See [`Logger::set_record_transform`].
+pub type RecordTransform = Arc<dyn Fn(RecordOwned) -> Option<RecordOwned> + Send + Sync>;
+
 /// A logger structure.
 ///
 /// A logger contains a combination of sinks, and sinks implement writing log
@@ -65,9 +73,17 @@ fn check_logger_name(name: impl AsRef<str>) -> StdResult<(), SetLoggerNameError>
 pub struct Logger {
     name: Option<String>,
     level_filter: Atomic<LevelFilter>,
+    max_verbosity: Atomic<u8>,
     sinks: Sinks,
+    fields: Vec<(String, String)>,
+    #[cfg(feature = "metrics")]
+    emit_metrics: bool,
+    // Indexed by `Level as usize`, registered on the first record
+    #[cfg(feature = "metrics")]
+    metrics_counters: OnceCell<Vec<metrics::Counter>>,
     flush_level_filter: Atomic<LevelFilter>,
     error_handler: SpinRwLock<Option<ErrorHandler>>,
+    record_transform: SpinRwLock<Option<RecordTransform>>,
     periodic_flusher: Mutex<Option<(Duration, PeriodicWorker)>>,
 }
 
@@ -78,9 +94,15 @@ impl Logger {
         LoggerBuilder {
             name: None,
             level_filter: LevelFilter::MoreSevereEqual(Level::Info),
+            max_verbosity: u8::MAX,
             sinks: vec![],
-            flush_level_filter: LevelFilter::Off,
+            fields: vec![],
+            #[cfg(feature = "metrics")]
+            emit_metrics: false,
+            sink_errors: vec![],
+            flush_level_filter: LevelFilter::MoreSevereEqual(Level::Critical),
             error_handler: None,
+            record_transform: None,
         }
     }
 
@@ -93,6 +115,12 @@ impl Logger {
     }
 
     /// Sets the logger name.
+    ///
+    /// The level filter is not changed, even if the environment variable
+    /// `SPDLOG_RS_LEVEL` has a rule for the new name, see [`init_env_level`].
+    /// Use [`Logger::fork_with_name`] to apply it.
+    ///
+    /// [`init_env_level`]: crate::init_env_level
     pub fn set_name<S>(&mut self, name: Option<S>) -> StdResult<(), SetLoggerNameError>
     where
         S: Into<String>,
@@ -138,14 +166,98 @@ impl Logger {
         self.level_filter().compare(level)
     }
 
+    /// Determines if a log message with the specified level would be written
+    /// by any sink.
+    ///
+    /// Unlike [`Logger::should_log`], which only checks the level filter of
+    /// the logger, it also checks the level filters of the sinks (see
+    /// [`Sink::should_log`]), so it returns `false` if all sinks would discard
+    /// the record, or if the logger has no sinks. It's intended to gate
+    /// expensive work done before logging, e.g. running a loop to gather data
+    /// for a debug record, where the laziness of log macros is not enough.
+    ///
+    /// It only loads a few atomics and does not lock or allocate. However, the
+    /// result is a hint: the level filters may be changed concurrently, and
+    /// combined sinks may discard the record in their sub-sinks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::sync::Arc;
+    /// use spdlog::prelude::*;
+    ///
+    /// # let logger: Arc<Logger> = spdlog::default_logger();
+    /// if logger.is_enabled(Level::Debug) {
+    ///     let total: u64 = (1..=100).sum();
+    ///     debug!(logger: logger, "total: {}", total);
+    /// }
+    /// ```
+    #[must_use]
+    pub fn is_enabled(&self, level: Level) -> bool {
+        self.should_log(level) && self.sinks.iter().any(|sink| sink.should_log(level))
+    }
+
     /// Logs a record.
     ///
     /// Users usually do not use this function directly, use log macros instead.
+    ///
+    /// Errors that occur in sinks are passed to the error handler, see
+    /// [`Logger::try_log`] for returning them to the caller instead.
     pub fn log(&self, record: &Record) {
-        if !self.should_log(record.level()) {
+        self.log_impl(record, &mut |err| self.handle_error(err));
+    }
+
+    /// Logs a record, and returns the errors instead of calling the error
+    /// handler.
+    ///
+    /// Like [`Logger::log`], a failing sink does not prevent the record from
+    /// being logged to the remaining sinks, and if the record triggers a flush,
+    /// errors that occur in flushing are returned as well. If more than one
+    /// error occurred, [`Error::Multiple`] will be returned.
+    ///
+    /// Use [`Logger::log`] (and log macros) for most records, where a failure
+    /// is not the business of the caller. Use this function on critical paths
+    /// where the caller must handle a failure explicitly, e.g. refusing an
+    /// operation if its audit record cannot be written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spdlog::{ErrorChain, Logger, Record};
+    ///
+    /// fn write_audit(audit_logger: &Logger, record: &Record) -> bool {
+    ///     if let Err(err) = audit_logger.try_log(record) {
+    ///         // Refuse the operation, retry, alert, ...
+    ///         eprintln!("failed to write audit record: {}", ErrorChain::new(&err));
+    ///         return false;
+    ///     }
+    ///     true
+    /// }
+    /// ```
+    pub fn try_log(&self, record: &Record) -> Result<()> {
+        let mut errors = Vec::new();
+        self.log_impl(record, &mut |err| errors.push(err));
+        errors.into_iter().fold(Ok(()), Error::push_err)
+    }
+
+    fn log_impl(&self, record: &Record, on_error: &mut impl FnMut(Error)) {
+        if !self.should_log(record.level()) || !self.should_log_verbosity(record.verbosity()) {
             return;
         }
-        self.sink_record(record);
+
+        #[cfg(feature = "metrics")]
+        if self.emit_metrics {
+            self.metrics_counters.get_or_init(|| {
+                Level::iter()
+                    .map(|level| metrics::counter!("log_records_total", "level" => level.as_str()))
+                    .collect()
+            })[record.level() as usize]
+                .increment(1);
+        }
+
+        record.with_context(&self.fields, |record| {
+            self.transform_and_sink_record(record, on_error)
+        });
     }
 
     /// Flushes any buffered records.
@@ -160,6 +272,26 @@ impl Logger {
         self.flush_sinks();
     }
 
+    /// Flushes any buffered records, and returns the errors instead of calling
+    /// the error handler.
+    ///
+    /// All sinks are flushed in the order in which they were added, a failing
+    /// sink does not prevent the remaining sinks from being flushed. If more
+    /// than one sink failed, [`Error::Multiple`] will be returned.
+    ///
+    /// This is useful for a clean shutdown, where the caller wants to know
+    /// whether all records have been durably written.
+    pub fn flush_all(&self) -> Result<()> {
+        let mut result = Ok(());
+        for (index, sink) in self.sinks.iter().enumerate() {
+            result = Error::push_result(
+                result,
+                sink.flush().map_err(|err| sink_error(index, sink, err)),
+            );
+        }
+        result
+    }
+
     /// Gets the flush level filter.
     #[must_use]
     pub fn flush_level_filter(&self) -> LevelFilter {
@@ -169,7 +301,13 @@ impl Logger {
     /// Sets a flush level filter.
     ///
     /// When logging a new record, flush the buffer if this filter condition is
-    /// true.
+    /// true. All sinks are flushed after the record has been delivered to
+    /// them, regardless of their own buffering policies, which is like
+    /// `flush_on` of C++ spdlog.
+    ///
+    /// Defaults to `LevelFilter::MoreSevereEqual(Level::Critical)`, so that a
+    /// critical record, which is likely logged right before a crash, is not
+    /// lost in a buffer. Set it to [`LevelFilter::Off`] to disable it.
     ///
     /// This auto-flush policy can work with [`Logger::set_flush_period`]
     /// together.
@@ -203,6 +341,12 @@ impl Logger {
 
     /// Sets the log filter level.
     ///
+    /// The level filter is stored in an atomic, so checking it when logging
+    /// is lock-free, and the new level filter takes effect promptly for all
+    /// threads logging to this logger. Loggers forked from it by
+    /// [`Logger::fork_with`] have their own level filters, which are not
+    /// affected.
+    ///
     /// # Examples
     ///
     /// See [`Logger::should_log`].
@@ -210,6 +354,50 @@ impl Logger {
         self.level_filter.store(level_filter, Ordering::Relaxed);
     }
 
+    /// Gets the maximum verbosity.
+    #[must_use]
+    pub fn max_verbosity(&self) -> u8 {
+        self.max_verbosity.load(Ordering::Relaxed)
+    }
+
+    /// Sets the maximum verbosity.
+    ///
+    /// Records with a [verbosity] greater than it are discarded, regardless of
+    /// their levels. It's intended for numeric verbosity options of command
+    /// line programs, e.g. `-vv` shows trace records with verbosity up to `2`.
+    /// Records logged without `v = <u8>` have verbosity `0`, so they are never
+    /// discarded by it.
+    ///
+    /// Defaults to `u8::MAX`, i.e. no records are discarded by verbosity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::sync::Arc;
+    /// use spdlog::prelude::*;
+    ///
+    /// # let logger: Arc<Logger> = spdlog::default_logger();
+    /// # let verbose_count = 2;
+    /// logger.set_level_filter(LevelFilter::All);
+    /// logger.set_max_verbosity(verbose_count); // e.g. `-vv`
+    ///
+    /// trace!(logger: logger, v = 1, "shown");
+    /// trace!(logger: logger, v = 2, "shown");
+    /// trace!(logger: logger, v = 3, "discarded");
+    /// ```
+    ///
+    /// [verbosity]: crate::Record::verbosity
+    pub fn set_max_verbosity(&self, max_verbosity: u8) {
+        self.max_verbosity.store(max_verbosity, Ordering::Relaxed);
+    }
+
+    /// Determines if a log message with the specified verbosity would be
+    /// logged, according to [`Logger::set_max_verbosity`].
+    #[must_use]
+    pub fn should_log_verbosity(&self, verbosity: u8) -> bool {
+        verbosity <= self.max_verbosity()
+    }
+
     /// Sets periodic flush.
     ///
     /// This function receives a `&Arc<Self>`. Calling it will spawn a new
@@ -261,6 +449,20 @@ impl Logger {
     }
 
     /// Gets a reference to sinks in the logger.
+    ///
+    /// Together with [`Sink::level_filter`] and [`Sink::type_name`], the
+    /// runtime configuration of the logger can be introspected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spdlog::sink::Sink;
+    ///
+    /// # let logger = spdlog::default_logger();
+    /// for sink in logger.sinks() {
+    ///     println!("{}: {:?}", sink.type_name(), sink.level_filter());
+    /// }
+    /// ```
     #[must_use]
     pub fn sinks(&self) -> &[Arc<dyn Sink>] {
         &self.sinks
@@ -278,19 +480,62 @@ impl Logger {
     /// called. If no handler is set, the error will be print to `stderr` and
     /// then ignored.
     ///
+    /// An error from one sink does not prevent the record from being delivered
+    /// to the remaining sinks, the handler will be called once for each failed
+    /// sink.
+    ///
     /// # Examples
     ///
     /// ```
-    /// use spdlog::prelude::*;
+    /// use spdlog::{prelude::*, Error, ErrorChain};
     ///
-    /// spdlog::default_logger().set_error_handler(Some(|err: spdlog::Error| {
-    ///     panic!("spdlog-rs error: {}", err)
+    /// spdlog::default_logger().set_error_handler(Some(|err: Error| {
+    ///     // Errors from sinks are wrapped in `Error::Sink`
+    ///     match err.strip_sink() {
+    ///         Error::WriteRecord(_) => { /* e.g. the disk is full */ }
+    ///         _ => panic!("spdlog-rs error: {}", ErrorChain::new(&err)),
+    ///     }
     /// }));
     /// ```
     pub fn set_error_handler(&self, handler: Option<ErrorHandler>) {
         *self.error_handler.write() = handler;
     }
 
+    /// Sets a record transform.
+    ///
+    /// The transform is called for each record that passes the level filter of
+    /// the logger, before the record is dispatched to sinks. It can rewrite the
+    /// record (e.g. redact sensitive data from the payload) with
+    /// [`RecordOwned::set_payload`] and [`RecordOwned::set_level`], or drop the
+    /// record by returning `None`. Sinks see the returned record, including
+    /// their level filters and the flush level filter of the logger.
+    ///
+    /// # Ownership
+    ///
+    /// [`Record`]s borrow their data from the logging call, so they cannot be
+    /// mutated. If a transform is set, each record passing the level filter is
+    /// converted into a [`RecordOwned`] via [`Record::to_owned`], which copies
+    /// the payload and the logger name, and then moved into the transform.
+    /// Loggers without a transform are not affected by this cost.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use spdlog::prelude::*;
+    ///
+    /// spdlog::default_logger().set_record_transform(Some(Arc::new(|mut record: spdlog::RecordOwned| {
+    ///     if record.payload().contains("password") {
+    ///         record.set_payload("*** redacted ***");
+    ///     }
+    ///     Some(record)
+    /// })));
+    /// ```
+    pub fn set_record_transform(&self, transform: Option<RecordTransform>) {
+        *self.record_transform.write() = transform;
+    }
+
     /// Fork and configure a separate new logger.
     ///
     /// This function creates a new logger object that inherits logger
@@ -348,7 +593,12 @@ impl Logger {
     /// given name. The created new logger object will be a separate object
     /// from `Arc<Self>`. (No ownership sharing)
     ///
-    /// This is a shorthand wrapper for [`Logger::fork_with`].
+    /// This is a shorthand wrapper for [`Logger::fork_with`], except that the
+    /// level filter is overridden by the environment variable
+    /// `SPDLOG_RS_LEVEL` for the new name if a rule matches it, as if the new
+    /// logger was built with the name, see [`init_env_level`].
+    ///
+    /// [`init_env_level`]: crate::init_env_level
     ///
     /// # Examples
     ///
@@ -370,44 +620,136 @@ impl Logger {
     {
         self.fork_with(|new| {
             new.set_name(new_name).map_err(InvalidArgumentError::from)?;
+            if let Some(level) = env_level::logger_level(env_level::LoggerKind::Other(new.name())) {
+                new.set_level_filter(level);
+            }
             Ok(())
         })
     }
 
+    /// Constructs a [`LoggerBuilder`] seeded with the configuration of this
+    /// logger.
+    ///
+    /// Unlike [`Logger::fork_with`], the configuration can be changed with the
+    /// builder methods before building the new logger, e.g. adding a sink or
+    /// setting the level filter, without re-specifying the rest.
+    ///
+    /// The following properties are copied from this logger, with their
+    /// current values:
+    ///
+    /// - the name;
+    /// - the level filter and the maximum verbosity;
+    /// - the sinks, which are shared via [`Arc`] rather than cloned, so a sink
+    ///   receives the records of both loggers, and changing e.g. the formatter
+    ///   of a sink affects both;
+    /// - the fields;
+    /// - the flush level filter;
+    /// - the error handler and the record transform;
+    /// - whether metrics are emitted, with feature `metrics` enabled.
+    ///
+    /// The flush period set by [`Logger::set_flush_period`] is not copied, as
+    /// the builder has no such parameter, set it on the new logger if needed.
+    /// As with any builder, the level filter is overridden by the environment
+    /// variable `SPDLOG_RS_LEVEL` when building, see [`init_env_level`].
+    ///
+    /// [`init_env_level`]: crate::init_env_level
+    ///
+    /// # Examples
+    ///
+    /// ```
+    #[doc = include_str!(concat!(env!("OUT_DIR"), "/test_utils/common_for_doc_test.rs"))]
+    /// # use std::sync::Arc;
+    /// # use spdlog::prelude::*;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let audit_sink = Arc::new(test_utils::StringSink::new());
+    /// let app: Arc<Logger> = /* ... */
+    /// # Arc::new(Logger::builder().name("app").build()?);
+    /// // Same sinks and configuration, plus an audit sink and all levels
+    /// let audit: Logger = app
+    ///     .to_builder()
+    ///     .name("app-audit")
+    ///     .level_filter(LevelFilter::All)
+    ///     .sink(audit_sink)
+    ///     .build()?;
+    /// # Ok(()) }
+    /// ```
+    #[must_use]
+    pub fn to_builder(&self) -> LoggerBuilder {
+        LoggerBuilder {
+            name: self.name.clone(),
+            level_filter: self.level_filter(),
+            max_verbosity: self.max_verbosity(),
+            sinks: self.sinks.clone(),
+            fields: self.fields.clone(),
+            #[cfg(feature = "metrics")]
+            emit_metrics: self.emit_metrics,
+            sink_errors: vec![],
+            flush_level_filter: self.flush_level_filter(),
+            error_handler: *self.error_handler.read(),
+            record_transform: self.record_transform.read().clone(),
+        }
+    }
+
     // This will lose the periodic flush property, if any.
     #[must_use]
     fn clone_lossy(&self) -> Self {
         Logger {
             name: self.name.clone(),
             level_filter: Atomic::new(self.level_filter()),
+            max_verbosity: Atomic::new(self.max_verbosity()),
             sinks: self.sinks.clone(),
+            fields: self.fields.clone(),
+            #[cfg(feature = "metrics")]
+            emit_metrics: self.emit_metrics,
+            #[cfg(feature = "metrics")]
+            metrics_counters: OnceCell::new(),
             flush_level_filter: Atomic::new(self.flush_level_filter()),
             periodic_flusher: Mutex::new(None),
             error_handler: SpinRwLock::new(*self.error_handler.read()),
+            record_transform: SpinRwLock::new(self.record_transform.read().clone()),
+        }
+    }
+
+    fn transform_and_sink_record(&self, record: &Record, on_error: &mut impl FnMut(Error)) {
+        let record_transform = self.record_transform.read().clone();
+        match record_transform {
+            Some(transform) => {
+                if let Some(record) = transform(record.to_owned()) {
+                    self.sink_record(&record.as_ref(), on_error);
+                }
+            }
+            None => self.sink_record(record, on_error),
         }
     }
 
-    fn sink_record(&self, record: &Record) {
-        self.sinks.iter().for_each(|sink| {
+    fn sink_record(&self, record: &Record, on_error: &mut impl FnMut(Error)) {
+        // Do not stop on the first error, so that a failing sink never silences
+        // the healthy ones.
+        self.sinks.iter().enumerate().for_each(|(index, sink)| {
             if let Err(err) = sink.log(record) {
-                self.handle_error(err);
+                on_error(sink_error(index, sink, err));
             }
         });
 
         if self.should_flush(record) {
-            self.flush();
+            self.sinks.iter().enumerate().for_each(|(index, sink)| {
+                if let Err(err) = sink.flush() {
+                    on_error(sink_error(index, sink, err));
+                }
+            });
         }
     }
 
     fn flush_sinks(&self) {
-        self.sinks.iter().for_each(|sink| {
+        self.sinks.iter().enumerate().for_each(|(index, sink)| {
             if let Err(err) = sink.flush() {
-                self.handle_error(err);
+                self.handle_error(sink_error(index, sink, err));
             }
         });
     }
 
-    fn handle_error(&self, err: Error) {
+    pub(crate) fn handle_error(&self, err: Error) {
         if let Some(handler) = self.error_handler.read().as_ref() {
             handler(err)
         } else {
@@ -427,6 +769,11 @@ impl Logger {
     }
 }
 
+#[must_use]
+fn sink_error(index: usize, sink: &Arc<dyn Sink>, err: Error) -> Error {
+    Error::Sink(SinkError::new(index, sink.type_name(), err))
+}
+
 impl Clone for Logger {
     /// Clones the `Logger`.
     ///
@@ -450,9 +797,15 @@ impl Clone for Logger {
 pub struct LoggerBuilder {
     name: Option<String>,
     level_filter: LevelFilter,
+    max_verbosity: u8,
     sinks: Sinks,
+    fields: Vec<(String, String)>,
+    #[cfg(feature = "metrics")]
+    emit_metrics: bool,
+    sink_errors: Vec<BuildSinkError>,
     flush_level_filter: LevelFilter,
     error_handler: Option<ErrorHandler>,
+    record_transform: Option<RecordTransform>,
 }
 
 impl LoggerBuilder {
@@ -495,12 +848,60 @@ impl LoggerBuilder {
         self
     }
 
+    /// Sets the maximum verbosity.
+    ///
+    /// This parameter is **optional**, and defaults to `u8::MAX`.
+    ///
+    /// See the documentation of [`Logger::set_max_verbosity`] for the
+    /// description of this parameter.
+    pub fn max_verbosity(&mut self, max_verbosity: u8) -> &mut Self {
+        self.max_verbosity = max_verbosity;
+        self
+    }
+
     /// Add a [`Sink`].
     pub fn sink(&mut self, sink: Arc<dyn Sink>) -> &mut Self {
         self.sinks.push(sink);
         self
     }
 
+    /// Add a [`Sink`] from the result of building it.
+    ///
+    /// If the result is an error, [`LoggerBuilder::build`] will return
+    /// [`Error::BuildSink`] identifying the index and the type of the failed
+    /// sink, so that misconfigurations can be diagnosed at startup. If multiple
+    /// sinks failed, [`Error::Multiple`] containing all of them will be
+    /// returned.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use spdlog::{prelude::*, sink::FileSink};
+    ///
+    /// # fn main() -> Result<(), spdlog::Error> {
+    /// let logger: Logger = Logger::builder()
+    ///     .try_sink(FileSink::builder().path("/path/to/log_file").build())
+    ///     .build()?;
+    /// # Ok(()) }
+    /// ```
+    pub fn try_sink<S>(&mut self, sink: Result<S>) -> &mut Self
+    where
+        S: Sink + 'static,
+    {
+        match sink {
+            Ok(sink) => self.sinks.push(Arc::new(sink)),
+            Err(err) => {
+                let index = self.sinks.len() + self.sink_errors.len();
+                self.sink_errors.push(BuildSinkError::new(
+                    index,
+                    std::any::type_name::<S>(),
+                    Arc::new(err),
+                ));
+            }
+        }
+        self
+    }
+
     /// Add multiple [`Sink`]s.
     pub fn sinks<I>(&mut self, sinks: I) -> &mut Self
     where
@@ -510,9 +911,85 @@ impl LoggerBuilder {
         self
     }
 
+    /// Adds a default field, which is attached to every record of the logger as
+    /// a key-value.
+    ///
+    /// This parameter is **optional**, and defaults to no fields.
+    ///
+    /// # Merging
+    ///
+    /// The key-values of a record are the default fields of the logger in the
+    /// order they were added, followed by the key-values passed to the log
+    /// macro in their order. If a key-value passed to the log macro has the
+    /// same key as a default field, it overrides the default field, i.e. the
+    /// default field is removed. Adding a default field with an existing key
+    /// does not replace the existing one, both are kept.
+    ///
+    /// The key-values of the [MDC] of the logging thread are placed between
+    /// the default fields and those passed to the log macro, overriding default
+    /// fields with the same key, see [`Record::key_values`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use spdlog::{formatter::GelfFormatter, prelude::*, sink::StdStreamSink, sink::StdStream};
+    ///
+    /// # fn main() -> Result<(), spdlog::Error> {
+    /// let sink = Arc::new(
+    ///     StdStreamSink::builder()
+    ///         .std_stream(StdStream::Stdout)
+    ///         .formatter(Box::new(GelfFormatter::new("my-host")))
+    ///         .build()?,
+    /// );
+    /// let logger = Logger::builder()
+    ///     .sink(sink)
+    ///     .with_field("service", "auth")
+    ///     .with_field("region", "eu")
+    ///     .build()?;
+    ///
+    /// // Key-values: service=auth, region=us, user=alice
+    /// info!(logger: logger, kv: { region = "us", user = "alice" }, "login");
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// [MDC]: crate::mdc
+    pub fn with_field<K, V>(&mut self, key: K, value: V) -> &mut Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.fields.push((key.into(), value.into()));
+        self
+    }
+
+    /// Sets whether to emit metrics of logged records to the [`metrics`]
+    /// facade.
+    ///
+    /// This parameter is **optional**, and defaults to `false`.
+    ///
+    /// When enabled, counter `log_records_total` with label `level` (e.g.
+    /// `error`) is incremented for every record passing the level filter of the
+    /// logger, regardless of whether sinks filter it out or it's dropped by the
+    /// [record transform]. The counters of all levels are registered with the
+    /// recorder on the first such record, starting from 0, and their handles
+    /// are reused afterwards, so install the recorder before logging. If no
+    /// recorder is installed then, the counters are no-op for the lifetime of
+    /// the logger.
+    ///
+    /// [`metrics`]: https://docs.rs/metrics
+    /// [record transform]: Logger::set_record_transform
+    #[cfg(feature = "metrics")]
+    pub fn emit_metrics(&mut self, emit_metrics: bool) -> &mut Self {
+        self.emit_metrics = emit_metrics;
+        self
+    }
+
     /// Sets the flush level filter.
     ///
-    /// This paramter is **optional**, and defaults to [`LevelFilter::Off`].
+    /// This paramter is **optional**, and defaults to
+    /// `LevelFilter::MoreSevereEqual(Level::Critical)`.
     ///
     /// See the documentation of [`Logger::set_flush_level_filter`] for the
     /// description of this parameter.
@@ -532,7 +1009,25 @@ impl LoggerBuilder {
         self
     }
 
+    /// Sets the record transform.
+    ///
+    /// This parameter is **optional**, and defaults to `None`.
+    ///
+    /// See the documentation of [`Logger::set_record_transform`] for the
+    /// description of this parameter.
+    pub fn record_transform(&mut self, transform: RecordTransform) -> &mut Self {
+        self.record_transform = Some(transform);
+        self
+    }
+
     /// Builds a [`Logger`].
+    ///
+    /// # Errors
+    ///
+    /// If the logger name is invalid, [`Error::InvalidArgument`] will be
+    /// returned. If any sink added via [`LoggerBuilder::try_sink`] failed to be
+    /// built, [`Error::BuildSink`] (or [`Error::Multiple`] if more than one)
+    /// will be returned.
     pub fn build(&mut self) -> Result<Logger> {
         self.build_inner(self.preset_level(false))
     }
@@ -555,12 +1050,25 @@ impl LoggerBuilder {
             check_logger_name(name).map_err(InvalidArgumentError::from)?;
         }
 
+        self.sink_errors
+            .iter()
+            .cloned()
+            .map(Error::BuildSink)
+            .fold(Ok(()), Error::push_err)?;
+
         let logger = Logger {
             name: self.name.clone(),
             level_filter: Atomic::new(self.level_filter),
+            max_verbosity: Atomic::new(self.max_verbosity),
             sinks: self.sinks.clone(),
+            fields: self.fields.clone(),
+            #[cfg(feature = "metrics")]
+            emit_metrics: self.emit_metrics,
+            #[cfg(feature = "metrics")]
+            metrics_counters: OnceCell::new(),
             flush_level_filter: Atomic::new(self.flush_level_filter),
             error_handler: SpinRwLock::new(self.error_handler),
+            record_transform: SpinRwLock::new(self.record_transform.clone()),
             periodic_flusher: Mutex::new(None),
         };
 
@@ -592,10 +1100,10 @@ impl LoggerBuilder {
 
 #[cfg(test)]
 mod tests {
-    use std::{thread, time::Duration};
+    use std::{fs, sync::atomic::AtomicUsize, thread, time::Duration};
 
     use super::*;
-    use crate::{prelude::*, test_utils::*};
+    use crate::{prelude::*, sink::FileSink, test_utils::*};
 
     #[test]
     fn send_sync() {
@@ -603,6 +1111,43 @@ mod tests {
         assert_sync::<Logger>();
     }
 
+    #[test]
+    fn set_level_filter_across_threads() {
+        let sink = Arc::new(CounterSink::new());
+        let logger = Arc::new(build_test_logger(|b| {
+            b.sink(sink.clone()).level_filter(LevelFilter::All)
+        }));
+
+        let logging_thread = {
+            let (logger, sink) = (logger.clone(), sink.clone());
+            thread::spawn(move || {
+                // Logs until a record is filtered, i.e. the change is observed
+                let mut logged = 0;
+                loop {
+                    let before = sink.log_count();
+                    info!(logger: logger, "hello");
+                    if sink.log_count() == before {
+                        break;
+                    }
+                    logged += 1;
+                }
+                for _ in 0..1000 {
+                    info!(logger: logger, "filtered");
+                }
+                logged
+            })
+        };
+
+        while sink.log_count() == 0 {
+            thread::yield_now();
+        }
+        logger.set_level_filter(LevelFilter::Off);
+
+        let logged = logging_thread.join().unwrap();
+        assert!(logged > 0);
+        assert_eq!(sink.log_count(), logged);
+    }
+
     #[test]
     fn flush_level() {
         let test_sink = Arc::new(CounterSink::new());
@@ -611,6 +1156,8 @@ mod tests {
         trace!(logger: test_logger, "");
         error!(logger: test_logger, "");
         assert_eq!(test_sink.flush_count(), 0);
+        critical!(logger: test_logger, "");
+        assert_eq!(test_sink.flush_count(), 1);
         test_sink.reset();
 
         test_logger.set_flush_level_filter(LevelFilter::MoreSevereEqual(Level::Warn));
@@ -680,6 +1227,397 @@ mod tests {
         };
     }
 
+    #[test]
+    fn logger_name_in_records() {
+        let log_with_name = |name: Option<&str>| {
+            let sink = Arc::new(StringSink::new());
+            let logger = build_test_logger(|b| {
+                if let Some(name) = name {
+                    b.name(name);
+                }
+                b.sink(sink.clone())
+            });
+            info!(logger: logger, "hello");
+            sink.clone_string()
+        };
+
+        assert!(log_with_name(Some("network")).contains("] [network] [info] "));
+        let unnamed = log_with_name(None);
+        assert!(unnamed.contains("] [info] "));
+        assert!(!unnamed.contains("[]"));
+    }
+
+    #[test]
+    fn is_enabled() {
+        let info_sink = Arc::new(CounterSink::new());
+        info_sink.set_level_filter(LevelFilter::MoreSevereEqual(Level::Info));
+        let warn_sink = Arc::new(CounterSink::new());
+        warn_sink.set_level_filter(LevelFilter::MoreSevereEqual(Level::Warn));
+        let logger = build_test_logger(|b| {
+            b.sink(info_sink.clone())
+                .sink(warn_sink.clone())
+                .level_filter(LevelFilter::All)
+        });
+
+        assert!(logger.should_log(Level::Debug));
+        assert!(!logger.is_enabled(Level::Debug));
+        assert!(logger.is_enabled(Level::Info));
+
+        info_sink.set_level_filter(LevelFilter::Off);
+        assert!(!logger.is_enabled(Level::Info));
+        assert!(logger.is_enabled(Level::Warn));
+
+        logger.set_level_filter(LevelFilter::Off);
+        assert!(!logger.is_enabled(Level::Warn));
+
+        let no_sinks = build_test_logger(|b| b.level_filter(LevelFilter::All));
+        assert!(!no_sinks.is_enabled(Level::Critical));
+    }
+
+    #[test]
+    fn continue_on_sink_error() {
+        static ERROR_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+        let test_sink = Arc::new(CounterSink::new());
+        let test_logger = Logger::builder()
+            .sink(Arc::new(FailingSink::new()))
+            .sink(test_sink.clone())
+            .sink(Arc::new(FailingSink::new()))
+            .error_handler(|_| {
+                ERROR_COUNT.fetch_add(1, Ordering::Relaxed);
+            })
+            .build()
+            .unwrap();
+
+        info!(logger: test_logger, "hello");
+        info!(logger: test_logger, "world");
+        assert_eq!(test_sink.payloads(), vec!["hello", "world"]);
+        assert_eq!(ERROR_COUNT.load(Ordering::Relaxed), 4);
+
+        test_logger.flush();
+        assert_eq!(test_sink.flush_count(), 1);
+        assert_eq!(ERROR_COUNT.load(Ordering::Relaxed), 6);
+    }
+
+    #[test]
+    fn try_log() {
+        let test_sink = Arc::new(CounterSink::new());
+        let test_logger = build_test_logger(|b| {
+            b.sink(Arc::new(FailingSink::new()))
+                .sink(test_sink.clone())
+                .flush_level_filter(LevelFilter::MoreSevereEqual(Level::Error))
+        });
+
+        let sink_error = |err: &Error| match err {
+            Error::Sink(err) => {
+                assert_eq!(err.index(), 0);
+                assert!(err.sink_type().ends_with("::FailingSink"));
+                match err.error() {
+                    Error::__ForInternalTestsUseOnly(i) => *i,
+                    err => panic!("unexpected error: {}", err),
+                }
+            }
+            err => panic!("unexpected error: {}", err),
+        };
+
+        match test_logger.try_log(&Record::new(Level::Info, "hello")) {
+            Err(err) => assert_eq!(sink_error(&err), 1),
+            Ok(()) => panic!("expected an error"),
+        }
+        match test_logger.try_log(&Record::new(Level::Error, "world")) {
+            Err(Error::Multiple(errors)) => {
+                assert_eq!(errors.iter().map(sink_error).collect::<Vec<_>>(), [1, 2])
+            }
+            _ => panic!("expected multiple errors"),
+        }
+        assert_eq!(test_sink.payloads(), vec!["hello", "world"]);
+        assert_eq!(test_sink.flush_count(), 1);
+
+        let test_logger = build_test_logger(|b| b.sink(test_sink.clone()));
+        assert!(test_logger.try_log(&Record::new(Level::Info, "ok")).is_ok());
+    }
+
+    #[test]
+    fn flush_all() {
+        let test_sink = Arc::new(CounterSink::new());
+        let test_logger = Logger::builder()
+            .sink(test_sink.clone())
+            .sink(test_sink.clone())
+            .build()
+            .unwrap();
+
+        assert!(test_logger.flush_all().is_ok());
+        assert_eq!(test_sink.flush_count(), 2);
+    }
+
+    #[test]
+    fn introspect_sinks() {
+        let test_sink = Arc::new(CounterSink::new());
+        test_sink.set_level_filter(LevelFilter::MoreSevereEqual(Level::Warn));
+        let test_logger = Logger::builder()
+            .sink(test_sink)
+            .sink(Arc::new(StringSink::new()))
+            .build()
+            .unwrap();
+
+        let sinks = test_logger
+            .sinks()
+            .iter()
+            .map(|sink| (sink.type_name(), sink.level_filter()))
+            .collect::<Vec<_>>();
+        assert_eq!(sinks.len(), 2);
+        assert!(sinks[0].0.ends_with("::CounterSink"));
+        assert_eq!(sinks[0].1, LevelFilter::MoreSevereEqual(Level::Warn));
+        assert!(sinks[1].0.ends_with("::StringSink"));
+    }
+
+    #[test]
+    fn record_transform() {
+        let test_sink = Arc::new(CounterSink::new());
+        let test_logger = build_test_logger(|b| {
+            b.sink(test_sink.clone())
+                .record_transform(Arc::new(|mut record: RecordOwned| {
+                    if record.payload().contains("drop me") {
+                        return None;
+                    }
+                    let token = regex::Regex::new(r"token=\w+").unwrap();
+                    let redacted = token.replace_all(record.payload(), "token=***");
+                    if let std::borrow::Cow::Owned(redacted) = redacted {
+                        record.set_payload(redacted);
+                    }
+                    Some(record)
+                }))
+        });
+
+        info!(logger: test_logger, "login user=alice token=s3cr3t ok");
+        info!(logger: test_logger, "drop me");
+        info!(logger: test_logger, "nothing sensitive");
+        assert_eq!(
+            test_sink.payloads(),
+            vec![
+                "login user=alice token=*** ok".to_string(),
+                "nothing sensitive".to_string()
+            ]
+        );
+
+        // Applied after filtering
+        test_sink.reset();
+        debug!(logger: test_logger, "token=filtered");
+        info!(logger: test_logger, "token=abc");
+        assert_eq!(test_sink.payloads(), vec!["token=***".to_string()]);
+
+        test_sink.reset();
+        test_logger.set_record_transform(None);
+        warn!(logger: test_logger, "token=abc");
+        assert_eq!(test_sink.payloads(), vec!["token=abc".to_string()]);
+    }
+
+    #[test]
+    fn default_fields() {
+        let test_sink = Arc::new(CounterSink::new());
+        let test_logger = build_test_logger(|b| {
+            b.sink(test_sink.clone())
+                .with_field("service", "auth")
+                .with_field("region", "eu")
+        });
+
+        let key_values = |record: &RecordOwned| {
+            record
+                .key_values()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect::<Vec<_>>()
+        };
+        let pairs = |kvs: &[(&str, &str)]| {
+            kvs.iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect::<Vec<_>>()
+        };
+
+        info!(logger: test_logger, "no per-call fields");
+        info!(logger: test_logger, kv: { region = "us", user = "alice" }, "override");
+
+        let records = test_sink.records();
+        assert_eq!(
+            key_values(&records[0]),
+            pairs(&[("service", "auth"), ("region", "eu")])
+        );
+        assert_eq!(
+            key_values(&records[1]),
+            pairs(&[("service", "auth"), ("region", "us"), ("user", "alice")])
+        );
+    }
+
+    #[test]
+    fn mdc_fields() {
+        let test_sink = Arc::new(CounterSink::new());
+        let test_logger = build_test_logger(|b| {
+            b.sink(test_sink.clone())
+                .with_field("service", "auth")
+                .with_field("region", "eu")
+        });
+        let forward_sink = Arc::new(CounterSink::new());
+        let forward_logger =
+            build_test_logger(|b| b.sink(forward_sink.clone()).with_field("host", "h1"));
+
+        let collect = |kvs: &mut dyn Iterator<Item = (&str, &str)>| {
+            kvs.map(|(k, v)| format!("{}={}", k, v))
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+
+        {
+            let _region = crate::mdc::scoped("region", "ap");
+            let _trace = crate::mdc::scoped("trace", "t1");
+            info!(logger: test_logger, kv: { trace = "t2" }, "override");
+
+            // The MDC captured by the record is not merged again
+            let record = test_sink.records().pop().unwrap();
+            forward_logger.log(&record.as_ref());
+        }
+        info!(logger: test_logger, "no MDC");
+
+        let records = test_sink.records();
+        assert_eq!(
+            collect(&mut records[0].key_values()),
+            "service=auth region=ap trace=t2"
+        );
+        assert_eq!(collect(&mut records[0].mdc_key_values()), "region=ap");
+        assert_eq!(
+            collect(&mut records[1].key_values()),
+            "service=auth region=eu"
+        );
+        assert_eq!(collect(&mut records[1].mdc_key_values()), "");
+
+        let forwarded = forward_sink.records().pop().unwrap();
+        assert_eq!(
+            collect(&mut forwarded.key_values()),
+            "host=h1 service=auth region=ap trace=t2"
+        );
+        assert_eq!(collect(&mut forwarded.mdc_key_values()), "region=ap");
+    }
+
+    #[test]
+    fn max_verbosity() {
+        let test_sink = Arc::new(CounterSink::new());
+        let test_logger = build_test_logger(|b| {
+            b.sink(test_sink.clone())
+                .level_filter(LevelFilter::All)
+                .max_verbosity(2)
+        });
+
+        trace!(logger: test_logger, "no verbosity");
+        trace!(logger: test_logger, v = 1, "verbosity 1");
+        trace!(logger: test_logger, v = 2, kv: { k = 0 }, "verbosity 2");
+        trace!(logger: test_logger, v = 3, "verbosity 3");
+        debug!(logger: test_logger, v = 3, "verbosity 3");
+        assert_eq!(
+            test_sink.payloads(),
+            vec!["no verbosity", "verbosity 1", "verbosity 2"]
+        );
+        let verbosities = test_sink
+            .records()
+            .iter()
+            .map(|record| record.verbosity())
+            .collect::<Vec<_>>();
+        assert_eq!(verbosities, vec![0, 1, 2]);
+
+        test_sink.reset();
+        test_logger.set_max_verbosity(0);
+        trace!(logger: test_logger, "no verbosity");
+        trace!(logger: test_logger, v = 1, "verbosity 1");
+        assert_eq!(test_sink.payloads(), vec!["no verbosity"]);
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn emit_metrics() {
+        use metrics_util::{
+            debugging::{DebugValue, DebuggingRecorder},
+            CompositeKey, MetricKind,
+        };
+
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+        let counters = || {
+            let mut counters = snapshotter
+                .snapshot()
+                .into_vec()
+                .into_iter()
+                .map(|(key, _, _, value)| {
+                    let (kind, key): (MetricKind, _) = CompositeKey::into_parts(key);
+                    assert_eq!(kind, MetricKind::Counter);
+                    assert_eq!(key.name(), "log_records_total");
+                    let level = key.labels().next().unwrap().value().to_string();
+                    match value {
+                        DebugValue::Counter(count) => (level, count),
+                        _ => unreachable!(),
+                    }
+                })
+                .collect::<Vec<_>>();
+            counters.sort();
+            counters
+        };
+
+        let test_sink = Arc::new(CounterSink::new());
+        let test_logger = build_test_logger(|b| b.sink(test_sink.clone()).emit_metrics(true));
+        let silent_logger = build_test_logger(|b| b.sink(test_sink.clone()));
+
+        metrics::with_local_recorder(&recorder, || {
+            error!(logger: test_logger, "1");
+            error!(logger: test_logger, "2");
+            warn!(logger: test_logger, "3");
+            info!(logger: test_logger, "4");
+            // Filtered out by the logger
+            test_logger.set_level_filter(LevelFilter::MoreSevereEqual(Level::Warn));
+            info!(logger: test_logger, "5");
+            // Not enabled
+            critical!(logger: silent_logger, "6");
+        });
+
+        assert_eq!(
+            counters(),
+            [
+                ("critical".to_string(), 0),
+                ("debug".to_string(), 0),
+                ("error".to_string(), 2),
+                ("info".to_string(), 1),
+                ("trace".to_string(), 0),
+                ("warn".to_string(), 1)
+            ]
+        );
+        assert_eq!(test_sink.log_count(), 5);
+    }
+
+    #[test]
+    fn builder_try_sink() {
+        let file_path = TEST_LOGS_PATH.join("logger_builder_try_sink_file");
+        fs::write(&file_path, "").unwrap();
+        // the parent of the path is a file, so the sink fails to open it
+        let bad_path = file_path.join("log_file");
+
+        let good_sink = Arc::new(StringSink::new());
+        let err = Logger::builder()
+            .sink(good_sink)
+            .try_sink(FileSink::builder().path(bad_path).build())
+            .build()
+            .err()
+            .unwrap();
+
+        match err {
+            Error::BuildSink(err) => {
+                assert_eq!(err.index(), 1);
+                assert!(err.sink_type().ends_with("FileSink"));
+            }
+            _ => panic!("unexpected error: {}", err),
+        }
+
+        let logger = Logger::builder()
+            .try_sink(Ok(StringSink::new()))
+            .build()
+            .unwrap();
+        assert_eq!(logger.sinks().len(), 1);
+    }
+
     #[test]
     fn env_level() {
         macro_rules! assert_levels {
@@ -861,4 +1799,33 @@ mod tests {
         assert_eq!(test_sink.1.log_count(), 0);
         assert_eq!(test_sink.1.flush_count(), 1);
     }
+
+    #[test]
+    fn to_builder() {
+        let test_sink = (Arc::new(CounterSink::new()), Arc::new(CounterSink::new()));
+        let old = build_test_logger(|b| {
+            b.name("app")
+                .sink(test_sink.0.clone())
+                .level_filter(LevelFilter::MoreSevereEqual(Level::Warn))
+                .flush_level_filter(LevelFilter::All)
+        });
+
+        let new = old.to_builder().sink(test_sink.1.clone()).build().unwrap();
+        assert_eq!(new.name(), Some("app"));
+        assert_eq!(new.level_filter(), old.level_filter());
+        assert_eq!(new.flush_level_filter(), LevelFilter::All);
+        assert_eq!(new.sinks().len(), 2);
+
+        info!(logger: new, "filtered out");
+        warn!(logger: new, "to both sinks");
+        assert_eq!(test_sink.0.payloads(), ["to both sinks"]);
+        assert_eq!(test_sink.1.payloads(), ["to both sinks"]);
+        assert_eq!(test_sink.1.flush_count(), 1);
+
+        // The old logger is left untouched
+        warn!(logger: old, "to the first sink");
+        assert_eq!(old.sinks().len(), 1);
+        assert_eq!(test_sink.0.log_count(), 2);
+        assert_eq!(test_sink.1.log_count(), 1);
+    }
 }
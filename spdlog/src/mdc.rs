@@ -0,0 +1,195 @@
+//! Provides the mapped diagnostic context (MDC).
+//!
+//! The MDC is a set of key-values of the current thread, e.g. the ID of the
+//! request being handled, which are attached to all records logged on the
+//! thread while they are set, without passing them to each log macro.
+//!
+//! The key-values are captured into a record when it enters a logger, or when
+//! it's queued by an asynchronous sink (e.g. [`AsyncPoolSink`]) if it's logged
+//! to the sink directly. So sinks writing records on other threads get the MDC
+//! of the thread that logged them. A record captures the MDC only once, i.e.
+//! forwarding it to another logger, possibly on another thread, does not
+//! capture the MDC of that thread. See [`Record::key_values`] for their order
+//! among the other key-values of a record, and [`Record::mdc_key_values`] to
+//! get them alone.
+//!
+//! # Examples
+//!
+//! ```
+//! use spdlog::{info, mdc};
+//!
+//! fn handle_request(request_id: u64) {
+//!     let _guard = mdc::scoped("request_id", request_id.to_string());
+//!
+//!     // Key-values: request_id=42, user=alice
+//!     info!(kv: { user = "alice" }, "handling request");
+//! }
+//!
+//! handle_request(42);
+//! ```
+//!
+//! [`AsyncPoolSink`]: crate::sink::AsyncPoolSink
+//! [`Record::key_values`]: crate::Record::key_values
+//! [`Record::mdc_key_values`]: crate::Record::mdc_key_values
+
+use std::{cell::RefCell, marker::PhantomData, rc::Rc};
+
+type Entries = Vec<(String, String)>;
+
+thread_local! {
+    // Copied on write, so that the logger keeps a snapshot while calling sinks,
+    // which may modify the MDC.
+    static MDC: RefCell<Rc<Entries>> = RefCell::new(Rc::new(Vec::new()));
+}
+
+/// Inserts a key-value into the MDC of the current thread.
+///
+/// If the key is already present, its value is replaced in place and the old
+/// value is returned. Otherwise the key-value is appended.
+pub fn insert<K, V>(key: K, value: V) -> Option<String>
+where
+    K: Into<String>,
+    V: Into<String>,
+{
+    let (key, value) = (key.into(), value.into());
+    modify(
+        |entries| match entries.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, old)) => Some(std::mem::replace(old, value)),
+            None => {
+                entries.push((key, value));
+                None
+            }
+        },
+    )
+}
+
+/// Gets the value of a key in the MDC of the current thread.
+#[must_use]
+pub fn get(key: &str) -> Option<String> {
+    MDC.with(|mdc| {
+        mdc.borrow()
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value.clone())
+    })
+}
+
+/// Removes a key from the MDC of the current thread, returning its value.
+pub fn remove(key: &str) -> Option<String> {
+    modify(|entries| {
+        let index = entries.iter().position(|(k, _)| k == key)?;
+        Some(entries.remove(index).1)
+    })
+}
+
+/// Removes all key-values from the MDC of the current thread.
+pub fn clear() {
+    MDC.with(|mdc| *mdc.borrow_mut() = Rc::new(Vec::new()));
+}
+
+/// Inserts a key-value into the MDC of the current thread until the returned
+/// guard is dropped.
+///
+/// When the guard is dropped, the previous value of the key is restored, or
+/// the key is removed if it was not present. Guards should be dropped in the
+/// reverse order of their creation, which is the case for guards bound to
+/// variables in nested scopes.
+///
+/// # Examples
+///
+/// See the documentation of [module `mdc`].
+///
+/// [module `mdc`]: crate::mdc
+pub fn scoped<K, V>(key: K, value: V) -> MdcGuard
+where
+    K: Into<String>,
+    V: Into<String>,
+{
+    let key = key.into();
+    let previous = insert(key.clone(), value);
+    MdcGuard {
+        key,
+        previous,
+        _not_send: PhantomData,
+    }
+}
+
+/// The guard returned by [`scoped`], restoring the MDC of the current thread
+/// when dropped.
+#[must_use = "the key-value is removed from the MDC immediately if the guard is not bound"]
+pub struct MdcGuard {
+    key: String,
+    previous: Option<String>,
+    // The guard restores the MDC of the thread it was created on
+    _not_send: PhantomData<*const ()>,
+}
+
+impl Drop for MdcGuard {
+    fn drop(&mut self) {
+        match self.previous.take() {
+            Some(previous) => {
+                insert(std::mem::take(&mut self.key), previous);
+            }
+            None => {
+                remove(&self.key);
+            }
+        }
+    }
+}
+
+// Gets a snapshot of the MDC of the current thread, or `None` if it's empty.
+#[must_use]
+pub(crate) fn snapshot() -> Option<Rc<Entries>> {
+    MDC.with(|mdc| {
+        let mdc = mdc.borrow();
+        (!mdc.is_empty()).then(|| Rc::clone(&mdc))
+    })
+}
+
+fn modify<R>(f: impl FnOnce(&mut Entries) -> R) -> R {
+    MDC.with(|mdc| f(Rc::make_mut(&mut *mdc.borrow_mut())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_get_remove() {
+        clear();
+        assert_eq!(insert("a", "1"), None);
+        assert_eq!(insert("b", "2"), None);
+        assert_eq!(insert("a", "3"), Some("1".to_string()));
+        assert_eq!(get("a").as_deref(), Some("3"));
+
+        // A snapshot is not affected by later modifications
+        let captured = snapshot().unwrap();
+        assert_eq!(remove("a").as_deref(), Some("3"));
+        assert_eq!(remove("a"), None);
+        assert_eq!(
+            *captured,
+            [
+                ("a".to_string(), "3".to_string()),
+                ("b".to_string(), "2".to_string())
+            ]
+        );
+
+        clear();
+        assert!(snapshot().is_none());
+    }
+
+    #[test]
+    fn scoped_guard() {
+        clear();
+        insert("user", "alice");
+        {
+            let _user = scoped("user", "bob");
+            let _request = scoped("request_id", "42");
+            assert_eq!(get("user").as_deref(), Some("bob"));
+            assert_eq!(get("request_id").as_deref(), Some("42"));
+        }
+        assert_eq!(get("user").as_deref(), Some("alice"));
+        assert_eq!(get("request_id"), None);
+        clear();
+    }
+}
@@ -22,10 +22,11 @@ impl PeriodicWorker {
             active: active.clone(),
             thread: Some(thread::spawn(move || loop {
                 let guard = active.0.lock_expect();
-                let (_, res) = active
+                let res = active
                     .1
                     .wait_timeout_while(guard, interval, |active| *active)
-                    .unwrap();
+                    .unwrap()
+                    .1;
 
                 if !res.timed_out() || !callback() {
                     return;
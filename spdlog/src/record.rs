@@ -2,11 +2,13 @@
 
 use std::{
     borrow::{Borrow, Cow},
-    cell::RefCell,
+    cell::{Cell, RefCell},
+    fmt,
+    ops::Range,
     time::SystemTime,
 };
 
-use crate::{Level, SourceLocation};
+use crate::{sync::Arc, Level, SourceLocation};
 
 /// Represents a log record.
 ///
@@ -17,6 +19,28 @@ use crate::{Level, SourceLocation};
 /// manipulate these structures in order to process log records. `Record`s are
 /// automatically created by log macros and so are not seen by log users.
 ///
+/// # Ownership
+///
+/// A `Record` borrows as much as possible from the log call site. The logger
+/// name is borrowed from the [`Logger`], and the payload is borrowed if the
+/// log macro is called without format arguments, otherwise it's formatted once
+/// on the logging thread, into a buffer reused by the thread, before it's
+/// passed to any sink. Key-values are borrowed too, except for the values
+/// passed to log macros, which are formatted into owned `String`s. The
+/// remaining data (level, source location, time and TID) are small values.
+///
+/// Therefore, a `Record` is only valid during the [`Sink::log`] call. To fan
+/// out a record to multiple sinks, pass the same `&Record` to each of them,
+/// which costs nothing. [`Clone`] is also implemented, it copies the borrowed
+/// parts cheaply, but allocates for the parts that are owned (e.g.
+/// key-values).
+///
+/// If a record needs to outlive the call, e.g. to be queued for another thread
+/// as the asynchronous sinks do, convert it into a [`RecordOwned`] with
+/// [`Record::to_owned`], or with [`Record::into_owned`] which reuses the owned
+/// parts instead of copying them.
+///
+/// [`Logger`]: crate::logger::Logger
 /// [`Logger::log`]: crate::logger::Logger::log
 /// [`Sink::log`]: crate::sink::Sink::log
 /// [`log!`]: crate::log
@@ -26,15 +50,40 @@ use crate::{Level, SourceLocation};
 pub struct Record<'a> {
     logger_name: Option<Cow<'a, str>>,
     payload: Cow<'a, str>,
+    // The raw bytes of the payload, if it's logged by `log_bytes!`. `payload` is
+    // their lossy UTF-8 conversion then.
+    raw_payload: Option<Cow<'a, [u8]>>,
+    key_values: Cow<'a, [KeyValue<'a>]>,
     inner: Cow<'a, RecordInner>,
 }
 
+pub(crate) type KeyValue<'a> = (Cow<'a, str>, Cow<'a, str>);
+
 #[derive(Clone, Debug)]
 struct RecordInner {
     level: Level,
+    verbosity: u8,
+    // Static, so that tagging a record never allocates
+    tags: &'static [&'static str],
     source_location: Option<SourceLocation>,
     time: SystemTime,
     tid: u64,
+    // Whether the thread-local context (i.e. the MDC) of the logging thread has
+    // been captured, so that the context of other threads, e.g. the worker
+    // threads of asynchronous sinks, is never captured.
+    context_captured: bool,
+    // The part of `key_values` captured from the MDC. Not `Range<usize>`, to fit
+    // in the padding.
+    mdc_range: (u16, u16),
+}
+
+const NO_TAGS: &[&str] = &[];
+
+impl RecordInner {
+    #[must_use]
+    fn mdc_range(&self) -> Range<usize> {
+        usize::from(self.mdc_range.0)..usize::from(self.mdc_range.1)
+    }
 }
 
 impl<'a> Record<'a> {
@@ -49,11 +98,17 @@ impl<'a> Record<'a> {
         Record {
             logger_name: None,
             payload: payload.into(),
+            raw_payload: None,
+            key_values: Cow::Borrowed(&[]),
             inner: Cow::Owned(RecordInner {
                 level,
+                verbosity: 0,
+                tags: NO_TAGS,
                 source_location: None,
                 time: SystemTime::now(),
                 tid: get_current_tid(),
+                context_captured: false,
+                mdc_range: (0, 0),
             }),
         }
     }
@@ -72,11 +127,37 @@ impl<'a> Record<'a> {
     /// Creates a [`RecordOwned`] that doesn't have lifetimes.
     #[must_use]
     pub fn to_owned(&self) -> RecordOwned {
-        RecordOwned {
-            logger_name: self.logger_name.clone().map(|n| n.into_owned()),
-            payload: self.payload.to_string(),
+        RecordOwned::new(RecordOwnedData {
+            logger_name: self.logger_name.as_deref().map(String::from),
+            payload: String::from(self.payload.as_ref()),
+            raw_payload: self.raw_payload.as_deref().map(<[u8]>::to_vec),
+            key_values: self
+                .key_values
+                .iter()
+                .map(|(key, value)| (Cow::Owned(key.to_string()), Cow::Owned(value.to_string())))
+                .collect(),
             inner: self.inner.clone().into_owned(),
-        }
+        })
+    }
+
+    /// Converts into a [`RecordOwned`] that doesn't have lifetimes.
+    ///
+    /// Unlike [`Record::to_owned`], the parts that are already owned are moved
+    /// instead of copied.
+    #[must_use]
+    pub fn into_owned(self) -> RecordOwned {
+        RecordOwned::new(RecordOwnedData {
+            logger_name: self.logger_name.map(Cow::into_owned),
+            payload: self.payload.into_owned(),
+            raw_payload: self.raw_payload.map(Cow::into_owned),
+            key_values: self
+                .key_values
+                .into_owned()
+                .into_iter()
+                .map(|(key, value)| (Cow::Owned(key.into_owned()), Cow::Owned(value.into_owned())))
+                .collect(),
+            inner: self.inner.into_owned(),
+        })
     }
 
     /// Gets the logger name.
@@ -91,12 +172,94 @@ impl<'a> Record<'a> {
         self.inner.level
     }
 
+    /// Gets the verbosity.
+    ///
+    /// It's a sub-level of the level of the record, set with `v = <u8>` in
+    /// log macros, and defaults to `0`. See [`Logger::set_max_verbosity`] for
+    /// filtering records by it.
+    ///
+    /// [`Logger::set_max_verbosity`]: crate::Logger::set_max_verbosity
+    #[must_use]
+    pub fn verbosity(&self) -> u8 {
+        self.inner.verbosity
+    }
+
+    /// Gets the tags.
+    ///
+    /// Tags are set with `tags = [...]` in log macros, and are empty by
+    /// default. They are coarse labels for filtering and routing records, e.g.
+    /// `"audit"`, see [`TagRoutingSink`]. Unlike key-values, they are static
+    /// strings, so tagging a record never allocates.
+    ///
+    /// [`TagRoutingSink`]: crate::sink::TagRoutingSink
+    #[must_use]
+    pub fn tags(&self) -> &'static [&'static str] {
+        self.inner.tags
+    }
+
+    /// Determines if the record has the given tag.
+    #[must_use]
+    pub fn has_tag(&self, tag: &str) -> bool {
+        has_tag(self.inner.tags, tag)
+    }
+
     /// Gets the payload.
+    ///
+    /// For records logged by [`log_bytes!`], it's the bytes converted with
+    /// [`String::from_utf8_lossy`], see [`Record::payload_bytes`] for the
+    /// bytes as they are.
+    ///
+    /// [`log_bytes!`]: crate::log_bytes
     #[must_use]
     pub fn payload(&self) -> &str {
         self.payload.borrow()
     }
 
+    /// Gets the payload as bytes.
+    ///
+    /// For records logged by [`log_bytes!`], it's the bytes passed to the
+    /// macro, which may not be valid UTF-8. Otherwise, it's the same as
+    /// [`Record::payload`]. Sinks writing bytes rather than text use it to pass
+    /// binary payloads through unchanged.
+    ///
+    /// [`log_bytes!`]: crate::log_bytes
+    #[must_use]
+    pub fn payload_bytes(&self) -> &[u8] {
+        match &self.raw_payload {
+            Some(raw_payload) => raw_payload,
+            None => self.payload.as_bytes(),
+        }
+    }
+
+    /// Gets the key-values.
+    ///
+    /// They are the default fields of the logger, followed by the key-values
+    /// of the [MDC] of the logging thread, followed by the key-values passed to
+    /// the log macro. A key-value passed to the log macro overrides those with
+    /// the same key in the MDC and the default fields, and a key-value in the
+    /// MDC overrides the default fields with the same key. See
+    /// [`LoggerBuilder::with_field`] for details.
+    ///
+    /// [MDC]: crate::mdc
+    /// [`LoggerBuilder::with_field`]: crate::LoggerBuilder::with_field
+    pub fn key_values(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.key_values
+            .iter()
+            .map(|(key, value)| (key.as_ref(), value.as_ref()))
+    }
+
+    /// Gets the key-values captured from the [MDC] of the logging thread.
+    ///
+    /// They are a part of [`Record::key_values`], excluding those overridden
+    /// by the key-values passed to the log macro.
+    ///
+    /// [MDC]: crate::mdc
+    pub fn mdc_key_values(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.key_values[self.inner.mdc_range()]
+            .iter()
+            .map(|(key, value)| (key.as_ref(), value.as_ref()))
+    }
+
     /// Gets the source location.
     #[must_use]
     pub fn source_location(&self) -> Option<&SourceLocation> {
@@ -123,10 +286,101 @@ impl<'a> Record<'a> {
         Self {
             logger_name: self.logger_name.clone(),
             payload: new.into(),
+            raw_payload: None,
+            key_values: Cow::Borrowed(&self.key_values),
             inner: Cow::Borrowed(&self.inner),
         }
     }
 
+    // Calls `f` with the record merged with the default key-values of a logger,
+    // and with the context of the current thread captured if it has not been.
+    //
+    // It's called when the record enters a logger, and when it's queued to be
+    // processed on another thread.
+    pub(crate) fn with_context<R>(
+        &self,
+        defaults: &[(String, String)],
+        f: impl FnOnce(&Record) -> R,
+    ) -> R {
+        if self.inner.context_captured {
+            if defaults.is_empty() {
+                f(self)
+            } else {
+                f(&self.with_default_key_values(defaults, &[]))
+            }
+        } else {
+            let mdc = crate::mdc::snapshot();
+            let mdc = mdc.as_deref().map_or(&[][..], Vec::as_slice);
+            f(&self.with_default_key_values(defaults, mdc))
+        }
+    }
+
+    // Merges the default key-values of a logger and the MDC, see
+    // `Record::key_values` for the semantics. The MDC is ignored if the record
+    // has captured the context.
+    #[must_use]
+    fn with_default_key_values<'b>(
+        &'b self,
+        defaults: &'b [(String, String)],
+        mdc: &'b [(String, String)],
+    ) -> Record<'b> {
+        let mdc = if self.inner.context_captured {
+            &[]
+        } else {
+            mdc
+        };
+        let (key_values, mdc_range) =
+            if defaults.is_empty() && mdc.is_empty() {
+                (Cow::Borrowed(&*self.key_values), self.inner.mdc_range())
+            } else {
+                let overridden_by_own = |key: &str| self.key_values.iter().any(|(k, _)| k == key);
+                let overridden_by_mdc = |key: &str| mdc.iter().any(|(k, _)| k == key);
+
+                let mut key_values =
+                    Vec::with_capacity(defaults.len() + mdc.len() + self.key_values.len());
+                key_values.extend(
+                    defaults
+                        .iter()
+                        .filter(|(key, _)| !overridden_by_own(key) && !overridden_by_mdc(key))
+                        .map(|(key, value)| {
+                            (Cow::Borrowed(key.as_str()), Cow::Borrowed(value.as_str()))
+                        }),
+                );
+                let offset = key_values.len();
+                key_values.extend(mdc.iter().filter(|(key, _)| !overridden_by_own(key)).map(
+                    |(key, value)| (Cow::Borrowed(key.as_str()), Cow::Borrowed(value.as_str())),
+                ));
+                let mdc_range = if mdc.is_empty() {
+                    let range = self.inner.mdc_range();
+                    range.start + offset..range.end + offset
+                } else {
+                    offset..key_values.len()
+                };
+                key_values.extend(self.key_values.iter().map(|(key, value)| {
+                    (Cow::Borrowed(key.as_ref()), Cow::Borrowed(value.as_ref()))
+                }));
+                (Cow::Owned(key_values), mdc_range)
+            };
+
+        Record {
+            logger_name: self.logger_name.as_deref().map(Cow::Borrowed),
+            payload: Cow::Borrowed(&self.payload),
+            raw_payload: self.raw_payload.as_deref().map(Cow::Borrowed),
+            key_values,
+            inner: if self.inner.context_captured && mdc_range == self.inner.mdc_range() {
+                Cow::Borrowed(&self.inner)
+            } else {
+                // MDCs with more than `u16::MAX` key-values are not supported
+                let to_u16 = |index| u16::try_from(index).unwrap_or(u16::MAX);
+                Cow::Owned(RecordInner {
+                    context_captured: true,
+                    mdc_range: (to_u16(mdc_range.start), to_u16(mdc_range.end)),
+                    ..self.inner.as_ref().clone()
+                })
+            },
+        }
+    }
+
     #[cfg(feature = "log")]
     #[must_use]
     pub(crate) fn from_log_crate_record(
@@ -151,13 +405,19 @@ impl<'a> Record<'a> {
                 Some(literal_str) => literal_str.into(),
                 None => args.to_string().into(),
             },
+            raw_payload: None,
+            key_values: Cow::Borrowed(&[]),
             inner: Cow::Owned(RecordInner {
                 level: record.level().into(),
+                verbosity: 0,
+                tags: NO_TAGS,
                 source_location: SourceLocation::from_log_crate_record(record),
                 time,
                 // For records from `log` crate, they never seem to come from different threads, so
                 // getting the current TID here should be correct
                 tid: get_current_tid(),
+                context_captured: false,
+                mdc_range: (0, 0),
             }),
         }
     }
@@ -169,12 +429,27 @@ impl<'a> Record<'a> {
 }
 
 /// [`Record`] without lifetimes version.
+///
+/// All of its data is owned, so it can be stored or sent to other threads.
+/// The data is shared by reference counting, so cloning it is cheap, e.g. for
+/// queuing the same record for multiple sinks. Setters copy the data first if
+/// it's shared. Use [`RecordOwned::as_ref`] to pass it to APIs taking a
+/// [`Record`] without copying.
 // We do not `impl From<&Record> for RecordOwned` because it does not follow the
 // Rust naming convention. Use `record.to_owned()` instead.
 #[derive(Clone, Debug)]
 pub struct RecordOwned {
+    data: Arc<RecordOwnedData>,
+}
+
+#[derive(Clone, Debug)]
+struct RecordOwnedData {
+    // Not boxed slices, since converting into them reallocates if there is spare
+    // capacity, which defeats `Record::into_owned`
     logger_name: Option<String>,
     payload: String,
+    raw_payload: Option<Vec<u8>>,
+    key_values: Vec<KeyValue<'static>>,
     inner: RecordInner,
 }
 
@@ -183,47 +458,229 @@ impl RecordOwned {
     #[doc(hidden)]
     pub const __SIZE_OF: usize = std::mem::size_of::<Self>();
 
+    #[must_use]
+    fn new(data: RecordOwnedData) -> Self {
+        Self {
+            data: Arc::new(data),
+        }
+    }
+
     /// References as [`Record`] cheaply.
     #[must_use]
-    pub fn as_ref(&self) -> Record {
+    pub fn as_ref(&self) -> Record<'_> {
         Record {
-            logger_name: self.logger_name.as_deref().map(Cow::Borrowed),
-            payload: Cow::Borrowed(&self.payload),
-            inner: Cow::Borrowed(&self.inner),
+            logger_name: self.data.logger_name.as_deref().map(Cow::Borrowed),
+            payload: Cow::Borrowed(&self.data.payload),
+            raw_payload: self.data.raw_payload.as_deref().map(Cow::Borrowed),
+            key_values: Cow::Borrowed(&self.data.key_values),
+            inner: Cow::Borrowed(&self.data.inner),
         }
     }
 
     /// Gets the logger name.
     #[must_use]
     pub fn logger_name(&self) -> Option<&str> {
-        self.logger_name.as_deref()
+        self.data.logger_name.as_deref()
     }
 
     /// Gets the level.
     #[must_use]
     pub fn level(&self) -> Level {
-        self.inner.level
+        self.data.inner.level
+    }
+
+    /// Gets the verbosity.
+    ///
+    /// See [`Record::verbosity`] for details.
+    #[must_use]
+    pub fn verbosity(&self) -> u8 {
+        self.data.inner.verbosity
+    }
+
+    /// Gets the tags.
+    ///
+    /// See [`Record::tags`] for details.
+    #[must_use]
+    pub fn tags(&self) -> &'static [&'static str] {
+        self.data.inner.tags
+    }
+
+    /// Determines if the record has the given tag.
+    #[must_use]
+    pub fn has_tag(&self, tag: &str) -> bool {
+        has_tag(self.data.inner.tags, tag)
     }
 
     /// Gets the payload.
     #[must_use]
     pub fn payload(&self) -> &str {
-        self.payload.borrow()
+        self.data.payload.borrow()
+    }
+
+    /// Gets the payload as bytes.
+    ///
+    /// See [`Record::payload_bytes`] for details.
+    #[must_use]
+    pub fn payload_bytes(&self) -> &[u8] {
+        match &self.data.raw_payload {
+            Some(raw_payload) => raw_payload,
+            None => self.data.payload.as_bytes(),
+        }
+    }
+
+    /// Gets the key-values.
+    ///
+    /// See [`Record::key_values`] for details.
+    pub fn key_values(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.data
+            .key_values
+            .iter()
+            .map(|(key, value)| (key.as_ref(), value.as_ref()))
+    }
+
+    /// Gets the key-values captured from the MDC of the logging thread.
+    ///
+    /// See [`Record::mdc_key_values`] for details.
+    pub fn mdc_key_values(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.data.key_values[self.data.inner.mdc_range()]
+            .iter()
+            .map(|(key, value)| (key.as_ref(), value.as_ref()))
     }
 
     /// Gets the source location.
     #[must_use]
     pub fn source_location(&self) -> Option<&SourceLocation> {
-        self.inner.source_location.as_ref()
+        self.data.inner.source_location.as_ref()
     }
 
     /// Gets the time when the record was created.
     #[must_use]
     pub fn time(&self) -> SystemTime {
-        self.inner.time
+        self.data.inner.time
     }
 
     // When adding more getters, also add to `Record`
+
+    /// Sets the level.
+    ///
+    /// See [`Logger::set_record_transform`] for rewriting records before they
+    /// are dispatched to sinks.
+    ///
+    /// [`Logger::set_record_transform`]: crate::Logger::set_record_transform
+    pub fn set_level(&mut self, level: Level) {
+        Arc::make_mut(&mut self.data).inner.level = level;
+    }
+
+    /// Sets the payload.
+    ///
+    /// The raw bytes of a record logged by [`log_bytes!`] are discarded.
+    ///
+    /// See [`Logger::set_record_transform`] for rewriting records before they
+    /// are dispatched to sinks.
+    ///
+    /// [`log_bytes!`]: crate::log_bytes
+    /// [`Logger::set_record_transform`]: crate::Logger::set_record_transform
+    pub fn set_payload<S>(&mut self, payload: S)
+    where
+        S: Into<String>,
+    {
+        let data = Arc::make_mut(&mut self.data);
+        data.payload = payload.into();
+        data.raw_payload = None;
+    }
+}
+
+// A reusable buffer of a record for asynchronous sinks.
+//
+// Unlike `RecordOwned`, refilling it retains the allocated capacity, so once
+// warmed up, records are copied into it without allocating.
+#[cfg(feature = "multi-thread")]
+#[derive(Debug)]
+pub(crate) struct RecordBuf {
+    // Not `Option<String>`, to retain the capacity when there is no name
+    logger_name: String,
+    has_logger_name: bool,
+    payload: String,
+    // Not retaining the capacity, to keep the buffer small, as raw payloads are
+    // rare
+    raw_payload: Option<Box<[u8]>>,
+    // Only the first `key_values_len` elements are valid, the rest are kept for
+    // their capacity
+    key_values: Vec<KeyValue<'static>>,
+    key_values_len: usize,
+    inner: RecordInner,
+}
+
+#[cfg(feature = "multi-thread")]
+impl RecordBuf {
+    #[must_use]
+    pub(crate) fn with_payload_capacity(capacity: usize) -> Self {
+        Self {
+            logger_name: String::new(),
+            has_logger_name: false,
+            payload: String::with_capacity(capacity),
+            raw_payload: None,
+            key_values: Vec::new(),
+            key_values_len: 0,
+            inner: RecordInner {
+                level: Level::Info,
+                verbosity: 0,
+                tags: NO_TAGS,
+                source_location: None,
+                time: SystemTime::UNIX_EPOCH,
+                tid: 0,
+                context_captured: false,
+                mdc_range: (0, 0),
+            },
+        }
+    }
+
+    pub(crate) fn fill(&mut self, record: &Record) {
+        fn assign(dest: &mut Cow<'static, str>, src: &str) {
+            let dest = dest.to_mut();
+            dest.clear();
+            dest.push_str(src);
+        }
+
+        self.logger_name.clear();
+        self.has_logger_name = record.logger_name.is_some();
+        if let Some(logger_name) = &record.logger_name {
+            self.logger_name.push_str(logger_name);
+        }
+
+        self.payload.clear();
+        self.payload.push_str(&record.payload);
+
+        self.raw_payload = record.raw_payload.as_deref().map(Box::from);
+
+        for (i, (key, value)) in record.key_values.iter().enumerate() {
+            match self.key_values.get_mut(i) {
+                Some((dest_key, dest_value)) => {
+                    assign(dest_key, key);
+                    assign(dest_value, value);
+                }
+                None => self
+                    .key_values
+                    .push((Cow::Owned(key.to_string()), Cow::Owned(value.to_string()))),
+            }
+        }
+        self.key_values_len = record.key_values.len();
+
+        self.inner = record.inner.as_ref().clone();
+    }
+
+    #[must_use]
+    pub(crate) fn as_record(&self) -> Record<'_> {
+        Record {
+            logger_name: self
+                .has_logger_name
+                .then(|| Cow::Borrowed(self.logger_name.as_str())),
+            payload: Cow::Borrowed(&self.payload),
+            raw_payload: self.raw_payload.as_deref().map(Cow::Borrowed),
+            key_values: Cow::Borrowed(&self.key_values[..self.key_values_len]),
+            inner: Cow::Borrowed(&self.inner),
+        }
+    }
 }
 
 /// The builder of [`Record`].
@@ -259,6 +716,34 @@ impl<'a> RecordBuilder<'a> {
         self
     }
 
+    /// Sets the raw bytes of the payload.
+    #[must_use]
+    pub(crate) fn raw_payload(mut self, raw_payload: &'a [u8]) -> Self {
+        self.record.raw_payload = Some(Cow::Borrowed(raw_payload));
+        self
+    }
+
+    /// Sets the key-values.
+    #[must_use]
+    pub(crate) fn key_values(mut self, key_values: Vec<KeyValue<'a>>) -> Self {
+        self.record.key_values = Cow::Owned(key_values);
+        self
+    }
+
+    /// Sets the verbosity.
+    #[must_use]
+    pub(crate) fn verbosity(mut self, verbosity: u8) -> Self {
+        self.record.inner.to_mut().verbosity = verbosity;
+        self
+    }
+
+    /// Sets the tags.
+    #[must_use]
+    pub fn tags(mut self, tags: &'static [&'static str]) -> Self {
+        self.record.inner.to_mut().tags = tags;
+        self
+    }
+
     /// Sets the source location.
     // `Option` in the parameter is for the convenience of passing the result of
     // the macro `source_location_current` directly.
@@ -268,6 +753,13 @@ impl<'a> RecordBuilder<'a> {
         self
     }
 
+    /// Sets the time.
+    #[must_use]
+    pub(crate) fn time(mut self, time: SystemTime) -> Self {
+        self.record.inner.to_mut().time = time;
+        self
+    }
+
     /// Builds a [`Record`].
     #[must_use]
     pub(crate) fn build(self) -> Record<'a> {
@@ -275,6 +767,56 @@ impl<'a> RecordBuilder<'a> {
     }
 }
 
+// Calls `f` with a buffer reused by the current thread, for formatting
+// payloads.
+//
+// The buffer is taken out of the thread-local while it's in use, so reentrant
+// calls, e.g. from a `Display` implementation that logs, get a new buffer
+// instead of clobbering it.
+pub(crate) fn with_payload_buf<R>(f: impl FnOnce(&mut String) -> R) -> R {
+    // Larger buffers are dropped after use, so that a huge payload does not
+    // keep the memory for the rest of the thread's lifetime.
+    const MAX_RETAINED_CAPACITY: usize = 64 * 1024;
+
+    thread_local! {
+        static PAYLOAD_BUF: Cell<String> = Cell::new(String::new());
+    }
+
+    let mut buf = PAYLOAD_BUF.try_with(Cell::take).unwrap_or_default();
+    buf.clear();
+    let res = f(&mut buf);
+    if buf.capacity() <= MAX_RETAINED_CAPACITY {
+        // Fails only if the thread-local is being destroyed
+        let _ = PAYLOAD_BUF.try_with(|cell| cell.set(buf));
+    }
+    res
+}
+
+// Calls `f` with the payload of the format arguments.
+//
+// Format arguments without arguments are borrowed as a string, otherwise
+// they're formatted into the buffer of `with_payload_buf`. Formatting happens
+// here on the logging thread, before any sink is called, so that `Display`
+// implementations never run while a sink holds a lock.
+pub(crate) fn with_formatted_payload<R>(args: fmt::Arguments, f: impl FnOnce(Cow<str>) -> R) -> R {
+    match args.as_str() {
+        Some(literal_str) => f(Cow::Borrowed(literal_str)),
+        None => with_payload_buf(|buf| {
+            // Writing into a `String` fails only if a `Display` implementation fails,
+            // which `ToString` also treats as a bug
+            fmt::Write::write_fmt(buf, args)
+                .expect("a Display implementation returned an error unexpectedly");
+            f(Cow::Borrowed(buf))
+        }),
+    }
+}
+
+// Records have a few tags at most, a linear search is the fastest.
+#[must_use]
+fn has_tag(tags: &[&str], tag: &str) -> bool {
+    tags.contains(&tag)
+}
+
 fn get_current_tid() -> u64 {
     #[cfg(target_os = "linux")]
     #[must_use]
@@ -308,3 +850,82 @@ fn get_current_tid() -> u64 {
 
     TID.with(|tid| *tid.borrow_mut().get_or_insert_with(get_current_tid_inner))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::*;
+
+    #[test]
+    fn into_owned() {
+        const TAGS: &[&str] = &["audit", "security"];
+
+        // With spare capacity, which would be lost by a reallocation
+        let mut payload = String::with_capacity(64);
+        payload.push_str("formatted payload");
+        let payload_ptr = payload.as_ptr();
+        let record = Record::builder(Level::Warn, payload)
+            .logger_name("logger")
+            .tags(TAGS)
+            .build();
+        let time = record.time();
+        assert!(record.has_tag("audit"));
+        assert!(!record.has_tag("aud"));
+
+        let owned = record.clone().into_owned();
+        assert_eq!(owned.logger_name(), Some("logger"));
+        assert_eq!(owned.level(), Level::Warn);
+        assert_eq!(owned.payload(), "formatted payload");
+        assert_eq!(owned.tags(), ["audit", "security"]);
+        assert!(owned.has_tag("security"));
+        assert_eq!(owned.time(), time);
+
+        // The owned payload is moved instead of copied.
+        let owned = record.into_owned();
+        assert_eq!(owned.payload().as_ptr(), payload_ptr);
+    }
+
+    #[test]
+    fn clone_owned() {
+        let owned = Record::new(Level::Info, "payload").to_owned();
+
+        // Cloning shares the data
+        let mut cloned = owned.clone();
+        assert_eq!(cloned.payload().as_ptr(), owned.payload().as_ptr());
+
+        // Setters do not affect the other clones
+        cloned.set_level(Level::Error);
+        cloned.set_payload("new payload");
+        assert_eq!(cloned.level(), Level::Error);
+        assert_eq!(cloned.payload(), "new payload");
+        assert_eq!(owned.level(), Level::Info);
+        assert_eq!(owned.payload(), "payload");
+    }
+
+    #[test]
+    fn formatted_payload() {
+        assert_send::<Record>();
+        assert_sync::<Record>();
+
+        let x = 42;
+        with_formatted_payload(format_args!("x={}", x), |payload| {
+            assert_eq!(payload, "x=42");
+
+            // Reentrant calls do not clobber the outer payload
+            with_formatted_payload(format_args!("y={}", x + 1), |payload| {
+                assert_eq!(payload, "y=43")
+            });
+            assert_eq!(payload, "x=42");
+        });
+
+        // The buffer is reused
+        let first = with_formatted_payload(format_args!("{}", x), |payload| payload.as_ptr());
+        let second = with_formatted_payload(format_args!("{}", x), |payload| payload.as_ptr());
+        assert_eq!(first, second);
+
+        // Literals are borrowed
+        with_formatted_payload(format_args!("literal"), |payload| {
+            assert!(matches!(payload, Cow::Borrowed("literal")))
+        });
+    }
+}
@@ -0,0 +1,96 @@
+//! Provides the graceful shutdown of asynchronous workers.
+
+#[cfg(feature = "multi-thread")]
+use crate::sync::*;
+
+/// Shuts down all asynchronous workers, ensuring that records queued before are
+/// written and flushed.
+///
+/// It's intended to be called at the end of `main`, since dropping loggers and
+/// sinks does not reliably happen at process exit (e.g. the default logger is
+/// never dropped), and records still queued in asynchronous sinks may be lost.
+///
+/// It performs the following steps in order:
+///
+///  1. Flushes the default logger, if it has been initialized.
+///  2. Processes the remaining queued operations of all [`ThreadPool`]s and
+///     joins their threads.
+///  3. Flushes the sub-sinks of all [`AsyncPoolSink`]s.
+///  4. Writes the pending batches of all [`AsyncBatchSink`]s and joins their
+///     threads.
+///
+/// Loggers other than the default logger are not flushed, but the records they
+/// have sent to asynchronous sinks are written.
+///
+/// It's idempotent, and it's safe to call even if there are no asynchronous
+/// sinks.
+///
+/// # Logging after shutdown
+///
+/// Logging after shutdown is best-effort. Asynchronous sinks are still usable,
+/// but they perform operations synchronously on the calling thread, so their
+/// performance benefits are lost.
+///
+/// # Examples
+///
+/// ```
+/// use spdlog::prelude::*;
+///
+/// fn main() {
+///     info!("the last log before exiting");
+///     spdlog::shutdown();
+/// }
+/// ```
+///
+/// [`ThreadPool`]: crate::ThreadPool
+/// [`AsyncPoolSink`]: crate::sink::AsyncPoolSink
+/// [`AsyncBatchSink`]: crate::sink::AsyncBatchSink
+pub fn shutdown() {
+    if let Some(default_logger) = crate::DEFAULT_LOGGER.get() {
+        default_logger.load().flush();
+    }
+
+    #[cfg(feature = "multi-thread")]
+    {
+        // Do not hold the lock while shutting down, since it may build sinks
+        let mut workers = WORKERS
+            .lock_expect()
+            .iter()
+            .filter_map(|(stage, worker)| worker.upgrade().map(|worker| (*stage, worker)))
+            .collect::<Vec<_>>();
+        workers.sort_by_key(|(stage, _)| *stage);
+
+        for (_, worker) in workers {
+            worker.shutdown();
+        }
+    }
+}
+
+// An asynchronous worker which needs to be shut down by `shutdown`.
+#[cfg(feature = "multi-thread")]
+pub(crate) trait AsyncWorker: Send + Sync {
+    // Must be idempotent.
+    fn shutdown(&self);
+}
+
+// Workers are shut down in the order of stages.
+#[cfg(feature = "multi-thread")]
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
+pub(crate) enum ShutdownStage {
+    ThreadPool,
+    AsyncPoolSink,
+    AsyncBatchSink,
+}
+
+#[cfg(feature = "multi-thread")]
+type Workers = Vec<(ShutdownStage, Weak<dyn AsyncWorker>)>;
+
+#[cfg(feature = "multi-thread")]
+static WORKERS: Lazy<Mutex<Workers>> = Lazy::new(Mutex::default);
+
+#[cfg(feature = "multi-thread")]
+pub(crate) fn register_async_worker(stage: ShutdownStage, worker: Arc<dyn AsyncWorker>) {
+    let mut workers = WORKERS.lock_expect();
+    workers.retain(|(_, worker)| worker.strong_count() != 0);
+    workers.push((stage, Arc::downgrade(&worker)));
+}
@@ -0,0 +1,292 @@
+//! Provides flushing the default logger on termination signals, and reopening
+//! file sinks on signals.
+
+use std::{
+    io,
+    os::raw::{c_int, c_void},
+    thread,
+};
+
+use cfg_if::cfg_if;
+
+use crate::{sink::FileSink, sync::*, DEFAULT_LOGGER};
+
+/// Signals that can be handled by [`install_flush_on_signal`] and
+/// [`install_reopen_on_signal`].
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[non_exhaustive]
+pub enum Signal {
+    /// `SIGTERM`, usually sent by process managers and container runtimes for
+    /// an orderly termination.
+    Terminate,
+    /// `SIGINT`, usually sent by pressing `Ctrl+C` in terminals.
+    Interrupt,
+    /// `SIGHUP`, sent when the controlling terminal is closed.
+    Hangup,
+    /// `SIGQUIT`, usually sent by pressing `Ctrl+\` in terminals.
+    Quit,
+    /// `SIGUSR1`, a user-defined signal, used by some daemons for reopening
+    /// log files.
+    User1,
+}
+
+impl Signal {
+    #[must_use]
+    fn raw(&self) -> c_int {
+        match self {
+            Self::Terminate => libc::SIGTERM,
+            Self::Interrupt => libc::SIGINT,
+            Self::Hangup => libc::SIGHUP,
+            Self::Quit => libc::SIGQUIT,
+            Self::User1 => libc::SIGUSR1,
+        }
+    }
+}
+
+// The write end of the self-pipe, `-1` if it has not been created.
+static PIPE_WRITE_FD: AtomicI32 = AtomicI32::new(-1);
+
+static PIPE_INIT: Lazy<io::Result<()>> = Lazy::new(|| {
+    let mut fds = [0 as c_int; 2];
+    if unsafe { libc::pipe(fds.as_mut_ptr()) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    let [read_fd, write_fd] = fds;
+
+    thread::Builder::new()
+        .name("spdlog-signal".into())
+        .spawn(move || signal_thread(read_fd))?;
+
+    PIPE_WRITE_FD.store(write_fd, Ordering::SeqCst);
+    Ok(())
+});
+
+/// Installs handlers for the given signals, flushing the default logger before
+/// the signal is handled as it would have been.
+///
+/// By default, these signals terminate the process without running any exit
+/// handler, so buffered records in sinks (e.g. [`FileSink`]) are lost. With
+/// this function, when one of the given signals arrives, the default logger is
+/// flushed, then the disposition the signal had before is restored, and the
+/// signal is raised again, so the process still terminates as it would have
+/// (or the handler previously installed by the application is called).
+///
+/// # Limitations
+///
+/// - Only the default logger is flushed. `spdlog-rs` does not have a registry,
+///   other loggers are not known to it.
+///
+/// - Flushing is not async-signal-safe, so it's not performed in the signal
+///   handler. The handler only writes to a pipe, and a dedicated thread named
+///   `spdlog-signal` performs the flush. If the process is terminated by other
+///   means in the meantime, or a sink blocks while flushing, records may still
+///   be lost.
+///
+/// - Previously installed handlers for the given signals are replaced until the
+///   signal arrives. Do not use this function if the application handles these
+///   signals by itself and expects them more than once, flush the loggers in
+///   the application's handler instead.
+///
+/// # Errors
+///
+/// Returns an error if the pipe or the thread failed to be created, or a
+/// handler failed to be installed.
+///
+/// # Examples
+///
+/// ```
+/// use spdlog::Signal;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// spdlog::install_flush_on_signal(&[Signal::Terminate, Signal::Interrupt])?;
+/// # Ok(()) }
+/// ```
+///
+/// [`FileSink`]: crate::sink::FileSink
+pub fn install_flush_on_signal(signals: &[Signal]) -> io::Result<()> {
+    signals.iter().try_for_each(|signal| {
+        FLUSH_SIGNALS.lock_expect().push(signal.raw());
+        install_handler(*signal)
+    })
+}
+
+// Signals registered by `install_flush_on_signal`.
+static FLUSH_SIGNALS: Lazy<Mutex<Vec<c_int>>> = Lazy::new(Mutex::default);
+
+// A file sink to be reopened on the signal, registered by
+// `install_reopen_on_signal`.
+type ReopenSink = (c_int, Weak<FileSink>);
+
+static REOPEN_SINKS: Lazy<Mutex<Vec<ReopenSink>>> = Lazy::new(Mutex::default);
+
+// The dispositions of signals before the first handler was installed for them.
+static PREVIOUS_ACTIONS: Lazy<Mutex<Vec<(c_int, libc::sigaction)>>> = Lazy::new(Mutex::default);
+
+/// Installs a handler for the given signal, reopening the given file sinks when
+/// it arrives.
+///
+/// This is the usual way for cooperating with `logrotate`, see the
+/// [documentation of `FileSink`] for an example configuration. The sinks are
+/// reopened by [`FileSink::reopen`], errors are passed to the error handlers
+/// of the sinks.
+///
+/// Unlike [`install_flush_on_signal`], the process is not terminated. Calling
+/// this function multiple times for the same signal adds more sinks to reopen,
+/// and it takes precedence over [`install_flush_on_signal`] for the signal.
+///
+/// The sinks are not kept alive by the handler, dropped sinks are skipped.
+/// Once all the sinks registered for the signal have been dropped, the signal
+/// is no longer swallowed: when it arrives, the disposition it had before the
+/// first handler of `spdlog-rs` was installed is restored, and the signal is
+/// raised again to be handled by it, e.g. terminating the process for
+/// `SIGHUP` by default. Unless [`install_flush_on_signal`] was also called
+/// for the signal, which then takes over.
+///
+/// The limitations of [`install_flush_on_signal`] apply as well, i.e. sinks
+/// are reopened on a dedicated thread, and previously installed handlers for
+/// the signal are replaced.
+///
+/// # Errors
+///
+/// Returns an error if the pipe or the thread failed to be created, or the
+/// handler failed to be installed.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::sync::Arc;
+///
+/// use spdlog::{prelude::*, sink::FileSink, Signal};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let file_sink = Arc::new(FileSink::builder().path("/var/log/my-app/app.log").build()?);
+/// spdlog::install_reopen_on_signal(Signal::Hangup, &[file_sink.clone()])?;
+///
+/// let logger = Logger::builder().sink(file_sink).build()?;
+/// # Ok(()) }
+/// ```
+///
+/// [documentation of `FileSink`]: crate::sink::FileSink#cooperating-with-logrotate
+pub fn install_reopen_on_signal(signal: Signal, sinks: &[Arc<FileSink>]) -> io::Result<()> {
+    REOPEN_SINKS.lock_expect().extend(
+        sinks
+            .iter()
+            .map(|sink| (signal.raw(), Arc::downgrade(sink))),
+    );
+    install_handler(signal)
+}
+
+fn install_handler(signal: Signal) -> io::Result<()> {
+    if let Err(err) = &*PIPE_INIT {
+        return Err(io::Error::new(err.kind(), err.to_string()));
+    }
+
+    unsafe {
+        let mut action: libc::sigaction = std::mem::zeroed();
+        action.sa_sigaction = signal_handler as extern "C" fn(c_int) as libc::sighandler_t;
+        action.sa_flags = libc::SA_RESTART;
+        libc::sigemptyset(&mut action.sa_mask);
+
+        let mut previous_actions = PREVIOUS_ACTIONS.lock_expect();
+        let mut previous_action: libc::sigaction = std::mem::zeroed();
+        if libc::sigaction(signal.raw(), &action, &mut previous_action) != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        if previous_actions
+            .iter()
+            .all(|(signum, _)| *signum != signal.raw())
+        {
+            previous_actions.push((signal.raw(), previous_action));
+        }
+    }
+    Ok(())
+}
+
+fn restore_previous_action(signum: c_int) {
+    let previous_actions = PREVIOUS_ACTIONS.lock_expect();
+    match previous_actions.iter().find(|(s, _)| *s == signum) {
+        Some((_, previous_action)) => unsafe {
+            libc::sigaction(signum, previous_action, std::ptr::null_mut());
+        },
+        None => unsafe {
+            libc::signal(signum, libc::SIG_DFL);
+        },
+    }
+}
+
+extern "C" fn signal_handler(signum: c_int) {
+    // Only async-signal-safe operations are allowed here. `write` may change
+    // `errno`, which is restored for the interrupted code.
+    let errno = errno_location();
+    let saved_errno = unsafe { *errno };
+
+    let fd = PIPE_WRITE_FD.load(Ordering::SeqCst);
+    if fd >= 0 {
+        let byte = signum as u8;
+        unsafe { libc::write(fd, &byte as *const u8 as *const c_void, 1) };
+    }
+
+    unsafe { *errno = saved_errno };
+}
+
+#[must_use]
+fn errno_location() -> *mut c_int {
+    cfg_if! {
+        if #[cfg(any(target_os = "linux", target_os = "emscripten", target_os = "redox"))] {
+            unsafe { libc::__errno_location() }
+        } else if #[cfg(any(target_os = "android", target_os = "netbsd", target_os = "openbsd"))] {
+            unsafe { libc::__errno() }
+        } else {
+            unsafe { libc::__error() }
+        }
+    }
+}
+
+fn signal_thread(read_fd: c_int) {
+    loop {
+        let mut byte = 0_u8;
+        let res = unsafe { libc::read(read_fd, &mut byte as *mut u8 as *mut c_void, 1) };
+        if res < 0 && io::Error::last_os_error().kind() == io::ErrorKind::Interrupted {
+            continue;
+        }
+        if res != 1 {
+            return;
+        }
+        let signum = byte as c_int;
+
+        let (is_reopen_signal, reopen_sinks) = {
+            let mut all_reopen_sinks = REOPEN_SINKS.lock_expect();
+            let is_reopen_signal = all_reopen_sinks
+                .iter()
+                .any(|(reopen_signum, _)| *reopen_signum == signum);
+            all_reopen_sinks.retain(|(_, sink)| sink.strong_count() > 0);
+            let reopen_sinks = all_reopen_sinks
+                .iter()
+                .filter(|(reopen_signum, _)| *reopen_signum == signum)
+                .filter_map(|(_, sink)| sink.upgrade())
+                .collect::<Vec<_>>();
+            (is_reopen_signal, reopen_sinks)
+        };
+        if !reopen_sinks.is_empty() {
+            reopen_sinks
+                .iter()
+                .for_each(|sink| sink.reopen_or_handle_error());
+            continue;
+        }
+        if is_reopen_signal && !FLUSH_SIGNALS.lock_expect().contains(&signum) {
+            // All the sinks have been dropped, handle the signal as if the
+            // handler was never installed.
+            restore_previous_action(signum);
+            unsafe { libc::raise(signum) };
+            continue;
+        }
+
+        if let Some(default_logger) = DEFAULT_LOGGER.get() {
+            default_logger.load().flush();
+        }
+
+        // Handle the signal as if the handler was never installed.
+        restore_previous_action(signum);
+        unsafe { libc::raise(signum) };
+    }
+}
@@ -0,0 +1,965 @@
+use std::{
+    cell::RefCell,
+    collections::VecDeque,
+    convert::Infallible,
+    mem, thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    error::{InvalidArgumentError, SendToChannelError, SendToChannelErrorDropped},
+    formatter::FmtExtraInfo,
+    shutdown::{register_async_worker, AsyncWorker, ShutdownStage},
+    sink::{helper, OverflowPolicy, Sink},
+    sync::*,
+    Error, Record, RecordBuf, Result, StringBuf,
+};
+
+// The number of written batches kept for reusing their buffers.
+const MAX_SPARE_BATCHES: usize = 2;
+
+thread_local! {
+    // Reused for formatting records on each thread
+    static FORMAT_BUF: RefCell<StringBuf> = RefCell::new(StringBuf::new());
+}
+
+/// A [combined sink], accumulating formatted records and writing them to the
+/// sub-sink in batches on a dedicated thread.
+///
+/// It's intended to be used with sinks sending messages over the network (e.g.
+/// [`UdpSink`]), the network I/O is taken off the logging threads, and the
+/// worker thread is woken up once per batch rather than once per record.
+///
+/// How a batch is written to the sub-sink is specified by [`write_mode`]:
+///
+///  - With [`BatchWriteMode::PerRecord`] (the default), each record is written
+///    by its own [`Sink::write_formatted`] call. This is required for
+///    message-oriented sub-sinks, e.g. [`UdpSink`] sends each call as a
+///    datagram, and a syslog or GELF collector expects one record per datagram.
+///
+///  - With [`BatchWriteMode::Concatenated`], the formatted text of the records
+///    in a batch is concatenated and written by a single
+///    [`Sink::write_formatted`] call, which greatly reduces the per-record
+///    overhead for stream-oriented sub-sinks, e.g. a TCP connection or a bulk
+///    HTTP endpoint accepting newline-delimited records.
+///
+/// Records are formatted by the formatter of this sink when they are logged,
+/// and appended to the pending batch. A batch is written when any of the
+/// following triggers is reached:
+///
+///  - it contains [`max_batch`] records;
+///  - it has been pending for [`max_delay`] since its first record;
+///  - it would exceed [`max_bytes`] bytes with the next record;
+///  - [`Sink::flush`] is called on this sink, this sink is dropped, or
+///    [`spdlog::shutdown`] is called.
+///
+/// After [`spdlog::shutdown`] is called, the worker thread is joined and
+/// records are written to the sub-sink synchronously, one batch per record.
+///
+/// With [`BatchWriteMode::Concatenated`], the record passed along with a batch
+/// to the sub-sink is the last record in the batch, so it's recommended to
+/// filter levels on this sink rather than on the sub-sink.
+///
+/// Buffers of written batches are reused for the following batches, so once
+/// warmed up, logging a record does not allocate.
+///
+/// # Memory limit
+///
+/// If the sub-sink is slower than records are logged, e.g. the network is
+/// congested, batches pile up in memory waiting to be written. To keep a slow
+/// destination from exhausting the memory of the process, limit the total size
+/// of the formatted text of records logged but not yet written with
+/// [`max_buffered_bytes`]. When a record would exceed the limit, it's handled
+/// according to [`overflow_policy`]. The limit is in bytes rather than records,
+/// so it holds regardless of how much the sizes of records vary.
+///
+/// # Note
+///
+/// If writing a batch (or a record of it, with [`BatchWriteMode::PerRecord`])
+/// fails, it will be retried for up to [`max_retries`] times. If it still
+/// fails, it is dropped and the last error is passed to the error handler of
+/// this sink.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::{sync::Arc, time::Duration};
+///
+/// use spdlog::{
+///     prelude::*,
+///     sink::{AsyncBatchSink, UdpSink},
+/// };
+///
+/// # fn main() -> Result<(), spdlog::Error> {
+/// let udp_sink = UdpSink::builder()
+///     .server_addr("127.0.0.1:514".parse().unwrap())
+///     .build()?;
+/// let sink = AsyncBatchSink::builder()
+///     .sink(Arc::new(udp_sink))
+///     .max_batch(100)
+///     .max_delay(Duration::from_millis(500))
+///     .build()?;
+/// let logger = Logger::builder().sink(Arc::new(sink)).build()?;
+///
+/// info!(logger: logger, "hello, collector");
+/// # Ok(()) }
+/// ```
+///
+/// [combined sink]: index.html#combined-sink
+/// [`UdpSink`]: crate::sink::UdpSink
+/// [`write_mode`]: AsyncBatchSinkBuilder::write_mode
+/// [`max_batch`]: AsyncBatchSinkBuilder::max_batch
+/// [`max_delay`]: AsyncBatchSinkBuilder::max_delay
+/// [`max_bytes`]: AsyncBatchSinkBuilder::max_bytes
+/// [`max_retries`]: AsyncBatchSinkBuilder::max_retries
+/// [`spdlog::shutdown`]: crate::shutdown
+/// [`max_buffered_bytes`]: AsyncBatchSinkBuilder::max_buffered_bytes
+/// [`overflow_policy`]: AsyncBatchSinkBuilder::overflow_policy
+pub struct AsyncBatchSink {
+    shared: Arc<Shared>,
+}
+
+impl AsyncBatchSink {
+    /// Constructs a builder of `AsyncBatchSink`.
+    #[must_use]
+    pub fn builder() -> AsyncBatchSinkBuilder<()> {
+        AsyncBatchSinkBuilder {
+            common_builder_impl: helper::CommonBuilderImpl::new(),
+            sink: (),
+            max_batch: 512,
+            max_delay: Duration::from_secs(1),
+            max_bytes: 64 * 1024,
+            max_retries: 2,
+            retry_interval: Duration::from_millis(100),
+            max_buffered_bytes: None,
+            overflow_policy: OverflowPolicy::Block,
+            write_mode: BatchWriteMode::PerRecord,
+        }
+    }
+
+    /// Gets a reference to the sub-sink.
+    #[must_use]
+    pub fn sink(&self) -> &Arc<dyn Sink> {
+        &self.shared.sink
+    }
+
+    /// Gets the total size in bytes of the formatted text of records logged
+    /// but not yet written to the sub-sink.
+    ///
+    /// See [the documentation of `AsyncBatchSink`](AsyncBatchSink#memory-limit)
+    /// for limiting it.
+    #[must_use]
+    pub fn buffered_bytes(&self) -> usize {
+        self.shared.buffered_bytes.load(Ordering::Relaxed)
+    }
+}
+
+impl Sink for AsyncBatchSink {
+    fn log(&self, record: &Record) -> Result<()> {
+        if !self.should_log(record.level()) {
+            return Ok(());
+        }
+
+        let format_and_push = |buf: &mut StringBuf| {
+            buf.clear();
+            self.shared
+                .common_impl
+                .formatter
+                .read()
+                .format(record, buf)?;
+            self.shared.push(record, buf)
+        };
+        FORMAT_BUF.with(|buf| match buf.try_borrow_mut() {
+            Ok(mut buf) => format_and_push(&mut buf),
+            // Reentered, e.g. the formatter logs to this sink
+            Err(_) => format_and_push(&mut StringBuf::new()),
+        })
+    }
+
+    fn write_formatted(
+        &self,
+        record: &Record,
+        formatted: &StringBuf,
+        _extra_info: &FmtExtraInfo,
+    ) -> Result<()> {
+        if !self.should_log(record.level()) {
+            return Ok(());
+        }
+
+        self.shared.push(record, formatted)
+    }
+
+    fn flush(&self) -> Result<()> {
+        self.shared.write_pending(true);
+        self.shared.sink.flush()
+    }
+
+    helper::common_impl!(@Sink: shared.common_impl);
+}
+
+impl Drop for AsyncBatchSink {
+    fn drop(&mut self) {
+        self.shared.shutdown();
+    }
+}
+
+/// How [`AsyncBatchSink`] writes a batch to the sub-sink.
+///
+/// See [the documentation of `AsyncBatchSink`](AsyncBatchSink) for details.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[non_exhaustive]
+pub enum BatchWriteMode {
+    /// Each record is written by its own [`Sink::write_formatted`] call, in
+    /// order, for message-oriented sub-sinks.
+    PerRecord,
+    /// The formatted text of the records is concatenated and written by a
+    /// single [`Sink::write_formatted`] call, for stream-oriented sub-sinks.
+    Concatenated,
+}
+
+// --------------------------------------------------
+
+struct Shared {
+    common_impl: helper::CommonImpl,
+    sink: Arc<dyn Sink>,
+    max_batch: usize,
+    max_delay: Duration,
+    max_bytes: usize,
+    max_retries: usize,
+    retry_interval: Duration,
+    max_buffered_bytes: Option<usize>,
+    overflow_policy: OverflowPolicy,
+    write_mode: BatchWriteMode,
+    // Only modified while `state` is locked, atomic for `buffered_bytes`
+    buffered_bytes: AtomicUsize,
+    state: Mutex<State>,
+    wakeup: Condvar,
+    // Notified when written batches are subtracted from `buffered_bytes`
+    drained: Condvar,
+    // Held while writing batches, to keep them in order between the worker thread
+    // and `flush`. It contains the batches being written, and is swapped with
+    // `State::sealed` to reuse the capacity of both.
+    writing: Mutex<VecDeque<Batch>>,
+    // `None` after shutdown
+    worker: Mutex<Option<thread::JoinHandle<()>>>,
+}
+
+#[derive(Default)]
+struct State {
+    // Batches that reached a trigger and are waiting to be written
+    sealed: VecDeque<Batch>,
+    current: Option<Batch>,
+    // Written batches, whose buffers are reused
+    spare: Vec<Batch>,
+    shutdown: bool,
+}
+
+// The slots of records are kept along with their buffers when the batch is
+// reused, only the first `count` ones (or the first one) are valid.
+struct Batch {
+    // One slot per record for `BatchWriteMode::PerRecord`, or a single slot of
+    // the concatenated text and the last record for `BatchWriteMode::Concatenated`
+    slots: Vec<(StringBuf, RecordBuf)>,
+    count: usize,
+    bytes: usize,
+    deadline: Instant,
+}
+
+impl Batch {
+    #[must_use]
+    fn new() -> Self {
+        Self {
+            slots: vec![],
+            count: 0,
+            bytes: 0,
+            deadline: Instant::now(),
+        }
+    }
+
+    fn push(&mut self, record: &Record, formatted: &str, write_mode: BatchWriteMode) {
+        let index = match write_mode {
+            BatchWriteMode::PerRecord => self.count,
+            BatchWriteMode::Concatenated => 0,
+        };
+        if index == self.slots.len() {
+            self.slots
+                .push((StringBuf::new(), RecordBuf::with_payload_capacity(0)));
+        }
+        let (text, last_record) = &mut self.slots[index];
+        if index == self.count {
+            text.clear();
+        }
+        text.push_str(formatted);
+        last_record.fill(record);
+        self.count += 1;
+        self.bytes += formatted.len();
+    }
+
+    #[must_use]
+    fn slots(&self) -> &[(StringBuf, RecordBuf)] {
+        &self.slots[..self.count.min(self.slots.len())]
+    }
+}
+
+impl Shared {
+    fn push(&self, record: &Record, formatted: &str) -> Result<()> {
+        let mut state = self.state.lock_expect();
+
+        if let Some(max_buffered_bytes) = self.max_buffered_bytes {
+            loop {
+                let buffered_bytes = self.buffered_bytes.load(Ordering::Relaxed);
+                // A record larger than the limit is accepted when nothing is buffered,
+                // otherwise it could never be
+                if state.shutdown
+                    || buffered_bytes == 0
+                    || buffered_bytes + formatted.len() <= max_buffered_bytes
+                {
+                    break;
+                }
+                match self.overflow_policy {
+                    OverflowPolicy::Block => {
+                        // Write the current batch without waiting for its deadline
+                        if let Some(current) = state.current.take() {
+                            state.sealed.push_back(current);
+                        }
+                        self.wakeup.notify_one();
+                        state = self.drained.wait(state).expect("lock is poisoned");
+                    }
+                    OverflowPolicy::DropIncoming => {
+                        return Err(Error::SendToChannel(
+                            SendToChannelError::Full,
+                            SendToChannelErrorDropped::Record(record.to_owned()),
+                        ))
+                    }
+                }
+            }
+        }
+
+        let exceeds_bytes = state.current.as_ref().map_or(false, |current| {
+            current.bytes + formatted.len() > self.max_bytes
+        });
+        if exceeds_bytes {
+            let current = state.current.take().unwrap();
+            state.sealed.push_back(current);
+        }
+
+        let mut notify = false;
+        if state.current.is_none() {
+            let mut batch = state.spare.pop().unwrap_or_else(Batch::new);
+            batch.deadline = Instant::now() + self.max_delay;
+            state.current = Some(batch);
+            notify = true; // The worker needs the new deadline
+        }
+        let current = state.current.as_mut().unwrap();
+        current.push(record, formatted, self.write_mode);
+        self.buffered_bytes
+            .fetch_add(formatted.len(), Ordering::Relaxed);
+
+        if current.count >= self.max_batch || current.bytes >= self.max_bytes {
+            let current = state.current.take().unwrap();
+            state.sealed.push_back(current);
+            notify = true;
+        }
+
+        let shutdown = state.shutdown;
+        drop(state);
+        if shutdown {
+            // The worker thread has exited, write it on the current thread
+            self.write_pending(true);
+        } else if notify {
+            self.wakeup.notify_one();
+        }
+        Ok(())
+    }
+
+    // Writes sealed batches, and the current batch if it's due or `force` is
+    // `true`.
+    fn write_pending(&self, force: bool) {
+        let mut batches = self.writing.lock_expect();
+
+        {
+            let mut state = self.state.lock_expect();
+            mem::swap(&mut *batches, &mut state.sealed);
+            let due = state
+                .current
+                .as_ref()
+                .map_or(false, |current| force || current.deadline <= Instant::now());
+            if due {
+                batches.extend(state.current.take());
+            }
+        }
+        if batches.is_empty() {
+            return;
+        }
+
+        for batch in batches.iter() {
+            self.write_batch(batch);
+        }
+
+        let mut state = self.state.lock_expect();
+        for mut batch in batches.drain(..) {
+            self.buffered_bytes
+                .fetch_sub(batch.bytes, Ordering::Relaxed);
+            if state.spare.len() < MAX_SPARE_BATCHES {
+                batch.count = 0;
+                batch.bytes = 0;
+                state.spare.push(batch);
+            }
+        }
+        drop(state);
+        self.drained.notify_all();
+    }
+
+    fn write_batch(&self, batch: &Batch) {
+        for (text, record) in batch.slots() {
+            self.write_with_retries(&record.as_record(), text);
+        }
+    }
+
+    fn write_with_retries(&self, record: &Record, text: &StringBuf) {
+        let extra_info = FmtExtraInfo::new();
+
+        let mut retries = 0;
+        while let Err(err) = self.sink.write_formatted(record, text, &extra_info) {
+            if retries == self.max_retries {
+                self.common_impl.non_returnable_error("AsyncBatchSink", err);
+                return;
+            }
+            retries += 1;
+            thread::sleep(self.retry_interval);
+        }
+    }
+
+    fn run(&self) {
+        loop {
+            let mut state = self.state.lock_expect();
+            loop {
+                if state.shutdown || !state.sealed.is_empty() {
+                    break;
+                }
+                let timeout = match &state.current {
+                    Some(current) => {
+                        match current.deadline.checked_duration_since(Instant::now()) {
+                            Some(timeout) if !timeout.is_zero() => Some(timeout),
+                            _ => break,
+                        }
+                    }
+                    None => None,
+                };
+                state = match timeout {
+                    Some(timeout) => {
+                        self.wakeup
+                            .wait_timeout(state, timeout)
+                            .expect("lock is poisoned")
+                            .0
+                    }
+                    None => self.wakeup.wait(state).expect("lock is poisoned"),
+                };
+            }
+            let shutdown = state.shutdown;
+            drop(state);
+
+            self.write_pending(shutdown);
+            if shutdown {
+                if let Err(err) = self.sink.flush() {
+                    self.common_impl.non_returnable_error("AsyncBatchSink", err);
+                }
+                return;
+            }
+        }
+    }
+}
+
+impl AsyncWorker for Shared {
+    fn shutdown(&self) {
+        self.state.lock_expect().shutdown = true;
+        self.wakeup.notify_one();
+        self.drained.notify_all();
+        if let Some(worker) = self.worker.lock_expect().take() {
+            worker
+                .join()
+                .expect("AsyncBatchSink: worker thread panicked");
+        }
+    }
+}
+
+/// The builder of [`AsyncBatchSink`].
+#[doc = include_str!("../../include/doc/generic-builder-note.md")]
+/// # Examples
+///
+/// - Building a [`AsyncBatchSink`].
+///
+///   ```no_run
+///   use std::{sync::Arc, time::Duration};
+///
+///   use spdlog::sink::{AsyncBatchSink, UdpSink};
+///
+///   # fn main() -> Result<(), spdlog::Error> {
+///   # let udp_sink = UdpSink::builder()
+///   #     .server_addr("127.0.0.1:514".parse().unwrap())
+///   #     .build()?;
+///   let sink: AsyncBatchSink = AsyncBatchSink::builder()
+///       .sink(Arc::new(udp_sink)) // required
+///       // .max_batch(100) // optional
+///       // .max_delay(Duration::from_millis(500)) // optional
+///       .build()?;
+///   # Ok(()) }
+///   ```
+///
+/// - If any required parameters are missing, a compile-time error will be
+///   raised.
+///
+///   ```compile_fail,E0061
+///   use std::time::Duration;
+///
+///   use spdlog::sink::AsyncBatchSink;
+///
+///   # fn main() -> Result<(), spdlog::Error> {
+///   let sink: AsyncBatchSink = AsyncBatchSink::builder()
+///       // .sink(udp_sink) // required
+///       .max_delay(Duration::from_millis(500)) // optional
+///       .build()?;
+///   # Ok(()) }
+///   ```
+pub struct AsyncBatchSinkBuilder<ArgSink> {
+    common_builder_impl: helper::CommonBuilderImpl,
+    sink: ArgSink,
+    max_batch: usize,
+    max_delay: Duration,
+    max_bytes: usize,
+    max_retries: usize,
+    retry_interval: Duration,
+    max_buffered_bytes: Option<usize>,
+    overflow_policy: OverflowPolicy,
+    write_mode: BatchWriteMode,
+}
+
+impl<ArgSink> AsyncBatchSinkBuilder<ArgSink> {
+    /// The sub-sink to write batches to.
+    ///
+    /// This parameter is **required**.
+    #[must_use]
+    pub fn sink(self, sink: Arc<dyn Sink>) -> AsyncBatchSinkBuilder<Arc<dyn Sink>> {
+        AsyncBatchSinkBuilder {
+            common_builder_impl: self.common_builder_impl,
+            sink,
+            max_batch: self.max_batch,
+            max_delay: self.max_delay,
+            max_bytes: self.max_bytes,
+            max_retries: self.max_retries,
+            retry_interval: self.retry_interval,
+            max_buffered_bytes: self.max_buffered_bytes,
+            overflow_policy: self.overflow_policy,
+            write_mode: self.write_mode,
+        }
+    }
+
+    /// Specifies the maximum number of records in a batch.
+    ///
+    /// It must be greater than 0, otherwise [`Error::InvalidArgument`] will be
+    /// returned when building.
+    ///
+    /// This parameter is **optional**, and defaults to `512`.
+    #[must_use]
+    pub fn max_batch(mut self, max_batch: usize) -> Self {
+        self.max_batch = max_batch;
+        self
+    }
+
+    /// Specifies the maximum time a batch can be pending since its first
+    /// record.
+    ///
+    /// This parameter is **optional**, and defaults to 1 second.
+    #[must_use]
+    pub fn max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Specifies the maximum size in bytes of the formatted text of a batch.
+    ///
+    /// A single record larger than this size is still written, as a batch on
+    /// its own.
+    ///
+    /// It must be greater than 0, otherwise [`Error::InvalidArgument`] will be
+    /// returned when building.
+    ///
+    /// This parameter is **optional**, and defaults to 64 KiB.
+    #[must_use]
+    pub fn max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    /// Specifies how many times writing a failed batch is retried before the
+    /// batch is dropped.
+    ///
+    /// This parameter is **optional**, and defaults to `2`.
+    #[must_use]
+    pub fn max_retries(mut self, max_retries: usize) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Specifies the interval between retries of writing a failed batch.
+    ///
+    /// This parameter is **optional**, and defaults to 100 milliseconds.
+    #[must_use]
+    pub fn retry_interval(mut self, retry_interval: Duration) -> Self {
+        self.retry_interval = retry_interval;
+        self
+    }
+
+    /// Specifies the maximum total size in bytes of the formatted text of
+    /// records logged but not yet written to the sub-sink.
+    ///
+    /// A record exceeding it is handled according to
+    /// [`AsyncBatchSinkBuilder::overflow_policy`]. A single record larger than
+    /// this size is still accepted when nothing is buffered. See [the
+    /// documentation of `AsyncBatchSink`](AsyncBatchSink#memory-limit).
+    ///
+    /// This parameter is **optional**, and defaults to no limit.
+    #[must_use]
+    pub fn max_buffered_bytes(mut self, max_buffered_bytes: usize) -> Self {
+        self.max_buffered_bytes = Some(max_buffered_bytes);
+        self
+    }
+
+    /// Specifies the policy for handling records exceeding
+    /// [`AsyncBatchSinkBuilder::max_buffered_bytes`].
+    ///
+    /// With [`OverflowPolicy::Block`], the logging thread waits until enough
+    /// buffered records are written. With [`OverflowPolicy::DropIncoming`],
+    /// the record is dropped and [`Error::SendToChannel`] with
+    /// [`SendToChannelError::Full`] is returned.
+    ///
+    /// This parameter is **optional**, and defaults to
+    /// [`OverflowPolicy::Block`].
+    #[must_use]
+    pub fn overflow_policy(mut self, overflow_policy: OverflowPolicy) -> Self {
+        self.overflow_policy = overflow_policy;
+        self
+    }
+
+    /// Specifies how a batch is written to the sub-sink.
+    ///
+    /// Use [`BatchWriteMode::Concatenated`] only if the sub-sink is
+    /// stream-oriented, see [the documentation of
+    /// `AsyncBatchSink`](AsyncBatchSink) for details.
+    ///
+    /// This parameter is **optional**, and defaults to
+    /// [`BatchWriteMode::PerRecord`].
+    #[must_use]
+    pub fn write_mode(mut self, write_mode: BatchWriteMode) -> Self {
+        self.write_mode = write_mode;
+        self
+    }
+
+    helper::common_impl!(@SinkBuilder: common_builder_impl);
+}
+
+impl AsyncBatchSinkBuilder<()> {
+    #[doc(hidden)]
+    #[deprecated(note = "\n\n\
+        builder compile-time error:\n\
+        - missing required field `sink`\n\n\
+    ")]
+    pub fn build(self, _: Infallible) {}
+}
+
+impl AsyncBatchSinkBuilder<Arc<dyn Sink>> {
+    /// Builds a [`AsyncBatchSink`].
+    ///
+    /// # Errors
+    ///
+    /// If `max_batch` or `max_bytes` is 0, [`Error::InvalidArgument`] will be
+    /// returned.
+    pub fn build(self) -> Result<AsyncBatchSink> {
+        if self.max_batch == 0 || self.max_bytes == 0 {
+            return Err(Error::InvalidArgument(InvalidArgumentError::BatchLimit(
+                format!(
+                    "max batch and max bytes must be greater than 0, but got {} and {}",
+                    self.max_batch, self.max_bytes
+                ),
+            )));
+        }
+
+        let shared = Arc::new(Shared {
+            common_impl: helper::CommonImpl::from_builder(self.common_builder_impl),
+            sink: self.sink,
+            max_batch: self.max_batch,
+            max_delay: self.max_delay,
+            max_bytes: self.max_bytes,
+            max_retries: self.max_retries,
+            retry_interval: self.retry_interval,
+            max_buffered_bytes: self.max_buffered_bytes,
+            overflow_policy: self.overflow_policy,
+            write_mode: self.write_mode,
+            buffered_bytes: AtomicUsize::new(0),
+            state: Mutex::new(State::default()),
+            wakeup: Condvar::new(),
+            drained: Condvar::new(),
+            writing: Mutex::new(VecDeque::new()),
+            worker: Mutex::new(None),
+        });
+
+        let worker = {
+            let shared = shared.clone();
+            thread::spawn(move || shared.run())
+        };
+        *shared.worker.lock_expect() = Some(worker);
+        register_async_worker(ShutdownStage::AsyncBatchSink, shared.clone());
+
+        Ok(AsyncBatchSink { shared })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{prelude::*, test_utils::*};
+
+    // Records the text of each write, fails the first `failures` writes
+    struct BatchSink {
+        batches: Mutex<Vec<String>>,
+        failures: AtomicUsize,
+    }
+
+    impl BatchSink {
+        #[must_use]
+        fn new(failures: usize) -> Self {
+            Self {
+                batches: Mutex::new(vec![]),
+                failures: AtomicUsize::new(failures),
+            }
+        }
+
+        #[must_use]
+        fn batches(&self) -> Vec<String> {
+            self.batches.lock_expect().clone()
+        }
+    }
+
+    impl Sink for BatchSink {
+        fn log(&self, _record: &Record) -> Result<()> {
+            unreachable!()
+        }
+
+        fn write_formatted(
+            &self,
+            _record: &Record,
+            formatted: &StringBuf,
+            _extra_info: &FmtExtraInfo,
+        ) -> Result<()> {
+            if self.failures.load(Ordering::SeqCst) > 0 {
+                self.failures.fetch_sub(1, Ordering::SeqCst);
+                return Err(Error::__ForInternalTestsUseOnly(0));
+            }
+            self.batches.lock_expect().push(formatted.to_string());
+            Ok(())
+        }
+
+        fn flush(&self) -> Result<()> {
+            Ok(())
+        }
+
+        fn level_filter(&self) -> LevelFilter {
+            LevelFilter::All
+        }
+
+        fn set_level_filter(&self, _level_filter: LevelFilter) {}
+
+        fn set_formatter(&self, _formatter: Box<dyn crate::formatter::Formatter>) {}
+
+        fn set_error_handler(&self, _handler: Option<crate::ErrorHandler>) {}
+    }
+
+    #[must_use]
+    fn builder(batch_sink: &Arc<BatchSink>) -> AsyncBatchSinkBuilder<Arc<dyn Sink>> {
+        AsyncBatchSink::builder()
+            .sink(batch_sink.clone())
+            .formatter(Box::new(NoModFormatter::new()))
+            .max_delay(Duration::from_secs(60))
+            .retry_interval(Duration::from_millis(1))
+            .write_mode(BatchWriteMode::Concatenated)
+    }
+
+    #[test]
+    fn per_record() {
+        let batch_sink = Arc::new(BatchSink::new(1));
+        let sink = builder(&batch_sink)
+            .write_mode(BatchWriteMode::PerRecord)
+            .max_batch(3)
+            .build()
+            .unwrap();
+
+        for payload in ["a", "b", "c", "d"] {
+            sink.log(&Record::new(Level::Info, payload)).unwrap();
+        }
+        sink.flush().unwrap();
+        // The failed write of the first record is retried on its own
+        assert_eq!(batch_sink.batches(), ["a", "b", "c", "d"]);
+
+        // Slots of reused batches are overwritten
+        sink.log(&Record::new(Level::Info, "e")).unwrap();
+        sink.flush().unwrap();
+        assert_eq!(batch_sink.batches(), ["a", "b", "c", "d", "e"]);
+    }
+
+    #[test]
+    fn max_batch() {
+        let batch_sink = Arc::new(BatchSink::new(0));
+        let sink = builder(&batch_sink).max_batch(3).build().unwrap();
+        let logger = build_test_logger(|b| b.sink(Arc::new(sink)));
+
+        for payload in ["a", "b", "c", "d"] {
+            info!(logger: logger, "{}", payload);
+        }
+        thread::sleep(Duration::from_millis(100));
+        assert_eq!(batch_sink.batches(), ["abc"]);
+    }
+
+    #[test]
+    fn max_delay() {
+        let batch_sink = Arc::new(BatchSink::new(0));
+        let sink = builder(&batch_sink)
+            .max_delay(Duration::from_millis(200))
+            .build()
+            .unwrap();
+        let logger = build_test_logger(|b| b.sink(Arc::new(sink)));
+
+        info!(logger: logger, "a");
+        info!(logger: logger, "b");
+        thread::sleep(Duration::from_millis(50));
+        assert!(batch_sink.batches().is_empty());
+
+        thread::sleep(Duration::from_millis(400));
+        assert_eq!(batch_sink.batches(), ["ab"]);
+    }
+
+    #[test]
+    fn max_bytes() {
+        let batch_sink = Arc::new(BatchSink::new(0));
+        let sink = builder(&batch_sink).max_bytes(5).build().unwrap();
+        let logger = build_test_logger(|b| b.sink(Arc::new(sink)));
+
+        info!(logger: logger, "aa");
+        info!(logger: logger, "bb");
+        // Exceeds with the pending batch, which is written without it
+        info!(logger: logger, "cc");
+        // Reaches the limit by itself
+        info!(logger: logger, "dddddd");
+        info!(logger: logger, "e");
+        thread::sleep(Duration::from_millis(100));
+        assert_eq!(batch_sink.batches(), ["aabb", "cc", "dddddd"]);
+    }
+
+    #[test]
+    fn flush_and_drop() {
+        let batch_sink = Arc::new(BatchSink::new(0));
+        let sink = Arc::new(builder(&batch_sink).build().unwrap());
+
+        sink.log(&Record::new(Level::Info, "a")).unwrap();
+        sink.flush().unwrap();
+        assert_eq!(batch_sink.batches(), ["a"]);
+
+        sink.log(&Record::new(Level::Info, "b")).unwrap();
+        sink.log(&Record::new(Level::Info, "c")).unwrap();
+        drop(sink);
+        assert_eq!(batch_sink.batches(), ["a", "bc"]);
+    }
+
+    #[test]
+    fn retry() {
+        static ERROR_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+        let batch_sink = Arc::new(BatchSink::new(2));
+        let sink = builder(&batch_sink).max_retries(2).build().unwrap();
+        sink.log(&Record::new(Level::Info, "a")).unwrap();
+        sink.flush().unwrap();
+        assert_eq!(batch_sink.batches(), ["a"]);
+
+        let batch_sink = Arc::new(BatchSink::new(2));
+        let sink = builder(&batch_sink)
+            .max_retries(1)
+            .error_handler(|err| {
+                assert!(matches!(err, Error::__ForInternalTestsUseOnly(0)));
+                ERROR_COUNT.fetch_add(1, Ordering::SeqCst);
+            })
+            .build()
+            .unwrap();
+        sink.log(&Record::new(Level::Info, "a")).unwrap();
+        sink.flush().unwrap();
+        assert!(batch_sink.batches().is_empty());
+        assert_eq!(ERROR_COUNT.load(Ordering::SeqCst), 1);
+
+        // The sink still works after a dropped batch
+        sink.log(&Record::new(Level::Info, "b")).unwrap();
+        sink.flush().unwrap();
+        assert_eq!(batch_sink.batches(), ["b"]);
+    }
+
+    #[test]
+    fn max_buffered_bytes() {
+        // The first batch fails once, and stays buffered until it's retried
+        let batch_sink = Arc::new(BatchSink::new(1));
+        let sink = builder(&batch_sink)
+            .max_batch(1)
+            .retry_interval(Duration::from_millis(300))
+            .max_buffered_bytes(10)
+            .overflow_policy(OverflowPolicy::DropIncoming)
+            .build()
+            .unwrap();
+
+        let log = |payload: &str| sink.log(&Record::new(Level::Info, payload));
+        log("aaaa").unwrap();
+        log("bbbb").unwrap();
+        assert_eq!(sink.buffered_bytes(), 8);
+        for payload in ["cccc", &"x".repeat(20)] {
+            assert!(matches!(
+                log(payload),
+                Err(Error::SendToChannel(
+                    SendToChannelError::Full,
+                    SendToChannelErrorDropped::Record(_)
+                ))
+            ));
+        }
+        assert_eq!(sink.buffered_bytes(), 8);
+
+        thread::sleep(Duration::from_millis(600));
+        assert_eq!(sink.buffered_bytes(), 0);
+        // Accepted when nothing is buffered
+        log(&"x".repeat(20)).unwrap();
+        sink.flush().unwrap();
+        assert_eq!(batch_sink.batches(), ["aaaa", "bbbb", &"x".repeat(20)]);
+        assert_eq!(sink.buffered_bytes(), 0);
+
+        // Blocks until the first batch is written
+        let batch_sink = Arc::new(BatchSink::new(1));
+        let sink = builder(&batch_sink)
+            .retry_interval(Duration::from_millis(300))
+            .max_buffered_bytes(10)
+            .build()
+            .unwrap();
+        sink.log(&Record::new(Level::Info, "aaaa")).unwrap();
+        sink.log(&Record::new(Level::Info, "bbbb")).unwrap();
+        let start = Instant::now();
+        sink.log(&Record::new(Level::Info, "cccc")).unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(200));
+        sink.flush().unwrap();
+        assert_eq!(batch_sink.batches(), ["aaaabbbb", "cccc"]);
+    }
+
+    #[test]
+    fn invalid_limits() {
+        let batch_sink = Arc::new(BatchSink::new(0));
+        assert!(matches!(
+            builder(&batch_sink).max_batch(0).build(),
+            Err(Error::InvalidArgument(InvalidArgumentError::BatchLimit(_)))
+        ));
+        assert!(matches!(
+            builder(&batch_sink).max_bytes(0).build(),
+            Err(Error::InvalidArgument(InvalidArgumentError::BatchLimit(_)))
+        ));
+    }
+}
@@ -1,34 +1,75 @@
+use crossbeam::queue::ArrayQueue;
+
 use crate::{
     default_error_handler, default_thread_pool,
     formatter::Formatter,
-    sink::{helper, OverflowPolicy, Sink, Sinks},
+    shutdown::{register_async_worker, AsyncWorker, ShutdownStage},
+    sink::{helper, Completion, CompletionNotifier, OverflowPolicy, Sink, Sinks},
     sync::*,
-    Error, ErrorHandler, LevelFilter, Record, RecordOwned, Result, ThreadPool,
+    Error, ErrorHandler, LevelFilter, Record, RecordBuf, Result, ThreadPool,
 };
 
+// The minimum number of record buffers kept for reuse.
+const DEFAULT_RECORD_POOL_CAPACITY: usize = 64;
+
+// The payload capacity of each preallocated record buffer.
+const PREALLOCATED_PAYLOAD_CAPACITY: usize = 256;
+
 /// A [combined sink], logging and flushing [asynchronously]
 /// (thread-pool-based).
 ///
 /// This sink sends `log` and `flush` operations to the inside thread pool for
 /// asynchronous processing.
 ///
+/// Sub-sinks that [prefer synchronous calls] are not called asynchronously,
+/// they are called on the thread calling `log` and `flush` of this sink.
+///
 /// # Note
 ///
 /// Errors that occur in `log` and `flush` will not be returned directly,
 /// instead the error handler will be called.
 ///
+/// # Shutdown
+///
+/// [`spdlog::shutdown`] processes the remaining queued operations of the
+/// thread pool and then flushes sub-sinks. After that, operations are
+/// processed synchronously on the calling thread.
+///
+/// # Allocations
+///
+/// Records are copied into buffers taken from a pool of this sink, and the
+/// buffers are returned to the pool after the records are processed. Once the
+/// pool is warmed up, logging a record does not allocate, unless the record is
+/// larger than any buffer previously used. The pool can be preallocated with
+/// [`AsyncPoolSinkBuilder::records_per_flush`].
+///
+/// # Durability
+///
+/// Records queued or written to buffered sub-sinks but not yet flushed are lost
+/// if the process crashes. Besides flushing by the logger (see
+/// [`Logger::flush_level_filter`] and [`Logger::set_flush_period`]), this sink
+/// can flush its sub-sinks every N records, see
+/// [`AsyncPoolSinkBuilder::flush_every_records`].
+///
 /// # Examples
 ///
 /// See [./examples] directory.
 ///
 /// [combined sink]: index.html#combined-sink
 /// [asynchronously]: index.html#asynchronous-combined-sink
+/// [prefer synchronous calls]: Sink::prefers_sync
+/// [`spdlog::shutdown`]: crate::shutdown
 /// [./examples]: https://github.com/SpriteOvO/spdlog-rs/tree/main/examples
+/// [`Logger::flush_level_filter`]: crate::Logger::flush_level_filter
+/// [`Logger::set_flush_period`]: crate::Logger::set_flush_period
 // The names `AsyncSink` and `AsyncRuntimeSink` is reserved for future use.
 pub struct AsyncPoolSink {
     level_filter: Atomic<LevelFilter>,
     overflow_policy: OverflowPolicy,
     thread_pool: Arc<ThreadPool>,
+    sinks: Sinks,
+    // Sub-sinks preferring synchronous calls, the others are in `backend`
+    sync_sinks: Sinks,
     backend: Arc<Backend>,
 }
 
@@ -41,6 +82,8 @@ impl AsyncPoolSink {
             overflow_policy: OverflowPolicy::Block,
             sinks: Sinks::new(),
             thread_pool: None,
+            records_per_flush: None,
+            flush_every_records: 0,
             error_handler: None,
         }
     }
@@ -48,7 +91,17 @@ impl AsyncPoolSink {
     /// Gets a reference to internal sinks in the combined sink.
     #[must_use]
     pub fn sinks(&self) -> &[Arc<dyn Sink>] {
-        &self.backend.sinks
+        &self.sinks
+    }
+
+    /// Gets a reference to the thread pool used by the sink.
+    ///
+    /// It can be used to query the capacity and the current length of the
+    /// operation queue, see [`ThreadPool::capacity`] and
+    /// [`ThreadPool::queue_len`].
+    #[must_use]
+    pub fn thread_pool(&self) -> &Arc<ThreadPool> {
+        &self.thread_pool
     }
 
     /// Sets a error handler.
@@ -56,6 +109,73 @@ impl AsyncPoolSink {
         self.backend.error_handler.swap(handler, Ordering::Relaxed);
     }
 
+    /// Logs a record asynchronously, and returns a token that completes when
+    /// the record has been written to all sub-sinks and they have been
+    /// flushed.
+    ///
+    /// This function returns as soon as the operation is sent to the thread
+    /// pool, just like [`Sink::log`]. Waiting for the returned [`Completion`]
+    /// is optional, e.g. `.await` it only for the records whose durability
+    /// matters. Dropping it does not cancel the operation.
+    ///
+    /// If the record is filtered out by the level filter of this sink, the
+    /// returned token is already completed.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Sink::log`], in which case the record is
+    /// not logged and there is no token.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spdlog::{sink::AsyncPoolSink, Record};
+    ///
+    /// // In an async context, e.g. a custom sink or an adapter forwarding records.
+    /// async fn log_durably(sink: &AsyncPoolSink, record: &Record<'_>) -> spdlog::Result<()> {
+    ///     sink.log_with_completion(record)?.await;
+    ///     Ok(())
+    /// }
+    ///
+    /// // Or block on it in a synchronous context.
+    /// fn log_durably_blocking(sink: &AsyncPoolSink, record: &Record) -> spdlog::Result<()> {
+    ///     sink.log_with_completion(record)?.wait();
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn log_with_completion(&self, record: &Record) -> Result<Completion> {
+        if !self.should_log(record.level()) {
+            return Ok(Completion::completed());
+        }
+
+        self.log_sync(record);
+        self.flush_sync();
+
+        let (completion, notifier) = Completion::new();
+        self.assign_task(Task::Log {
+            backend: self.clone_backend(),
+            record: self.backend.fill_record_buf(record),
+            completion: Some(notifier),
+        })?;
+        Ok(completion)
+    }
+
+    fn log_sync(&self, record: &Record) {
+        for sink in &self.sync_sinks {
+            if let Err(err) = sink.log(record) {
+                self.backend.handle_error(err);
+            }
+        }
+    }
+
+    fn flush_sync(&self) {
+        for sink in &self.sync_sinks {
+            if let Err(err) = sink.flush() {
+                self.backend.handle_error(err);
+            }
+        }
+    }
+
     fn assign_task(&self, task: Task) -> Result<()> {
         self.thread_pool.assign_task(task, self.overflow_policy)
     }
@@ -69,15 +189,18 @@ impl AsyncPoolSink {
 impl Sink for AsyncPoolSink {
     fn log(&self, record: &Record) -> Result<()> {
         if self.should_log(record.level()) {
+            self.log_sync(record);
             self.assign_task(Task::Log {
                 backend: self.clone_backend(),
-                record: record.to_owned(),
+                record: self.backend.fill_record_buf(record),
+                completion: None,
             })?;
         }
         Ok(())
     }
 
     fn flush(&self) -> Result<()> {
+        self.flush_sync();
         self.assign_task(Task::Flush {
             backend: self.clone_backend(),
         })
@@ -86,11 +209,35 @@ impl Sink for AsyncPoolSink {
     /// For [`AsyncPoolSink`], the function performs the same call to all
     /// internal sinks.
     fn set_formatter(&self, formatter: Box<dyn Formatter>) {
-        for sink in &self.backend.sinks {
+        for sink in &self.sinks {
             sink.set_formatter(formatter.clone_box())
         }
     }
 
+    fn prefers_sync(&self) -> bool {
+        !self.sync_sinks.is_empty()
+    }
+
+    fn clone_box(&self) -> Option<Box<dyn Sink>> {
+        let backend = Arc::new(Backend::new(
+            self.backend.sinks.clone(),
+            self.backend.error_handler.load(Ordering::Relaxed),
+            self.backend.record_pool.capacity(),
+            0,
+            self.backend.flush_every_records,
+        ));
+        register_async_worker(ShutdownStage::AsyncPoolSink, backend.clone());
+
+        Some(Box::new(AsyncPoolSink {
+            level_filter: Atomic::new(self.level_filter.load(Ordering::Relaxed)),
+            overflow_policy: self.overflow_policy,
+            thread_pool: self.thread_pool.clone(),
+            sinks: self.sinks.clone(),
+            sync_sinks: self.sync_sinks.clone(),
+            backend,
+        }))
+    }
+
     helper::common_impl! {
         @SinkCustom {
             level_filter: level_filter,
@@ -106,6 +253,8 @@ pub struct AsyncPoolSinkBuilder {
     sinks: Sinks,
     overflow_policy: OverflowPolicy,
     thread_pool: Option<Arc<ThreadPool>>,
+    records_per_flush: Option<usize>,
+    flush_every_records: usize,
     error_handler: Option<ErrorHandler>,
 }
 
@@ -139,6 +288,47 @@ impl AsyncPoolSinkBuilder {
         self
     }
 
+    /// Specifies the expected number of records logged between two flushes,
+    /// i.e. the number of records that may be queued at once.
+    ///
+    /// Buffers for that many records are preallocated when building, and they
+    /// are reused for the following records, so that the first burst of
+    /// records does not allocate. More records can still be queued, with
+    /// buffers allocated on demand.
+    ///
+    /// Regardless of this parameter, at least 64 buffers are kept for reuse.
+    ///
+    /// This parameter is **optional**, and defaults to `0`, i.e. buffers are
+    /// allocated on demand.
+    #[must_use]
+    pub fn records_per_flush(mut self, records_per_flush: usize) -> Self {
+        self.records_per_flush = Some(records_per_flush);
+        self
+    }
+
+    /// Specifies the number of records after which the asynchronously called
+    /// sub-sinks are flushed, regardless of time.
+    ///
+    /// The worker thread flushes the sub-sinks after processing every
+    /// `flush_every_records` records, bounding the number of records lost if
+    /// the process crashes. It's a tradeoff between durability and throughput:
+    /// a smaller number loses fewer records, while flushing more often, which
+    /// is expensive for sinks like [`FileSink`].
+    ///
+    /// It complements flushing by the logger, e.g. by level or periodically.
+    /// Sub-sinks that [prefer synchronous calls] are not affected.
+    ///
+    /// This parameter is **optional**, and defaults to `0`, i.e. sub-sinks are
+    /// not flushed by the number of records.
+    ///
+    /// [`FileSink`]: crate::sink::FileSink
+    /// [prefer synchronous calls]: Sink::prefers_sync
+    #[must_use]
+    pub fn flush_every_records(mut self, flush_every_records: usize) -> Self {
+        self.flush_every_records = flush_every_records;
+        self
+    }
+
     /// Specifies a custom thread pool.
     ///
     /// This parameter is **optional**, and defaults to the built-in thread
@@ -153,10 +343,20 @@ impl AsyncPoolSinkBuilder {
 
     /// Builds a [`AsyncPoolSink`].
     pub fn build(self) -> Result<AsyncPoolSink> {
-        let backend = Arc::new(Backend {
-            sinks: self.sinks.clone(),
-            error_handler: Atomic::new(self.error_handler),
-        });
+        let (sync_sinks, async_sinks) = self
+            .sinks
+            .iter()
+            .cloned()
+            .partition(|sink| sink.prefers_sync());
+        let preallocated = self.records_per_flush.unwrap_or(0);
+        let backend = Arc::new(Backend::new(
+            async_sinks,
+            self.error_handler,
+            preallocated.max(DEFAULT_RECORD_POOL_CAPACITY),
+            preallocated,
+            self.flush_every_records,
+        ));
+        register_async_worker(ShutdownStage::AsyncPoolSink, backend.clone());
 
         let thread_pool = self.thread_pool.unwrap_or_else(default_thread_pool);
 
@@ -164,6 +364,8 @@ impl AsyncPoolSinkBuilder {
             level_filter: Atomic::new(self.level_filter),
             overflow_policy: self.overflow_policy,
             thread_pool,
+            sinks: self.sinks,
+            sync_sinks,
             backend,
         })
     }
@@ -178,9 +380,54 @@ impl AsyncPoolSinkBuilder {
 pub(crate) struct Backend {
     sinks: Sinks,
     error_handler: helper::SinkErrorHandler,
+    // Buffers of processed records, reused for the following records
+    record_pool: ArrayQueue<RecordBuf>,
+    // 0 if sinks are not flushed by the number of records
+    flush_every_records: usize,
+    logged_count: AtomicUsize,
 }
 
 impl Backend {
+    #[must_use]
+    fn new(
+        sinks: Sinks,
+        error_handler: Option<ErrorHandler>,
+        pool_capacity: usize,
+        preallocated: usize,
+        flush_every_records: usize,
+    ) -> Self {
+        let record_pool = ArrayQueue::new(pool_capacity);
+        for _ in 0..preallocated {
+            let _ = record_pool.push(RecordBuf::with_payload_capacity(
+                PREALLOCATED_PAYLOAD_CAPACITY,
+            ));
+        }
+        Self {
+            sinks,
+            error_handler: Atomic::new(error_handler),
+            record_pool,
+            flush_every_records,
+            logged_count: AtomicUsize::new(0),
+        }
+    }
+
+    // The context of the logging thread is captured here, as the record is
+    // processed on another thread.
+    #[must_use]
+    fn fill_record_buf(&self, record: &Record) -> RecordBuf {
+        let mut buf = self
+            .record_pool
+            .pop()
+            .unwrap_or_else(|| RecordBuf::with_payload_capacity(0));
+        record.with_context(&[], |record| buf.fill(record));
+        buf
+    }
+
+    fn recycle_record_buf(&self, buf: RecordBuf) {
+        // Dropped if the pool is full
+        let _ = self.record_pool.push(buf);
+    }
+
     fn log(&self, record: &Record) {
         for sink in &self.sinks {
             if let Err(err) = sink.log(record) {
@@ -189,6 +436,15 @@ impl Backend {
         }
     }
 
+    // Counts a logged record, returns `true` if sinks should be flushed by the
+    // number of records.
+    #[must_use]
+    fn count_logged(&self) -> bool {
+        self.flush_every_records != 0
+            && (self.logged_count.fetch_add(1, Ordering::Relaxed) + 1) % self.flush_every_records
+                == 0
+    }
+
     fn flush(&self) {
         for sink in &self.sinks {
             if let Err(err) = sink.flush() {
@@ -204,10 +460,18 @@ impl Backend {
     }
 }
 
+impl AsyncWorker for Backend {
+    fn shutdown(&self) {
+        self.flush();
+    }
+}
+
 pub(crate) enum Task {
     Log {
         backend: Arc<Backend>,
-        record: RecordOwned,
+        record: RecordBuf,
+        // The record is flushed before the notifier is dropped if it's present.
+        completion: Option<CompletionNotifier>,
     },
     Flush {
         backend: Arc<Backend>,
@@ -218,8 +482,16 @@ impl Task {
     // calls this function in async threads
     pub(crate) fn exec(self) {
         match self {
-            Task::Log { backend, record } => {
-                backend.log(&record.as_ref());
+            Task::Log {
+                backend,
+                record,
+                completion,
+            } => {
+                backend.log(&record.as_record());
+                if backend.count_logged() || completion.is_some() {
+                    backend.flush();
+                }
+                backend.recycle_record_buf(record);
             }
             Task::Flush { backend } => {
                 backend.flush();
@@ -230,10 +502,13 @@ impl Task {
 
 #[cfg(test)]
 mod tests {
-    use std::{thread::sleep, time::Duration};
+    use std::{
+        thread::{self, sleep},
+        time::Duration,
+    };
 
     use super::*;
-    use crate::{prelude::*, test_utils::*};
+    use crate::{error::SendToChannelError, prelude::*, sink::TeeSink, test_utils::*};
 
     #[test]
     fn default_thread_pool() {
@@ -318,6 +593,177 @@ mod tests {
         assert_eq!(counter_sink.flush_count(), 1);
     }
 
+    #[test]
+    fn flush_every_records() {
+        const N: usize = 100;
+
+        let counter_sink = Arc::new(CounterSink::new());
+        let thread_pool = Arc::new(ThreadPool::builder().build().unwrap());
+        let logger = build_test_logger(|b| {
+            b.sink(Arc::new(
+                AsyncPoolSink::builder()
+                    .sink(counter_sink.clone())
+                    .thread_pool(thread_pool)
+                    .flush_every_records(N)
+                    .build()
+                    .unwrap(),
+            ))
+            .level_filter(LevelFilter::All)
+        });
+
+        for i in 0..N * 2 {
+            info!(logger: logger, "{}", i);
+        }
+        sleep(Duration::from_millis(200));
+        assert_eq!(counter_sink.log_count(), N * 2);
+        assert_eq!(counter_sink.flush_count(), 2);
+
+        info!(logger: logger, "not flushed yet");
+        sleep(Duration::from_millis(50));
+        assert_eq!(counter_sink.log_count(), N * 2 + 1);
+        assert_eq!(counter_sink.flush_count(), 2);
+    }
+
+    #[test]
+    fn capture_context_on_logging_thread() {
+        let counter_sink = Arc::new(CounterSink::new());
+        let thread_pool = Arc::new(
+            ThreadPool::builder()
+                .on_thread_spawn(|| {
+                    crate::mdc::insert("thread", "worker");
+                })
+                .build()
+                .unwrap(),
+        );
+        let async_sink = Arc::new(
+            AsyncPoolSink::builder()
+                .sink(counter_sink.clone())
+                .thread_pool(thread_pool)
+                .build()
+                .unwrap(),
+        );
+        let logger = build_test_logger(|b| b.sink(async_sink.clone()));
+
+        thread::spawn(move || {
+            let _thread = crate::mdc::scoped("thread", "A");
+            info!(logger: logger, "via logger");
+            // Records logged to the sink directly are captured when queued
+            async_sink.log(&Record::new(Level::Info, "direct")).unwrap();
+        })
+        .join()
+        .unwrap();
+        sleep(Duration::from_millis(50));
+
+        let records = counter_sink.records();
+        assert_eq!(records.len(), 2);
+        for record in &records {
+            assert_eq!(record.key_values().collect::<Vec<_>>(), [("thread", "A")]);
+            assert_eq!(record.mdc_key_values().count(), 1);
+        }
+
+        // Forwarding a captured record on another thread does not capture the
+        // context of that thread, even if the captured one is empty
+        let empty = Arc::new(CounterSink::new());
+        let empty_logger = build_test_logger(|b| b.sink(empty.clone()));
+        info!(logger: empty_logger, "empty context");
+        let forward_sink = Arc::new(CounterSink::new());
+        let forward_logger = build_test_logger(|b| b.sink(forward_sink.clone()));
+        thread::spawn(move || {
+            let _thread = crate::mdc::scoped("thread", "B");
+            forward_logger.log(&records[0].as_ref());
+            forward_logger.log(&empty.records()[0].as_ref());
+        })
+        .join()
+        .unwrap();
+
+        let forwarded = forward_sink.records();
+        assert_eq!(
+            forwarded[0].key_values().collect::<Vec<_>>(),
+            [("thread", "A")]
+        );
+        assert_eq!(forwarded[1].key_values().count(), 0);
+    }
+
+    #[test]
+    fn overflow_with_capacity_1() {
+        static WORKER_STARTED: AtomicBool = AtomicBool::new(false);
+
+        let counter_sink = Arc::new(CounterSink::new());
+        let (release_tx, release_rx) = std::sync::mpsc::channel::<()>();
+        let release_rx = Mutex::new(release_rx);
+        let thread_pool = Arc::new(
+            ThreadPool::builder()
+                .capacity(1)
+                // Keep the worker busy, so operations stay in the queue
+                .on_thread_spawn(move || {
+                    WORKER_STARTED.store(true, Ordering::SeqCst);
+                    release_rx.lock_expect().recv().unwrap();
+                })
+                .build()
+                .unwrap(),
+        );
+        while !WORKER_STARTED.load(Ordering::SeqCst) {
+            sleep(Duration::from_millis(1));
+        }
+
+        let sink = AsyncPoolSink::builder()
+            .sink(counter_sink.clone())
+            .thread_pool(thread_pool)
+            .overflow_policy(OverflowPolicy::DropIncoming)
+            .build()
+            .unwrap();
+        assert_eq!(sink.thread_pool().capacity(), Some(1));
+
+        sink.log(&Record::new(Level::Info, "first")).unwrap();
+        assert_eq!(sink.thread_pool().queue_len(), 1);
+        assert!(matches!(
+            sink.log(&Record::new(Level::Info, "second")),
+            Err(Error::SendToChannel(SendToChannelError::Full, _))
+        ));
+
+        release_tx.send(()).unwrap();
+        // Dropping the thread pool waits for the remaining operations
+        drop(sink);
+        assert_eq!(counter_sink.payloads(), vec!["first".to_string()]);
+    }
+
+    #[test]
+    fn log_with_completion() {
+        let counter_sink = Arc::new(CounterSink::with_delay(Some(Duration::from_millis(200))));
+        let thread_pool = Arc::new(ThreadPool::builder().build().unwrap());
+        let async_sink = AsyncPoolSink::builder()
+            .sink(counter_sink.clone())
+            .thread_pool(thread_pool)
+            .level_filter(LevelFilter::MoreSevereEqual(Level::Warn))
+            .build()
+            .unwrap();
+
+        let completion = async_sink
+            .log_with_completion(&Record::new(Level::Warn, "meow"))
+            .unwrap();
+        assert!(!completion.is_completed());
+        assert_eq!(counter_sink.log_count(), 0);
+
+        completion.wait();
+        assert_eq!(counter_sink.log_count(), 1);
+        assert_eq!(counter_sink.flush_count(), 1);
+
+        // Dropping the token does not cancel the operation.
+        drop(
+            async_sink
+                .log_with_completion(&Record::new(Level::Error, "nya"))
+                .unwrap(),
+        );
+        sleep(Duration::from_millis(750));
+        assert_eq!(counter_sink.log_count(), 2);
+
+        let filtered = async_sink
+            .log_with_completion(&Record::new(Level::Info, "filtered"))
+            .unwrap();
+        assert!(filtered.is_completed());
+        assert_eq!(counter_sink.log_count(), 2);
+    }
+
     #[test]
     fn async_opeartions() {
         let counter_sink = Arc::new(CounterSink::with_delay(Some(Duration::from_secs(1))));
@@ -358,4 +804,81 @@ mod tests {
         assert_eq!(counter_sink.log_count(), 2);
         assert_eq!(counter_sink.flush_count(), 1);
     }
+
+    struct ThreadIdSink {
+        prefers_sync: bool,
+        thread_ids: Mutex<Vec<thread::ThreadId>>,
+    }
+
+    impl ThreadIdSink {
+        fn new(prefers_sync: bool) -> Self {
+            Self {
+                prefers_sync,
+                thread_ids: Mutex::new(vec![]),
+            }
+        }
+    }
+
+    impl Sink for ThreadIdSink {
+        fn log(&self, _record: &Record) -> Result<()> {
+            self.thread_ids.lock_expect().push(thread::current().id());
+            Ok(())
+        }
+
+        fn flush(&self) -> Result<()> {
+            Ok(())
+        }
+
+        fn prefers_sync(&self) -> bool {
+            self.prefers_sync
+        }
+
+        fn level_filter(&self) -> LevelFilter {
+            LevelFilter::All
+        }
+
+        fn set_level_filter(&self, _level_filter: LevelFilter) {
+            unimplemented!("no-op")
+        }
+
+        fn set_formatter(&self, _formatter: Box<dyn Formatter>) {
+            unimplemented!("no-op")
+        }
+
+        fn set_error_handler(&self, _handler: Option<ErrorHandler>) {
+            unimplemented!("no-op")
+        }
+    }
+
+    #[test]
+    fn prefers_sync() {
+        let sync_sink = Arc::new(ThreadIdSink::new(true));
+        let async_sink = Arc::new(ThreadIdSink::new(false));
+        let thread_pool = Arc::new(ThreadPool::builder().build().unwrap());
+        let pool_sink = AsyncPoolSink::builder()
+            .sink(sync_sink.clone())
+            .sink(async_sink.clone())
+            .thread_pool(thread_pool)
+            .build()
+            .unwrap();
+        assert!(pool_sink.prefers_sync());
+        assert_eq!(pool_sink.sinks().len(), 2);
+
+        pool_sink.log(&Record::new(Level::Info, "meow")).unwrap();
+        // Logged before `log` returns
+        assert_eq!(
+            *sync_sink.thread_ids.lock_expect(),
+            [thread::current().id()]
+        );
+
+        sleep(Duration::from_millis(250));
+        let async_thread_ids = async_sink.thread_ids.lock_expect();
+        assert_eq!(async_thread_ids.len(), 1);
+        assert_ne!(async_thread_ids[0], thread::current().id());
+
+        let tee_sink = TeeSink::builder().sink(async_sink.clone()).build().unwrap();
+        assert!(!tee_sink.prefers_sync());
+        let tee_sink = TeeSink::builder().sink(sync_sink).build().unwrap();
+        assert!(tee_sink.prefers_sync());
+    }
 }
@@ -0,0 +1,146 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll, Waker},
+    time::Duration,
+};
+
+use crate::sync::*;
+
+/// A token that completes when an asynchronous operation has been performed.
+///
+/// It's returned by [`AsyncPoolSink::log_with_completion`]. Waiting for it is
+/// optional, dropping it does not cancel the operation.
+///
+/// The token can be waited in a blocking way with [`Completion::wait`] and
+/// [`Completion::wait_timeout`], or be `.await`ed, as it implements
+/// [`Future`].
+///
+/// # Note
+///
+/// The token also completes if the operation is discarded without being
+/// performed, e.g. the thread pool is dropped before it is processed. Errors
+/// that occur in performing the operation are not reported by the token,
+/// instead the error handler of the sink will be called as usual.
+///
+/// [`AsyncPoolSink::log_with_completion`]: crate::sink::AsyncPoolSink::log_with_completion
+#[must_use = "dropping the token does not cancel the operation, but there is no way to wait for it"]
+pub struct Completion {
+    state: Arc<CompletionState>,
+}
+
+// Completes the associated `Completion` when dropped.
+pub(crate) struct CompletionNotifier {
+    state: Arc<CompletionState>,
+}
+
+struct CompletionState {
+    inner: Mutex<CompletionInner>,
+    condvar: Condvar,
+}
+
+#[derive(Default)]
+struct CompletionInner {
+    completed: bool,
+    waker: Option<Waker>,
+}
+
+impl Completion {
+    pub(crate) fn new() -> (Self, CompletionNotifier) {
+        let state = Arc::new(CompletionState {
+            inner: Mutex::new(CompletionInner::default()),
+            condvar: Condvar::new(),
+        });
+        let notifier = CompletionNotifier {
+            state: Arc::clone(&state),
+        };
+        (Self { state }, notifier)
+    }
+
+    pub(crate) fn completed() -> Self {
+        let (completion, notifier) = Self::new();
+        drop(notifier);
+        completion
+    }
+
+    /// Checks whether the operation has been performed, without blocking.
+    #[must_use]
+    pub fn is_completed(&self) -> bool {
+        self.state.inner.lock_expect().completed
+    }
+
+    /// Blocks the current thread until the operation has been performed.
+    pub fn wait(&self) {
+        let mut inner = self.state.inner.lock_expect();
+        while !inner.completed {
+            inner = self.state.condvar.wait(inner).expect("lock is poisoned");
+        }
+    }
+
+    /// Blocks the current thread until the operation has been performed, or the
+    /// timeout elapses.
+    ///
+    /// Returns `true` if the operation has been performed.
+    #[must_use]
+    pub fn wait_timeout(&self, timeout: Duration) -> bool {
+        let inner = self.state.inner.lock_expect();
+        let (inner, _) = self
+            .state
+            .condvar
+            .wait_timeout_while(inner, timeout, |inner| !inner.completed)
+            .expect("lock is poisoned");
+        inner.completed
+    }
+}
+
+impl Future for Completion {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut inner = self.state.inner.lock_expect();
+        if inner.completed {
+            Poll::Ready(())
+        } else {
+            inner.waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+impl Drop for CompletionNotifier {
+    fn drop(&mut self) {
+        let waker = {
+            let mut inner = self.state.inner.lock_expect();
+            inner.completed = true;
+            inner.waker.take()
+        };
+        self.state.condvar.notify_all();
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use super::*;
+
+    #[test]
+    fn notify() {
+        let (completion, notifier) = Completion::new();
+        assert!(!completion.is_completed());
+        assert!(!completion.wait_timeout(Duration::from_millis(10)));
+
+        let handle = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            drop(notifier);
+        });
+        completion.wait();
+        assert!(completion.is_completed());
+        handle.join().unwrap();
+
+        assert!(Completion::completed().is_completed());
+    }
+}
@@ -1,11 +1,16 @@
+mod async_batch_sink;
 mod async_pool_sink;
+mod completion;
 
+pub use async_batch_sink::*;
 pub use async_pool_sink::*;
+pub use completion::*;
 
 /// Overflow policy for [asynchronous sinks].
 ///
 /// When the channel is full, an incoming operation is handled according to the
-/// specified policy.
+/// specified policy. It's also used by [`AsyncBatchSink`] when its buffered
+/// records reach [`AsyncBatchSinkBuilder::max_buffered_bytes`].
 ///
 /// [asynchronous sinks]: index.html#asynchronous-combined-sink
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
@@ -0,0 +1,363 @@
+//! Provides a sink that sends formatted log messages over a channel.
+
+use std::{
+    convert::Infallible,
+    sync::mpsc::{self, SendError, TrySendError},
+};
+
+use crate::{
+    error::{SendToChannelError, SendToChannelErrorDropped},
+    formatter::FmtExtraInfo,
+    sink::{helper, OverflowPolicy, Sink},
+    sync::*,
+    Error, Record, Result, StringBuf,
+};
+
+/// A sink that formats each record and sends the formatted `String` over a
+/// [`std::sync::mpsc`] channel.
+///
+/// It's useful for forwarding log messages to another part of the program,
+/// e.g. showing them in a UI or asserting them in tests.
+///
+/// If the channel is bounded (the sender is a [`SyncSender`]) and full, the
+/// message is handled according to [`ChannelSinkBuilder::overflow_policy`].
+///
+/// If the receiver has been dropped, the first failed message is reported by
+/// returning [`Error::SendToChannel`] with
+/// [`SendToChannelError::Disconnected`], which is passed to the error handler
+/// when logging via a [`Logger`]. Later messages are silently discarded.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::{mpsc, Arc};
+///
+/// use spdlog::{prelude::*, sink::ChannelSink};
+///
+/// # fn main() -> Result<(), spdlog::Error> {
+/// let (sender, receiver) = mpsc::channel();
+/// let sink = ChannelSink::builder().sender(sender).build()?;
+/// let logger = Logger::builder().sink(Arc::new(sink)).build()?;
+///
+/// info!(logger: logger, "hello, channel");
+/// assert!(receiver.recv().unwrap().contains("hello, channel"));
+/// # Ok(()) }
+/// ```
+///
+/// [`SyncSender`]: std::sync::mpsc::SyncSender
+/// [`SendToChannelError::Disconnected`]: crate::error::SendToChannelError::Disconnected
+/// [`Logger`]: crate::Logger
+pub struct ChannelSink {
+    common_impl: helper::CommonImpl,
+    sender: Sender,
+    overflow_policy: OverflowPolicy,
+    disconnected: AtomicBool,
+}
+
+impl ChannelSink {
+    /// Constructs a builder of `ChannelSink`.
+    #[must_use]
+    pub fn builder() -> ChannelSinkBuilder<()> {
+        ChannelSinkBuilder {
+            common_builder_impl: helper::CommonBuilderImpl::new(),
+            sender: (),
+            overflow_policy: OverflowPolicy::Block,
+        }
+    }
+
+    fn send(&self, record: &Record, message: String) -> Result<()> {
+        let error = match &self.sender {
+            Sender::Unbounded(sender) => match sender.lock_expect().send(message) {
+                Ok(()) => return Ok(()),
+                Err(SendError(_)) => SendToChannelError::Disconnected,
+            },
+            Sender::Bounded(sender) => {
+                let result = match self.overflow_policy {
+                    OverflowPolicy::Block => sender
+                        .send(message)
+                        .map_err(|SendError(_)| SendToChannelError::Disconnected),
+                    OverflowPolicy::DropIncoming => {
+                        sender.try_send(message).map_err(|err| match err {
+                            TrySendError::Full(_) => SendToChannelError::Full,
+                            TrySendError::Disconnected(_) => SendToChannelError::Disconnected,
+                        })
+                    }
+                };
+                match result {
+                    Ok(()) => return Ok(()),
+                    Err(err) => err,
+                }
+            }
+        };
+
+        // Only the first failure caused by disconnection is reported
+        if let SendToChannelError::Disconnected = error {
+            if self.disconnected.swap(true, Ordering::Relaxed) {
+                return Ok(());
+            }
+        }
+        Err(Error::SendToChannel(
+            error,
+            SendToChannelErrorDropped::Record(record.to_owned()),
+        ))
+    }
+}
+
+impl Sink for ChannelSink {
+    fn log(&self, record: &Record) -> Result<()> {
+        if !self.should_log(record.level()) || self.disconnected.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+
+        let mut string_buf = StringBuf::new();
+        self.common_impl
+            .formatter
+            .read()
+            .format(record, &mut string_buf)?;
+
+        self.send(record, string_buf.to_string())
+    }
+
+    fn write_formatted(
+        &self,
+        record: &Record,
+        formatted: &StringBuf,
+        _extra_info: &FmtExtraInfo,
+    ) -> Result<()> {
+        if !self.should_log(record.level()) || self.disconnected.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+
+        self.send(record, formatted.to_string())
+    }
+
+    fn flush(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn clone_box(&self) -> Option<Box<dyn Sink>> {
+        let sender = match &self.sender {
+            Sender::Unbounded(sender) => {
+                Sender::Unbounded(Mutex::new(sender.lock_expect().clone()))
+            }
+            Sender::Bounded(sender) => Sender::Bounded(sender.clone()),
+        };
+        Some(Box::new(ChannelSink {
+            common_impl: self.common_impl.clone_config(),
+            sender,
+            overflow_policy: self.overflow_policy,
+            disconnected: AtomicBool::new(self.disconnected.load(Ordering::Relaxed)),
+        }))
+    }
+
+    helper::common_impl!(@Sink: common_impl);
+}
+
+// --------------------------------------------------
+
+// `mpsc::Sender` is not `Sync` before Rust 1.72, so it's guarded by a mutex.
+enum Sender {
+    Unbounded(Mutex<mpsc::Sender<String>>),
+    Bounded(mpsc::SyncSender<String>),
+}
+
+/// The sending half of a channel accepted by [`ChannelSinkBuilder::sender`].
+///
+/// It's converted from either an [`mpsc::Sender<String>`] of an unbounded
+/// channel or an [`mpsc::SyncSender<String>`] of a bounded channel.
+///
+/// [`mpsc::Sender<String>`]: std::sync::mpsc::Sender
+/// [`mpsc::SyncSender<String>`]: std::sync::mpsc::SyncSender
+pub struct ChannelSender(Sender);
+
+impl From<mpsc::Sender<String>> for ChannelSender {
+    fn from(sender: mpsc::Sender<String>) -> Self {
+        Self(Sender::Unbounded(Mutex::new(sender)))
+    }
+}
+
+impl From<mpsc::SyncSender<String>> for ChannelSender {
+    fn from(sender: mpsc::SyncSender<String>) -> Self {
+        Self(Sender::Bounded(sender))
+    }
+}
+
+/// The builder of [`ChannelSink`].
+#[doc = include_str!("../include/doc/generic-builder-note.md")]
+/// # Examples
+///
+/// - Building a [`ChannelSink`].
+///
+///   ```
+///   use std::sync::mpsc;
+///
+///   use spdlog::sink::{ChannelSink, OverflowPolicy};
+///
+///   # fn main() -> Result<(), spdlog::Error> {
+///   let (sender, receiver) = mpsc::sync_channel(1024);
+///   let sink: ChannelSink = ChannelSink::builder()
+///       .sender(sender) // required
+///       // .overflow_policy(OverflowPolicy::DropIncoming) // optional
+///       .build()?;
+///   # Ok(()) }
+///   ```
+///
+/// - If any required parameters are missing, a compile-time error will be
+///   raised.
+///
+///   ```compile_fail,E0061
+///   use spdlog::sink::{ChannelSink, OverflowPolicy};
+///
+///   # fn main() -> Result<(), spdlog::Error> {
+///   let sink: ChannelSink = ChannelSink::builder()
+///       // .sender(sender) // required
+///       .overflow_policy(OverflowPolicy::DropIncoming) // optional
+///       .build()?;
+///   # Ok(()) }
+///   ```
+pub struct ChannelSinkBuilder<ArgSender> {
+    common_builder_impl: helper::CommonBuilderImpl,
+    sender: ArgSender,
+    overflow_policy: OverflowPolicy,
+}
+
+impl<ArgSender> ChannelSinkBuilder<ArgSender> {
+    /// The sender of the channel to send formatted log messages to.
+    ///
+    /// Both [`mpsc::Sender<String>`] and [`mpsc::SyncSender<String>`] are
+    /// accepted.
+    ///
+    /// This parameter is **required**.
+    ///
+    /// [`mpsc::Sender<String>`]: std::sync::mpsc::Sender
+    /// [`mpsc::SyncSender<String>`]: std::sync::mpsc::SyncSender
+    #[must_use]
+    pub fn sender<S>(self, sender: S) -> ChannelSinkBuilder<ChannelSender>
+    where
+        S: Into<ChannelSender>,
+    {
+        ChannelSinkBuilder {
+            common_builder_impl: self.common_builder_impl,
+            sender: sender.into(),
+            overflow_policy: self.overflow_policy,
+        }
+    }
+
+    /// Specifies how messages are handled when a bounded channel is full.
+    ///
+    /// With [`OverflowPolicy::DropIncoming`], the message is dropped and
+    /// [`Error::SendToChannel`] with [`SendToChannelError::Full`] is returned.
+    /// It has no effect on unbounded channels.
+    ///
+    /// This parameter is **optional**, and defaults to
+    /// [`OverflowPolicy::Block`].
+    ///
+    /// [`SendToChannelError::Full`]: crate::error::SendToChannelError::Full
+    #[must_use]
+    pub fn overflow_policy(mut self, overflow_policy: OverflowPolicy) -> Self {
+        self.overflow_policy = overflow_policy;
+        self
+    }
+
+    helper::common_impl!(@SinkBuilder: common_builder_impl);
+}
+
+impl ChannelSinkBuilder<()> {
+    #[doc(hidden)]
+    #[deprecated(note = "\n\n\
+        builder compile-time error:\n\
+        - missing required field `sender`\n\n\
+    ")]
+    pub fn build(self, _: Infallible) {}
+}
+
+impl ChannelSinkBuilder<ChannelSender> {
+    /// Builds a [`ChannelSink`].
+    ///
+    /// # Errors
+    ///
+    /// This function will not return an error for now, the return type is
+    /// reserved for future changes.
+    pub fn build(self) -> Result<ChannelSink> {
+        Ok(ChannelSink {
+            common_impl: helper::CommonImpl::from_builder(self.common_builder_impl),
+            sender: self.sender.0,
+            overflow_policy: self.overflow_policy,
+            disconnected: AtomicBool::new(false),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{prelude::*, test_utils::*};
+
+    #[test]
+    fn send() {
+        let (sender, receiver) = mpsc::channel();
+        let sink = ChannelSink::builder()
+            .sender(sender)
+            .formatter(Box::new(NoModFormatter::new()))
+            .build()
+            .unwrap();
+        let logger = build_test_logger(|b| b.sink(Arc::new(sink)));
+
+        info!(logger: logger, "hello");
+        warn!(logger: logger, "world");
+        assert_eq!(receiver.try_iter().collect::<Vec<_>>(), ["hello", "world"]);
+    }
+
+    #[test]
+    fn overflow_policy() {
+        let (sender, receiver) = mpsc::sync_channel(1);
+        let sink = ChannelSink::builder()
+            .sender(sender)
+            .formatter(Box::new(NoModFormatter::new()))
+            .overflow_policy(OverflowPolicy::DropIncoming)
+            .build()
+            .unwrap();
+
+        sink.log(&Record::new(Level::Info, "first")).unwrap();
+        assert!(matches!(
+            sink.log(&Record::new(Level::Info, "second")),
+            Err(Error::SendToChannel(
+                SendToChannelError::Full,
+                SendToChannelErrorDropped::Record(record)
+            )) if record.payload() == "second"
+        ));
+        assert_eq!(receiver.recv().unwrap(), "first");
+
+        sink.log(&Record::new(Level::Info, "third")).unwrap();
+        assert_eq!(receiver.recv().unwrap(), "third");
+    }
+
+    #[test]
+    fn disconnected() {
+        static ERROR_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+        let (sender, receiver) = mpsc::sync_channel(1);
+        let sink = ChannelSink::builder()
+            .sender(sender)
+            .formatter(Box::new(NoModFormatter::new()))
+            .build()
+            .unwrap();
+        let logger = build_test_logger(|b| {
+            b.sink(Arc::new(sink)).error_handler(|err| {
+                assert!(matches!(
+                    err,
+                    Error::Sink(err) if matches!(
+                        err.error(),
+                        Error::SendToChannel(SendToChannelError::Disconnected, _)
+                    )
+                ));
+                ERROR_COUNT.fetch_add(1, Ordering::SeqCst);
+            })
+        });
+        drop(receiver);
+
+        info!(logger: logger, "hello");
+        info!(logger: logger, "world");
+        assert_eq!(ERROR_COUNT.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,483 @@
+use std::{
+    convert::Infallible,
+    fmt,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    error::InvalidArgumentError,
+    formatter::FmtExtraInfo,
+    sink::{helper, Sink},
+    sync::*,
+    Error, Record, Result, StringBuf,
+};
+
+/// The state of the circuit of a [`CircuitBreakerSink`].
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum CircuitState {
+    /// Records are written to the sub-sink as usual.
+    Closed,
+    /// Records are dropped without being written to the sub-sink, until the
+    /// cooldown period elapses.
+    Open,
+    /// The cooldown period has elapsed, and a single record is being written
+    /// to the sub-sink as a probe, while the others are dropped.
+    HalfOpen,
+}
+
+impl fmt::Display for CircuitState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Self::Closed => "closed",
+            Self::Open => "open",
+            Self::HalfOpen => "half-open",
+        })
+    }
+}
+
+/// A [combined sink], protecting the logging path from a sub-sink whose target
+/// is persistently down, e.g. a network sink.
+///
+/// Without it, every record waits for the sub-sink to fail, e.g. for a
+/// connection timeout, which adds latency to every log call while the target
+/// is down. `CircuitBreakerSink` stops writing to the sub-sink for a while once
+/// it keeps failing:
+///
+/// - The circuit is initially **closed**, records are written to the sub-sink
+///   and its errors are returned as usual.
+///
+/// - After [`failure_threshold`] consecutive failures of writing records, the
+///   circuit **opens**. Records are dropped immediately, without touching the
+///   sub-sink, for the [`cooldown`] period.
+///
+/// - After the cooldown period, the circuit is **half-open**. The next record
+///   is written to the sub-sink as a probe, and the records arriving during the
+///   probe are dropped. If the probe succeeds, the circuit closes, otherwise it
+///   opens again for another cooldown period.
+///
+/// Dropped records are not reported as errors, as that would flood the error
+/// handler while the target is down. Instead, state transitions are reported
+/// to the error handler of this sink as [`Error::CircuitStateChanged`], and
+/// dropped records are counted, see [`CircuitBreakerSink::dropped_count`].
+///
+/// Flushing is forwarded to the sub-sink only if the circuit is closed, and
+/// its result does not affect the circuit. Clones of this sink share the
+/// circuit, as they share the sub-sink.
+///
+/// # Examples
+///
+/// ```
+/// use std::{sync::Arc, time::Duration};
+///
+/// use spdlog::{
+///     prelude::*,
+///     sink::{CircuitBreakerSink, UdpSink},
+///     ErrorChain,
+/// };
+///
+/// # fn main() -> Result<(), spdlog::Error> {
+/// let udp_sink = Arc::new(
+///     UdpSink::builder()
+///         .server_addr("127.0.0.1:12201".parse().unwrap())
+///         .build()?,
+/// );
+/// let sink = Arc::new(
+///     CircuitBreakerSink::builder()
+///         .sink(udp_sink)
+///         .failure_threshold(3)
+///         .cooldown(Duration::from_secs(10))
+///         .error_handler(|err| eprintln!("log server: {}", ErrorChain::new(&err)))
+///         .build()?,
+/// );
+/// let logger = Logger::builder().sink(sink).build()?;
+///
+/// info!(logger: logger, "hello");
+/// # Ok(()) }
+/// ```
+///
+/// [combined sink]: index.html#combined-sink
+/// [`failure_threshold`]: CircuitBreakerSinkBuilder::failure_threshold
+/// [`cooldown`]: CircuitBreakerSinkBuilder::cooldown
+pub struct CircuitBreakerSink {
+    common_impl: helper::CommonImpl,
+    sink: Arc<dyn Sink>,
+    failure_threshold: u32,
+    cooldown: Duration,
+    // Shared with clones
+    breaker: Arc<Mutex<Breaker>>,
+    dropped_count: Arc<Atomic<u64>>,
+}
+
+struct Breaker {
+    state: CircuitState,
+    consecutive_failures: u32,
+    opened_at: Instant,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+enum Admission {
+    Write,
+    Probe,
+    Drop,
+}
+
+impl CircuitBreakerSink {
+    /// Constructs a builder of `CircuitBreakerSink`.
+    #[must_use]
+    pub fn builder() -> CircuitBreakerSinkBuilder<()> {
+        CircuitBreakerSinkBuilder {
+            common_builder_impl: helper::CommonBuilderImpl::new(),
+            sink: (),
+            failure_threshold: 5,
+            cooldown: Duration::from_secs(30),
+        }
+    }
+
+    /// Gets a reference to the sub-sink.
+    #[must_use]
+    pub fn sink(&self) -> &Arc<dyn Sink> {
+        &self.sink
+    }
+
+    /// Gets the current state of the circuit.
+    #[must_use]
+    pub fn state(&self) -> CircuitState {
+        self.breaker.lock_expect().state
+    }
+
+    /// Gets the number of records dropped while the circuit was not closed.
+    #[must_use]
+    pub fn dropped_count(&self) -> u64 {
+        self.dropped_count.load(Ordering::Relaxed)
+    }
+
+    fn guard(&self, write: impl FnOnce() -> Result<()>) -> Result<()> {
+        let (admission, transition) = self.admit();
+        self.report(transition);
+        if admission == Admission::Drop {
+            self.dropped_count.fetch_add(1, Ordering::Relaxed);
+            return Ok(());
+        }
+
+        let result = write();
+        self.report(self.settle(admission, result.is_ok()));
+        result
+    }
+
+    #[must_use]
+    fn admit(&self) -> (Admission, Option<CircuitState>) {
+        let mut breaker = self.breaker.lock_expect();
+        match breaker.state {
+            CircuitState::Closed => (Admission::Write, None),
+            CircuitState::Open if breaker.opened_at.elapsed() >= self.cooldown => {
+                breaker.state = CircuitState::HalfOpen;
+                (Admission::Probe, Some(CircuitState::HalfOpen))
+            }
+            CircuitState::Open | CircuitState::HalfOpen => (Admission::Drop, None),
+        }
+    }
+
+    #[must_use]
+    fn settle(&self, admission: Admission, succeeded: bool) -> Option<CircuitState> {
+        let mut breaker = self.breaker.lock_expect();
+        if succeeded {
+            breaker.consecutive_failures = 0;
+            if admission == Admission::Probe {
+                breaker.state = CircuitState::Closed;
+                return Some(CircuitState::Closed);
+            }
+            return None;
+        }
+
+        breaker.consecutive_failures = breaker.consecutive_failures.saturating_add(1);
+        let open = match admission {
+            Admission::Probe => true,
+            // Another thread may have opened the circuit concurrently
+            _ => {
+                breaker.state == CircuitState::Closed
+                    && breaker.consecutive_failures >= self.failure_threshold
+            }
+        };
+        if !open {
+            return None;
+        }
+        breaker.state = CircuitState::Open;
+        breaker.opened_at = Instant::now();
+        Some(CircuitState::Open)
+    }
+
+    // Called without holding the lock, as the error handler may log to a
+    // logger containing this sink.
+    fn report(&self, transition: Option<CircuitState>) {
+        if let Some(state) = transition {
+            self.common_impl
+                .non_returnable_error("CircuitBreakerSink", Error::CircuitStateChanged(state));
+        }
+    }
+}
+
+impl Sink for CircuitBreakerSink {
+    fn log(&self, record: &Record) -> Result<()> {
+        if !self.should_log(record.level()) {
+            return Ok(());
+        }
+
+        self.guard(|| self.sink.log(record))
+    }
+
+    fn write_formatted(
+        &self,
+        record: &Record,
+        formatted: &StringBuf,
+        extra_info: &FmtExtraInfo,
+    ) -> Result<()> {
+        if !self.should_log(record.level()) {
+            return Ok(());
+        }
+
+        self.guard(|| self.sink.write_formatted(record, formatted, extra_info))
+    }
+
+    fn flush(&self) -> Result<()> {
+        if self.state() != CircuitState::Closed {
+            return Ok(());
+        }
+        self.sink.flush()
+    }
+
+    fn prefers_sync(&self) -> bool {
+        self.sink.prefers_sync()
+    }
+
+    fn clone_box(&self) -> Option<Box<dyn Sink>> {
+        Some(Box::new(CircuitBreakerSink {
+            common_impl: self.common_impl.clone_config(),
+            sink: self.sink.clone(),
+            failure_threshold: self.failure_threshold,
+            cooldown: self.cooldown,
+            breaker: self.breaker.clone(),
+            dropped_count: self.dropped_count.clone(),
+        }))
+    }
+
+    helper::common_impl!(@Sink: common_impl);
+}
+
+/// The builder of [`CircuitBreakerSink`].
+#[doc = include_str!("../include/doc/generic-builder-note.md")]
+/// # Examples
+///
+/// - Building a [`CircuitBreakerSink`].
+///
+///   ```
+///   use std::{sync::Arc, time::Duration};
+///
+///   use spdlog::sink::{CircuitBreakerSink, UdpSink};
+///
+///   # fn main() -> Result<(), spdlog::Error> {
+///   # let udp_sink = Arc::new(
+///   #     UdpSink::builder()
+///   #         .server_addr("127.0.0.1:12201".parse().unwrap())
+///   #         .build()?,
+///   # );
+///   let sink: CircuitBreakerSink = CircuitBreakerSink::builder()
+///       .sink(udp_sink) // required
+///       .failure_threshold(3) // optional, defaults to `5`
+///       .cooldown(Duration::from_secs(10)) // optional, defaults to 30 seconds
+///       .build()?;
+///   # Ok(()) }
+///   ```
+///
+/// - If any required parameters are missing, a compile-time error will be
+///   raised.
+///
+///   ```compile_fail,E0061
+///   use spdlog::sink::CircuitBreakerSink;
+///
+///   # fn main() -> Result<(), spdlog::Error> {
+///   let sink: CircuitBreakerSink = CircuitBreakerSink::builder()
+///       // .sink(udp_sink) // required
+///       .failure_threshold(3) // optional, defaults to `5`
+///       .build()?;
+///   # Ok(()) }
+///   ```
+pub struct CircuitBreakerSinkBuilder<ArgSink> {
+    common_builder_impl: helper::CommonBuilderImpl,
+    sink: ArgSink,
+    failure_threshold: u32,
+    cooldown: Duration,
+}
+
+impl<ArgSink> CircuitBreakerSinkBuilder<ArgSink> {
+    /// The sub-sink to protect.
+    ///
+    /// This parameter is **required**.
+    #[must_use]
+    pub fn sink(self, sink: Arc<dyn Sink>) -> CircuitBreakerSinkBuilder<Arc<dyn Sink>> {
+        CircuitBreakerSinkBuilder {
+            common_builder_impl: self.common_builder_impl,
+            sink,
+            failure_threshold: self.failure_threshold,
+            cooldown: self.cooldown,
+        }
+    }
+
+    /// Specifies the number of consecutive failures of writing records that
+    /// opens the circuit.
+    ///
+    /// It must be greater than `0`.
+    ///
+    /// This parameter is **optional**, and defaults to `5`.
+    #[must_use]
+    pub fn failure_threshold(mut self, failure_threshold: u32) -> Self {
+        self.failure_threshold = failure_threshold;
+        self
+    }
+
+    /// Specifies how long the circuit stays open before a probe record is
+    /// written to the sub-sink.
+    ///
+    /// This parameter is **optional**, and defaults to 30 seconds.
+    #[must_use]
+    pub fn cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = cooldown;
+        self
+    }
+
+    helper::common_impl!(@SinkBuilder: common_builder_impl);
+}
+
+impl CircuitBreakerSinkBuilder<()> {
+    #[doc(hidden)]
+    #[deprecated(note = "\n\n\
+        builder compile-time error:\n\
+        - missing required field `sink`\n\n\
+    ")]
+    pub fn build(self, _: Infallible) {}
+}
+
+impl CircuitBreakerSinkBuilder<Arc<dyn Sink>> {
+    /// Builds a [`CircuitBreakerSink`].
+    ///
+    /// # Errors
+    ///
+    /// If the failure threshold is `0`, [`Error::InvalidArgument`] will be
+    /// returned.
+    pub fn build(self) -> Result<CircuitBreakerSink> {
+        if self.failure_threshold == 0 {
+            return Err(InvalidArgumentError::FailureThreshold(
+                "must be greater than 0".to_string(),
+            )
+            .into());
+        }
+
+        Ok(CircuitBreakerSink {
+            common_impl: helper::CommonImpl::from_builder(self.common_builder_impl),
+            sink: self.sink,
+            failure_threshold: self.failure_threshold,
+            cooldown: self.cooldown,
+            breaker: Arc::new(Mutex::new(Breaker {
+                state: CircuitState::Closed,
+                consecutive_failures: 0,
+                opened_at: Instant::now(),
+            })),
+            dropped_count: Arc::new(Atomic::new(0)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use super::*;
+    use crate::{prelude::*, test_utils::*};
+
+    static TRANSITIONS: Lazy<Mutex<Vec<CircuitState>>> = Lazy::new(Mutex::default);
+
+    #[test]
+    fn state_machine() {
+        let failing_sink = Arc::new(FailingSink::new());
+        let sink = CircuitBreakerSink::builder()
+            .sink(failing_sink.clone())
+            .failure_threshold(3)
+            .cooldown(Duration::from_millis(200))
+            .error_handler(|err| match err {
+                Error::CircuitStateChanged(state) => TRANSITIONS.lock_expect().push(state),
+                err => panic!("unexpected error: {}", err),
+            })
+            .build()
+            .unwrap();
+        let log = || sink.log(&Record::new(Level::Info, "record"));
+        let transitions = || TRANSITIONS.lock_expect().clone();
+
+        // Failures below the threshold are returned, and reset by a success
+        failing_sink.set_failing(true);
+        assert!(log().is_err());
+        assert!(log().is_err());
+        failing_sink.set_failing(false);
+        log().unwrap();
+        failing_sink.set_failing(true);
+        assert!(log().is_err());
+        assert!(log().is_err());
+        assert_eq!(sink.state(), CircuitState::Closed);
+
+        // Opens at the threshold, then fast-fails
+        assert!(log().is_err());
+        assert_eq!(sink.state(), CircuitState::Open);
+        assert_eq!(transitions(), [CircuitState::Open]);
+        log().unwrap();
+        log().unwrap();
+        assert_eq!(failing_sink.attempts(), 6);
+        assert_eq!(sink.dropped_count(), 2);
+
+        // A failed probe opens it again
+        thread::sleep(Duration::from_millis(300));
+        assert!(log().is_err());
+        assert_eq!(sink.state(), CircuitState::Open);
+        assert_eq!(
+            transitions(),
+            [
+                CircuitState::Open,
+                CircuitState::HalfOpen,
+                CircuitState::Open
+            ]
+        );
+        log().unwrap();
+        assert_eq!(failing_sink.attempts(), 7);
+
+        // A successful probe closes it
+        failing_sink.set_failing(false);
+        thread::sleep(Duration::from_millis(300));
+        log().unwrap();
+        assert_eq!(sink.state(), CircuitState::Closed);
+        assert_eq!(
+            transitions(),
+            [
+                CircuitState::Open,
+                CircuitState::HalfOpen,
+                CircuitState::Open,
+                CircuitState::HalfOpen,
+                CircuitState::Closed
+            ]
+        );
+        log().unwrap();
+        assert_eq!(failing_sink.written(), 3);
+        assert_eq!(sink.dropped_count(), 3);
+    }
+
+    #[test]
+    fn zero_threshold() {
+        let result = CircuitBreakerSink::builder()
+            .sink(Arc::new(FailingSink::new()))
+            .failure_threshold(0)
+            .build();
+        assert!(matches!(
+            result,
+            Err(Error::InvalidArgument(
+                InvalidArgumentError::FailureThreshold(_)
+            ))
+        ));
+    }
+}
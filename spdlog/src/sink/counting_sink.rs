@@ -0,0 +1,297 @@
+use std::{convert::Infallible, sync::Arc};
+
+use crate::{
+    formatter::FmtExtraInfo,
+    sink::{helper, Sink},
+    sync::*,
+    Record, Result, StringBuf,
+};
+
+/// A [combined sink], counting the records written and the errors encountered
+/// by the sub-sink.
+///
+/// It's intended for health checks, e.g. an endpoint reporting whether a sink
+/// is actually receiving traffic and writing it successfully. Counting is a
+/// few atomic additions per record, and sinks not wrapped in a `CountingSink`
+/// pay nothing for it.
+///
+/// A record is counted as written if the sub-sink returns `Ok` for it. Errors
+/// returned by the sub-sink for writing records and flushing are counted as
+/// errors, and then returned to the caller as usual. Records filtered out by
+/// the level filter of this sink are not counted at all.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// use spdlog::{
+///     prelude::*,
+///     sink::{CountingSink, FileSink},
+/// };
+///
+/// # fn main() -> Result<(), spdlog::Error> {
+/// # let path = std::env::temp_dir().join("counting_sink_example.log");
+/// let file_sink = FileSink::builder().path(path).build()?;
+/// let sink = Arc::new(CountingSink::builder().sink(Arc::new(file_sink)).build()?);
+/// let logger = Logger::builder().sink(sink.clone()).build()?;
+///
+/// info!(logger: logger, "hello");
+///
+/// let counts = sink.counts();
+/// println!("{} records, {} errors", counts.records(), counts.errors());
+/// # Ok(()) }
+/// ```
+///
+/// [combined sink]: index.html#combined-sink
+pub struct CountingSink {
+    common_impl: helper::CommonImpl,
+    sink: Arc<dyn Sink>,
+    // Shared with clones
+    counters: Arc<Counters>,
+}
+
+/// The counts of a [`CountingSink`].
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub struct SinkCounts {
+    records: u64,
+    errors: u64,
+}
+
+#[derive(Default)]
+struct Counters {
+    records: Atomic<u64>,
+    errors: Atomic<u64>,
+}
+
+impl CountingSink {
+    /// Constructs a builder of `CountingSink`.
+    #[must_use]
+    pub fn builder() -> CountingSinkBuilder<()> {
+        CountingSinkBuilder {
+            common_builder_impl: helper::CommonBuilderImpl::new(),
+            sink: (),
+        }
+    }
+
+    /// Gets a reference to the sub-sink.
+    #[must_use]
+    pub fn sink(&self) -> &Arc<dyn Sink> {
+        &self.sink
+    }
+
+    /// Gets the counts so far.
+    ///
+    /// The counts are shared with the clones of this sink.
+    #[must_use]
+    pub fn counts(&self) -> SinkCounts {
+        SinkCounts {
+            records: self.counters.records.load(Ordering::Relaxed),
+            errors: self.counters.errors.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Resets the counts to zero, returning the counts before resetting.
+    ///
+    /// The two counts are reset separately, so a record written concurrently
+    /// may be counted in either the returned counts or the following ones,
+    /// but never in both or neither.
+    pub fn reset_counts(&self) -> SinkCounts {
+        SinkCounts {
+            records: self.counters.records.swap(0, Ordering::Relaxed),
+            errors: self.counters.errors.swap(0, Ordering::Relaxed),
+        }
+    }
+
+    fn count(&self, result: Result<()>) -> Result<()> {
+        let counter = match result {
+            Ok(()) => &self.counters.records,
+            Err(_) => &self.counters.errors,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        result
+    }
+}
+
+impl SinkCounts {
+    /// Gets the number of records written successfully by the sub-sink.
+    #[must_use]
+    pub fn records(&self) -> u64 {
+        self.records
+    }
+
+    /// Gets the number of errors returned by the sub-sink, for writing records
+    /// and flushing.
+    #[must_use]
+    pub fn errors(&self) -> u64 {
+        self.errors
+    }
+}
+
+impl Sink for CountingSink {
+    fn log(&self, record: &Record) -> Result<()> {
+        if !self.should_log(record.level()) {
+            return Ok(());
+        }
+
+        self.count(self.sink.log(record))
+    }
+
+    fn write_formatted(
+        &self,
+        record: &Record,
+        formatted: &StringBuf,
+        extra_info: &FmtExtraInfo,
+    ) -> Result<()> {
+        if !self.should_log(record.level()) {
+            return Ok(());
+        }
+
+        self.count(self.sink.write_formatted(record, formatted, extra_info))
+    }
+
+    fn flush(&self) -> Result<()> {
+        let result = self.sink.flush();
+        if result.is_err() {
+            self.counters.errors.fetch_add(1, Ordering::Relaxed);
+        }
+        result
+    }
+
+    fn prefers_sync(&self) -> bool {
+        self.sink.prefers_sync()
+    }
+
+    fn clone_box(&self) -> Option<Box<dyn Sink>> {
+        Some(Box::new(CountingSink {
+            common_impl: self.common_impl.clone_config(),
+            sink: self.sink.clone(),
+            counters: self.counters.clone(),
+        }))
+    }
+
+    helper::common_impl!(@Sink: common_impl);
+}
+
+/// The builder of [`CountingSink`].
+#[doc = include_str!("../include/doc/generic-builder-note.md")]
+/// # Examples
+///
+/// - Building a [`CountingSink`].
+///
+///   ```
+///   use std::sync::Arc;
+///
+///   use spdlog::sink::{CountingSink, FileSink};
+///
+///   # fn main() -> Result<(), spdlog::Error> {
+///   # let path = std::env::temp_dir().join("counting_sink_builder.log");
+///   # let file_sink = FileSink::builder().path(path).build()?;
+///   let sink: CountingSink = CountingSink::builder()
+///       .sink(Arc::new(file_sink)) // required
+///       .build()?;
+///   # Ok(()) }
+///   ```
+///
+/// - If any required parameters are missing, a compile-time error will be
+///   raised.
+///
+///   ```compile_fail,E0061
+///   use spdlog::sink::CountingSink;
+///
+///   # fn main() -> Result<(), spdlog::Error> {
+///   let sink: CountingSink = CountingSink::builder()
+///       // .sink(file_sink) // required
+///       .build()?;
+///   # Ok(()) }
+///   ```
+pub struct CountingSinkBuilder<ArgSink> {
+    common_builder_impl: helper::CommonBuilderImpl,
+    sink: ArgSink,
+}
+
+impl<ArgSink> CountingSinkBuilder<ArgSink> {
+    /// The sub-sink to count the records and errors of.
+    ///
+    /// This parameter is **required**.
+    #[must_use]
+    pub fn sink(self, sink: Arc<dyn Sink>) -> CountingSinkBuilder<Arc<dyn Sink>> {
+        CountingSinkBuilder {
+            common_builder_impl: self.common_builder_impl,
+            sink,
+        }
+    }
+
+    helper::common_impl!(@SinkBuilder: common_builder_impl);
+}
+
+impl CountingSinkBuilder<()> {
+    #[doc(hidden)]
+    #[deprecated(note = "\n\n\
+        builder compile-time error:\n\
+        - missing required field `sink`\n\n\
+    ")]
+    pub fn build(self, _: Infallible) {}
+}
+
+impl CountingSinkBuilder<Arc<dyn Sink>> {
+    /// Builds a [`CountingSink`].
+    pub fn build(self) -> Result<CountingSink> {
+        Ok(CountingSink {
+            common_impl: helper::CommonImpl::from_builder(self.common_builder_impl),
+            sink: self.sink,
+            counters: Arc::new(Counters::default()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{prelude::*, test_utils::*};
+
+    #[test]
+    fn count_records() {
+        const N: u64 = 10;
+
+        let sink = Arc::new(
+            CountingSink::builder()
+                .sink(Arc::new(CounterSink::new()))
+                .build()
+                .unwrap(),
+        );
+        let logger = build_test_logger(|b| b.sink(sink.clone()));
+
+        for i in 0..N {
+            info!(logger: logger, "record {}", i);
+        }
+        logger.flush();
+        assert_eq!(sink.counts().records(), N);
+        assert_eq!(sink.counts().errors(), 0);
+
+        // Clones share the counts
+        let cloned = sink.clone_box().unwrap();
+        cloned.log(&Record::new(Level::Info, "cloned")).unwrap();
+        assert_eq!(sink.reset_counts().records(), N + 1);
+        assert_eq!(sink.counts(), SinkCounts::default());
+    }
+
+    #[test]
+    fn count_errors() {
+        let sink = Arc::new(
+            CountingSink::builder()
+                .sink(Arc::new(FailingSink::new()))
+                .build()
+                .unwrap(),
+        );
+        let logger = build_test_logger(|b| b.sink(sink.clone()).error_handler(|_| {}));
+
+        info!(logger: logger, "hello");
+        info!(logger: logger, "world");
+        logger.flush();
+
+        let counts = sink.counts();
+        assert_eq!(counts.records(), 0);
+        assert_eq!(counts.errors(), 3);
+    }
+}
@@ -0,0 +1,347 @@
+//! Provides a daily file sink.
+
+use std::{convert::Infallible, path::PathBuf};
+
+use crate::{
+    formatter::{FmtExtraInfo, Formatter},
+    sink::{helper, RotatingFileSink, RotationPolicy, Sink},
+    ErrorHandler, LevelFilter, Record, Result, StringBuf,
+};
+
+/// A sink with a collection of files as the target, creating a new file at a
+/// fixed local time every day.
+///
+/// This is a convenience type for [`RotatingFileSink`] with
+/// [`RotationPolicy::Daily`], corresponding to `daily_file_sink` in C++
+/// `spdlog`. The date of the day is embedded in the file name, see
+/// [`DailyFileSinkBuilder::base_path`] for details.
+///
+/// # Examples
+///
+/// See [./examples] directory.
+///
+/// [./examples]: https://github.com/SpriteOvO/spdlog-rs/tree/main/spdlog/examples
+pub struct DailyFileSink {
+    rotating: RotatingFileSink,
+}
+
+/// The builder of [`DailyFileSink`].
+#[doc = include_str!("../include/doc/generic-builder-note.md")]
+/// # Examples
+///
+/// - Building a [`DailyFileSink`].
+///
+///   ```no_run
+///   use spdlog::sink::DailyFileSink;
+///
+///   # fn main() -> Result<(), spdlog::Error> {
+///   let sink: DailyFileSink = DailyFileSink::builder()
+///       .base_path("/path/to/app.log") // required
+///       // .rotation_time(2, 30) // optional, defaults to `(0, 0)`
+///       // .max_files(30) // optional, defaults to `0` for no limit
+///       // .rotate_on_open(true) // optional, defaults to `false`
+///       .build()?;
+///   # Ok(()) }
+///   ```
+///
+/// - If any required parameters are missing, a compile-time error will be
+///   raised.
+///
+///   ```compile_fail,E0061
+///   use spdlog::sink::DailyFileSink;
+///
+///   # fn main() -> Result<(), spdlog::Error> {
+///   let sink: DailyFileSink = DailyFileSink::builder()
+///       // .base_path("/path/to/app.log") // required
+///       .rotation_time(2, 30) // optional, defaults to `(0, 0)`
+///       .max_files(30) // optional, defaults to `0` for no limit
+///       .rotate_on_open(true) // optional, defaults to `false`
+///       .build()?;
+///   # Ok(()) }
+///   ```
+pub struct DailyFileSinkBuilder<ArgBP> {
+    common_builder_impl: helper::CommonBuilderImpl,
+    base_path: ArgBP,
+    path_template: bool,
+    hour: u32,
+    minute: u32,
+    max_files: usize,
+    rotate_on_open: bool,
+}
+
+impl DailyFileSink {
+    /// Constructs a builder of `DailyFileSink`.
+    #[must_use]
+    pub fn builder() -> DailyFileSinkBuilder<()> {
+        DailyFileSinkBuilder {
+            common_builder_impl: helper::CommonBuilderImpl::new(),
+            base_path: (),
+            path_template: false,
+            hour: 0,
+            minute: 0,
+            max_files: 0,
+            rotate_on_open: false,
+        }
+    }
+}
+
+impl Sink for DailyFileSink {
+    fn log(&self, record: &Record) -> Result<()> {
+        self.rotating.log(record)
+    }
+
+    fn write_formatted(
+        &self,
+        record: &Record,
+        formatted: &StringBuf,
+        extra_info: &FmtExtraInfo,
+    ) -> Result<()> {
+        self.rotating.write_formatted(record, formatted, extra_info)
+    }
+
+    fn flush(&self) -> Result<()> {
+        self.rotating.flush()
+    }
+
+    fn level_filter(&self) -> LevelFilter {
+        self.rotating.level_filter()
+    }
+
+    fn set_level_filter(&self, level_filter: LevelFilter) {
+        self.rotating.set_level_filter(level_filter)
+    }
+
+    fn set_formatter(&self, formatter: Box<dyn Formatter>) {
+        self.rotating.set_formatter(formatter)
+    }
+
+    fn set_error_handler(&self, handler: Option<ErrorHandler>) {
+        self.rotating.set_error_handler(handler)
+    }
+
+    fn clone_box(&self) -> Option<Box<dyn Sink>> {
+        Some(Box::new(DailyFileSink {
+            rotating: self.rotating.clone_sink(),
+        }))
+    }
+}
+
+impl<ArgBP> DailyFileSinkBuilder<ArgBP> {
+    /// Specifies the base path of the log file.
+    ///
+    /// The date of the day is inserted in the front of the extension, if there
+    /// is not an extension, it will be appended to the end.
+    ///
+    /// Supposes the given base path is `/path/to/app.log`, the eventual file
+    /// names may look like the following:
+    ///
+    /// - `/path/to/app-2022-03-23.log`
+    /// - `/path/to/app-2022-03-24.log`
+    ///
+    /// Note that the date is separated by `-` as in C++ `spdlog`, while
+    /// [`RotationPolicy::Daily`] of [`RotatingFileSink`] separates it by `_`.
+    ///
+    /// It's used literally, unless [`DailyFileSinkBuilder::path_template`] is
+    /// enabled.
+    ///
+    /// This parameter is **required**.
+    #[must_use]
+    pub fn base_path<P>(self, base_path: P) -> DailyFileSinkBuilder<PathBuf>
+    where
+        P: Into<PathBuf>,
+    {
+        DailyFileSinkBuilder {
+            common_builder_impl: self.common_builder_impl,
+            base_path: base_path.into(),
+            path_template: self.path_template,
+            hour: self.hour,
+            minute: self.minute,
+            max_files: self.max_files,
+            rotate_on_open: self.rotate_on_open,
+        }
+    }
+
+    /// Specifies the local time point of the day to create a new file.
+    ///
+    /// The range of `hour` is [0, 23], and the range of `minute` is [0, 59].
+    ///
+    /// This parameter is **optional**, and defaults to `(0, 0)`.
+    #[must_use]
+    pub fn rotation_time(mut self, hour: u32, minute: u32) -> Self {
+        self.hour = hour;
+        self.minute = minute;
+        self
+    }
+
+    /// Specifies whether the base path is a template, resolved when the sink
+    /// is built.
+    ///
+    /// See [the documentation of `FileSinkBuilder::path_template`] for the
+    /// grammar.
+    ///
+    /// This parameter is **optional**, and defaults to `false`.
+    ///
+    /// [the documentation of `FileSinkBuilder::path_template`]: crate::sink::FileSinkBuilder::path_template#path-templates
+    #[must_use]
+    pub fn path_template(mut self, path_template: bool) -> Self {
+        self.path_template = path_template;
+        self
+    }
+
+    /// Specifies the maximum number of files.
+    ///
+    /// If the number of existing files reaches this parameter, the oldest file
+    /// will be deleted on the next rotation.
+    ///
+    /// Pass `0` for no limit.
+    ///
+    /// This parameter is **optional**, and defaults to `0`.
+    #[must_use]
+    pub fn max_files(mut self, max_files: usize) -> Self {
+        self.max_files = max_files;
+        self
+    }
+
+    /// Specifies whether to truncate the file of the current day when
+    /// constructing `DailyFileSink`.
+    ///
+    /// This parameter is **optional**, and defaults to `false`.
+    #[must_use]
+    pub fn rotate_on_open(mut self, rotate_on_open: bool) -> Self {
+        self.rotate_on_open = rotate_on_open;
+        self
+    }
+
+    helper::common_impl!(@SinkBuilder: common_builder_impl);
+}
+
+impl DailyFileSinkBuilder<()> {
+    #[doc(hidden)]
+    #[deprecated(note = "\n\n\
+        builder compile-time error:\n\
+        - missing required field `base_path`\n\n\
+    ")]
+    pub fn build(self, _: Infallible) {}
+}
+
+impl DailyFileSinkBuilder<PathBuf> {
+    /// Builds a [`DailyFileSink`].
+    ///
+    /// # Errors
+    ///
+    /// If the argument `rotation_time` is invalid, or
+    /// [`DailyFileSinkBuilder::path_template`] is enabled and the base path
+    /// template is invalid, [`Error::InvalidArgument`] will be returned. If an
+    /// error occurs opening the file,
+    /// [`Error::CreateDirectory`] or [`Error::OpenFile`] will be returned.
+    ///
+    /// [`Error::InvalidArgument`]: crate::Error::InvalidArgument
+    /// [`Error::CreateDirectory`]: crate::Error::CreateDirectory
+    /// [`Error::OpenFile`]: crate::Error::OpenFile
+    pub fn build(self) -> Result<DailyFileSink> {
+        let common = self.common_builder_impl;
+
+        let mut builder = RotatingFileSink::builder()
+            .base_path(self.base_path)
+            .path_template(self.path_template)
+            .rotation_policy(RotationPolicy::Daily {
+                hour: self.hour,
+                minute: self.minute,
+            })
+            .max_files(self.max_files)
+            .rotate_on_open(self.rotate_on_open)
+            .date_separator('-')
+            .level_filter(common.level_filter);
+        if let Some(formatter) = common.formatter {
+            builder = builder.formatter(formatter);
+        }
+        if let Some(handler) = common.error_handler {
+            builder = builder.error_handler(handler);
+        }
+
+        Ok(DailyFileSink {
+            rotating: builder.build()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, time::Duration};
+
+    use chrono::prelude::*;
+
+    use super::*;
+    use crate::{prelude::*, sync::*, test_utils::*, Error};
+
+    static LOGS_PATH: Lazy<PathBuf> = Lazy::new(|| {
+        let path = TEST_LOGS_PATH.join("daily_file_sink");
+        fs::create_dir_all(&path).unwrap();
+        path
+    });
+
+    #[test]
+    fn file_name_and_retention() {
+        fs::remove_dir_all(LOGS_PATH.as_path()).unwrap();
+        fs::create_dir(LOGS_PATH.as_path()).unwrap();
+
+        const DAY_1: Duration = Duration::from_secs(60 * 60 * 24);
+
+        let local_time_now = Local::now();
+        let sink = DailyFileSink::builder()
+            .base_path(LOGS_PATH.join("app.log"))
+            .rotation_time(local_time_now.hour(), local_time_now.minute())
+            .max_files(2)
+            .build()
+            .unwrap();
+        let logger = build_test_logger(|b| b.sink(Arc::new(sink)));
+        logger.set_level_filter(LevelFilter::All);
+
+        let file_name = |time: DateTime<Local>| {
+            format!(
+                "app-{}-{:02}-{:02}.log",
+                time.year(),
+                time.month(),
+                time.day()
+            )
+        };
+        let exist_files = || {
+            let mut files = fs::read_dir(LOGS_PATH.as_path())
+                .unwrap()
+                .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+                .collect::<Vec<_>>();
+            files.sort();
+            files
+        };
+
+        let mut record = Record::new(Level::Info, "test log message");
+        let initial_time = record.time();
+        logger.log(&record);
+        assert_eq!(exist_files(), vec![file_name(initial_time.into())]);
+
+        record.set_time(initial_time + DAY_1);
+        logger.log(&record);
+        record.set_time(initial_time + DAY_1 * 2);
+        logger.log(&record);
+        assert_eq!(
+            exist_files(),
+            vec![
+                file_name((initial_time + DAY_1).into()),
+                file_name((initial_time + DAY_1 * 2).into())
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_rotation_time() {
+        let build = |hour, minute| {
+            DailyFileSink::builder()
+                .base_path(LOGS_PATH.join("invalid.log"))
+                .rotation_time(hour, minute)
+                .build()
+        };
+
+        assert!(matches!(build(24, 0), Err(Error::InvalidArgument(_))));
+        assert!(matches!(build(0, 60), Err(Error::InvalidArgument(_))));
+    }
+}
@@ -160,6 +160,23 @@ impl Sink for DedupSink {
         self.flush_sinks()
     }
 
+    fn prefers_sync(&self) -> bool {
+        self.sinks.iter().any(|sink| sink.prefers_sync())
+    }
+
+    fn clone_box(&self) -> Option<Box<dyn Sink>> {
+        Some(Box::new(DedupSink {
+            common_impl: self.common_impl.clone_config(),
+            sinks: self.sinks.clone(),
+            skip_duration: self.skip_duration,
+            // Repetitions are detected per clone
+            state: Mutex::new(DedupSinkState {
+                last_record: None,
+                skipped_count: 0,
+            }),
+        }))
+    }
+
     helper::common_impl!(@Sink: common_impl);
 }
 
@@ -230,7 +247,7 @@ impl DedupSinkBuilder<Duration> {
 
 #[cfg(test)]
 mod tests {
-    use std::thread::sleep;
+    use std::{fmt, thread::sleep};
 
     use super::*;
     use crate::{prelude::*, test_utils::*};
@@ -313,4 +330,30 @@ mod tests {
         assert_eq!(records[12].payload(), "Meow~ Meow...");
         assert_eq!(records[12].level(), Level::Info);
     }
+
+    #[test]
+    fn reentrant_payload() {
+        struct Reentrant<'a>(&'a Logger);
+
+        impl fmt::Display for Reentrant<'_> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                info!(logger: self.0, "inner");
+                f.write_str("outer")
+            }
+        }
+
+        let test_sink = Arc::new(CounterSink::new());
+        let dedup_sink = Arc::new(
+            DedupSink::builder()
+                .skip_duration(Duration::from_secs(1))
+                .sink(test_sink.clone())
+                .build()
+                .unwrap(),
+        );
+        let test = build_test_logger(|b| b.sink(dedup_sink));
+
+        // The payload is formatted before the sink is locked
+        info!(logger: test, "{}", Reentrant(&test));
+        assert_eq!(test_sink.payloads(), ["inner", "outer"]);
+    }
 }
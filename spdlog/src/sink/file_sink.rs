@@ -8,21 +8,93 @@ use std::{
 };
 
 use crate::{
-    sink::{helper, Sink},
+    formatter::{FmtExtraInfo, StyleHint},
+    sink::{helper, path_template, FlushErrorPolicy, Sink},
     sync::*,
-    utils, Error, Record, Result, StringBuf,
+    terminal_style::{HintStyleCodes, LevelStyleCodes, Style, StyleMode},
+    utils, Error, Level, Record, Result, StringBuf,
 };
 
 /// A sink with a file as the target.
 ///
+/// # Styles
+///
+/// By default, the style range of formatted records is ignored, so log files
+/// are not polluted with ANSI escape codes. To keep the styles, e.g. for
+/// viewing the file with `less -R`, opt in by setting the style mode to
+/// [`StyleMode::Always`] with [`FileSinkBuilder::style_mode`]. A file is never
+/// a terminal, so [`StyleMode::Auto`] is the same as [`StyleMode::Never`].
+///
+/// # Cooperating with logrotate
+///
+/// When an external tool such as `logrotate` renames the file, the sink keeps
+/// writing to the renamed file, since it holds the file open. Call
+/// [`FileSink::reopen`] after the rotation to continue with a new file at the
+/// original path. On Unix, [`install_reopen_on_signal`] calls it when the
+/// process receives a signal, which pairs with a `logrotate` configuration
+/// like:
+///
+/// ```text
+/// /var/log/my-app/*.log {
+///     daily
+///     rotate 7
+///     compress
+///     delaycompress
+///     postrotate
+///         kill -HUP $(cat /run/my-app.pid)
+///     endscript
+/// }
+/// ```
+///
+/// `delaycompress` leaves the newest rotated file uncompressed, as records may
+/// still be written to it until the sink is reopened.
+///
+/// # Sharing a file between processes
+///
+/// Records written by several processes to the same file may interleave and
+/// corrupt each other. To catch such misconfiguration early, enable
+/// [`FileSinkBuilder::exclusive`], then the sink holds an advisory lock on the
+/// file and building a second sink for the file fails.
+///
+/// Some deployments share a file intentionally, e.g. a sidecar container
+/// tailing a file written by several processes. On POSIX systems, each `write`
+/// to a file opened in append mode is appended atomically, without being
+/// interleaved with other writes (for regular files on local file systems; the
+/// limit `PIPE_BUF` only applies to pipes and FIFOs). However, by default the
+/// sink buffers records and may split or merge them into several `write`
+/// calls, so records from different processes may interleave in the middle of
+/// a line. Enable [`FileSinkBuilder::shared_append`] to write each record with
+/// a single `write` call to a file opened in append mode.
+///
+/// # Large records
+///
+/// By default, a record is formatted into a buffer before it's written to the
+/// file, so a record with a very large payload (e.g. a dumped request body)
+/// temporarily takes as much memory again. Enable
+/// [`FileSinkBuilder::streaming`] to format records straight into the file
+/// with [`Formatter::format_stream`] instead.
+///
 /// # Examples
 ///
 /// See [./examples] directory.
 ///
+/// [`install_reopen_on_signal`]: crate::install_reopen_on_signal
+/// [`Formatter::format_stream`]: crate::formatter::Formatter::format_stream
 /// [./examples]: https://github.com/SpriteOvO/spdlog-rs/tree/main/spdlog/examples
 pub struct FileSink {
     common_impl: helper::CommonImpl,
-    file: SpinMutex<BufWriter<File>>,
+    path: PathBuf,
+    // Shared with clones. A blocking mutex, since it's held while formatting
+    // records in the streaming mode.
+    file: Arc<Mutex<BufWriter<File>>>,
+    exclusive: bool,
+    shared_append: bool,
+    streaming: bool,
+    utf8_bom: bool,
+    flush_error: helper::FlushErrorState,
+    should_render_style: bool,
+    level_style_codes: LevelStyleCodes,
+    hint_style_codes: HintStyleCodes,
 }
 
 impl FileSink {
@@ -31,7 +103,14 @@ impl FileSink {
     pub fn builder() -> FileSinkBuilder<()> {
         FileSinkBuilder {
             path: (),
+            path_template: false,
             truncate: false,
+            exclusive: false,
+            shared_append: false,
+            streaming: false,
+            utf8_bom: false,
+            flush_error_policy: FlushErrorPolicy::Propagate,
+            style_mode: StyleMode::Never,
             common_builder_impl: helper::CommonBuilderImpl::new(),
         }
     }
@@ -59,6 +138,127 @@ impl FileSink {
             .truncate(truncate)
             .build()
     }
+
+    /// Closes the file and opens the file at the original path again.
+    ///
+    /// Buffered records are flushed to the old file first. The new file is
+    /// created if it does not exist, and it's opened in append mode regardless
+    /// of [`FileSinkBuilder::truncate`]. If [`FileSinkBuilder::utf8_bom`] is
+    /// enabled and the new file is empty, the BOM is written to it. The file is
+    /// swapped while writing is locked, so concurrent records are written
+    /// entirely to either the old or the new file. If
+    /// [`FileSinkBuilder::exclusive`] is enabled, the lock is moved from the
+    /// old file to the new file.
+    ///
+    /// This is typically called after an external tool (e.g. `logrotate`)
+    /// renamed the file, see the [type-level documentation] for details.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs opening or locking the file,
+    /// [`Error::CreateDirectory`], [`Error::OpenFile`] or [`Error::LockFile`]
+    /// will be returned and the sink keeps writing to the old file. If an error
+    /// occurs flushing the old file, [`Error::FlushBuffer`] will be returned
+    /// after the new file is in use.
+    ///
+    /// [type-level documentation]: FileSink#cooperating-with-logrotate
+    pub fn reopen(&self) -> Result<()> {
+        let new_file = utils::open_file(&self.path, false)?;
+
+        let mut old_file = {
+            let mut file = self.file.lock_expect();
+            if self.exclusive {
+                // The new file may be the old one if it has not been renamed, and
+                // the lock on the old file would conflict with it.
+                let _ = utils::unlock_file(file.get_ref());
+                if let Err(err) = utils::try_lock_file_exclusive(&new_file) {
+                    let _ = utils::try_lock_file_exclusive(file.get_ref());
+                    return Err(Error::LockFile(err));
+                }
+            }
+            if self.utf8_bom {
+                utils::write_utf8_bom_if_empty(&new_file)?;
+            }
+            std::mem::replace(&mut *file, new_writer(new_file, self.shared_append))
+        };
+        old_file.flush().map_err(Error::FlushBuffer)
+    }
+
+    // Used at `install_reopen_on_signal`, where there is no caller to return
+    // errors to.
+    #[cfg(unix)]
+    pub(crate) fn reopen_or_handle_error(&self) {
+        if let Err(err) = self.reopen() {
+            self.common_impl.non_returnable_error("FileSink", err)
+        }
+    }
+
+    /// Sets the style of the specified log level.
+    ///
+    /// It takes effect only if the style mode is [`StyleMode::Always`].
+    pub fn set_style(&mut self, level: Level, style: Style) {
+        self.level_style_codes.set_code(level, style);
+    }
+
+    /// Sets the style of the spans with the specified [`StyleHint`].
+    ///
+    /// The spans are not styled unless a style is set for their hint.
+    ///
+    /// The style of [`StyleHint::Level`] is determined by the level of the
+    /// record, use [`Self::set_style`] for it instead. Setting it here has no
+    /// effect.
+    pub fn set_hint_style(&mut self, hint: StyleHint, style: Style) {
+        self.hint_style_codes.set_code(hint, style);
+    }
+
+    fn write(
+        &self,
+        record: &Record,
+        string_buf: &StringBuf,
+        extra_info: &FmtExtraInfo,
+    ) -> Result<()> {
+        let style_ranges = extra_info
+            .style_ranges()
+            .filter(|_| self.should_render_style);
+
+        if !self.shared_append {
+            return self
+                .level_style_codes
+                .write_styled(
+                    &self.hint_style_codes,
+                    &mut *self.file.lock_expect(),
+                    record.level(),
+                    string_buf.as_bytes(),
+                    style_ranges,
+                )
+                .map_err(Error::WriteRecord);
+        }
+
+        // Render the styles ahead, so that the record is written in a single call
+        let styled: Vec<u8>;
+        let bytes = if self.should_render_style {
+            let mut buf = Vec::with_capacity(string_buf.len() + 16);
+            self.level_style_codes
+                .write_styled(
+                    &self.hint_style_codes,
+                    &mut buf,
+                    record.level(),
+                    string_buf.as_bytes(),
+                    style_ranges,
+                )
+                .map_err(Error::WriteRecord)?;
+            styled = buf;
+            &styled[..]
+        } else {
+            string_buf.as_bytes()
+        };
+        // The buffer of `BufWriter` is always empty in this mode, bypass it
+        self.file
+            .lock_expect()
+            .get_mut()
+            .write_all(bytes)
+            .map_err(Error::WriteRecord)
+    }
 }
 
 impl Sink for FileSink {
@@ -67,22 +267,56 @@ impl Sink for FileSink {
             return Ok(());
         }
 
+        if self.streaming && !self.shared_append && !self.should_render_style {
+            return self
+                .common_impl
+                .formatter
+                .read()
+                .format_stream(record, &mut *self.file.lock_expect());
+        }
+
         let mut string_buf = StringBuf::new();
-        self.common_impl
+        let extra_info = self
+            .common_impl
             .formatter
             .read()
             .format(record, &mut string_buf)?;
 
-        self.file
-            .lock()
-            .write_all(string_buf.as_bytes())
-            .map_err(Error::WriteRecord)?;
+        self.write(record, &string_buf, &extra_info)
+    }
 
-        Ok(())
+    fn write_formatted(
+        &self,
+        record: &Record,
+        formatted: &StringBuf,
+        extra_info: &FmtExtraInfo,
+    ) -> Result<()> {
+        if !self.should_log(record.level()) {
+            return Ok(());
+        }
+
+        self.write(record, formatted, extra_info)
     }
 
     fn flush(&self) -> Result<()> {
-        self.file.lock().flush().map_err(Error::FlushBuffer)
+        self.flush_error
+            .handle(self.file.lock_expect().flush().map_err(Error::FlushBuffer))
+    }
+
+    fn clone_box(&self) -> Option<Box<dyn Sink>> {
+        Some(Box::new(FileSink {
+            common_impl: self.common_impl.clone_config(),
+            path: self.path.clone(),
+            file: self.file.clone(),
+            exclusive: self.exclusive,
+            shared_append: self.shared_append,
+            streaming: self.streaming,
+            utf8_bom: self.utf8_bom,
+            flush_error: helper::FlushErrorState::new(self.flush_error.policy()),
+            should_render_style: self.should_render_style,
+            level_style_codes: self.level_style_codes.clone(),
+            hint_style_codes: self.hint_style_codes.clone(),
+        }))
     }
 
     helper::common_impl!(@Sink: common_impl);
@@ -90,9 +324,9 @@ impl Sink for FileSink {
 
 impl Drop for FileSink {
     fn drop(&mut self) {
-        if let Err(err) = self.file.lock().flush() {
-            self.common_impl
-                .non_returnable_error("FileSink", Error::FlushBuffer(err))
+        let flush_result = self.file.lock_expect().flush().map_err(Error::FlushBuffer);
+        if let Err(err) = self.flush_error.handle(flush_result) {
+            self.common_impl.non_returnable_error("FileSink", err)
         }
     }
 }
@@ -112,6 +346,8 @@ impl Drop for FileSink {
 ///   let sink: FileSink = FileSink::builder()
 ///       .path("/path/to/log_file") // required
 ///       // .truncate(true) // optional, defaults to `false`
+///       /* .style_mode(StyleMode::Always) // optional, defaults to
+///                                         // `StyleMode::Never` */
 ///       .build()?;
 ///   # Ok(()) }
 ///   ```
@@ -132,12 +368,22 @@ impl Drop for FileSink {
 pub struct FileSinkBuilder<ArgPath> {
     common_builder_impl: helper::CommonBuilderImpl,
     path: ArgPath,
+    path_template: bool,
     truncate: bool,
+    exclusive: bool,
+    shared_append: bool,
+    streaming: bool,
+    utf8_bom: bool,
+    flush_error_policy: FlushErrorPolicy,
+    style_mode: StyleMode,
 }
 
 impl<ArgPath> FileSinkBuilder<ArgPath> {
     /// The path of the log file.
     ///
+    /// It's used literally, unless [`FileSinkBuilder::path_template`] is
+    /// enabled.
+    ///
     /// This parameter is **required**.
     #[must_use]
     pub fn path<P>(self, path: P) -> FileSinkBuilder<PathBuf>
@@ -147,10 +393,31 @@ impl<ArgPath> FileSinkBuilder<ArgPath> {
         FileSinkBuilder {
             common_builder_impl: self.common_builder_impl,
             path: path.into(),
+            path_template: self.path_template,
             truncate: self.truncate,
+            exclusive: self.exclusive,
+            shared_append: self.shared_append,
+            streaming: self.streaming,
+            utf8_bom: self.utf8_bom,
+            flush_error_policy: self.flush_error_policy,
+            style_mode: self.style_mode,
         }
     }
 
+    /// Specifies whether the path is a template, e.g.
+    /// `"/var/log/${SERVICE}/app-{pid}.log"`, resolved when the sink is built.
+    ///
+    /// If it is `false`, the path is used literally, even if it contains `{`,
+    /// `}`, `$` or `~`.
+    ///
+    /// This parameter is **optional**, and defaults to `false`.
+    #[doc = include_str!("../include/doc/path-template.md")]
+    #[must_use]
+    pub fn path_template(mut self, path_template: bool) -> Self {
+        self.path_template = path_template;
+        self
+    }
+
     /// If it is true, the existing contents of the filewill be discarded.
     ///
     /// This parameter is **optional**, and defaults to `false`.
@@ -160,6 +427,104 @@ impl<ArgPath> FileSinkBuilder<ArgPath> {
         self
     }
 
+    /// If it is true, an advisory exclusive lock is acquired on the file, and
+    /// building fails if another sink (possibly in another process) holds it.
+    ///
+    /// The lock is released when the file is closed, i.e. when the sink and
+    /// all its clones are dropped. Being advisory, it only prevents other
+    /// sinks with this option enabled (or other programs acquiring the same
+    /// kind of lock, i.e. `flock` on Unix and `LockFileEx` on Windows) from
+    /// opening the file, but not plain writes. See [the documentation of
+    /// `FileSink`](FileSink#sharing-a-file-between-processes).
+    ///
+    /// This parameter is **optional**, and defaults to `false`.
+    #[must_use]
+    pub fn exclusive(mut self, exclusive: bool) -> Self {
+        self.exclusive = exclusive;
+        self
+    }
+
+    /// If it is true, the file is always opened in append mode, records are not
+    /// buffered, and each record is written with a single `write` call.
+    ///
+    /// This is intended for files shared by several processes, so that records
+    /// are not interleaved in the middle, see [the documentation of
+    /// `FileSink`](FileSink#sharing-a-file-between-processes). If
+    /// [`FileSinkBuilder::truncate`] is also enabled, the file is truncated
+    /// after opening in append mode.
+    ///
+    /// The operating system may still write fewer bytes than requested, e.g.
+    /// when the disk is full, then the rest of the record is written with
+    /// further calls.
+    ///
+    /// This parameter is **optional**, and defaults to `false`.
+    #[must_use]
+    pub fn shared_append(mut self, shared_append: bool) -> Self {
+        self.shared_append = shared_append;
+        self
+    }
+
+    /// If it is true, records are formatted straight into the file, without
+    /// buffering the whole formatted text of each record in memory.
+    ///
+    /// This is intended for records with very large payloads, see [the
+    /// documentation of `FileSink`](FileSink#large-records). The file is locked
+    /// while a record is being formatted, so concurrent records wait longer,
+    /// and if the formatter fails halfway, a partial record is left in the
+    /// file. The formatter must not log to this sink (or its clones), which
+    /// would deadlock on the lock.
+    ///
+    /// It has no effect if [`FileSinkBuilder::shared_append`] is enabled or
+    /// styles are rendered (see [`FileSinkBuilder::style_mode`]), as they
+    /// require the whole formatted text, and records are buffered then.
+    ///
+    /// This parameter is **optional**, and defaults to `false`.
+    #[must_use]
+    pub fn streaming(mut self, streaming: bool) -> Self {
+        self.streaming = streaming;
+        self
+    }
+
+    /// If it is true, the UTF-8 byte order mark (`EF BB BF`) is written to the
+    /// file before any records if the file is empty, i.e. it's newly created
+    /// or truncated.
+    ///
+    /// Some Windows tools, e.g. older versions of Notepad and Excel, guess the
+    /// encoding of a file without a BOM as the legacy code page, and display
+    /// non-ASCII text garbled. An existing non-empty file is appended to as
+    /// is, without checking whether it begins with a BOM.
+    ///
+    /// This parameter is **optional**, and defaults to `false`.
+    #[must_use]
+    pub fn utf8_bom(mut self, utf8_bom: bool) -> Self {
+        self.utf8_bom = utf8_bom;
+        self
+    }
+
+    /// Specifies the policy for handling errors flushing the buffer.
+    ///
+    /// See [`FlushErrorPolicy`] for details.
+    ///
+    /// This parameter is **optional**, and defaults to
+    /// [`FlushErrorPolicy::Propagate`].
+    #[must_use]
+    pub fn flush_error_policy(mut self, flush_error_policy: FlushErrorPolicy) -> Self {
+        self.flush_error_policy = flush_error_policy;
+        self
+    }
+
+    /// Specifies the style mode.
+    ///
+    /// Only [`StyleMode::Always`] renders styles, see [the documentation of
+    /// `FileSink`](FileSink#styles).
+    ///
+    /// This parameter is **optional**, and defaults to [`StyleMode::Never`].
+    #[must_use]
+    pub fn style_mode(mut self, style_mode: StyleMode) -> Self {
+        self.style_mode = style_mode;
+        self
+    }
+
     helper::common_impl!(@SinkBuilder: common_builder_impl);
 }
 
@@ -177,16 +542,357 @@ impl FileSinkBuilder<PathBuf> {
     ///
     /// # Errors
     ///
-    /// If an error occurs opening the file, [`Error::CreateDirectory`] or
-    /// [`Error::OpenFile`] will be returned.
+    /// If [`FileSinkBuilder::path_template`] is enabled and the path template
+    /// is invalid, [`Error::InvalidArgument`] will be returned. If an error
+    /// occurs opening the file, [`Error::CreateDirectory`] or
+    /// [`Error::OpenFile`] will be returned. If
+    /// [`FileSinkBuilder::exclusive`] is enabled and the file cannot be locked,
+    /// e.g. it is already locked, [`Error::LockFile`] will be returned.
     pub fn build(self) -> Result<FileSink> {
-        let file = utils::open_file(self.path, self.truncate)?;
+        let path = if self.path_template {
+            path_template::resolve(&self.path)?
+        } else {
+            self.path
+        };
+        // Do not truncate the file before knowing it's not locked by others, and
+        // keep it in append mode for `shared_append`
+        let truncate_later = self.truncate && (self.exclusive || self.shared_append);
+        let file = utils::open_file(&path, self.truncate && !truncate_later)?;
+        if self.exclusive {
+            utils::try_lock_file_exclusive(&file).map_err(Error::LockFile)?;
+        }
+        if truncate_later {
+            file.set_len(0).map_err(Error::OpenFile)?;
+        }
+        if self.utf8_bom {
+            utils::write_utf8_bom_if_empty(&file)?;
+        }
 
         let sink = FileSink {
             common_impl: helper::CommonImpl::from_builder(self.common_builder_impl),
-            file: SpinMutex::new(BufWriter::new(file)),
+            path,
+            file: Arc::new(Mutex::new(new_writer(file, self.shared_append))),
+            exclusive: self.exclusive,
+            shared_append: self.shared_append,
+            streaming: self.streaming,
+            utf8_bom: self.utf8_bom,
+            flush_error: helper::FlushErrorState::new(self.flush_error_policy),
+            should_render_style: self.style_mode == StyleMode::Always,
+            level_style_codes: LevelStyleCodes::default(),
+            hint_style_codes: HintStyleCodes::default(),
         };
 
         Ok(sink)
     }
 }
+
+fn new_writer(file: File, shared_append: bool) -> BufWriter<File> {
+    if shared_append {
+        BufWriter::with_capacity(0, file)
+    } else {
+        BufWriter::new(file)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::{
+        error::InvalidArgumentError, formatter::FullFormatter, prelude::*, test_utils::*, EOL,
+    };
+
+    static LOGS_PATH: Lazy<PathBuf> = Lazy::new(|| {
+        let path = TEST_LOGS_PATH.join("file_sink");
+        fs::create_dir_all(&path).unwrap();
+        path
+    });
+
+    #[test]
+    fn style_mode() {
+        let log_to_file = |file_name: &str, style_mode: Option<StyleMode>| {
+            let path = LOGS_PATH.join(file_name);
+            let mut builder = FileSink::builder().path(&path).truncate(true);
+            if let Some(style_mode) = style_mode {
+                builder = builder.style_mode(style_mode);
+            }
+            let logger = build_test_logger(|b| b.sink(Arc::new(builder.build().unwrap())));
+
+            info!(logger: logger, "hello");
+            logger.flush();
+            fs::read_to_string(path).unwrap()
+        };
+
+        // The default formatter has a style range, but no SGR sequence is written.
+        for (file_name, style_mode) in [
+            ("default.log", None),
+            ("auto.log", Some(StyleMode::Auto)),
+            ("never.log", Some(StyleMode::Never)),
+        ] {
+            let content = log_to_file(file_name, style_mode);
+            assert!(content.contains("[info]"));
+            assert!(!content.contains('\x1b'));
+        }
+
+        let content = log_to_file("always.log", Some(StyleMode::Always));
+        let style_code = LevelStyleCodes::default().code(Level::Info).clone();
+        assert!(content.contains(&format!("[{}info{}]", style_code.start, style_code.end)));
+    }
+
+    #[test]
+    fn hint_styles() {
+        let path = LOGS_PATH.join("hint_styles.log");
+        let mut sink = FileSink::builder()
+            .path(&path)
+            .truncate(true)
+            .style_mode(StyleMode::Always)
+            .build()
+            .unwrap();
+        sink.set_hint_style(StyleHint::LoggerName, Style::builder().underline().build());
+        let logger = build_test_logger(|b| b.sink(Arc::new(sink)).name("network"));
+
+        info!(logger: logger, "hello");
+        logger.flush();
+
+        let content = fs::read_to_string(path).unwrap();
+        let style_code = LevelStyleCodes::default().code(Level::Info).clone();
+        assert!(content.contains(&format!(
+            "] [\x1b[4mnetwork\x1b[m] [{}info{}]",
+            style_code.start, style_code.end
+        )));
+    }
+
+    #[test]
+    fn path_template() {
+        std::env::set_var("SPDLOG_RS_TEST_FILE_SINK_SERVICE", "my-service");
+        let template = LOGS_PATH.join("${SPDLOG_RS_TEST_FILE_SINK_SERVICE}/app-{pid}.log");
+        let path = LOGS_PATH.join(format!("my-service/app-{}.log", std::process::id()));
+
+        let sink = FileSink::builder()
+            .path(template)
+            .path_template(true)
+            .truncate(true)
+            .formatter(Box::new(NoModFormatter::new()))
+            .build()
+            .unwrap();
+        let logger = build_test_logger(|b| b.sink(Arc::new(sink)));
+
+        info!(logger: logger, "hello");
+        logger.flush();
+        assert_eq!(fs::read_to_string(path).unwrap(), "hello");
+
+        assert!(matches!(
+            FileSink::builder()
+                .path(LOGS_PATH.join("${SPDLOG_RS_TEST_FILE_SINK_MISSING}/app.log"))
+                .path_template(true)
+                .build(),
+            Err(Error::InvalidArgument(InvalidArgumentError::PathTemplate(
+                _
+            )))
+        ));
+
+        // Used literally unless enabled
+        let literal_path = LOGS_PATH.join("${SPDLOG_RS_TEST_FILE_SINK_MISSING}-{pid}.log");
+        let sink = FileSink::builder()
+            .path(&literal_path)
+            .truncate(true)
+            .formatter(Box::new(NoModFormatter::new()))
+            .build()
+            .unwrap();
+        let logger = build_test_logger(|b| b.sink(Arc::new(sink)));
+
+        info!(logger: logger, "hello");
+        logger.flush();
+        assert_eq!(fs::read_to_string(literal_path).unwrap(), "hello");
+    }
+
+    #[test]
+    fn flush_on_critical() {
+        let path = LOGS_PATH.join("flush_on_critical.log");
+        let sink = FileSink::builder()
+            .path(&path)
+            .truncate(true)
+            .formatter(Box::new(NoModFormatter::new()))
+            .build()
+            .unwrap();
+        let logger = build_test_logger(|b| b.sink(Arc::new(sink)));
+
+        // Buffered
+        info!(logger: logger, "hello ");
+        assert_eq!(fs::read_to_string(&path).unwrap(), "");
+
+        critical!(logger: logger, "crashing");
+        assert_eq!(fs::read_to_string(&path).unwrap(), "hello crashing");
+    }
+
+    #[test]
+    fn reopen() {
+        let path = LOGS_PATH.join("reopen.log");
+        let rotated_path = LOGS_PATH.join("reopen.log.1");
+        let sink = Arc::new(
+            FileSink::builder()
+                .path(&path)
+                .truncate(true)
+                .formatter(Box::new(NoModFormatter::new()))
+                .build()
+                .unwrap(),
+        );
+        let logger = build_test_logger(|b| b.sink(sink.clone()));
+
+        info!(logger: logger, "before rotation ");
+        fs::rename(&path, &rotated_path).unwrap();
+        info!(logger: logger, "still in rotated file");
+        sink.reopen().unwrap();
+        info!(logger: logger, "in new file");
+        logger.flush();
+
+        assert_eq!(
+            fs::read_to_string(&rotated_path).unwrap(),
+            "before rotation still in rotated file"
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), "in new file");
+    }
+
+    #[test]
+    fn utf8_bom() {
+        let path = LOGS_PATH.join("utf8_bom.log");
+        let rotated_path = LOGS_PATH.join("utf8_bom.log.1");
+        let build = |truncate| {
+            FileSink::builder()
+                .path(&path)
+                .truncate(truncate)
+                .utf8_bom(true)
+                .formatter(Box::new(NoModFormatter::new()))
+                .build()
+                .unwrap()
+        };
+
+        let logger = build_test_logger(|b| b.sink(Arc::new(build(true))));
+        info!(logger: logger, "hello ");
+        drop(logger);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "\u{FEFF}hello ");
+
+        // Not written again to a non-empty file
+        let sink = Arc::new(build(false));
+        let logger = build_test_logger(|b| b.sink(sink.clone()));
+        info!(logger: logger, "world");
+        logger.flush();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "\u{FEFF}hello world");
+
+        // Written to the new file on reopening
+        fs::rename(&path, &rotated_path).unwrap();
+        sink.reopen().unwrap();
+        info!(logger: logger, "reopened");
+        logger.flush();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "\u{FEFF}reopened");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn exclusive() {
+        let path = LOGS_PATH.join("exclusive.log");
+        let build = |truncate| {
+            FileSink::builder()
+                .path(&path)
+                .truncate(truncate)
+                .exclusive(true)
+                .formatter(Box::new(NoModFormatter::new()))
+                .build()
+        };
+
+        let sink = Arc::new(build(true).unwrap());
+        let logger = build_test_logger(|b| b.sink(sink.clone()));
+        info!(logger: logger, "hello ");
+        logger.flush();
+
+        // The file is neither locked again nor truncated
+        assert!(matches!(build(true), Err(Error::LockFile(_))));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "hello ");
+        // A non-exclusive sink is not prevented
+        assert!(FileSink::builder().path(&path).build().is_ok());
+
+        // The lock is kept after reopening the same file
+        sink.reopen().unwrap();
+        assert!(matches!(build(false), Err(Error::LockFile(_))));
+
+        drop(logger);
+        drop(sink);
+        assert!(build(false).is_ok());
+    }
+
+    #[test]
+    fn shared_append() {
+        let path = LOGS_PATH.join("shared_append.log");
+        fs::write(&path, "stale").unwrap();
+        let build = |truncate| {
+            FileSink::builder()
+                .path(&path)
+                .truncate(truncate)
+                .shared_append(true)
+                .style_mode(StyleMode::Always)
+                .formatter(Box::new(NoModFormatter::new()))
+                .build()
+                .unwrap()
+        };
+
+        let first = build_test_logger(|b| b.sink(Arc::new(build(true))));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "");
+        let second = build_test_logger(|b| b.sink(Arc::new(build(false))));
+
+        // Written immediately and appended in order, without flushing
+        info!(logger: first, "a ");
+        info!(logger: second, "b ");
+        info!(logger: first, "c ");
+        assert_eq!(fs::read_to_string(&path).unwrap(), "a b c ");
+    }
+
+    #[test]
+    fn streaming() {
+        let path = LOGS_PATH.join("streaming.log");
+        let sink = FileSink::builder()
+            .path(&path)
+            .truncate(true)
+            .streaming(true)
+            .formatter(Box::new(FullFormatter::new()))
+            .build()
+            .unwrap();
+        let logger = build_test_logger(|b| b.sink(Arc::new(sink)));
+
+        let body = "0123456789abcdef".repeat(4 * 1024 * 1024 / 16);
+        info!(logger: logger, kv: { len = body.len() }, "body: {}", body);
+        info!(logger: logger, "done");
+        logger.flush();
+
+        let content = fs::read_to_string(path).unwrap();
+        let lines = content.split_terminator(EOL).collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].ends_with(&format!("] body: {} len=4194304", body)));
+        assert!(lines[1].ends_with("] done"));
+    }
+
+    #[test]
+    fn clone_box() {
+        let path = LOGS_PATH.join("clone_box.log");
+        let sink = FileSink::builder()
+            .path(&path)
+            .truncate(true)
+            .formatter(Box::new(NoModFormatter::new()))
+            .build()
+            .unwrap();
+        let cloned: Arc<dyn Sink> = sink.clone_box().unwrap().into();
+        cloned.set_level_filter(LevelFilter::MoreSevereEqual(Level::Warn));
+        assert_eq!(sink.level_filter(), LevelFilter::All);
+
+        let logger = build_test_logger(|b| b.sink(Arc::new(sink)));
+        let cloned_logger = build_test_logger(|b| b.sink(cloned));
+
+        info!(logger: logger, "a ");
+        info!(logger: cloned_logger, "filtered ");
+        warn!(logger: cloned_logger, "b ");
+        // The buffer is shared, so flushing either of them is enough
+        cloned_logger.flush();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "a b ");
+    }
+}
@@ -3,8 +3,9 @@ use cfg_if::cfg_if;
 use crate::{
     formatter::{Formatter, FullFormatter},
     prelude::*,
+    sink::FlushErrorPolicy,
     sync::*,
-    Error, ErrorHandler,
+    Error, ErrorHandler, Result,
 };
 
 pub(crate) type SinkErrorHandler = Atomic<Option<ErrorHandler>>;
@@ -51,6 +52,16 @@ impl CommonImpl {
         }
     }
 
+    // Copies the configuration for `Sink::clone_box`.
+    #[must_use]
+    pub(crate) fn clone_config(&self) -> Self {
+        Self {
+            level_filter: Atomic::new(self.level_filter.load(Ordering::Relaxed)),
+            formatter: SpinRwLock::new(self.formatter.read().clone_box()),
+            error_handler: Atomic::new(self.error_handler.load(Ordering::Relaxed)),
+        }
+    }
+
     pub(crate) fn non_returnable_error(&self, from: impl AsRef<str>, err: Error) {
         match self.error_handler.load(Ordering::Relaxed) {
             Some(handler) => handler(err),
@@ -59,6 +70,43 @@ impl CommonImpl {
     }
 }
 
+// Applies a `FlushErrorPolicy` to the results of flushing a sink.
+pub(crate) struct FlushErrorState {
+    policy: FlushErrorPolicy,
+    failing: AtomicBool,
+}
+
+impl FlushErrorState {
+    #[must_use]
+    pub(crate) fn new(policy: FlushErrorPolicy) -> Self {
+        Self {
+            policy,
+            failing: AtomicBool::new(false),
+        }
+    }
+
+    #[must_use]
+    pub(crate) fn policy(&self) -> FlushErrorPolicy {
+        self.policy
+    }
+
+    pub(crate) fn handle(&self, result: Result<()>) -> Result<()> {
+        let err = match result {
+            Ok(()) => {
+                self.failing.store(false, Ordering::Relaxed);
+                return Ok(());
+            }
+            Err(err) => err,
+        };
+        let was_failing = self.failing.swap(true, Ordering::Relaxed);
+        match self.policy {
+            FlushErrorPolicy::Propagate => Err(err),
+            FlushErrorPolicy::ReportOnce if !was_failing => Err(err),
+            FlushErrorPolicy::ReportOnce | FlushErrorPolicy::Ignore => Ok(()),
+        }
+    }
+}
+
 pub(crate) struct CommonBuilderImpl {
     pub(crate) level_filter: LevelFilter,
     pub(crate) formatter: Option<Box<dyn Formatter>>,
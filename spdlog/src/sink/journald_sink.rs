@@ -3,49 +3,9 @@ use std::{io, os::raw::c_int};
 use crate::{
     formatter::JournaldFormatter,
     sink::{helper, Sink},
-    Error, Level, Record, Result, StdResult, StringBuf,
+    Error, Record, Result, StdResult, StringBuf,
 };
 
-#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
-enum SyslogLevel {
-    _Emerg = 0,
-    _Alert = 1,
-    Crit = 2,
-    Err = 3,
-    Warning = 4,
-    _Notice = 5,
-    Info = 6,
-    Debug = 7,
-}
-
-#[derive(Clone, Eq, PartialEq, Hash, Debug)]
-struct SyslogLevels([SyslogLevel; Level::count()]);
-
-impl SyslogLevels {
-    #[must_use]
-    const fn new() -> Self {
-        Self([
-            SyslogLevel::Crit,    // Critical
-            SyslogLevel::Err,     // Error
-            SyslogLevel::Warning, // Warn
-            SyslogLevel::Info,    // Info
-            SyslogLevel::Debug,   // Debug
-            SyslogLevel::Debug,   // Trace
-        ])
-    }
-
-    #[must_use]
-    fn level(&self, level: Level) -> SyslogLevel {
-        self.0[level as usize]
-    }
-}
-
-impl Default for SyslogLevels {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
 fn journal_send(args: impl Iterator<Item = impl AsRef<str>>) -> StdResult<(), io::Error> {
     #[cfg(target_os = "linux")] // https://github.com/rust-lang/rust/issues/97976
     use libsystemd_sys::{const_iovec, journal as ffi};
@@ -72,6 +32,8 @@ fn journal_send(args: impl Iterator<Item = impl AsRef<str>>) -> StdResult<(), io
 /// | `Debug`    | `debug`   |
 /// | `Trace`    | `debug`   |
 ///
+/// The priorities are syslog severities, see [`Level::to_syslog_severity`].
+///
 /// # Note
 ///
 /// It requires an additional system dependency `libsystemd`.
@@ -87,13 +49,13 @@ fn journal_send(args: impl Iterator<Item = impl AsRef<str>>) -> StdResult<(), io
 /// ```
 /// pacman -S systemd
 /// ```
+///
+/// [`Level::to_syslog_severity`]: crate::Level::to_syslog_severity
 pub struct JournaldSink {
     common_impl: helper::CommonImpl,
 }
 
 impl JournaldSink {
-    const SYSLOG_LEVELS: SyslogLevels = SyslogLevels::new();
-
     /// Constructs a builder of `JournaldSink`.
     #[must_use]
     pub fn builder() -> JournaldSinkBuilder {
@@ -117,10 +79,7 @@ impl Sink for JournaldSink {
 
         let kvs = [
             format!("MESSAGE={}", string_buf),
-            format!(
-                "PRIORITY={}",
-                JournaldSink::SYSLOG_LEVELS.level(record.level()) as u32
-            ),
+            format!("PRIORITY={}", record.level().to_syslog_severity()),
         ];
 
         let srcloc_kvs = match record.source_location() {
@@ -138,6 +97,12 @@ impl Sink for JournaldSink {
         Ok(())
     }
 
+    fn clone_box(&self) -> Option<Box<dyn Sink>> {
+        Some(Box::new(JournaldSink {
+            common_impl: self.common_impl.clone_config(),
+        }))
+    }
+
     helper::common_impl!(@Sink: common_impl);
 }
 
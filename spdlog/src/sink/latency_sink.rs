@@ -0,0 +1,311 @@
+use std::{
+    convert::Infallible,
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+use crate::{
+    formatter::FmtExtraInfo,
+    sink::{helper, Sink},
+    sync::*,
+    Record, Result, StringBuf,
+};
+
+/// A [combined sink], measuring the delivery latency of records written by the
+/// sub-sink.
+///
+/// The latency of a record is the time elapsed from its creation (see
+/// [`Record::time`]) to the moment the sub-sink has written it. When the
+/// sub-sink is wrapped in an [asynchronous combined sink], or the logger's
+/// sinks are slow, it reveals the backlog in wall-clock terms, e.g. for
+/// monitoring log delivery against an SLO.
+///
+/// Only records written successfully are measured. A record created later than
+/// the moment it's written, e.g. due to a system clock adjustment, is measured
+/// as zero latency.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// use spdlog::{
+///     prelude::*,
+///     sink::{FileSink, LatencySink},
+/// };
+///
+/// # fn main() -> Result<(), spdlog::Error> {
+/// # let path = std::env::temp_dir().join("latency_sink_example.log");
+/// let file_sink = FileSink::builder().path(path).build()?;
+/// let sink = Arc::new(LatencySink::builder().sink(Arc::new(file_sink)).build()?);
+/// let logger = Logger::builder().sink(sink.clone()).build()?;
+///
+/// info!(logger: logger, "hello");
+///
+/// let stats = sink.take_stats();
+/// println!("{} records, max latency {:?}", stats.count(), stats.max());
+/// # Ok(()) }
+/// ```
+///
+/// [combined sink]: index.html#combined-sink
+/// [asynchronous combined sink]: index.html#asynchronous-combined-sink
+pub struct LatencySink {
+    common_impl: helper::CommonImpl,
+    sink: Arc<dyn Sink>,
+    // Shared with clones
+    stats: Arc<Mutex<LatencyStats>>,
+}
+
+/// Statistics of the delivery latency measured by [`LatencySink`].
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub struct LatencyStats {
+    count: u64,
+    total: Duration,
+    max: Duration,
+    last: Duration,
+}
+
+impl LatencySink {
+    /// Constructs a builder of `LatencySink`.
+    #[must_use]
+    pub fn builder() -> LatencySinkBuilder<()> {
+        LatencySinkBuilder {
+            common_builder_impl: helper::CommonBuilderImpl::new(),
+            sink: (),
+        }
+    }
+
+    /// Gets a reference to the sub-sink.
+    #[must_use]
+    pub fn sink(&self) -> &Arc<dyn Sink> {
+        &self.sink
+    }
+
+    /// Gets the statistics measured so far.
+    ///
+    /// The statistics are shared with the clones of this sink.
+    #[must_use]
+    pub fn stats(&self) -> LatencyStats {
+        *self.stats.lock_expect()
+    }
+
+    /// Gets the statistics measured so far, and resets them.
+    ///
+    /// It's useful for reporting the statistics periodically, each report
+    /// covering the records written since the previous one.
+    pub fn take_stats(&self) -> LatencyStats {
+        std::mem::take(&mut *self.stats.lock_expect())
+    }
+
+    fn measure(&self, record: &Record, result: Result<()>) -> Result<()> {
+        if result.is_ok() {
+            let latency = SystemTime::now()
+                .duration_since(record.time())
+                .unwrap_or_default();
+            self.stats.lock_expect().push(latency);
+        }
+        result
+    }
+}
+
+impl LatencyStats {
+    /// Gets the number of measured records.
+    #[must_use]
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Gets the mean latency.
+    ///
+    /// Returns `None` if no record has been measured.
+    #[must_use]
+    pub fn mean(&self) -> Option<Duration> {
+        if self.count == 0 {
+            return None;
+        }
+        let nanos = self.total.as_nanos() / u128::from(self.count);
+        Some(Duration::from_nanos(nanos as u64))
+    }
+
+    /// Gets the maximum latency.
+    ///
+    /// Returns [`Duration::ZERO`] if no record has been measured.
+    #[must_use]
+    pub fn max(&self) -> Duration {
+        self.max
+    }
+
+    /// Gets the latency of the last measured record.
+    ///
+    /// Returns [`Duration::ZERO`] if no record has been measured.
+    #[must_use]
+    pub fn last(&self) -> Duration {
+        self.last
+    }
+
+    fn push(&mut self, latency: Duration) {
+        self.count += 1;
+        self.total += latency;
+        self.max = self.max.max(latency);
+        self.last = latency;
+    }
+}
+
+impl Sink for LatencySink {
+    fn log(&self, record: &Record) -> Result<()> {
+        if !self.should_log(record.level()) {
+            return Ok(());
+        }
+
+        self.measure(record, self.sink.log(record))
+    }
+
+    fn write_formatted(
+        &self,
+        record: &Record,
+        formatted: &StringBuf,
+        extra_info: &FmtExtraInfo,
+    ) -> Result<()> {
+        if !self.should_log(record.level()) {
+            return Ok(());
+        }
+
+        self.measure(
+            record,
+            self.sink.write_formatted(record, formatted, extra_info),
+        )
+    }
+
+    fn flush(&self) -> Result<()> {
+        self.sink.flush()
+    }
+
+    fn prefers_sync(&self) -> bool {
+        self.sink.prefers_sync()
+    }
+
+    fn clone_box(&self) -> Option<Box<dyn Sink>> {
+        Some(Box::new(LatencySink {
+            common_impl: self.common_impl.clone_config(),
+            sink: self.sink.clone(),
+            stats: self.stats.clone(),
+        }))
+    }
+
+    helper::common_impl!(@Sink: common_impl);
+}
+
+/// The builder of [`LatencySink`].
+#[doc = include_str!("../include/doc/generic-builder-note.md")]
+/// # Examples
+///
+/// - Building a [`LatencySink`].
+///
+///   ```
+///   use std::sync::Arc;
+///
+///   use spdlog::sink::{FileSink, LatencySink};
+///
+///   # fn main() -> Result<(), spdlog::Error> {
+///   # let path = std::env::temp_dir().join("latency_sink_builder.log");
+///   # let file_sink = FileSink::builder().path(path).build()?;
+///   let sink: LatencySink = LatencySink::builder()
+///       .sink(Arc::new(file_sink)) // required
+///       .build()?;
+///   # Ok(()) }
+///   ```
+///
+/// - If any required parameters are missing, a compile-time error will be
+///   raised.
+///
+///   ```compile_fail,E0061
+///   use spdlog::sink::LatencySink;
+///
+///   # fn main() -> Result<(), spdlog::Error> {
+///   let sink: LatencySink = LatencySink::builder()
+///       // .sink(file_sink) // required
+///       .build()?;
+///   # Ok(()) }
+///   ```
+pub struct LatencySinkBuilder<ArgSink> {
+    common_builder_impl: helper::CommonBuilderImpl,
+    sink: ArgSink,
+}
+
+impl<ArgSink> LatencySinkBuilder<ArgSink> {
+    /// The sub-sink to measure the delivery latency of.
+    ///
+    /// This parameter is **required**.
+    #[must_use]
+    pub fn sink(self, sink: Arc<dyn Sink>) -> LatencySinkBuilder<Arc<dyn Sink>> {
+        LatencySinkBuilder {
+            common_builder_impl: self.common_builder_impl,
+            sink,
+        }
+    }
+
+    helper::common_impl!(@SinkBuilder: common_builder_impl);
+}
+
+impl LatencySinkBuilder<()> {
+    #[doc(hidden)]
+    #[deprecated(note = "\n\n\
+        builder compile-time error:\n\
+        - missing required field `sink`\n\n\
+    ")]
+    pub fn build(self, _: Infallible) {}
+}
+
+impl LatencySinkBuilder<Arc<dyn Sink>> {
+    /// Builds a [`LatencySink`].
+    pub fn build(self) -> Result<LatencySink> {
+        Ok(LatencySink {
+            common_impl: helper::CommonImpl::from_builder(self.common_builder_impl),
+            sink: self.sink,
+            stats: Arc::new(Mutex::new(LatencyStats::default())),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{prelude::*, test_utils::*};
+
+    #[test]
+    fn measure() {
+        let delay = Duration::from_millis(50);
+        let sink = Arc::new(
+            LatencySink::builder()
+                .sink(Arc::new(CounterSink::with_delay(Some(delay))))
+                .build()
+                .unwrap(),
+        );
+        let logger = build_test_logger(|b| b.sink(sink.clone()));
+
+        assert_eq!(sink.stats(), LatencyStats::default());
+        assert_eq!(sink.stats().mean(), None);
+
+        info!(logger: logger, "hello");
+        info!(logger: logger, "world");
+
+        let stats = sink.stats();
+        assert_eq!(stats.count(), 2);
+        assert!(stats.mean().unwrap() >= delay);
+        assert!(stats.max() >= delay);
+        assert!(stats.last() >= delay);
+        assert!(stats.max() < Duration::from_secs(10));
+
+        // Clones share the statistics
+        let cloned = sink.clone_box().unwrap();
+        let future_record = Record::builder(Level::Info, "from the future")
+            .time(SystemTime::now() + Duration::from_secs(3600))
+            .build();
+        cloned.log(&future_record).unwrap();
+
+        let stats = sink.take_stats();
+        assert_eq!(stats.count(), 3);
+        assert_eq!(stats.last(), Duration::ZERO);
+        assert_eq!(sink.stats(), LatencyStats::default());
+    }
+}
@@ -0,0 +1,222 @@
+use std::{ops::RangeBounds, sync::Arc};
+
+use crate::{
+    formatter::FmtExtraInfo,
+    sink::{helper, Sink},
+    Error, Level, Record, Result, StringBuf,
+};
+
+/// A [combined sink], dispatching records to sub-sinks by their levels.
+///
+/// Each route maps a range of levels to a sub-sink, and a record is dispatched
+/// to the sub-sinks of all routes whose ranges contain its level. Routes may
+/// overlap, e.g. warnings and errors to `errors.log` while everything to
+/// `all.log`, which is cleaner than giving each sink its own level filter.
+///
+/// # Filtering
+///
+/// Records are filtered by the level filter of `LevelRoutingSink` first, then
+/// dispatched by the routes, and then filtered by the level filters of
+/// sub-sinks.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// use spdlog::{
+///     prelude::*,
+///     sink::{FileSink, LevelRoutingSink},
+/// };
+///
+/// # fn main() -> Result<(), spdlog::Error> {
+/// # let dir = std::env::temp_dir();
+/// let errors_sink = Arc::new(FileSink::builder().path(dir.join("errors.log")).build()?);
+/// let all_sink = Arc::new(FileSink::builder().path(dir.join("all.log")).build()?);
+///
+/// let sink = Arc::new(
+///     LevelRoutingSink::builder()
+///         .route(Level::Warn..=Level::Critical, errors_sink)
+///         .catch_all(all_sink)
+///         .build()?,
+/// );
+/// let logger = Logger::builder().sink(sink).build()?;
+///
+/// // Written to `all.log` only
+/// info!(logger: logger, "hello");
+/// // Written to both `errors.log` and `all.log`
+/// warn!(logger: logger, "disk is almost full");
+/// # Ok(()) }
+/// ```
+///
+/// [combined sink]: index.html#combined-sink
+pub struct LevelRoutingSink {
+    common_impl: helper::CommonImpl,
+    routes: Vec<Route>,
+}
+
+#[derive(Clone)]
+struct Route {
+    // Indexed by `Level as usize`
+    levels: [bool; Level::count()],
+    sink: Arc<dyn Sink>,
+}
+
+impl LevelRoutingSink {
+    /// Constructs a builder of `LevelRoutingSink`.
+    #[must_use]
+    pub fn builder() -> LevelRoutingSinkBuilder {
+        LevelRoutingSinkBuilder {
+            common_builder_impl: helper::CommonBuilderImpl::new(),
+            routes: vec![],
+        }
+    }
+
+    /// Gets an iterator over the sub-sinks that records of the specified level
+    /// are dispatched to, in the order their routes were added.
+    pub fn sinks_for(&self, level: Level) -> impl Iterator<Item = &Arc<dyn Sink>> {
+        self.routes
+            .iter()
+            .filter(move |route| route.levels[level as usize])
+            .map(|route| &route.sink)
+    }
+}
+
+impl Sink for LevelRoutingSink {
+    fn log(&self, record: &Record) -> Result<()> {
+        if !self.should_log(record.level()) {
+            return Ok(());
+        }
+
+        let mut result = Ok(());
+        for sink in self.sinks_for(record.level()) {
+            result = Error::push_result(result, sink.log(record));
+        }
+        result
+    }
+
+    fn write_formatted(
+        &self,
+        record: &Record,
+        formatted: &StringBuf,
+        extra_info: &FmtExtraInfo,
+    ) -> Result<()> {
+        if !self.should_log(record.level()) {
+            return Ok(());
+        }
+
+        let mut result = Ok(());
+        for sink in self.sinks_for(record.level()) {
+            result =
+                Error::push_result(result, sink.write_formatted(record, formatted, extra_info));
+        }
+        result
+    }
+
+    fn flush(&self) -> Result<()> {
+        let mut result = Ok(());
+        for route in &self.routes {
+            result = Error::push_result(result, route.sink.flush());
+        }
+        result
+    }
+
+    fn prefers_sync(&self) -> bool {
+        self.routes.iter().any(|route| route.sink.prefers_sync())
+    }
+
+    fn clone_box(&self) -> Option<Box<dyn Sink>> {
+        Some(Box::new(LevelRoutingSink {
+            common_impl: self.common_impl.clone_config(),
+            routes: self.routes.clone(),
+        }))
+    }
+
+    helper::common_impl!(@Sink: common_impl);
+}
+
+/// The builder of [`LevelRoutingSink`].
+///
+/// # Examples
+///
+/// See the documentation of [`LevelRoutingSink`].
+pub struct LevelRoutingSinkBuilder {
+    common_builder_impl: helper::CommonBuilderImpl,
+    routes: Vec<Route>,
+}
+
+impl LevelRoutingSinkBuilder {
+    /// Adds a route, dispatching records with levels in the range to the
+    /// sub-sink.
+    ///
+    /// Levels are ordered from the more verbose to the more severe, e.g.
+    /// `Level::Warn..=Level::Critical` contains warnings, errors and critical
+    /// records. Use `level..=level` for a single level.
+    #[must_use]
+    pub fn route<R>(mut self, levels: R, sink: Arc<dyn Sink>) -> Self
+    where
+        R: RangeBounds<Level>,
+    {
+        let mut mask = [false; Level::count()];
+        for level in Level::iter().filter(|level| levels.contains(level)) {
+            mask[level as usize] = true;
+        }
+        self.routes.push(Route { levels: mask, sink });
+        self
+    }
+
+    /// Adds a catch-all route, dispatching records of all levels to the
+    /// sub-sink, regardless of the other routes.
+    ///
+    /// It's equivalent to `route(.., sink)`.
+    #[must_use]
+    pub fn catch_all(self, sink: Arc<dyn Sink>) -> Self {
+        self.route(.., sink)
+    }
+
+    helper::common_impl!(@SinkBuilder: common_builder_impl);
+
+    /// Builds a [`LevelRoutingSink`].
+    pub fn build(self) -> Result<LevelRoutingSink> {
+        Ok(LevelRoutingSink {
+            common_impl: helper::CommonImpl::from_builder(self.common_builder_impl),
+            routes: self.routes,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{prelude::*, test_utils::*};
+
+    #[test]
+    fn overlapping_routes() {
+        let warn_sink = Arc::new(CounterSink::new());
+        let debug_sink = Arc::new(CounterSink::new());
+        let catch_all_sink = Arc::new(CounterSink::new());
+        let sink = Arc::new(
+            LevelRoutingSink::builder()
+                .route(Level::Warn..=Level::Critical, warn_sink.clone())
+                .route(Level::Debug..=Level::Debug, debug_sink.clone())
+                .catch_all(catch_all_sink.clone())
+                .build()
+                .unwrap(),
+        );
+        let logger = build_test_logger(|b| b.sink(sink.clone()).level_filter(LevelFilter::All));
+
+        warn!(logger: logger, "warn");
+        info!(logger: logger, "info");
+        debug!(logger: logger, "debug");
+        critical!(logger: logger, "critical");
+
+        assert_eq!(warn_sink.payloads(), ["warn", "critical"]);
+        assert_eq!(debug_sink.payloads(), ["debug"]);
+        assert_eq!(
+            catch_all_sink.payloads(),
+            ["warn", "info", "debug", "critical"]
+        );
+        assert_eq!(sink.sinks_for(Level::Trace).count(), 1);
+        assert_eq!(sink.sinks_for(Level::Error).count(), 2);
+    }
+}
@@ -0,0 +1,220 @@
+use std::{
+    ffi::CString,
+    io,
+    os::raw::{c_char, c_int},
+};
+
+use crate::{
+    formatter::{__pattern::Payload, PatternFormatter},
+    sink::{helper, Sink},
+    Error, Level, Record, Result, StringBuf,
+};
+
+#[cfg_attr(target_os = "android", link(name = "log"))]
+extern "C" {
+    fn __android_log_write(prio: c_int, tag: *const c_char, text: *const c_char) -> c_int;
+}
+
+// Priorities defined in `android/log.h`
+const ANDROID_LOG_VERBOSE: c_int = 2;
+const ANDROID_LOG_DEBUG: c_int = 3;
+const ANDROID_LOG_INFO: c_int = 4;
+const ANDROID_LOG_WARN: c_int = 5;
+const ANDROID_LOG_ERROR: c_int = 6;
+const ANDROID_LOG_FATAL: c_int = 7;
+
+/// The maximum length in bytes of tags, longer tags are rejected by Android
+/// versions before 8.0 (API level 26).
+const MAX_TAG_LEN: usize = 23;
+
+/// A sink with Android logcat as the target.
+///
+/// Records are written with `__android_log_write`, so they show up in `adb
+/// logcat` and Android Studio along with the logs of the Java side of the app.
+///
+/// The tag of a record is the name of its logger, or
+/// [`LogcatSinkBuilder::default_tag`] if the logger has no name. Tags longer
+/// than 23 bytes are truncated, as Android versions before 8.0 reject them.
+///
+/// Logcat records the time, the process and thread IDs, the priority and the
+/// tag on its own, so the default formatter of this sink only writes the
+/// payload, i.e. `PatternFormatter::new(pattern!("{payload}"))`.
+///
+/// # Log Level Mapping
+///
+/// | spdlog-rs  | Android priority      |
+/// |------------|-----------------------|
+/// | `Critical` | `ANDROID_LOG_FATAL`   |
+/// | `Error`    | `ANDROID_LOG_ERROR`   |
+/// | `Warn`     | `ANDROID_LOG_WARN`    |
+/// | `Info`     | `ANDROID_LOG_INFO`    |
+/// | `Debug`    | `ANDROID_LOG_DEBUG`   |
+/// | `Trace`    | `ANDROID_LOG_VERBOSE` |
+///
+/// # Note
+///
+/// It's only available on Android, with crate feature `native` enabled.
+///
+/// It links to `liblog`, which is a system library shipped with the Android
+/// NDK and present on all devices, so no additional dependency is required.
+/// Shared libraries loaded via JNI (e.g. built with `cargo ndk`) are linked to
+/// it automatically.
+pub struct LogcatSink {
+    common_impl: helper::CommonImpl,
+    default_tag: CString,
+}
+
+impl LogcatSink {
+    /// Constructs a builder of `LogcatSink`.
+    #[must_use]
+    pub fn builder() -> LogcatSinkBuilder {
+        LogcatSinkBuilder {
+            common_builder_impl: helper::CommonBuilderImpl::new(),
+            default_tag: "spdlog-rs".to_string(),
+        }
+    }
+}
+
+impl Sink for LogcatSink {
+    fn log(&self, record: &Record) -> Result<()> {
+        if !self.should_log(record.level()) {
+            return Ok(());
+        }
+
+        let mut string_buf = StringBuf::new();
+        self.common_impl
+            .formatter
+            .read()
+            .format(record, &mut string_buf)?;
+
+        let tag = record.logger_name().map(to_tag);
+        let tag = tag.as_ref().unwrap_or(&self.default_tag);
+        let text = to_c_string(&string_buf);
+
+        let result = unsafe {
+            __android_log_write(to_priority(record.level()), tag.as_ptr(), text.as_ptr())
+        };
+        if result < 0 {
+            return Err(Error::WriteRecord(io::Error::from_raw_os_error(-result)));
+        }
+        Ok(())
+    }
+
+    fn flush(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn clone_box(&self) -> Option<Box<dyn Sink>> {
+        Some(Box::new(LogcatSink {
+            common_impl: self.common_impl.clone_config(),
+            default_tag: self.default_tag.clone(),
+        }))
+    }
+
+    helper::common_impl!(@Sink: common_impl);
+}
+
+#[must_use]
+fn to_priority(level: Level) -> c_int {
+    match level {
+        Level::Critical => ANDROID_LOG_FATAL,
+        Level::Error => ANDROID_LOG_ERROR,
+        Level::Warn => ANDROID_LOG_WARN,
+        Level::Info => ANDROID_LOG_INFO,
+        Level::Debug => ANDROID_LOG_DEBUG,
+        Level::Trace => ANDROID_LOG_VERBOSE,
+    }
+}
+
+// Truncates the tag to `MAX_TAG_LEN` bytes, at a char boundary.
+#[must_use]
+fn to_tag(name: &str) -> CString {
+    let mut len = name.len().min(MAX_TAG_LEN);
+    while !name.is_char_boundary(len) {
+        len -= 1;
+    }
+    to_c_string(&name[..len])
+}
+
+// Interior NUL bytes would end the string early, they are removed.
+#[must_use]
+fn to_c_string(s: &str) -> CString {
+    CString::new(s.replace('\0', "")).unwrap()
+}
+
+/// The builder of [`LogcatSink`].
+///
+/// # Examples
+///
+/// - Building a [`LogcatSink`].
+///
+///   ```no_run
+///   use spdlog::{prelude::*, sink::LogcatSink};
+///
+///   # fn main() -> Result<(), spdlog::Error> {
+///   let sink: LogcatSink = LogcatSink::builder()
+///       .default_tag("my-app") // optional, defaults to `"spdlog-rs"`
+///       .level_filter(LevelFilter::MoreSevere(Level::Info)) // optional
+///       .build()?;
+///   # Ok(()) }
+///   ```
+pub struct LogcatSinkBuilder {
+    common_builder_impl: helper::CommonBuilderImpl,
+    default_tag: String,
+}
+
+impl LogcatSinkBuilder {
+    /// Specifies the tag of records whose loggers have no name.
+    ///
+    /// It's truncated to 23 bytes as the names of loggers are.
+    ///
+    /// This parameter is **optional**, and defaults to `"spdlog-rs"`.
+    #[must_use]
+    pub fn default_tag<S>(mut self, default_tag: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.default_tag = default_tag.into();
+        self
+    }
+
+    helper::common_impl!(@SinkBuilder: common_builder_impl);
+
+    /// Builds a [`LogcatSink`].
+    pub fn build(self) -> Result<LogcatSink> {
+        let sink = LogcatSink {
+            common_impl: helper::CommonImpl::from_builder_with_formatter(
+                self.common_builder_impl,
+                || Box::new(PatternFormatter::new(Payload::default())),
+            ),
+            default_tag: to_tag(&self.default_tag),
+        };
+        Ok(sink)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tag() {
+        assert_eq!(to_tag("network").to_str().unwrap(), "network");
+        assert_eq!(
+            to_tag("com.example.app.service.db.pool").to_str().unwrap(),
+            "com.example.app.service"
+        );
+        // Not splitting a char, `é` is 2 bytes
+        assert_eq!(
+            to_tag("aaaaaaaaaaaaaaaaaaaaaaé").to_str().unwrap(),
+            "aaaaaaaaaaaaaaaaaaaaaa"
+        );
+        assert_eq!(to_tag("a\0b").to_str().unwrap(), "ab");
+    }
+
+    #[test]
+    fn priority() {
+        assert_eq!(to_priority(Level::Critical), ANDROID_LOG_FATAL);
+        assert_eq!(to_priority(Level::Trace), ANDROID_LOG_VERBOSE);
+    }
+}
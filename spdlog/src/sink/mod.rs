@@ -34,7 +34,8 @@
 //! Since the thread pool has a capacity limit, the queue may be full in some
 //! cases. When users encounter this situation, they have the following options:
 //!
-//!  - Adjust to a larger capacity via [`ThreadPoolBuilder::capacity`].
+//!  - Adjust to a larger capacity via [`ThreadPoolBuilder::capacity`], or use
+//!    an unbounded channel via [`ThreadPoolBuilder::unbounded`].
 //!
 //!  - Adjust the overflow policy via [`AsyncPoolSinkBuilder::overflow_policy`].
 //!
@@ -44,41 +45,115 @@
 //!
 //! [`Logger`]: crate::logger::Logger
 //! [`ThreadPoolBuilder::capacity`]: crate::ThreadPoolBuilder::capacity
+//! [`ThreadPoolBuilder::unbounded`]: crate::ThreadPoolBuilder::unbounded
 
 #[cfg(feature = "multi-thread")]
 pub(crate) mod async_sink;
+#[cfg(feature = "multi-thread")]
+mod channel_sink;
+mod circuit_breaker_sink;
+mod counting_sink;
+mod daily_file_sink;
 mod dedup_sink;
 mod file_sink;
 mod helper;
+mod hourly_file_sink;
 #[cfg(any(
     all(target_os = "linux", feature = "native", feature = "libsystemd"),
     all(doc, not(doctest))
 ))]
 mod journald_sink;
+mod latency_sink;
+mod level_routing_sink;
+#[cfg(any(all(target_os = "android", feature = "native"), all(doc, not(doctest))))]
+mod logcat_sink;
+mod null_sink;
+#[cfg(any(
+    all(target_vendor = "apple", feature = "native"),
+    all(doc, not(doctest))
+))]
+mod os_log_sink;
+mod path_template;
 mod rotating_file_sink;
+mod single_threaded_file_sink;
 mod std_stream_sink;
+mod strip_color_sink;
+mod tag_routing_sink;
+mod tee_sink;
+mod udp_sink;
 #[cfg(any(all(windows, feature = "native"), all(doc, not(doctest))))]
 mod win_debug_sink;
 mod write_sink;
 
 #[cfg(feature = "multi-thread")]
 pub use async_sink::*;
+#[cfg(feature = "multi-thread")]
+pub use channel_sink::*;
+pub use circuit_breaker_sink::*;
+pub use counting_sink::*;
+pub use daily_file_sink::*;
 pub use dedup_sink::*;
 pub use file_sink::*;
+pub use hourly_file_sink::*;
 #[cfg(any(
     all(target_os = "linux", feature = "native", feature = "libsystemd"),
     all(doc, not(doctest))
 ))]
 pub use journald_sink::*;
+pub use latency_sink::*;
+pub use level_routing_sink::*;
+#[cfg(any(all(target_os = "android", feature = "native"), all(doc, not(doctest))))]
+pub use logcat_sink::*;
+pub use null_sink::*;
+#[cfg(any(
+    all(target_vendor = "apple", feature = "native"),
+    all(doc, not(doctest))
+))]
+pub use os_log_sink::*;
 pub use rotating_file_sink::*;
+pub use single_threaded_file_sink::*;
 pub use std_stream_sink::*;
+pub use strip_color_sink::*;
+pub use tag_routing_sink::*;
+pub use tee_sink::*;
+pub use udp_sink::*;
 #[cfg(any(all(windows, feature = "native"), all(doc, not(doctest))))]
 pub use win_debug_sink::*;
 pub use write_sink::*;
 
-use crate::{formatter::Formatter, sync::*, ErrorHandler, Level, LevelFilter, Record, Result};
+use crate::{
+    formatter::{FmtExtraInfo, Formatter},
+    sync::*,
+    ErrorHandler, Level, LevelFilter, Record, Result, StringBuf,
+};
 
 /// A trait for sinks.
+///
+/// # Formatting
+///
+/// Sinks format records by themselves, with the formatter set via
+/// [`Sink::set_formatter`]. Along with the formatted text,
+/// [`Formatter::format`] returns a [`FmtExtraInfo`], which is available to any
+/// sink, including those implemented outside this crate, e.g. for rendering
+/// [`FmtExtraInfo::style_range`] in colors.
+///
+/// A sink gets the formatted text and its [`FmtExtraInfo`] in two ways:
+///
+///  - In [`Sink::log`], from calling its own formatter.
+///  - In [`Sink::write_formatted`], from a combined sink (e.g. [`TeeSink`])
+///    that has formatted the record once for multiple sinks.
+///
+/// Sinks writing text should implement both methods with a shared write path
+/// taking the text and the [`FmtExtraInfo`], so that they behave the same way.
+/// See [./examples] directory for a sink rendering the style range on its own.
+///
+/// Sinks sending bytes to protocol targets (e.g. [`UdpSink`]) call
+/// [`Formatter::format_bytes`] in [`Sink::log`] instead, so that binary
+/// formatters can skip the UTF-8 text. In [`Sink::write_formatted`], they
+/// still receive text.
+///
+/// [`FmtExtraInfo::style_range`]: crate::formatter::FmtExtraInfo::style_range
+/// [./examples]: https://github.com/SpriteOvO/spdlog-rs/tree/main/spdlog/examples
 pub trait Sink: Sync + Send {
     /// Determines if a log message with the specified level would be logged.
     #[must_use]
@@ -92,6 +167,34 @@ pub trait Sink: Sync + Send {
     /// filter records.
     fn log(&self, record: &Record) -> Result<()>;
 
+    /// Writes a record that has already been formatted by another formatter.
+    ///
+    /// This is used by [`TeeSink`] to format a record only once for multiple
+    /// sinks. `formatted` and `extra_info` are the output of the other
+    /// formatter, the formatter of this sink is not used. `record` is the
+    /// original record, which is still needed for filtering and for
+    /// level-related output, e.g. [`StdStreamSink`] renders the
+    /// [`FmtExtraInfo::style_range`] of the shared text in its own color for
+    /// the level of the record.
+    ///
+    /// Implementors should always call [`Sink::should_log`] internally to
+    /// filter records.
+    ///
+    /// The default implementation ignores the formatted text and calls
+    /// [`Sink::log`], so sinks that do not write raw text still work, but
+    /// format the record by themselves.
+    ///
+    /// [`FmtExtraInfo::style_range`]: crate::formatter::FmtExtraInfo::style_range
+    fn write_formatted(
+        &self,
+        record: &Record,
+        formatted: &StringBuf,
+        extra_info: &FmtExtraInfo,
+    ) -> Result<()> {
+        let _ = (formatted, extra_info);
+        self.log(record)
+    }
+
     /// Flushes any buffered records.
     fn flush(&self) -> Result<()>;
 
@@ -116,7 +219,132 @@ pub trait Sink: Sync + Send {
     ///
     /// [`Logger`]: crate::logger::Logger
     fn set_error_handler(&self, handler: Option<ErrorHandler>);
+
+    /// Determines if the sink prefers to be called synchronously, on the thread
+    /// that logs the record.
+    ///
+    /// Sinks that read thread-local state while logging (e.g. a context
+    /// snapshotted per thread, like an MDC) would read the state of the wrong
+    /// thread if they were called on a worker thread of an [asynchronous
+    /// combined sink]. Such sinks should return `true`, then [`AsyncPoolSink`]
+    /// calls them synchronously in its `log` and `flush`, and only performs
+    /// operations on the other sub-sinks asynchronously.
+    ///
+    /// Data carried by [`Record`] is captured when it's constructed, so it's
+    /// always correct regardless of this hint.
+    ///
+    /// Combined sinks return `true` if any of their sub-sinks does. The default
+    /// implementation returns `false`.
+    ///
+    /// [asynchronous combined sink]: index.html#asynchronous-combined-sink
+    /// [`AsyncPoolSink`]: crate::sink::AsyncPoolSink
+    #[must_use]
+    fn prefers_sync(&self) -> bool {
+        false
+    }
+
+    /// Gets a descriptive name of the type of the sink, for introspection
+    /// purposes (e.g. showing sinks of loggers in an admin page).
+    ///
+    /// The default implementation returns [`std::any::type_name`] of the
+    /// implementor, e.g. `spdlog::sink::file_sink::FileSink`. Like
+    /// [`std::any::type_name`], the exact content is not guaranteed to be
+    /// stable, so it should not be relied on for identifying sinks.
+    #[must_use]
+    fn type_name(&self) -> &'static str {
+        std::any::type_name::<Self>()
+    }
+
+    /// Clones the sink into a new sink writing to the same target.
+    ///
+    /// It's useful for building a sink once as a template, then adding copies
+    /// of it to multiple loggers, each configured separately. Returns `None`
+    /// if the sink does not support cloning.
+    ///
+    /// # Semantics
+    ///
+    /// The configuration is copied, i.e. the level filter, the formatter, the
+    /// error handler and the other settings of the clone are independent of
+    /// the original sink. Setting them on either sink does not affect the
+    /// other.
+    ///
+    /// The target and the state tied to it are shared rather than duplicated:
+    ///
+    ///  - File sinks share the opened file and its buffer, no second handle is
+    ///    opened. So there are no conflicts in file locking or in rotation, the
+    ///    clones rotate together, and [`FileSink::reopen`] on either one
+    ///    reopens the file for both.
+    ///  - Other sinks writing to a target (e.g. [`StdStreamSink`] and
+    ///    [`UdpSink`]) share it along with its buffer, so the records of the
+    ///    clones are not interleaved in the middle.
+    ///  - Combined sinks share their sub-sinks, the sub-sinks are not cloned.
+    ///  - State affecting only what a sink outputs is not shared, e.g. a clone
+    ///    of [`DedupSink`] starts with no skipped records.
+    ///
+    /// [`AsyncBatchSink`] cannot be cloned, since it owns a worker thread and
+    /// pending batches. [`WriteSink`] cannot be cloned either, since its target
+    /// is not required to be `'static`.
+    ///
+    /// The default implementation returns `None`, sinks implemented outside
+    /// this crate opt in by overriding it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use spdlog::{prelude::*, sink::{Sink, StdStreamSink, StdStream}};
+    ///
+    /// # fn main() -> Result<(), spdlog::Error> {
+    /// let template = StdStreamSink::builder()
+    ///     .std_stream(StdStream::Stderr)
+    ///     .build()?;
+    ///
+    /// let verbose: Arc<dyn Sink> = template.clone_box().unwrap().into();
+    /// verbose.set_level_filter(LevelFilter::All);
+    /// let quiet: Arc<dyn Sink> = template.clone_box().unwrap().into();
+    /// quiet.set_level_filter(LevelFilter::MoreSevereEqual(Level::Warn));
+    ///
+    /// let network = Logger::builder().name("network").sink(verbose).build()?;
+    /// let storage = Logger::builder().name("storage").sink(quiet).build()?;
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// [`FileSink::reopen`]: crate::sink::FileSink::reopen
+    #[must_use]
+    fn clone_box(&self) -> Option<Box<dyn Sink>> {
+        None
+    }
 }
 
 /// A container for [`Sink`]s.
 pub type Sinks = Vec<Arc<dyn Sink>>;
+
+/// Policy for handling errors flushing the buffer of a sink, e.g. when the disk
+/// is full or a pipe is broken.
+///
+/// Such errors tend to persist, and a sink is flushed repeatedly (by the
+/// logger's flush policies, see [`Logger::set_flush_level_filter`] and
+/// [`Logger::set_flush_period`]), so the same error may be reported many times.
+/// The policy decides which of them are returned from [`Sink::flush`], and in
+/// turn passed to the error handler by the logger. Errors flushing the buffer
+/// when the sink is dropped are handled by the policy as well.
+///
+/// It's supported by sinks buffering records, i.e. [`FileSink`],
+/// [`RotatingFileSink`], [`StdStreamSink`] and [`WriteSink`].
+///
+/// [`Logger::set_flush_level_filter`]: crate::Logger::set_flush_level_filter
+/// [`Logger::set_flush_period`]: crate::Logger::set_flush_period
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[non_exhaustive]
+pub enum FlushErrorPolicy {
+    /// Every error is returned.
+    Propagate,
+    /// Only the first error of consecutive failed flushes is returned, the
+    /// following errors are ignored until a flush succeeds.
+    ReportOnce,
+    /// Errors are ignored, the sink continues as if the flush succeeded.
+    ///
+    /// Note that the buffered records may be lost.
+    Ignore,
+}
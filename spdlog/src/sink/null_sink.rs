@@ -0,0 +1,94 @@
+//! Provides a sink that discards all log messages.
+
+use crate::{formatter::Formatter, sink::Sink, sync::*, ErrorHandler, LevelFilter, Record, Result};
+
+/// A sink that discards all log messages.
+///
+/// It neither formats records nor holds a formatter, so logging to it costs
+/// only the level filter check. It's useful for disabling the output of a
+/// logger without removing it, as a placeholder in tests, or for measuring the
+/// overhead of the logging pipeline excluding sinks.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// use spdlog::{prelude::*, sink::NullSink};
+///
+/// # fn main() -> Result<(), spdlog::Error> {
+/// let logger = Logger::builder().sink(Arc::new(NullSink::new())).build()?;
+///
+/// info!(logger: logger, "this message goes nowhere");
+/// # Ok(()) }
+/// ```
+pub struct NullSink {
+    level_filter: Atomic<LevelFilter>,
+}
+
+impl NullSink {
+    /// Constructs a `NullSink`.
+    ///
+    /// The level filter defaults to [`LevelFilter::All`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            level_filter: Atomic::new(LevelFilter::All),
+        }
+    }
+}
+
+impl Default for NullSink {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Sink for NullSink {
+    fn log(&self, _record: &Record) -> Result<()> {
+        Ok(())
+    }
+
+    fn flush(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn level_filter(&self) -> LevelFilter {
+        self.level_filter.load(Ordering::Relaxed)
+    }
+
+    fn set_level_filter(&self, level_filter: LevelFilter) {
+        self.level_filter.store(level_filter, Ordering::Relaxed);
+    }
+
+    /// The formatter is discarded, as `NullSink` never formats records.
+    fn set_formatter(&self, _formatter: Box<dyn Formatter>) {}
+
+    /// The handler is discarded, as `NullSink` never fails.
+    fn set_error_handler(&self, _handler: Option<ErrorHandler>) {}
+
+    fn clone_box(&self) -> Option<Box<dyn Sink>> {
+        let sink = Self::new();
+        sink.set_level_filter(self.level_filter());
+        Some(Box::new(sink))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{prelude::*, test_utils::*};
+
+    #[test]
+    fn discard() {
+        let sink = Arc::new(NullSink::new());
+        let logger = build_test_logger(|b| b.sink(sink.clone()));
+
+        info!(logger: logger, "hello");
+        logger.flush();
+        assert!(sink.should_log(Level::Trace));
+
+        sink.set_level_filter(LevelFilter::Off);
+        assert!(!sink.should_log(Level::Critical));
+    }
+}
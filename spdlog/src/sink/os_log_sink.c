@@ -0,0 +1,8 @@
+// `os_log_with_type` is a macro, which encodes the format string and the
+// arguments at compile time, so it cannot be called from Rust directly.
+
+#include <os/log.h>
+
+void spdlog_rs_os_log_public_str(os_log_t log, os_log_type_t type, const char *message) {
+    os_log_with_type(log, type, "%{public}s", message);
+}
@@ -0,0 +1,301 @@
+use std::{
+    collections::HashMap,
+    ffi::{c_void, CString},
+    os::raw::{c_char, c_uchar},
+};
+
+use crate::{
+    formatter::{__pattern::Payload, PatternFormatter},
+    sink::{helper, Sink},
+    sync::*,
+    Level, Record, Result, StringBuf,
+};
+
+type OsLogT = *mut c_void;
+type OsLogTypeT = c_uchar;
+
+extern "C" {
+    fn os_log_create(subsystem: *const c_char, category: *const c_char) -> OsLogT;
+    fn os_log_type_enabled(log: OsLogT, r#type: OsLogTypeT) -> bool;
+    fn os_release(object: *mut c_void);
+
+    // Defined in `os_log_sink.c`, built by the build script.
+    fn spdlog_rs_os_log_public_str(log: OsLogT, r#type: OsLogTypeT, message: *const c_char);
+}
+
+// Types defined in `os/log.h`
+const OS_LOG_TYPE_DEFAULT: OsLogTypeT = 0x00;
+const OS_LOG_TYPE_INFO: OsLogTypeT = 0x01;
+const OS_LOG_TYPE_DEBUG: OsLogTypeT = 0x02;
+const OS_LOG_TYPE_ERROR: OsLogTypeT = 0x10;
+const OS_LOG_TYPE_FAULT: OsLogTypeT = 0x11;
+
+/// A sink with the Apple unified logging system (`os_log`) as the target.
+///
+/// Records are written with `os_log_with_type`, so they show up in
+/// Console.app and in the output of `log stream` and `log show`, along with
+/// the logs of the system and of the Swift and Objective-C sides of the app.
+///
+/// The subsystem of records is [`OsLogSinkBuilder::subsystem`], and their
+/// category is the name of their logger, or
+/// [`OsLogSinkBuilder::default_category`] if the logger has no name. Both can
+/// be used for filtering, e.g.
+///
+/// ```sh
+/// log stream --predicate 'subsystem == "com.example.app" && category == "net"'
+/// ```
+///
+/// The unified logging system records the time, the process and thread IDs,
+/// the type and the source image on its own, so the default formatter of this
+/// sink only writes the payload, i.e.
+/// `PatternFormatter::new(pattern!("{payload}"))`.
+///
+/// # Log Level Mapping
+///
+/// | spdlog-rs  | `os_log` type         |
+/// |------------|-----------------------|
+/// | `Critical` | `OS_LOG_TYPE_FAULT`   |
+/// | `Error`    | `OS_LOG_TYPE_ERROR`   |
+/// | `Warn`     | `OS_LOG_TYPE_DEFAULT` |
+/// | `Info`     | `OS_LOG_TYPE_INFO`    |
+/// | `Debug`    | `OS_LOG_TYPE_DEBUG`   |
+/// | `Trace`    | `OS_LOG_TYPE_DEBUG`   |
+///
+/// By default, the system keeps records of types `OS_LOG_TYPE_INFO` and
+/// `OS_LOG_TYPE_DEBUG` in memory only, and discards `OS_LOG_TYPE_DEBUG` unless
+/// debug logging is enabled for the subsystem, e.g. with `log config` or by
+/// `log stream --level debug`. Records discarded this way are not formatted.
+///
+/// # Privacy
+///
+/// `os_log` requires a static format string, and redacts dynamic arguments as
+/// `<private>` unless they are marked public, to keep personal data out of
+/// logs collected from users' devices. A record of spdlog-rs is formatted
+/// before being passed to `os_log`, so the whole formatted message is passed
+/// as a single string argument of format `"%{public}s"`.
+///
+/// Thus, **messages are never redacted**, they are visible in plain text to
+/// anyone who can read the logs of the device, including sysdiagnose reports
+/// sent to Apple or to you. Do not log personal data or secrets with this
+/// sink, or filter them out with a custom formatter.
+///
+/// # Note
+///
+/// It's only available on Apple platforms (macOS, iOS, etc.), with crate
+/// feature `native` enabled.
+///
+/// The functions of `os_log` are part of `libSystem`, which every program is
+/// linked to on these platforms, so no additional dependency is required.
+/// However, `os_log_with_type` is a C macro, so a small C shim calling it is
+/// compiled by the build script, which requires a C compiler (e.g. Xcode or
+/// its Command Line Tools). The unified logging system is available since
+/// macOS 10.12 and iOS 10.0.
+///
+/// Records are truncated by the system if they are too long, around 1 KiB for
+/// records kept persistently. Interior NUL bytes of messages are removed.
+pub struct OsLogSink {
+    common_impl: helper::CommonImpl,
+    subsystem: CString,
+    default_category: CString,
+    // Keyed by category, `os_log_create` is too expensive to call per record
+    handles: Mutex<HashMap<String, OsLogHandle>>,
+}
+
+struct OsLogHandle(OsLogT);
+
+// SAFETY: `os_log_t` objects are immutable and thread-safe.
+unsafe impl Send for OsLogHandle {}
+
+impl Drop for OsLogHandle {
+    fn drop(&mut self) {
+        unsafe { os_release(self.0) }
+    }
+}
+
+impl OsLogSink {
+    /// Constructs a builder of `OsLogSink`.
+    #[must_use]
+    pub fn builder() -> OsLogSinkBuilder {
+        OsLogSinkBuilder {
+            common_builder_impl: helper::CommonBuilderImpl::new(),
+            subsystem: "spdlog-rs".to_string(),
+            default_category: "default".to_string(),
+        }
+    }
+
+    fn handle(&self, category: Option<&str>) -> OsLogT {
+        let mut handles = self.handles.lock_expect();
+        let key = category.unwrap_or_default();
+        if let Some(handle) = handles.get(key) {
+            return handle.0;
+        }
+
+        let category = category.map(to_c_string);
+        let category = category.as_ref().unwrap_or(&self.default_category);
+        let log = unsafe { os_log_create(self.subsystem.as_ptr(), category.as_ptr()) };
+        handles.insert(key.to_string(), OsLogHandle(log));
+        log
+    }
+}
+
+impl Sink for OsLogSink {
+    fn log(&self, record: &Record) -> Result<()> {
+        if !self.should_log(record.level()) {
+            return Ok(());
+        }
+
+        let log = self.handle(record.logger_name());
+        let log_type = to_log_type(record.level());
+        if !unsafe { os_log_type_enabled(log, log_type) } {
+            return Ok(());
+        }
+
+        let mut string_buf = StringBuf::new();
+        self.common_impl
+            .formatter
+            .read()
+            .format(record, &mut string_buf)?;
+
+        let message = to_c_string(&string_buf);
+        unsafe { spdlog_rs_os_log_public_str(log, log_type, message.as_ptr()) };
+        Ok(())
+    }
+
+    fn flush(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn clone_box(&self) -> Option<Box<dyn Sink>> {
+        Some(Box::new(OsLogSink {
+            common_impl: self.common_impl.clone_config(),
+            subsystem: self.subsystem.clone(),
+            default_category: self.default_category.clone(),
+            handles: Mutex::new(HashMap::new()),
+        }))
+    }
+
+    helper::common_impl!(@Sink: common_impl);
+}
+
+#[must_use]
+fn to_log_type(level: Level) -> OsLogTypeT {
+    match level {
+        Level::Critical => OS_LOG_TYPE_FAULT,
+        Level::Error => OS_LOG_TYPE_ERROR,
+        Level::Warn => OS_LOG_TYPE_DEFAULT,
+        Level::Info => OS_LOG_TYPE_INFO,
+        Level::Debug | Level::Trace => OS_LOG_TYPE_DEBUG,
+    }
+}
+
+// Interior NUL bytes would end the string early, they are removed.
+#[must_use]
+fn to_c_string(s: &str) -> CString {
+    CString::new(s.replace('\0', "")).unwrap()
+}
+
+/// The builder of [`OsLogSink`].
+///
+/// # Examples
+///
+/// - Building a [`OsLogSink`].
+///
+///   ```no_run
+///   use spdlog::{prelude::*, sink::OsLogSink};
+///
+///   # fn main() -> Result<(), spdlog::Error> {
+///   let sink: OsLogSink = OsLogSink::builder()
+///       .subsystem("com.example.app") // optional, defaults to `"spdlog-rs"`
+///       .default_category("app") // optional, defaults to `"default"`
+///       .level_filter(LevelFilter::MoreSevere(Level::Info)) // optional
+///       .build()?;
+///   # Ok(()) }
+///   ```
+pub struct OsLogSinkBuilder {
+    common_builder_impl: helper::CommonBuilderImpl,
+    subsystem: String,
+    default_category: String,
+}
+
+impl OsLogSinkBuilder {
+    /// Specifies the subsystem of records, conventionally the bundle
+    /// identifier of the app in reverse DNS notation, e.g.
+    /// `"com.example.app"`.
+    ///
+    /// This parameter is **optional**, and defaults to `"spdlog-rs"`.
+    #[must_use]
+    pub fn subsystem<S>(mut self, subsystem: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.subsystem = subsystem.into();
+        self
+    }
+
+    /// Specifies the category of records whose loggers have no name.
+    ///
+    /// This parameter is **optional**, and defaults to `"default"`.
+    #[must_use]
+    pub fn default_category<S>(mut self, default_category: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.default_category = default_category.into();
+        self
+    }
+
+    helper::common_impl!(@SinkBuilder: common_builder_impl);
+
+    /// Builds a [`OsLogSink`].
+    pub fn build(self) -> Result<OsLogSink> {
+        let sink = OsLogSink {
+            common_impl: helper::CommonImpl::from_builder_with_formatter(
+                self.common_builder_impl,
+                || Box::new(PatternFormatter::new(Payload::default())),
+            ),
+            subsystem: to_c_string(&self.subsystem),
+            default_category: to_c_string(&self.default_category),
+            handles: Mutex::new(HashMap::new()),
+        };
+        Ok(sink)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LevelFilter;
+
+    #[test]
+    fn log_type() {
+        assert_eq!(to_log_type(Level::Critical), OS_LOG_TYPE_FAULT);
+        assert_eq!(to_log_type(Level::Warn), OS_LOG_TYPE_DEFAULT);
+        assert_eq!(to_log_type(Level::Trace), OS_LOG_TYPE_DEBUG);
+    }
+
+    #[test]
+    fn c_string() {
+        assert_eq!(to_c_string("hi\0!").to_str().unwrap(), "hi!");
+    }
+
+    #[test]
+    fn log_records() {
+        let sink = OsLogSink::builder()
+            .subsystem("com.github.spdlog-rs.test")
+            .level_filter(LevelFilter::All)
+            .build()
+            .unwrap();
+
+        for level in Level::iter() {
+            sink.log(&Record::new(level, "os_log_sink test")).unwrap();
+        }
+        // Interior NUL bytes and a named logger
+        sink.log(
+            &Record::builder(Level::Error, "a\0b")
+                .logger_name("os_log_sink")
+                .build(),
+        )
+        .unwrap();
+        assert_eq!(sink.handles.lock_expect().len(), 2);
+    }
+}
@@ -0,0 +1,158 @@
+//! Provides the resolution of path templates for file sinks.
+//!
+//! See `include/doc/path-template.md` for the grammar.
+
+use std::{
+    env,
+    path::{self, Path, PathBuf},
+    process,
+    result::Result as StdResult,
+};
+
+use chrono::prelude::*;
+
+use crate::{error::InvalidArgumentError, Error, Result};
+
+pub(crate) fn resolve(path: &Path) -> Result<PathBuf> {
+    match path.to_str() {
+        Some(template) => resolve_str(template)
+            .map(PathBuf::from)
+            .map_err(|err| Error::InvalidArgument(InvalidArgumentError::PathTemplate(err))),
+        None => Ok(path.to_path_buf()),
+    }
+}
+
+fn resolve_str(template: &str) -> StdResult<String, String> {
+    let mut resolved = String::with_capacity(template.len());
+    let mut rest = template;
+
+    if let Some(after) = rest.strip_prefix('~') {
+        if after.is_empty() || after.starts_with(path::is_separator) {
+            resolved.push_str(&home_dir()?);
+            rest = after;
+        }
+    }
+
+    while let Some(pos) = rest.find(&['$', '{', '}'][..]) {
+        resolved.push_str(&rest[..pos]);
+        let tail = &rest[pos..];
+
+        let consumed = if tail.starts_with("$$") || tail.starts_with("{{") || tail.starts_with("}}")
+        {
+            resolved.push_str(&tail[..1]);
+            2
+        } else if let Some(name_tail) = tail.strip_prefix("${") {
+            let name = closed_name(name_tail, template)?;
+            if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+                return Err(format!(
+                    "invalid environment variable name '{}' in '{}'",
+                    name, template
+                ));
+            }
+            let value = env::var(name).map_err(|err| {
+                format!("environment variable '{}' in '{}': {}", name, template, err)
+            })?;
+            resolved.push_str(&value);
+            name.len() + 3
+        } else if let Some(name_tail) = tail.strip_prefix('{') {
+            let name = closed_name(name_tail, template)?;
+            match name {
+                "pid" => resolved.push_str(&process::id().to_string()),
+                "date" => resolved.push_str(&Local::now().format("%Y-%m-%d").to_string()),
+                _ => {
+                    return Err(format!(
+                        "unknown placeholder '{{{}}}' in '{}'",
+                        name, template
+                    ))
+                }
+            }
+            name.len() + 2
+        } else if tail.starts_with('$') {
+            resolved.push('$');
+            1
+        } else {
+            return Err(format!("unmatched '}}' in '{}'", template));
+        };
+
+        rest = &tail[consumed..];
+    }
+    resolved.push_str(rest);
+
+    Ok(resolved)
+}
+
+fn closed_name<'a>(name_tail: &'a str, template: &str) -> StdResult<&'a str, String> {
+    name_tail
+        .find('}')
+        .map(|end| &name_tail[..end])
+        .ok_or_else(|| format!("unmatched '{{' in '{}'", template))
+}
+
+fn home_dir() -> StdResult<String, String> {
+    let var = if cfg!(windows) { "USERPROFILE" } else { "HOME" };
+    env::var(var).map_err(|err| format!("home directory ('{}'): {}", var, err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn env_expansion() {
+        env::set_var("SPDLOG_RS_TEST_PATH_TEMPLATE_SERVICE", "my-service");
+        env::remove_var("SPDLOG_RS_TEST_PATH_TEMPLATE_MISSING");
+
+        assert_eq!(
+            resolve_str("/var/log/${SPDLOG_RS_TEST_PATH_TEMPLATE_SERVICE}/app.log").unwrap(),
+            "/var/log/my-service/app.log"
+        );
+        assert_eq!(
+            resolve_str("logs/$${{SPDLOG_RS_TEST_PATH_TEMPLATE_SERVICE}}/$HOME.log").unwrap(),
+            "logs/${SPDLOG_RS_TEST_PATH_TEMPLATE_SERVICE}/$HOME.log"
+        );
+
+        assert!(resolve_str("/var/log/${SPDLOG_RS_TEST_PATH_TEMPLATE_MISSING}/app.log").is_err());
+        assert!(resolve_str("/var/log/${}/app.log").is_err());
+        assert!(resolve_str("/var/log/${BAD-NAME}/app.log").is_err());
+        assert!(resolve_str("/var/log/${SPDLOG_RS_TEST_PATH_TEMPLATE_SERVICE").is_err());
+    }
+
+    #[test]
+    fn home_expansion() {
+        let home = home_dir().unwrap();
+        let sep = path::MAIN_SEPARATOR;
+
+        assert_eq!(resolve_str("~").unwrap(), home);
+        assert_eq!(
+            resolve_str(&format!("~{}app.log", sep)).unwrap(),
+            format!("{}{}app.log", home, sep)
+        );
+        assert_eq!(resolve_str("~user/app.log").unwrap(), "~user/app.log");
+        assert_eq!(resolve_str("logs/~/app.log").unwrap(), "logs/~/app.log");
+    }
+
+    #[test]
+    fn placeholders() {
+        let pid = process::id();
+        let date = Local::now().format("%Y-%m-%d").to_string();
+
+        assert_eq!(
+            resolve_str("app-{pid}.log").unwrap(),
+            format!("app-{}.log", pid)
+        );
+        assert_eq!(
+            resolve_str("{date}/app-{pid}.log").unwrap(),
+            format!("{}/app-{}.log", date, pid)
+        );
+        assert_eq!(resolve_str("app-{{pid}}.log").unwrap(), "app-{pid}.log");
+
+        assert!(matches!(
+            resolve(Path::new("app-{unknown}.log")),
+            Err(Error::InvalidArgument(InvalidArgumentError::PathTemplate(
+                _
+            )))
+        ));
+        assert!(resolve_str("app-{pid.log").is_err());
+        assert!(resolve_str("app-pid}.log").is_err());
+    }
+}
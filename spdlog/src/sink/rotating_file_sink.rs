@@ -16,7 +16,8 @@ use chrono::prelude::*;
 
 use crate::{
     error::InvalidArgumentError,
-    sink::{helper, Sink},
+    formatter::FmtExtraInfo,
+    sink::{helper, path_template, FlushErrorPolicy, Sink},
     sync::*,
     utils, Error, Record, Result, StringBuf,
 };
@@ -53,14 +54,14 @@ pub enum RotationPolicy {
         /// Max file size (in bytes). Range: (0, u64::MAX].
         u64,
     ),
-    /// Rotates daily at the given time point.
+    /// Rotates daily at the given local time point.
     Daily {
         /// Hour of the time point. Range: [0, 23].
         hour: u32,
         /// Minute of the time point. Range: [0, 59].
         minute: u32,
     },
-    /// Rotates hourly.
+    /// Rotates hourly, at the top of each local hour.
     Hourly,
 }
 
@@ -82,6 +83,8 @@ struct RotatorFileSize {
     base_path: PathBuf,
     max_size: u64,
     max_files: usize,
+    sync_on_rotation: bool,
+    utf8_bom: bool,
     inner: SpinMutex<RotatorFileSizeInner>,
 }
 
@@ -93,7 +96,10 @@ struct RotatorFileSizeInner {
 struct RotatorTimePoint {
     base_path: PathBuf,
     time_point: TimePoint,
+    date_separator: char,
     max_files: usize,
+    sync_on_rotation: bool,
+    utf8_bom: bool,
     inner: SpinMutex<RotatorTimePointInner>,
 }
 
@@ -105,6 +111,7 @@ enum TimePoint {
 
 struct RotatorTimePointInner {
     file: BufWriter<File>,
+    file_path: PathBuf,
     rotation_time_point: SystemTime,
     file_paths: Option<LinkedList<PathBuf>>,
 }
@@ -153,14 +160,28 @@ struct RotatorTimePointInner {
 /// sink. For more information about different rotation policies, please refer
 /// to the documentation of [`RotationPolicy`].
 ///
+/// # Record Boundaries
+///
+/// Rotations only happen between records, a formatted record is always written
+/// entirely to a single log file. For [`RotationPolicy::FileSize`], a record
+/// that would make the current file exceed the max size is written to the new
+/// file, and a record larger than the max size is written to a file on its own
+/// rather than being split.
+///
+/// Therefore, if each formatted record is a single line, e.g. with
+/// [`JsonFormatter`], every log file is valid newline-delimited JSON.
+///
 /// # Examples
 ///
 /// See [./examples] directory.
 ///
+/// [`JsonFormatter`]: crate::formatter::JsonFormatter
 /// [./examples]: https://github.com/SpriteOvO/spdlog-rs/tree/main/spdlog/examples
 pub struct RotatingFileSink {
     common_impl: helper::CommonImpl,
-    rotator: RotatorKind,
+    // Shared with clones
+    rotator: Arc<RotatorKind>,
+    flush_error: helper::FlushErrorState,
 }
 
 /// The builder of [`RotatingFileSink`].
@@ -178,6 +199,7 @@ pub struct RotatingFileSink {
 ///       .rotation_policy(RotationPolicy::Hourly) // required
 ///       // .max_files(100) // optional, defaults to `0` for no limit
 ///       // .rotate_on_open(true) // optional, defaults to `false`
+///       // .sync_on_rotation(true) // optional, defaults to `false`
 ///       .build()?;
 ///   # Ok(()) }
 ///   ```
@@ -213,9 +235,14 @@ pub struct RotatingFileSink {
 pub struct RotatingFileSinkBuilder<ArgBP, ArgRP> {
     common_builder_impl: helper::CommonBuilderImpl,
     base_path: ArgBP,
+    path_template: bool,
     rotation_policy: ArgRP,
     max_files: usize,
     rotate_on_open: bool,
+    sync_on_rotation: bool,
+    utf8_bom: bool,
+    flush_error_policy: FlushErrorPolicy,
+    date_separator: char,
 }
 
 impl RotatingFileSink {
@@ -225,9 +252,14 @@ impl RotatingFileSink {
         RotatingFileSinkBuilder {
             common_builder_impl: helper::CommonBuilderImpl::new(),
             base_path: (),
+            path_template: false,
             rotation_policy: (),
             max_files: 0,
             rotate_on_open: false,
+            sync_on_rotation: false,
+            utf8_bom: false,
+            flush_error_policy: FlushErrorPolicy::Propagate,
+            date_separator: '_',
         }
     }
 
@@ -273,10 +305,20 @@ impl RotatingFileSink {
             .build()
     }
 
+    // Used at `Sink::clone_box` of this sink and the sinks wrapping it.
+    #[must_use]
+    pub(crate) fn clone_sink(&self) -> RotatingFileSink {
+        RotatingFileSink {
+            common_impl: self.common_impl.clone_config(),
+            rotator: self.rotator.clone(),
+            flush_error: helper::FlushErrorState::new(self.flush_error.policy()),
+        }
+    }
+
     #[cfg(test)]
     #[must_use]
     fn _current_size(&self) -> u64 {
-        if let RotatorKind::FileSize(rotator) = &self.rotator {
+        if let RotatorKind::FileSize(rotator) = &*self.rotator {
             rotator.inner.lock().current_size
         } else {
             panic!();
@@ -299,8 +341,25 @@ impl Sink for RotatingFileSink {
         self.rotator.log(record, &string_buf)
     }
 
+    fn write_formatted(
+        &self,
+        record: &Record,
+        formatted: &StringBuf,
+        _extra_info: &FmtExtraInfo,
+    ) -> Result<()> {
+        if !self.should_log(record.level()) {
+            return Ok(());
+        }
+
+        self.rotator.log(record, formatted)
+    }
+
     fn flush(&self) -> Result<()> {
-        self.rotator.flush()
+        self.flush_error.handle(self.rotator.flush())
+    }
+
+    fn clone_box(&self) -> Option<Box<dyn Sink>> {
+        Some(Box::new(self.clone_sink()))
     }
 
     helper::common_impl!(@Sink: common_impl);
@@ -308,9 +367,12 @@ impl Sink for RotatingFileSink {
 
 impl Drop for RotatingFileSink {
     fn drop(&mut self) {
-        if let Err(err) = self.rotator.drop_flush() {
-            self.common_impl
-                .non_returnable_error("RotatingFileSink", err)
+        // The last one of clones flushes the shared rotator
+        if let Some(rotator) = Arc::get_mut(&mut self.rotator) {
+            if let Err(err) = self.flush_error.handle(rotator.drop_flush()) {
+                self.common_impl
+                    .non_returnable_error("RotatingFileSink", err)
+            }
         }
     }
 }
@@ -369,14 +431,26 @@ impl RotatorFileSize {
         max_size: u64,
         max_files: usize,
         rotate_on_open: bool,
+        sync_on_rotation: bool,
+        utf8_bom: bool,
     ) -> Result<Self> {
         let file = utils::open_file(&base_path, false)?;
-        let current_size = file.metadata().map_err(Error::QueryFileMetadata)?.len();
+        let mut current_size = file.metadata().map_err(Error::QueryFileMetadata)?.len();
+        // The BOM is not counted in `current_size`, so that a file containing
+        // only the BOM is still regarded as empty and never rotated.
+        if utf8_bom
+            && !utils::write_utf8_bom_if_empty(&file)?
+            && utils::starts_with_utf8_bom(&base_path)?
+        {
+            current_size -= utils::UTF8_BOM_LEN;
+        }
 
         let res = Self {
             base_path,
             max_size,
             max_files,
+            sync_on_rotation,
+            utf8_bom,
             inner: SpinMutex::new(RotatorFileSizeInner::new(file, current_size)),
         };
 
@@ -390,12 +464,26 @@ impl RotatorFileSize {
 
     fn reopen(&self) -> Result<File> {
         // always truncate
-        utils::open_file(&self.base_path, true)
+        let file = utils::open_file(&self.base_path, true)?;
+        if self.utf8_bom {
+            utils::write_utf8_bom_if_empty(&file)?;
+        }
+        Ok(file)
     }
 
     fn rotate(&self, opened_file: &mut SpinMutexGuard<RotatorFileSizeInner>) -> Result<()> {
         let inner = || {
-            for i in (1..self.max_files).rev() {
+            // with no limit, shifts all the existing files
+            let max_files = match self.max_files {
+                0 => {
+                    (1..)
+                        .find(|index| !Self::calc_file_path(&self.base_path, *index).exists())
+                        .unwrap()
+                        + 1
+                }
+                max_files => max_files,
+            };
+            for i in (1..max_files).rev() {
                 let src = Self::calc_file_path(&self.base_path, i - 1);
                 if !src.exists() {
                     continue;
@@ -411,6 +499,12 @@ impl RotatorFileSize {
             Ok(())
         };
 
+        if self.sync_on_rotation {
+            if let Some(file) = opened_file.file.as_mut() {
+                sync_file(file)?;
+            }
+        }
+
         opened_file.file = None;
 
         let res = inner();
@@ -420,6 +514,11 @@ impl RotatorFileSize {
 
         opened_file.file = Some(BufWriter::new(self.reopen()?));
 
+        if self.sync_on_rotation {
+            // Report the error of renaming first, if any
+            return Error::push_result(res, utils::sync_parent_dir(&self.base_path));
+        }
+
         res
     }
 
@@ -451,7 +550,7 @@ impl RotatorFileSize {
     }
 
     // if `self.inner.file` is `None`, try to reopen the file.
-    fn lock_inner(&self) -> Result<SpinMutexGuard<RotatorFileSizeInner>> {
+    fn lock_inner(&self) -> Result<SpinMutexGuard<'_, RotatorFileSizeInner>> {
         let mut inner = self.inner.lock();
         if inner.file.is_none() {
             inner.file = Some(BufWriter::new(self.reopen()?));
@@ -464,11 +563,14 @@ impl Rotator for RotatorFileSize {
     fn log(&self, _record: &Record, string_buf: &StringBuf) -> Result<()> {
         let mut inner = self.lock_inner()?;
 
-        inner.current_size += string_buf.len() as u64;
-        if inner.current_size > self.max_size {
+        // Never rotates an empty file, a record larger than the max size is written
+        // to a file on its own.
+        let record_size = string_buf.len() as u64;
+        if inner.current_size != 0 && inner.current_size + record_size > self.max_size {
             self.rotate(&mut inner)?;
-            inner.current_size = string_buf.len() as u64;
+            inner.current_size = 0;
         }
+        inner.current_size += record_size;
 
         inner
             .file
@@ -511,15 +613,22 @@ impl RotatorTimePoint {
     fn new(
         base_path: PathBuf,
         time_point: TimePoint,
+        date_separator: char,
         max_files: usize,
         truncate: bool,
+        sync_on_rotation: bool,
+        utf8_bom: bool,
     ) -> Result<Self> {
         let now = SystemTime::now();
-        let file_path = Self::calc_file_path(base_path.as_path(), time_point, now);
-        let file = utils::open_file(file_path, truncate)?;
+        let file_path = Self::calc_file_path(base_path.as_path(), time_point, date_separator, now);
+        let file = utils::open_file(&file_path, truncate)?;
+        if utf8_bom {
+            utils::write_utf8_bom_if_empty(&file)?;
+        }
 
         let inner = RotatorTimePointInner {
             file: BufWriter::new(file),
+            file_path,
             rotation_time_point: Self::next_rotation_time_point(time_point, now),
             file_paths: None,
         };
@@ -527,7 +636,10 @@ impl RotatorTimePoint {
         let mut res = Self {
             base_path,
             time_point,
+            date_separator,
             max_files,
+            sync_on_rotation,
+            utf8_bom,
             inner: SpinMutex::new(inner),
         };
 
@@ -541,7 +653,12 @@ impl RotatorTimePoint {
             let mut file_paths = LinkedList::new();
 
             for _ in 0..max_files {
-                let file_path = Self::calc_file_path(&self.base_path, self.time_point, now);
+                let file_path = Self::calc_file_path(
+                    &self.base_path,
+                    self.time_point,
+                    self.date_separator,
+                    now,
+                );
 
                 if !file_path.exists() {
                     break;
@@ -559,38 +676,57 @@ impl RotatorTimePoint {
     // constructor.
     #[must_use]
     fn next_rotation_time_point(time_point: TimePoint, now: SystemTime) -> SystemTime {
-        let now: DateTime<Utc> = now.into();
-        let mut rotation_time: DateTime<Utc> = now;
-
         match time_point {
             TimePoint::Daily { hour, minute } => {
-                rotation_time = rotation_time
-                    .with_hour(hour)
-                    .unwrap()
-                    .with_minute(minute)
-                    .unwrap()
-                    .with_second(0)
-                    .unwrap()
-                    .with_nanosecond(0)
-                    .unwrap()
+                let now: DateTime<Local> = now.into();
+
+                // compute on local dates rather than adding 24 hours, so that days
+                // with a DST transition still rotate at the given local time.
+                let mut date = now.date_naive();
+                loop {
+                    let rotation_time =
+                        Self::local_time_point(&Local, date.and_hms_opt(hour, minute, 0).unwrap());
+                    if rotation_time > now {
+                        return rotation_time.into();
+                    }
+                    date = date.succ_opt().unwrap();
+                }
             }
-            TimePoint::Hourly => {
-                rotation_time = rotation_time
-                    .with_minute(0)
-                    .unwrap()
-                    .with_second(0)
-                    .unwrap()
-                    .with_nanosecond(0)
-                    .unwrap()
+            TimePoint::Hourly => Self::next_local_hour(&DateTime::<Local>::from(now)).into(),
+        }
+    }
+
+    // Computes the first time point after `now` at which the local hour changes.
+    //
+    // It's computed on local hours rather than by adding 1 hour to `now`
+    // truncated, since a DST transition does not always shift the offset by a
+    // whole hour (e.g. 30 minutes on Lord Howe Island). If the top of the next
+    // local hour is skipped by a DST transition, the transition itself is the
+    // time point.
+    #[must_use]
+    fn next_local_hour<Tz: TimeZone>(now: &DateTime<Tz>) -> DateTime<Tz> {
+        let tz = now.timezone();
+        let mut naive = now.date_naive().and_hms_opt(now.hour(), 0, 0).unwrap();
+        loop {
+            naive += chrono::Duration::hours(1);
+            let rotation_time = Self::local_time_point(&tz, naive);
+            if rotation_time > *now {
+                return rotation_time;
             }
-        };
+        }
+    }
 
-        if rotation_time < now {
-            rotation_time = rotation_time
-                .checked_add_signed(time_point.delta_chrono())
-                .unwrap();
+    // Converts a local naive date time to `DateTime<Tz>`. If the time does not
+    // exist (skipped by a DST transition), the first existing minute after it is
+    // used.
+    #[must_use]
+    fn local_time_point<Tz: TimeZone>(tz: &Tz, mut naive: NaiveDateTime) -> DateTime<Tz> {
+        loop {
+            if let Some(time) = tz.from_local_datetime(&naive).earliest() {
+                return time;
+            }
+            naive += chrono::Duration::minutes(1);
         }
-        rotation_time.into()
     }
 
     fn push_new_remove_old(
@@ -615,6 +751,7 @@ impl RotatorTimePoint {
     fn calc_file_path(
         base_path: impl AsRef<Path>,
         time_point: TimePoint,
+        date_separator: char,
         system_time: SystemTime,
     ) -> PathBuf {
         let base_path = base_path.as_ref();
@@ -631,7 +768,8 @@ impl RotatorTimePoint {
             TimePoint::Daily { .. } => {
                 // append y-m-d
                 file_name.push(format!(
-                    "_{}-{:02}-{:02}",
+                    "{}{}-{:02}-{:02}",
+                    date_separator,
                     local_time.year(),
                     local_time.month(),
                     local_time.day()
@@ -640,7 +778,8 @@ impl RotatorTimePoint {
             TimePoint::Hourly => {
                 // append y-m-d_h
                 file_name.push(format!(
-                    "_{}-{:02}-{:02}_{:02}",
+                    "{}{}-{:02}-{:02}_{:02}",
+                    date_separator,
                     local_time.year(),
                     local_time.month(),
                     local_time.day(),
@@ -663,19 +802,37 @@ impl Rotator for RotatorTimePoint {
     fn log(&self, record: &Record, string_buf: &StringBuf) -> Result<()> {
         let mut inner = self.inner.lock();
 
-        let mut file_path = None;
         let record_time = record.time();
-        let should_rotate = record_time >= inner.rotation_time_point;
+        let mut should_rotate = record_time >= inner.rotation_time_point;
 
         if should_rotate {
-            file_path = Some(Self::calc_file_path(
+            let file_path = Self::calc_file_path(
                 &self.base_path,
                 self.time_point,
+                self.date_separator,
                 record_time,
-            ));
-            inner.file = BufWriter::new(utils::open_file(file_path.as_ref().unwrap(), true)?);
+            );
             inner.rotation_time_point =
                 Self::next_rotation_time_point(self.time_point, record_time);
+
+            // the file path may be unchanged, e.g. the repeated local hour when DST ends,
+            // keep writing to the current file instead of truncating it.
+            if file_path == inner.file_path {
+                should_rotate = false;
+            } else {
+                if self.sync_on_rotation {
+                    sync_file(&mut inner.file)?;
+                }
+                let file = utils::open_file(&file_path, true)?;
+                if self.utf8_bom {
+                    utils::write_utf8_bom_if_empty(&file)?;
+                }
+                inner.file = BufWriter::new(file);
+                if self.sync_on_rotation {
+                    utils::sync_parent_dir(&file_path)?;
+                }
+                inner.file_path = file_path;
+            }
         }
 
         inner
@@ -684,7 +841,8 @@ impl Rotator for RotatorTimePoint {
             .map_err(Error::WriteRecord)?;
 
         if should_rotate && inner.file_paths.is_some() {
-            self.push_new_remove_old(file_path.unwrap(), &mut inner)?;
+            let file_path = inner.file_path.clone();
+            self.push_new_remove_old(file_path, &mut inner)?;
         }
 
         Ok(())
@@ -695,6 +853,11 @@ impl Rotator for RotatorTimePoint {
     }
 }
 
+fn sync_file(file: &mut BufWriter<File>) -> Result<()> {
+    file.flush().map_err(Error::FlushBuffer)?;
+    file.get_ref().sync_all().map_err(Error::SyncFile)
+}
+
 impl TimePoint {
     #[must_use]
     fn delta_std(&self) -> Duration {
@@ -706,14 +869,6 @@ impl TimePoint {
             Self::Hourly { .. } => HOUR_1,
         }
     }
-
-    #[must_use]
-    fn delta_chrono(&self) -> chrono::Duration {
-        match self {
-            Self::Daily { .. } => chrono::Duration::days(1),
-            Self::Hourly { .. } => chrono::Duration::hours(1),
-        }
-    }
 }
 
 impl<ArgBP, ArgRP> RotatingFileSinkBuilder<ArgBP, ArgRP> {
@@ -736,6 +891,9 @@ impl<ArgBP, ArgRP> RotatingFileSinkBuilder<ArgBP, ArgRP> {
     /// - `/path/to/base_file_2022-03-23_03.log`
     /// - `/path/to/base_file_2022-03-23_04.log`
     ///
+    /// It's used literally, unless [`RotatingFileSinkBuilder::path_template`]
+    /// is enabled.
+    ///
     /// This parameter is **required**.
     #[must_use]
     pub fn base_path<P>(self, base_path: P) -> RotatingFileSinkBuilder<PathBuf, ArgRP>
@@ -745,12 +903,33 @@ impl<ArgBP, ArgRP> RotatingFileSinkBuilder<ArgBP, ArgRP> {
         RotatingFileSinkBuilder {
             common_builder_impl: self.common_builder_impl,
             base_path: base_path.into(),
+            path_template: self.path_template,
             rotation_policy: self.rotation_policy,
             max_files: self.max_files,
             rotate_on_open: self.rotate_on_open,
+            sync_on_rotation: self.sync_on_rotation,
+            utf8_bom: self.utf8_bom,
+            flush_error_policy: self.flush_error_policy,
+            date_separator: self.date_separator,
         }
     }
 
+    /// Specifies whether the base path is a template, resolved when the sink
+    /// is built.
+    ///
+    /// See [the documentation of `FileSinkBuilder::path_template`] for the
+    /// grammar. Note that `{date}` is resolved only once, so the date in
+    /// rotated file names should be left to the rotation policy.
+    ///
+    /// This parameter is **optional**, and defaults to `false`.
+    ///
+    /// [the documentation of `FileSinkBuilder::path_template`]: crate::sink::FileSinkBuilder::path_template#path-templates
+    #[must_use]
+    pub fn path_template(mut self, path_template: bool) -> Self {
+        self.path_template = path_template;
+        self
+    }
+
     /// Specifies the rotation policy.
     ///
     /// This parameter is **required**.
@@ -762,9 +941,14 @@ impl<ArgBP, ArgRP> RotatingFileSinkBuilder<ArgBP, ArgRP> {
         RotatingFileSinkBuilder {
             common_builder_impl: self.common_builder_impl,
             base_path: self.base_path,
+            path_template: self.path_template,
             rotation_policy,
             max_files: self.max_files,
             rotate_on_open: self.rotate_on_open,
+            sync_on_rotation: self.sync_on_rotation,
+            utf8_bom: self.utf8_bom,
+            flush_error_policy: self.flush_error_policy,
+            date_separator: self.date_separator,
         }
     }
 
@@ -797,6 +981,64 @@ impl<ArgBP, ArgRP> RotatingFileSinkBuilder<ArgBP, ArgRP> {
         self
     }
 
+    /// Specifies whether to sync the completed log file to disk on rotation.
+    ///
+    /// If it is `true`, before moving on to the next log file, the completed
+    /// one will be flushed and synced to disk (`fsync`), and the parent
+    /// directory will also be synced to make the created or renamed files
+    /// durable. This makes rotated log files survive a power loss, at the cost
+    /// of latency on each rotation.
+    ///
+    /// Syncing the directory only takes effect on Unix-like platforms, it's a
+    /// no-op on other platforms.
+    ///
+    /// This parameter is **optional**, and defaults to `false`.
+    #[must_use]
+    pub fn sync_on_rotation(mut self, sync_on_rotation: bool) -> Self {
+        self.sync_on_rotation = sync_on_rotation;
+        self
+    }
+
+    /// Specifies whether to write the UTF-8 byte order mark (`EF BB BF`) to
+    /// each log file.
+    ///
+    /// If it is `true`, the BOM is written before any records to each log file
+    /// that is empty when opened, i.e. newly created or truncated, including
+    /// the files created by rotations. Some Windows tools, e.g. older versions
+    /// of Notepad and Excel, guess the encoding of a file without a BOM as the
+    /// legacy code page, and display non-ASCII text garbled.
+    ///
+    /// For the [`RotationPolicy::FileSize`] rotation policy, the BOM is not
+    /// counted toward the maximum size, so a log file may exceed it by the 3
+    /// bytes of the BOM.
+    ///
+    /// This parameter is **optional**, and defaults to `false`.
+    #[must_use]
+    pub fn utf8_bom(mut self, utf8_bom: bool) -> Self {
+        self.utf8_bom = utf8_bom;
+        self
+    }
+
+    /// Specifies the policy for handling errors flushing the buffer.
+    ///
+    /// See [`FlushErrorPolicy`] for details.
+    ///
+    /// This parameter is **optional**, and defaults to
+    /// [`FlushErrorPolicy::Propagate`].
+    #[must_use]
+    pub fn flush_error_policy(mut self, flush_error_policy: FlushErrorPolicy) -> Self {
+        self.flush_error_policy = flush_error_policy;
+        self
+    }
+
+    // Specifies the separator between the file stem and the date for the time
+    // point rotation policies, `DailyFileSink` uses `-` as C++ `spdlog` does.
+    #[must_use]
+    pub(crate) fn date_separator(mut self, date_separator: char) -> Self {
+        self.date_separator = date_separator;
+        self
+    }
+
     helper::common_impl!(@SinkBuilder: common_builder_impl);
 }
 
@@ -823,40 +1065,57 @@ impl RotatingFileSinkBuilder<PathBuf, RotationPolicy> {
     ///
     /// # Errors
     ///
-    /// If the argument `rotation_policy` is invalid, or an error occurs opening
-    /// the file, [`Error::CreateDirectory`] or [`Error::OpenFile`] will be
-    /// returned.
+    /// If the argument `rotation_policy` is invalid, or
+    /// [`RotatingFileSinkBuilder::path_template`] is enabled and the base path
+    /// template is invalid, [`Error::InvalidArgument`] will be returned. If an
+    /// error occurs opening the file, [`Error::CreateDirectory`] or
+    /// [`Error::OpenFile`] will be returned.
     pub fn build(self) -> Result<RotatingFileSink> {
         self.rotation_policy
             .validate()
             .map_err(|err| Error::InvalidArgument(InvalidArgumentError::RotationPolicy(err)))?;
 
+        let base_path = if self.path_template {
+            path_template::resolve(&self.base_path)?
+        } else {
+            self.base_path
+        };
+
         let rotator = match self.rotation_policy {
             RotationPolicy::FileSize(max_size) => RotatorKind::FileSize(RotatorFileSize::new(
-                self.base_path,
+                base_path,
                 max_size,
                 self.max_files,
                 self.rotate_on_open,
+                self.sync_on_rotation,
+                self.utf8_bom,
             )?),
             RotationPolicy::Daily { hour, minute } => {
                 RotatorKind::TimePoint(RotatorTimePoint::new(
-                    self.base_path,
+                    base_path,
                     TimePoint::Daily { hour, minute },
+                    self.date_separator,
                     self.max_files,
                     self.rotate_on_open,
+                    self.sync_on_rotation,
+                    self.utf8_bom,
                 )?)
             }
             RotationPolicy::Hourly => RotatorKind::TimePoint(RotatorTimePoint::new(
-                self.base_path,
+                base_path,
                 TimePoint::Hourly,
+                self.date_separator,
                 self.max_files,
                 self.rotate_on_open,
+                self.sync_on_rotation,
+                self.utf8_bom,
             )?),
         };
 
         let res = RotatingFileSink {
             common_impl: helper::CommonImpl::from_builder(self.common_builder_impl),
-            rotator,
+            rotator: Arc::new(rotator),
+            flush_error: helper::FlushErrorState::new(self.flush_error_policy),
         };
 
         Ok(res)
@@ -866,7 +1125,7 @@ impl RotatingFileSinkBuilder<PathBuf, RotationPolicy> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{prelude::*, test_utils::*, Level, Record};
+    use crate::{formatter::JsonFormatter, prelude::*, test_utils::*, Level, Record};
 
     static BASE_LOGS_PATH: Lazy<PathBuf> = Lazy::new(|| {
         let path = TEST_LOGS_PATH.join("rotating_file_sink");
@@ -1088,9 +1347,140 @@ mod tests {
                 )
             );
         }
+
+        #[test]
+        fn sync_on_rotation() {
+            // use a separate directory, since `rotate` test cleans `LOGS_PATH`
+            let logs_path = BASE_LOGS_PATH.join("sync_on_rotation");
+            if logs_path.exists() {
+                fs::remove_dir_all(&logs_path).unwrap();
+            }
+            let base_path = logs_path.join("test.log");
+
+            {
+                let sink = RotatingFileSink::builder()
+                    .base_path(&base_path)
+                    .rotation_policy(RotationPolicy::FileSize(4))
+                    .max_files(2)
+                    .sync_on_rotation(true)
+                    .formatter(Box::new(NoModFormatter::new()))
+                    .build()
+                    .unwrap();
+                let logger = build_test_logger(|b| b.sink(Arc::new(sink)));
+                logger.set_level_filter(LevelFilter::All);
+
+                info!(logger: logger, "abcd");
+                info!(logger: logger, "efgh");
+            }
+
+            let read_file = |index| {
+                fs::read_to_string(RotatorFileSize::calc_file_path(&base_path, index)).unwrap()
+            };
+            assert_eq!(read_file(0), "efgh");
+            assert_eq!(read_file(1), "abcd");
+        }
+
+        #[test]
+        fn utf8_bom() {
+            // use a separate directory, since `rotate` test cleans `LOGS_PATH`
+            let logs_path = BASE_LOGS_PATH.join("utf8_bom");
+            if logs_path.exists() {
+                fs::remove_dir_all(&logs_path).unwrap();
+            }
+            let base_path = logs_path.join("test.log");
+
+            {
+                let sink = Arc::new(
+                    RotatingFileSink::builder()
+                        .base_path(&base_path)
+                        .rotation_policy(RotationPolicy::FileSize(4))
+                        .utf8_bom(true)
+                        .formatter(Box::new(NoModFormatter::new()))
+                        .build()
+                        .unwrap(),
+                );
+                let logger = build_test_logger(|b| b.sink(sink.clone()));
+                logger.set_level_filter(LevelFilter::All);
+
+                // The BOM is not counted, the first record larger than the max size
+                // is still written to the first file.
+                assert_eq!(sink._current_size(), 0);
+                info!(logger: logger, "abcdef");
+                assert_eq!(sink._current_size(), 6);
+                info!(logger: logger, "ghij");
+                assert_eq!(sink._current_size(), 4);
+            }
+
+            let read_file = |index| {
+                fs::read_to_string(RotatorFileSize::calc_file_path(&base_path, index)).unwrap()
+            };
+            assert_eq!(read_file(0), "\u{FEFF}ghij");
+            assert_eq!(read_file(1), "\u{FEFF}abcdef");
+
+            // An existing file containing only the BOM is regarded as empty
+            fs::write(&base_path, "\u{FEFF}").unwrap();
+            let sink = RotatingFileSink::builder()
+                .base_path(&base_path)
+                .rotation_policy(RotationPolicy::FileSize(4))
+                .rotate_on_open(true)
+                .utf8_bom(true)
+                .build()
+                .unwrap();
+            assert_eq!(sink._current_size(), 0);
+            assert_eq!(read_file(0), "\u{FEFF}");
+            assert_eq!(read_file(1), "\u{FEFF}abcdef");
+        }
+
+        #[test]
+        fn ndjson() {
+            // use a separate directory, since `rotate` test cleans `LOGS_PATH`
+            let logs_path = BASE_LOGS_PATH.join("ndjson");
+            if logs_path.exists() {
+                fs::remove_dir_all(&logs_path).unwrap();
+            }
+            let base_path = logs_path.join("test.ndjson");
+
+            let payloads = (0..50)
+                .map(|i| format!("record {}\n\"{}\"", i, "x".repeat(i % 7 * 50)))
+                .collect::<Vec<_>>();
+            {
+                let sink = RotatingFileSink::builder()
+                    .base_path(&base_path)
+                    .rotation_policy(RotationPolicy::FileSize(256))
+                    .formatter(Box::new(JsonFormatter::new()))
+                    .build()
+                    .unwrap();
+                let logger = build_test_logger(|b| b.sink(Arc::new(sink)));
+                for payload in &payloads {
+                    info!(logger: logger, "{}", payload);
+                }
+            }
+
+            // The file with the largest index is the oldest
+            let file_count = fs::read_dir(&logs_path).unwrap().count();
+            assert!(file_count > 10);
+            let logged = (0..file_count)
+                .rev()
+                .flat_map(|index| {
+                    let path = RotatorFileSize::calc_file_path(&base_path, index);
+                    let content = fs::read_to_string(path).unwrap();
+                    assert!(content.ends_with('\n'));
+                    content
+                        .lines()
+                        .map(|line| {
+                            let object: serde_json::Value = serde_json::from_str(line).unwrap();
+                            object["payload"].as_str().unwrap().to_string()
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(logged, payloads);
+        }
     }
 
     mod policy_time_point {
+        use chrono::LocalResult;
+
         use super::*;
 
         static LOGS_PATH: Lazy<PathBuf> = Lazy::new(|| {
@@ -1107,6 +1497,7 @@ mod tests {
                 RotatorTimePoint::calc_file_path(
                     base_path,
                     TimePoint::Daily { hour: 8, minute: 9 },
+                    '_',
                     system_time,
                 )
                 .to_str()
@@ -1115,7 +1506,7 @@ mod tests {
             };
 
             let calc_hourly = |base_path| {
-                RotatorTimePoint::calc_file_path(base_path, TimePoint::Hourly, system_time)
+                RotatorTimePoint::calc_file_path(base_path, TimePoint::Hourly, '_', system_time)
                     .to_str()
                     .unwrap()
                     .to_string()
@@ -1143,6 +1534,127 @@ mod tests {
             run();
         }
 
+        #[test]
+        fn next_rotation_time_point_daily() {
+            let next = |hour, minute| {
+                let now = Local.with_ymd_and_hms(2012, 3, 4, 5, 6, 7).unwrap().into();
+                let next: DateTime<Local> = RotatorTimePoint::next_rotation_time_point(
+                    TimePoint::Daily { hour, minute },
+                    now,
+                )
+                .into();
+                next.naive_local()
+            };
+            let local = |d, h, m| {
+                NaiveDate::from_ymd_opt(2012, 3, d)
+                    .unwrap()
+                    .and_hms_opt(h, m, 0)
+                    .unwrap()
+            };
+
+            assert_eq!(next(8, 9), local(4, 8, 9));
+            assert_eq!(next(5, 7), local(4, 5, 7));
+            assert_eq!(next(5, 6), local(5, 5, 6));
+            assert_eq!(next(2, 30), local(5, 2, 30));
+        }
+
+        #[test]
+        fn next_rotation_time_point_hourly() {
+            let next = |h, m, s| {
+                let now = Local.with_ymd_and_hms(2012, 3, 4, h, m, s).unwrap().into();
+                let next: DateTime<Local> =
+                    RotatorTimePoint::next_rotation_time_point(TimePoint::Hourly, now).into();
+                next.naive_local()
+            };
+            let local = |h| {
+                NaiveDate::from_ymd_opt(2012, 3, 4)
+                    .unwrap()
+                    .and_hms_opt(h, 0, 0)
+                    .unwrap()
+            };
+
+            assert_eq!(next(5, 6, 7), local(6));
+            assert_eq!(next(5, 0, 0), local(6));
+            assert_eq!(next(5, 59, 59), local(6));
+        }
+
+        // Lord Howe Island in 2024, whose DST shifts the offset by 30 minutes:
+        // +11:00 until 04-06 15:00 UTC, +10:30 until 10-05 15:30 UTC, and +11:00
+        // afterwards.
+        #[derive(Clone)]
+        struct LordHowe2024;
+
+        impl TimeZone for LordHowe2024 {
+            type Offset = FixedOffset;
+
+            fn from_offset(_offset: &FixedOffset) -> Self {
+                LordHowe2024
+            }
+
+            fn offset_from_local_date(&self, local: &NaiveDate) -> LocalResult<FixedOffset> {
+                self.offset_from_local_datetime(&local.and_hms_opt(0, 0, 0).unwrap())
+            }
+
+            fn offset_from_local_datetime(
+                &self,
+                local: &NaiveDateTime,
+            ) -> LocalResult<FixedOffset> {
+                let offsets = [11 * 60, 10 * 60 + 30]
+                    .iter()
+                    .map(|minutes| FixedOffset::east_opt(minutes * 60).unwrap())
+                    .filter(|offset| self.offset_from_utc_datetime(&(*local - *offset)) == *offset)
+                    .collect::<Vec<_>>();
+                match offsets[..] {
+                    [] => LocalResult::None,
+                    [offset] => LocalResult::Single(offset),
+                    [earliest, latest] => LocalResult::Ambiguous(earliest, latest),
+                    _ => unreachable!(),
+                }
+            }
+
+            fn offset_from_utc_date(&self, utc: &NaiveDate) -> FixedOffset {
+                self.offset_from_utc_datetime(&utc.and_hms_opt(0, 0, 0).unwrap())
+            }
+
+            fn offset_from_utc_datetime(&self, utc: &NaiveDateTime) -> FixedOffset {
+                let utc_time = |month, day, hour, min| {
+                    NaiveDate::from_ymd_opt(2024, month, day)
+                        .unwrap()
+                        .and_hms_opt(hour, min, 0)
+                        .unwrap()
+                };
+                let is_dst = !(utc_time(4, 6, 15, 0)..utc_time(10, 5, 15, 30)).contains(utc);
+                let minutes = if is_dst { 11 * 60 } else { 10 * 60 + 30 };
+                FixedOffset::east_opt(minutes * 60).unwrap()
+            }
+        }
+
+        #[test]
+        fn next_rotation_time_point_hourly_half_hour_dst() {
+            let next = |month, day, hour, min| {
+                let now = LordHowe2024.from_utc_datetime(
+                    &NaiveDate::from_ymd_opt(2024, month, day)
+                        .unwrap()
+                        .and_hms_opt(hour, min, 0)
+                        .unwrap(),
+                );
+                RotatorTimePoint::next_local_hour(&now)
+                    .format("%m-%d %H:%M %:z")
+                    .to_string()
+            };
+
+            assert_eq!(next(5, 10, 5, 6), "05-10 16:00 +10:30");
+
+            // DST starts, the local time 02:00 is skipped to 02:30
+            assert_eq!(next(10, 5, 14, 40), "10-06 02:30 +11:00");
+            assert_eq!(next(10, 5, 15, 40), "10-06 03:00 +11:00");
+
+            // DST ends, the local time 01:30 to 02:00 occurs twice
+            assert_eq!(next(4, 6, 13, 59), "04-07 01:00 +11:00");
+            assert_eq!(next(4, 6, 14, 10), "04-07 02:00 +10:30");
+            assert_eq!(next(4, 6, 15, 10), "04-07 02:00 +10:30");
+        }
+
         #[test]
         fn rotate() {
             let build = |rotate_on_open| {
@@ -0,0 +1,381 @@
+//! Provides a file sink for single-threaded logging.
+
+use std::{
+    cell::RefCell,
+    convert::Infallible,
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::PathBuf,
+};
+
+use crate::{
+    formatter::FmtExtraInfo,
+    sink::{helper, path_template, Sink},
+    sync::*,
+    utils, Error, Record, Result, StringBuf,
+};
+
+/// A sink with a file as the target, for loggers used from a single thread.
+///
+/// [`FileSink`] locks a mutex around the file for each record, so that it can
+/// be shared between threads. For programs logging from a single thread, e.g.
+/// CLI tools and single-threaded servers, the lock is pure overhead. This sink
+/// replaces it with a check that the calling thread is the owner thread, which
+/// is a plain thread-local read and comparison.
+///
+/// An uncontended lock is cheap, so the gain is small compared to formatting
+/// and writing a record, see benchmark `bench_13_single_threaded_file` against
+/// `bench_1_file` in the `spdlog_rs` benchmark.
+///
+/// The sink is not a drop-in replacement for [`FileSink`], it does not support
+/// its options except [`SingleThreadedFileSinkBuilder::truncate`], and it
+/// cannot be cloned with [`Sink::clone_box`].
+///
+/// # Thread safety
+///
+/// The sink is still [`Send`] and [`Sync`] as required by [`Sink`], so it can
+/// be moved to and referenced from any thread, but it writes on one thread
+/// only:
+///
+/// - The owner thread is the first thread that writes a record to the sink or
+///   flushes it. The sink can be built on another thread, and moved to the
+///   owner thread before its first use. The owner never changes afterwards.
+///
+/// - Writing a record or flushing on any other thread fails with
+///   [`Error::WriteRecord`] or [`Error::FlushBuffer`] respectively, and the
+///   file is left untouched. The error is passed to the error handler as usual,
+///   so misuse shows up immediately instead of corrupting the file.
+///
+/// - Writing a record or flushing reentrantly, i.e. from a formatter or an
+///   error handler called by the sink itself, fails the same way.
+///
+/// - Dropping the sink flushes the file on the dropping thread, which is safe
+///   as it has exclusive access to the sink then.
+///
+/// Thus, the sink must not be used with loggers logging from several threads,
+/// or behind [asynchronous combined sinks], whose worker threads would become
+/// the owner. A logger flushing periodically (see
+/// [`Logger::set_flush_period`]) flushes on its own thread and fails, flush on
+/// the owner thread with [`Logger::flush`] instead.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// use spdlog::{prelude::*, sink::SingleThreadedFileSink};
+///
+/// # fn main() -> Result<(), spdlog::Error> {
+/// # let path = std::env::temp_dir().join("single_threaded_file_sink_example.log");
+/// let sink = Arc::new(SingleThreadedFileSink::builder().path(path).build()?);
+/// let logger = Logger::builder().sink(sink).build()?;
+///
+/// info!(logger: logger, "hello");
+/// logger.flush();
+/// # Ok(()) }
+/// ```
+///
+/// [`FileSink`]: crate::sink::FileSink
+/// [asynchronous combined sinks]: index.html#asynchronous-combined-sink
+/// [`Logger::set_flush_period`]: crate::Logger::set_flush_period
+/// [`Logger::flush`]: crate::Logger::flush
+pub struct SingleThreadedFileSink {
+    common_impl: helper::CommonImpl,
+    // Only accessed on the owner thread, see `access`
+    file: RefCell<BufWriter<File>>,
+    // The ID of the owner thread, or `NO_OWNER` before the first use
+    owner: AtomicUsize,
+}
+
+const NO_OWNER: usize = 0;
+
+static NEXT_THREAD_ID: AtomicUsize = AtomicUsize::new(NO_OWNER + 1);
+
+thread_local! {
+    // Unlike `std::thread::ThreadId`, obtaining it does not clone the handle of
+    // the current thread.
+    static THREAD_ID: usize = NEXT_THREAD_ID.fetch_add(1, Ordering::Relaxed);
+}
+
+// SAFETY: The only field that is not `Sync` is `file`, which is accessed via a
+// shared reference in `access` only, after checking that the current thread is
+// the owner thread. The owner is set once and never changes, so `file` is never
+// accessed via shared references on two threads.
+unsafe impl Sync for SingleThreadedFileSink {}
+
+impl SingleThreadedFileSink {
+    /// Constructs a builder of `SingleThreadedFileSink`.
+    #[must_use]
+    pub fn builder() -> SingleThreadedFileSinkBuilder<()> {
+        SingleThreadedFileSinkBuilder {
+            common_builder_impl: helper::CommonBuilderImpl::new(),
+            path: (),
+            path_template: false,
+            truncate: false,
+        }
+    }
+
+    // Calls `f` with the file if the current thread is the owner thread, the
+    // error returned otherwise is passed to `map_err`.
+    fn access<R>(
+        &self,
+        f: impl FnOnce(&mut BufWriter<File>) -> io::Result<R>,
+        map_err: fn(io::Error) -> Error,
+    ) -> Result<R> {
+        let current = THREAD_ID.with(|id| *id);
+        let owner = match self.owner.compare_exchange(
+            NO_OWNER,
+            current,
+            Ordering::Relaxed,
+            Ordering::Relaxed,
+        ) {
+            Ok(_) => current,
+            Err(owner) => owner,
+        };
+        if owner != current {
+            return Err(map_err(io::Error::new(
+                io::ErrorKind::Other,
+                "SingleThreadedFileSink used from a thread other than its owner thread",
+            )));
+        }
+
+        let mut file = self.file.try_borrow_mut().map_err(|_| {
+            map_err(io::Error::new(
+                io::ErrorKind::Other,
+                "SingleThreadedFileSink used reentrantly",
+            ))
+        })?;
+        f(&mut file).map_err(map_err)
+    }
+}
+
+impl Sink for SingleThreadedFileSink {
+    fn log(&self, record: &Record) -> Result<()> {
+        if !self.should_log(record.level()) {
+            return Ok(());
+        }
+
+        let mut string_buf = StringBuf::new();
+        let extra_info = self
+            .common_impl
+            .formatter
+            .read()
+            .format(record, &mut string_buf)?;
+
+        self.write_formatted(record, &string_buf, &extra_info)
+    }
+
+    fn write_formatted(
+        &self,
+        record: &Record,
+        formatted: &StringBuf,
+        _extra_info: &FmtExtraInfo,
+    ) -> Result<()> {
+        if !self.should_log(record.level()) {
+            return Ok(());
+        }
+
+        self.access(
+            |file| file.write_all(formatted.as_bytes()),
+            Error::WriteRecord,
+        )
+    }
+
+    fn flush(&self) -> Result<()> {
+        self.access(|file| file.flush(), Error::FlushBuffer)
+    }
+
+    helper::common_impl!(@Sink: common_impl);
+}
+
+impl Drop for SingleThreadedFileSink {
+    fn drop(&mut self) {
+        if let Err(err) = self.file.get_mut().flush() {
+            self.common_impl
+                .non_returnable_error("SingleThreadedFileSink", Error::FlushBuffer(err))
+        }
+    }
+}
+
+// --------------------------------------------------
+
+/// The builder of [`SingleThreadedFileSink`].
+#[doc = include_str!("../include/doc/generic-builder-note.md")]
+/// # Examples
+///
+/// - Building a [`SingleThreadedFileSink`].
+///
+///   ```no_run
+///   use spdlog::sink::SingleThreadedFileSink;
+///
+///   # fn main() -> Result<(), spdlog::Error> {
+///   let sink: SingleThreadedFileSink = SingleThreadedFileSink::builder()
+///       .path("/path/to/log_file") // required
+///       // .truncate(true) // optional, defaults to `false`
+///       .build()?;
+///   # Ok(()) }
+///   ```
+///
+/// - If any required parameters are missing, a compile-time error will be
+///   raised.
+///
+///   ```compile_fail,E0061
+///   use spdlog::sink::SingleThreadedFileSink;
+///
+///   # fn main() -> Result<(), spdlog::Error> {
+///   let sink: SingleThreadedFileSink = SingleThreadedFileSink::builder()
+///       // .path("/path/to/log_file") // required
+///       .truncate(true) // optional, defaults to `false`
+///       .build()?;
+///   # Ok(()) }
+///   ```
+pub struct SingleThreadedFileSinkBuilder<ArgPath> {
+    common_builder_impl: helper::CommonBuilderImpl,
+    path: ArgPath,
+    path_template: bool,
+    truncate: bool,
+}
+
+impl<ArgPath> SingleThreadedFileSinkBuilder<ArgPath> {
+    /// The path of the log file.
+    ///
+    /// It's used literally, unless
+    /// [`SingleThreadedFileSinkBuilder::path_template`] is enabled.
+    ///
+    /// This parameter is **required**.
+    #[must_use]
+    pub fn path<P>(self, path: P) -> SingleThreadedFileSinkBuilder<PathBuf>
+    where
+        P: Into<PathBuf>,
+    {
+        SingleThreadedFileSinkBuilder {
+            common_builder_impl: self.common_builder_impl,
+            path: path.into(),
+            path_template: self.path_template,
+            truncate: self.truncate,
+        }
+    }
+
+    /// Specifies whether the path is a template, e.g.
+    /// `"/var/log/${SERVICE}/app-{pid}.log"`, resolved when the sink is built.
+    ///
+    /// If it is `false`, the path is used literally, even if it contains `{`,
+    /// `}`, `$` or `~`.
+    ///
+    /// This parameter is **optional**, and defaults to `false`.
+    #[doc = include_str!("../include/doc/path-template.md")]
+    #[must_use]
+    pub fn path_template(mut self, path_template: bool) -> Self {
+        self.path_template = path_template;
+        self
+    }
+
+    /// If it is true, the existing contents of the file will be discarded.
+    ///
+    /// This parameter is **optional**, and defaults to `false`.
+    #[must_use]
+    pub fn truncate(mut self, truncate: bool) -> Self {
+        self.truncate = truncate;
+        self
+    }
+
+    helper::common_impl!(@SinkBuilder: common_builder_impl);
+}
+
+impl SingleThreadedFileSinkBuilder<()> {
+    #[doc(hidden)]
+    #[deprecated(note = "\n\n\
+        builder compile-time error:\n\
+        - missing required field `path`\n\n\
+    ")]
+    pub fn build(self, _: Infallible) {}
+}
+
+impl SingleThreadedFileSinkBuilder<PathBuf> {
+    /// Builds a [`SingleThreadedFileSink`].
+    ///
+    /// # Errors
+    ///
+    /// If [`SingleThreadedFileSinkBuilder::path_template`] is enabled and the
+    /// path template is invalid, [`Error::InvalidArgument`] will be returned.
+    /// If an error occurs opening the file, [`Error::CreateDirectory`] or
+    /// [`Error::OpenFile`] will be returned.
+    pub fn build(self) -> Result<SingleThreadedFileSink> {
+        let path = if self.path_template {
+            path_template::resolve(&self.path)?
+        } else {
+            self.path
+        };
+        let file = utils::open_file(path, self.truncate)?;
+
+        Ok(SingleThreadedFileSink {
+            common_impl: helper::CommonImpl::from_builder(self.common_builder_impl),
+            file: RefCell::new(BufWriter::new(file)),
+            owner: AtomicUsize::new(NO_OWNER),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, thread};
+
+    use super::*;
+    use crate::{prelude::*, test_utils::*};
+
+    static LOGS_PATH: Lazy<PathBuf> = Lazy::new(|| {
+        let path = TEST_LOGS_PATH.join("single_threaded_file_sink");
+        fs::create_dir_all(&path).unwrap();
+        path
+    });
+
+    #[test]
+    fn owner_thread() {
+        let path = LOGS_PATH.join("owner_thread.log");
+        let sink = Arc::new(
+            SingleThreadedFileSink::builder()
+                .path(&path)
+                .truncate(true)
+                .formatter(Box::new(NoModFormatter::new()))
+                .build()
+                .unwrap(),
+        );
+
+        // Built on this thread, but owned by the thread first using it
+        let owner = thread::spawn({
+            let sink = sink.clone();
+            move || {
+                let record = Record::new(Level::Info, "owned ");
+                sink.log(&record).unwrap();
+                sink.flush().unwrap();
+            }
+        });
+        owner.join().unwrap();
+
+        let record = Record::new(Level::Info, "not owned");
+        assert!(matches!(sink.log(&record), Err(Error::WriteRecord(_))));
+        assert!(matches!(sink.flush(), Err(Error::FlushBuffer(_))));
+
+        drop(sink);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "owned ");
+    }
+
+    #[test]
+    fn flush_on_drop() {
+        let path = LOGS_PATH.join("flush_on_drop.log");
+        let sink = SingleThreadedFileSink::builder()
+            .path(&path)
+            .truncate(true)
+            .formatter(Box::new(NoModFormatter::new()))
+            .build()
+            .unwrap();
+        let logger = build_test_logger(|b| b.sink(Arc::new(sink)));
+
+        info!(logger: logger, "hello ");
+        info!(logger: logger, "world");
+        assert_eq!(fs::read_to_string(&path).unwrap(), "");
+
+        drop(logger);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "hello world");
+    }
+}
@@ -2,15 +2,16 @@
 
 use std::{
     convert::Infallible,
-    io::{self, Write},
+    io::{self, BufWriter, Write},
+    ops::Range,
 };
 
-use if_chain::if_chain;
-
 use crate::{
-    sink::{helper, Sink},
-    terminal_style::{LevelStyleCodes, Style, StyleMode},
-    Error, Level, Record, Result, StringBuf,
+    formatter::{FmtExtraInfo, StyleHint},
+    sink::{helper, FlushErrorPolicy, Sink},
+    sync::*,
+    terminal_style::{self, HintStyleCodes, LevelStyleCodes, Style, StyleMode},
+    Error, Level, Record, Result, StringBuf, EOL,
 };
 
 /// An enum representing the available standard streams.
@@ -22,16 +23,38 @@ pub enum StdStream {
     Stderr,
 }
 
-// `io::stdout()` and `io::stderr()` return different types, and
-// `Std***::lock()` is not in any trait, so we need this struct to abstract
-// them.
+/// Flush policies for [`StdStreamSink`].
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum FlushPolicy {
+    /// Chooses the policy the same way as libc does.
+    ///
+    /// For `stdout`, it is [`FlushPolicy::EveryRecord`] if the stream is a
+    /// terminal for interactivity, otherwise (e.g. piped or redirected to a
+    /// file) it is [`FlushPolicy::Buffered`] for throughput. For `stderr`, it
+    /// is always [`FlushPolicy::EveryRecord`].
+    ///
+    /// Buffered records are flushed when the sink is dropped, but they are lost
+    /// if the process exits without dropping it (e.g. the sink is held by the
+    /// default logger) or aborts.
+    Auto,
+    /// Flushes the buffer once with each record.
+    EveryRecord,
+    /// Buffers records, and flushes when the buffer is full, [`Sink::flush`]
+    /// is called or the sink is dropped.
+    ///
+    /// See [`FlushPolicy::Auto`] for the records that may be lost.
+    Buffered,
+}
+
+// `io::stdout()` and `io::stderr()` return different types, so we need this
+// struct to abstract them.
 #[derive(Debug)]
-enum StdStreamDest<O, E> {
-    Stdout(O),
-    Stderr(E),
+enum StdStreamDest {
+    Stdout(io::Stdout),
+    Stderr(io::Stderr),
 }
 
-impl StdStreamDest<io::Stdout, io::Stderr> {
+impl StdStreamDest {
     #[must_use]
     fn new(stream: StdStream) -> Self {
         match stream {
@@ -40,14 +63,6 @@ impl StdStreamDest<io::Stdout, io::Stderr> {
         }
     }
 
-    #[must_use]
-    fn lock(&self) -> StdStreamDest<io::StdoutLock<'_>, io::StderrLock<'_>> {
-        match self {
-            StdStreamDest::Stdout(stream) => StdStreamDest::Stdout(stream.lock()),
-            StdStreamDest::Stderr(stream) => StdStreamDest::Stderr(stream.lock()),
-        }
-    }
-
     fn stream_type(&self) -> StdStream {
         match self {
             StdStreamDest::Stdout(_) => StdStream::Stdout,
@@ -56,38 +71,48 @@ impl StdStreamDest<io::Stdout, io::Stderr> {
     }
 }
 
-macro_rules! impl_write_for_dest {
-    ( $dest:ty ) => {
-        impl Write for $dest {
-            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-                match self {
-                    StdStreamDest::Stdout(stream) => stream.write(buf),
-                    StdStreamDest::Stderr(stream) => stream.write(buf),
-                }
-            }
+impl Write for StdStreamDest {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            StdStreamDest::Stdout(stream) => stream.write(buf),
+            StdStreamDest::Stderr(stream) => stream.write(buf),
+        }
+    }
 
-            fn flush(&mut self) -> io::Result<()> {
-                match self {
-                    StdStreamDest::Stdout(stream) => stream.flush(),
-                    StdStreamDest::Stderr(stream) => stream.flush(),
-                }
-            }
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            StdStreamDest::Stdout(stream) => stream.flush(),
+            StdStreamDest::Stderr(stream) => stream.flush(),
         }
-    };
+    }
 }
-impl_write_for_dest!(StdStreamDest<io::Stdout, io::Stderr>);
-impl_write_for_dest!(StdStreamDest<io::StdoutLock<'_>, io::StderrLock<'_>>);
 
 /// A sink with a std stream as the target.
 ///
 /// It writes styled text or plain text according to the given [`StyleMode`].
 ///
-/// Note that this sink always flushes the buffer once with each logging.
+/// Whether the buffer is flushed with each logging depends on the given
+/// [`FlushPolicy`], by default it is flushed with each record. The default is
+/// not [`FlushPolicy::Auto`], since the sinks of the default logger are never
+/// dropped, so records buffered when stdout is piped or redirected would be
+/// lost at process exit unless the application flushes the default logger
+/// itself, e.g. with [`flush_default`].
+///
+/// Long lines can be hard-wrapped for display, see
+/// [`StdStreamSinkBuilder::max_line_width`].
+///
+/// [`flush_default`]: crate::flush_default
 pub struct StdStreamSink {
     common_impl: helper::CommonImpl,
-    dest: StdStreamDest<io::Stdout, io::Stderr>,
+    // Shared with clones. A blocking mutex, since it's held while writing to
+    // the stream, which may block if it's a slow terminal or pipe.
+    dest: Arc<Mutex<BufWriter<StdStreamDest>>>,
+    flush_every_record: bool,
+    flush_error: helper::FlushErrorState,
     should_render_style: bool,
     level_style_codes: LevelStyleCodes,
+    hint_style_codes: HintStyleCodes,
+    max_line_width: Option<usize>,
 }
 
 impl StdStreamSink {
@@ -98,6 +123,9 @@ impl StdStreamSink {
             common_builder_impl: helper::CommonBuilderImpl::new(),
             std_stream: (),
             style_mode: StyleMode::Auto,
+            flush_policy: FlushPolicy::EveryRecord,
+            flush_error_policy: FlushErrorPolicy::Propagate,
+            max_line_width: None,
         }
     }
 
@@ -120,25 +148,109 @@ impl StdStreamSink {
         self.level_style_codes.set_code(level, style);
     }
 
+    /// Sets the style of the spans with the specified [`StyleHint`].
+    ///
+    /// The spans are not styled unless a style is set for their hint.
+    ///
+    /// The style of [`StyleHint::Level`] is determined by the level of the
+    /// record, use [`Self::set_style`] for it instead. Setting it here has no
+    /// effect.
+    pub fn set_hint_style(&mut self, hint: StyleHint, style: Style) {
+        self.hint_style_codes.set_code(hint, style);
+    }
+
     /// Sets the style mode.
     pub fn set_style_mode(&mut self, style_mode: StyleMode) {
-        self.should_render_style = Self::should_render_style(style_mode, self.dest.stream_type());
+        let stream = self.dest.lock_expect().get_ref().stream_type();
+        self.should_render_style = Self::should_render_style(style_mode, stream);
+    }
+
+    /// Sets the maximum display width of lines, or disables hard-wrapping with
+    /// `None`.
+    ///
+    /// See the documentation of [`StdStreamSinkBuilder::max_line_width`] for
+    /// the description of this parameter.
+    pub fn set_max_line_width(&mut self, max_line_width: Option<usize>) {
+        self.max_line_width = max_line_width;
     }
 
     #[must_use]
-    fn should_render_style(style_mode: StyleMode, stream: StdStream) -> bool {
+    fn is_terminal(stream: StdStream) -> bool {
         use is_terminal::IsTerminal;
-        let is_terminal = match stream {
+        match stream {
             StdStream::Stdout => io::stdout().is_terminal(),
             StdStream::Stderr => io::stderr().is_terminal(),
-        };
+        }
+    }
 
+    #[must_use]
+    fn should_render_style(style_mode: StyleMode, stream: StdStream) -> bool {
         match style_mode {
             StyleMode::Always => true,
-            StyleMode::Auto => is_terminal && enable_ansi_escape_sequences(),
+            StyleMode::Auto => Self::is_terminal(stream) && enable_ansi_escape_sequences(),
             StyleMode::Never => false,
         }
     }
+
+    fn write(
+        &self,
+        record: &Record,
+        string_buf: &StringBuf,
+        extra_info: &FmtExtraInfo,
+    ) -> Result<()> {
+        let mut dest = self.dest.lock_expect();
+
+        match self.max_line_width {
+            None => self.write_styled(
+                &mut *dest,
+                record,
+                string_buf.as_bytes(),
+                extra_info.style_ranges(),
+            ),
+            Some(max_width) => {
+                let (wrapped, style_ranges) = hard_wrap(record, string_buf, extra_info, max_width);
+                self.write_styled(
+                    &mut *dest,
+                    record,
+                    wrapped.as_bytes(),
+                    style_ranges.into_iter(),
+                )
+            }
+        }
+        .map_err(Error::WriteRecord)?;
+
+        if self.flush_every_record {
+            self.flush_error
+                .handle(dest.flush().map_err(Error::FlushBuffer))?;
+        }
+
+        Ok(())
+    }
+
+    fn write_styled(
+        &self,
+        dest: &mut impl Write,
+        record: &Record,
+        text: &[u8],
+        style_ranges: impl Iterator<Item = (Range<usize>, StyleHint)>,
+    ) -> io::Result<()> {
+        self.level_style_codes.write_styled(
+            &self.hint_style_codes,
+            dest,
+            record.level(),
+            text,
+            style_ranges.filter(|_| self.should_render_style),
+        )
+    }
+
+    #[must_use]
+    fn should_flush_every_record(flush_policy: FlushPolicy, stream: StdStream) -> bool {
+        match flush_policy {
+            FlushPolicy::Auto => stream == StdStream::Stderr || Self::is_terminal(stream),
+            FlushPolicy::EveryRecord => true,
+            FlushPolicy::Buffered => false,
+        }
+    }
 }
 
 impl Sink for StdStreamSink {
@@ -154,44 +266,52 @@ impl Sink for StdStreamSink {
             .read()
             .format(record, &mut string_buf)?;
 
-        let mut dest = self.dest.lock();
-
-        (|| {
-            if_chain! {
-                if self.should_render_style;
-                if let Some(style_range) = extra_info.style_range();
-                then {
-                    let style_code = self.level_style_codes.code(record.level());
-
-                    dest.write_all(string_buf[..style_range.start].as_bytes())?;
-                    dest.write_all(style_code.start.as_bytes())?;
-                    dest.write_all(string_buf[style_range.start..style_range.end].as_bytes())?;
-                    dest.write_all(style_code.end.as_bytes())?;
-                    dest.write_all(string_buf[style_range.end..].as_bytes())?;
-                } else {
-                    dest.write_all(string_buf.as_bytes())?;
-                }
-            }
-            Ok(())
-        })()
-        .map_err(Error::WriteRecord)?;
+        self.write(record, &string_buf, &extra_info)
+    }
 
-        // stderr is not buffered, so we don't need to flush it.
-        // https://doc.rust-lang.org/std/io/fn.stderr.html
-        if let StdStreamDest::Stdout(_) = dest {
-            dest.flush().map_err(Error::FlushBuffer)?;
+    fn write_formatted(
+        &self,
+        record: &Record,
+        formatted: &StringBuf,
+        extra_info: &FmtExtraInfo,
+    ) -> Result<()> {
+        if !self.should_log(record.level()) {
+            return Ok(());
         }
 
-        Ok(())
+        self.write(record, formatted, extra_info)
     }
 
     fn flush(&self) -> Result<()> {
-        self.dest.lock().flush().map_err(Error::FlushBuffer)
+        self.flush_error
+            .handle(self.dest.lock_expect().flush().map_err(Error::FlushBuffer))
+    }
+
+    fn clone_box(&self) -> Option<Box<dyn Sink>> {
+        Some(Box::new(StdStreamSink {
+            common_impl: self.common_impl.clone_config(),
+            dest: self.dest.clone(),
+            flush_every_record: self.flush_every_record,
+            flush_error: helper::FlushErrorState::new(self.flush_error.policy()),
+            should_render_style: self.should_render_style,
+            level_style_codes: self.level_style_codes.clone(),
+            hint_style_codes: self.hint_style_codes.clone(),
+            max_line_width: self.max_line_width,
+        }))
     }
 
     helper::common_impl!(@Sink: common_impl);
 }
 
+impl Drop for StdStreamSink {
+    fn drop(&mut self) {
+        let flush_result = self.dest.lock_expect().flush().map_err(Error::FlushBuffer);
+        if let Err(err) = self.flush_error.handle(flush_result) {
+            self.common_impl.non_returnable_error("StdStreamSink", err)
+        }
+    }
+}
+
 // --------------------------------------------------
 
 /// The builder of [`StdStreamSink`].
@@ -202,7 +322,7 @@ impl Sink for StdStreamSink {
 ///
 ///   ```
 ///   use spdlog::{
-///       sink::{StdStreamSink, StdStream},
+///       sink::{FlushPolicy, StdStreamSink, StdStream},
 ///       terminal_style::StyleMode
 ///   };
 ///
@@ -211,6 +331,8 @@ impl Sink for StdStreamSink {
 ///       .std_stream(StdStream::Stdout) // required
 ///       /* .style_mode(StyleMode::Never) // optional, defaults to
 ///                                        // `StyleMode::Auto` */
+///       /* .flush_policy(FlushPolicy::Auto) // optional, defaults to
+///                                           // `FlushPolicy::EveryRecord` */
 ///       .build()?;
 ///   # Ok(()) }
 ///   ```
@@ -236,6 +358,9 @@ pub struct StdStreamSinkBuilder<ArgSS> {
     common_builder_impl: helper::CommonBuilderImpl,
     std_stream: ArgSS,
     style_mode: StyleMode,
+    flush_policy: FlushPolicy,
+    flush_error_policy: FlushErrorPolicy,
+    max_line_width: Option<usize>,
 }
 
 impl<ArgSS> StdStreamSinkBuilder<ArgSS> {
@@ -248,6 +373,9 @@ impl<ArgSS> StdStreamSinkBuilder<ArgSS> {
             common_builder_impl: self.common_builder_impl,
             std_stream,
             style_mode: self.style_mode,
+            flush_policy: self.flush_policy,
+            flush_error_policy: self.flush_error_policy,
+            max_line_width: self.max_line_width,
         }
     }
 
@@ -260,6 +388,54 @@ impl<ArgSS> StdStreamSinkBuilder<ArgSS> {
         self
     }
 
+    /// Specifies the flush policy.
+    ///
+    /// Use [`FlushPolicy::Auto`] or [`FlushPolicy::Buffered`] to trade
+    /// durability for throughput when the stream is piped or redirected.
+    ///
+    /// This parameter is **optional**, and defaults to
+    /// [`FlushPolicy::EveryRecord`], see [`StdStreamSink`] for why it's not
+    /// [`FlushPolicy::Auto`].
+    #[must_use]
+    pub fn flush_policy(mut self, flush_policy: FlushPolicy) -> Self {
+        self.flush_policy = flush_policy;
+        self
+    }
+
+    /// Specifies the policy for handling errors flushing the buffer, e.g. when
+    /// the stream is piped to a process that has exited.
+    ///
+    /// See [`FlushErrorPolicy`] for details.
+    ///
+    /// This parameter is **optional**, and defaults to
+    /// [`FlushErrorPolicy::Propagate`].
+    #[must_use]
+    pub fn flush_error_policy(mut self, flush_error_policy: FlushErrorPolicy) -> Self {
+        self.flush_error_policy = flush_error_policy;
+        self
+    }
+
+    /// Specifies the maximum display width of lines, in columns.
+    ///
+    /// Lines wider than it are hard-wrapped, with the continuation lines
+    /// indented to align under the column where the payload starts. It's for
+    /// display only, e.g. in an interactive terminal, as it changes the text
+    /// written.
+    ///
+    /// The width is measured as a terminal displays the text: escape sequences
+    /// (e.g. styles) are not counted, and East Asian wide characters (e.g. CJK
+    /// characters) are counted as two columns. The payload is located by
+    /// searching the formatted text, continuation lines are not indented if
+    /// it's not found, or if the indentation leaves no room in the line.
+    ///
+    /// This parameter is **optional**, and defaults to `None`, i.e. lines are
+    /// not wrapped.
+    #[must_use]
+    pub fn max_line_width(mut self, max_line_width: usize) -> Self {
+        self.max_line_width = Some(max_line_width);
+        self
+    }
+
     helper::common_impl!(@SinkBuilder: common_builder_impl);
 }
 
@@ -277,16 +453,84 @@ impl StdStreamSinkBuilder<StdStream> {
     pub fn build(self) -> Result<StdStreamSink> {
         Ok(StdStreamSink {
             common_impl: helper::CommonImpl::from_builder(self.common_builder_impl),
-            dest: StdStreamDest::new(self.std_stream),
+            dest: Arc::new(Mutex::new(BufWriter::new(StdStreamDest::new(
+                self.std_stream,
+            )))),
+            flush_every_record: StdStreamSink::should_flush_every_record(
+                self.flush_policy,
+                self.std_stream,
+            ),
+            flush_error: helper::FlushErrorState::new(self.flush_error_policy),
             should_render_style: StdStreamSink::should_render_style(
                 self.style_mode,
                 self.std_stream,
             ),
             level_style_codes: LevelStyleCodes::default(),
+            hint_style_codes: HintStyleCodes::default(),
+            max_line_width: self.max_line_width,
         })
     }
 }
 
+// Hard-wraps the formatted text, returning the wrapped text and the style
+// ranges moved accordingly. Ranges are split at the wrap points, so that the
+// inserted line breaks and indentation are not styled.
+#[must_use]
+fn hard_wrap(
+    record: &Record,
+    formatted: &str,
+    extra_info: &FmtExtraInfo,
+    max_width: usize,
+) -> (String, Vec<(Range<usize>, StyleHint)>) {
+    let indent = payload_column(record, formatted, extra_info).filter(|&col| col < max_width);
+    let indent = indent.unwrap_or(0);
+    let points = terminal_style::wrap_points(formatted, max_width, indent);
+
+    let inserted_len = EOL.len() + indent;
+    let mut wrapped = String::with_capacity(formatted.len() + points.len() * inserted_len);
+    let mut copied = 0;
+    for &point in &points {
+        wrapped.push_str(&formatted[copied..point]);
+        wrapped.push_str(EOL);
+        wrapped.extend(std::iter::repeat(' ').take(indent));
+        copied = point;
+    }
+    wrapped.push_str(&formatted[copied..]);
+
+    // A range starting at a point starts after the inserted line break, and a
+    // range ending at a point ends before it.
+    let move_range = |range: Range<usize>| {
+        let start = range.start + points.partition_point(|&p| p <= range.start) * inserted_len;
+        let end = range.end + points.partition_point(|&p| p < range.end) * inserted_len;
+        start..end
+    };
+    let mut style_ranges = Vec::new();
+    for (range, hint) in extra_info.style_ranges() {
+        let mut start = range.start;
+        for &point in points.iter().filter(|&&p| range.start < p && p < range.end) {
+            style_ranges.push((move_range(start..point), hint));
+            start = point;
+        }
+        style_ranges.push((move_range(start..range.end), hint));
+    }
+
+    (wrapped, style_ranges)
+}
+
+// Returns the display column where the payload starts, searching after the
+// level, which precedes the payload in common formats.
+#[must_use]
+fn payload_column(record: &Record, formatted: &str, extra_info: &FmtExtraInfo) -> Option<usize> {
+    let payload = record.payload();
+    if payload.is_empty() {
+        return None;
+    }
+    let search_start = extra_info.style_range().map_or(0, |range| range.end);
+    let pos = formatted.get(search_start..)?.find(payload)? + search_start;
+    let line_start = formatted[..pos].rfind('\n').map_or(0, |pos| pos + 1);
+    Some(terminal_style::display_width(&formatted[line_start..pos]))
+}
+
 // --------------------------------------------------
 #[cfg(windows)]
 #[must_use]
@@ -327,3 +571,96 @@ fn enable_ansi_escape_sequences() -> bool {
 fn enable_ansi_escape_sequences() -> bool {
     true
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flush_every_record() {
+        let should_flush = StdStreamSink::should_flush_every_record;
+
+        for stream in [StdStream::Stdout, StdStream::Stderr] {
+            assert!(should_flush(FlushPolicy::EveryRecord, stream));
+            assert!(!should_flush(FlushPolicy::Buffered, stream));
+        }
+        assert!(should_flush(FlushPolicy::Auto, StdStream::Stderr));
+        assert_eq!(
+            should_flush(FlushPolicy::Auto, StdStream::Stdout),
+            StdStreamSink::is_terminal(StdStream::Stdout)
+        );
+    }
+
+    #[test]
+    fn flush_every_record_by_default() {
+        let sink = StdStreamSink::builder()
+            .std_stream(StdStream::Stdout)
+            .build()
+            .unwrap();
+        assert!(sink.flush_every_record);
+    }
+
+    #[test]
+    fn flush_error_policy() {
+        let sink = StdStreamSink::builder()
+            .std_stream(StdStream::Stdout)
+            .build()
+            .unwrap();
+        assert_eq!(sink.flush_error.policy(), FlushErrorPolicy::Propagate);
+
+        let sink = StdStreamSink::builder()
+            .std_stream(StdStream::Stdout)
+            .flush_error_policy(FlushErrorPolicy::ReportOnce)
+            .build()
+            .unwrap();
+        assert_eq!(sink.flush_error.policy(), FlushErrorPolicy::ReportOnce);
+    }
+
+    #[test]
+    fn hard_wrap_lines() {
+        let record = Record::new(Level::Info, "\x1b[1mbold\x1b[0m text 日本語");
+        let formatted = format!("[info] {} user=alice{}", record.payload(), EOL);
+        let user = formatted.find("user").unwrap();
+        let extra_info = FmtExtraInfo::builder()
+            .style_range(1..5)
+            .style_range_with_hint(user..user + 4, StyleHint::Key)
+            .build();
+
+        let (wrapped, style_ranges) = hard_wrap(&record, &formatted, &extra_info, 16);
+        let expected = [
+            "[info] \x1b[1mbold\x1b[0m text",
+            "        日本語 u",
+            "       ser=alice",
+            "",
+        ]
+        .join(EOL);
+        assert_eq!(wrapped, expected);
+        let styled = style_ranges
+            .iter()
+            .map(|(range, hint)| (&wrapped[range.clone()], *hint))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            styled,
+            [
+                ("info", StyleHint::Level),
+                ("u", StyleHint::Key),
+                ("ser", StyleHint::Key)
+            ]
+        );
+
+        // Not indented if the indentation leaves no room
+        let (wrapped, _) = hard_wrap(&record, &formatted, &extra_info, 6);
+        assert!(wrapped.starts_with(&format!("[info]{} \x1b[1mbold\x1b[0m {}text", EOL, EOL)));
+
+        // Not indented if the payload is not found
+        let other = Record::new(Level::Info, "not in the text");
+        let (wrapped, _) = hard_wrap(&other, "[info] abcdefgh", &FmtExtraInfo::new(), 10);
+        assert_eq!(wrapped, format!("[info] abc{}defgh", EOL));
+
+        // Wrapped lines fit in the width
+        let (wrapped, _) = hard_wrap(&record, &formatted, &extra_info, 12);
+        for line in wrapped.lines() {
+            assert!(terminal_style::display_width(line) <= 12, "{:?}", line);
+        }
+    }
+}
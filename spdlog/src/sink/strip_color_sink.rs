@@ -0,0 +1,240 @@
+use std::{convert::Infallible, sync::Arc};
+
+use crate::{
+    formatter::FmtExtraInfo,
+    sink::{helper, Sink},
+    strip_ansi, Record, Result, StringBuf,
+};
+
+/// A [combined sink], removing ANSI color codes from formatted records before
+/// the sub-sink writes them.
+///
+/// It's intended for forwarding records whose formatted text contains SGR
+/// escape sequences, e.g. colors written into the payload or by a formatter, to
+/// a destination that cannot render them, such as a file or a network
+/// collector. The sequences are removed by [`strip_ansi`].
+///
+/// Records logged to this sink are formatted by its own formatter, records
+/// passed from another combined sink keep their formatted text. In both cases,
+/// the sub-sink receives the stripped text without [style ranges], so it does
+/// not add colors again.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// use spdlog::{
+///     prelude::*,
+///     sink::{FileSink, StripColorSink},
+/// };
+///
+/// # fn main() -> Result<(), spdlog::Error> {
+/// # let path = std::env::temp_dir().join("strip_color_sink_example.log");
+/// let file_sink = FileSink::builder().path(path).build()?;
+/// let sink = StripColorSink::builder().sink(Arc::new(file_sink)).build()?;
+/// let logger = Logger::builder().sink(Arc::new(sink)).build()?;
+///
+/// info!(logger: logger, "written as \x1b[1mplain\x1b[0m text");
+/// # Ok(()) }
+/// ```
+///
+/// [combined sink]: index.html#combined-sink
+/// [`strip_ansi`]: crate::strip_ansi
+/// [style ranges]: crate::formatter::FmtExtraInfo::style_ranges
+pub struct StripColorSink {
+    common_impl: helper::CommonImpl,
+    sink: Arc<dyn Sink>,
+}
+
+impl StripColorSink {
+    /// Constructs a builder of `StripColorSink`.
+    #[must_use]
+    pub fn builder() -> StripColorSinkBuilder<()> {
+        StripColorSinkBuilder {
+            common_builder_impl: helper::CommonBuilderImpl::new(),
+            sink: (),
+        }
+    }
+
+    /// Gets a reference to the sub-sink.
+    #[must_use]
+    pub fn sink(&self) -> &Arc<dyn Sink> {
+        &self.sink
+    }
+
+    fn write_stripped(&self, record: &Record, formatted: &StringBuf) -> Result<()> {
+        let extra_info = FmtExtraInfo::new();
+        match strip_ansi(formatted) {
+            std::borrow::Cow::Borrowed(_) => {
+                self.sink.write_formatted(record, formatted, &extra_info)
+            }
+            std::borrow::Cow::Owned(stripped) => {
+                let mut string_buf = StringBuf::new();
+                string_buf.push_str(&stripped);
+                self.sink.write_formatted(record, &string_buf, &extra_info)
+            }
+        }
+    }
+}
+
+impl Sink for StripColorSink {
+    fn log(&self, record: &Record) -> Result<()> {
+        if !self.should_log(record.level()) {
+            return Ok(());
+        }
+
+        let mut string_buf = StringBuf::new();
+        self.common_impl
+            .formatter
+            .read()
+            .format(record, &mut string_buf)?;
+
+        self.write_stripped(record, &string_buf)
+    }
+
+    fn write_formatted(
+        &self,
+        record: &Record,
+        formatted: &StringBuf,
+        _extra_info: &FmtExtraInfo,
+    ) -> Result<()> {
+        if !self.should_log(record.level()) {
+            return Ok(());
+        }
+
+        self.write_stripped(record, formatted)
+    }
+
+    fn flush(&self) -> Result<()> {
+        self.sink.flush()
+    }
+
+    fn prefers_sync(&self) -> bool {
+        self.sink.prefers_sync()
+    }
+
+    fn clone_box(&self) -> Option<Box<dyn Sink>> {
+        Some(Box::new(StripColorSink {
+            common_impl: self.common_impl.clone_config(),
+            sink: self.sink.clone(),
+        }))
+    }
+
+    helper::common_impl!(@Sink: common_impl);
+}
+
+/// The builder of [`StripColorSink`].
+#[doc = include_str!("../include/doc/generic-builder-note.md")]
+/// # Examples
+///
+/// - Building a [`StripColorSink`].
+///
+///   ```
+///   use std::sync::Arc;
+///
+///   use spdlog::sink::{FileSink, StripColorSink};
+///
+///   # fn main() -> Result<(), spdlog::Error> {
+///   # let path = std::env::temp_dir().join("strip_color_sink_builder.log");
+///   # let file_sink = FileSink::builder().path(path).build()?;
+///   let sink: StripColorSink = StripColorSink::builder()
+///       .sink(Arc::new(file_sink)) // required
+///       .build()?;
+///   # Ok(()) }
+///   ```
+///
+/// - If any required parameters are missing, a compile-time error will be
+///   raised.
+///
+///   ```compile_fail,E0061
+///   use spdlog::sink::StripColorSink;
+///
+///   # fn main() -> Result<(), spdlog::Error> {
+///   let sink: StripColorSink = StripColorSink::builder()
+///       // .sink(file_sink) // required
+///       .build()?;
+///   # Ok(()) }
+///   ```
+pub struct StripColorSinkBuilder<ArgSink> {
+    common_builder_impl: helper::CommonBuilderImpl,
+    sink: ArgSink,
+}
+
+impl<ArgSink> StripColorSinkBuilder<ArgSink> {
+    /// The sub-sink to write stripped records to.
+    ///
+    /// This parameter is **required**.
+    #[must_use]
+    pub fn sink(self, sink: Arc<dyn Sink>) -> StripColorSinkBuilder<Arc<dyn Sink>> {
+        StripColorSinkBuilder {
+            common_builder_impl: self.common_builder_impl,
+            sink,
+        }
+    }
+
+    helper::common_impl!(@SinkBuilder: common_builder_impl);
+}
+
+impl StripColorSinkBuilder<()> {
+    #[doc(hidden)]
+    #[deprecated(note = "\n\n\
+        builder compile-time error:\n\
+        - missing required field `sink`\n\n\
+    ")]
+    pub fn build(self, _: Infallible) {}
+}
+
+impl StripColorSinkBuilder<Arc<dyn Sink>> {
+    /// Builds a [`StripColorSink`].
+    pub fn build(self) -> Result<StripColorSink> {
+        Ok(StripColorSink {
+            common_impl: helper::CommonImpl::from_builder(self.common_builder_impl),
+            sink: self.sink,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{prelude::*, test_utils::*};
+
+    #[test]
+    fn strip() {
+        let string_sink = Arc::new(StringSink::with(|b| {
+            b.formatter(Box::new(NoModFormatter::new()))
+        }));
+        let sink = StripColorSink::builder()
+            .sink(string_sink.clone())
+            .formatter(Box::new(NoModFormatter::new()))
+            .build()
+            .unwrap();
+        let logger = build_test_logger(|b| b.sink(Arc::new(sink)));
+
+        info!(logger: logger, "\x1b[1m\x1b[31mbold red\x1b[0m \x1b[32;1mgreen\x1b[m.");
+        info!(logger: logger, " \x1b[2Jkept");
+
+        assert_eq!(string_sink.clone_string(), "bold red green. \x1b[2Jkept");
+    }
+
+    #[test]
+    fn strip_formatted() {
+        let string_sink = Arc::new(StringSink::with(|b| {
+            b.formatter(Box::new(NoModFormatter::new()))
+        }));
+        let sink = StripColorSink::builder()
+            .sink(string_sink.clone())
+            .build()
+            .unwrap();
+
+        let record = Record::new(Level::Info, "");
+        let mut formatted = StringBuf::new();
+        formatted.push_str("[\x1b[32minfo\x1b[0m] hello\n");
+        let extra_info = FmtExtraInfo::builder().style_range(1..5).build();
+        sink.write_formatted(&record, &formatted, &extra_info)
+            .unwrap();
+
+        assert_eq!(string_sink.clone_string(), "[info] hello\n");
+    }
+}
@@ -0,0 +1,269 @@
+use std::sync::Arc;
+
+use crate::{
+    formatter::FmtExtraInfo,
+    sink::{helper, Sink},
+    Error, Record, Result, StringBuf,
+};
+
+/// A [combined sink], dispatching records to sub-sinks by their [tags].
+///
+/// Each route maps a tag to a sub-sink, and a record is dispatched to the
+/// sub-sinks of all routes whose tags it has. Besides, records can be
+/// dispatched to a sub-sink regardless of their tags with
+/// [`TagRoutingSinkBuilder::catch_all`], and records matching no routes can
+/// be dispatched to a sub-sink with [`TagRoutingSinkBuilder::unmatched`].
+///
+/// A record is dispatched to a sub-sink once, even if it matches multiple
+/// routes to the same sub-sink.
+///
+/// # Filtering
+///
+/// Records are filtered by the level filter of `TagRoutingSink` first, then
+/// dispatched by the routes, and then filtered by the level filters of
+/// sub-sinks.
+///
+/// # Examples
+///
+/// Sending records tagged `audit` to an append-only file for compliance, and
+/// the others to the regular log file.
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// use spdlog::{
+///     prelude::*,
+///     sink::{FileSink, TagRoutingSink},
+/// };
+///
+/// # fn main() -> Result<(), spdlog::Error> {
+/// # let dir = std::env::temp_dir();
+/// let audit_sink = Arc::new(FileSink::builder().path(dir.join("audit.log")).build()?);
+/// let app_sink = Arc::new(FileSink::builder().path(dir.join("app.log")).build()?);
+///
+/// let sink = Arc::new(
+///     TagRoutingSink::builder()
+///         .route("audit", audit_sink)
+///         .unmatched(app_sink)
+///         .build()?,
+/// );
+/// let logger = Logger::builder().sink(sink).build()?;
+///
+/// // Written to `app.log` only
+/// info!(logger: logger, "cache warmed up");
+/// // Written to `audit.log` only
+/// info!(logger: logger, tags = ["audit", "security"], "user alice logged in");
+/// # Ok(()) }
+/// ```
+///
+/// [combined sink]: index.html#combined-sink
+/// [tags]: crate::Record::tags
+pub struct TagRoutingSink {
+    common_impl: helper::CommonImpl,
+    routes: Vec<Route>,
+}
+
+#[derive(Clone)]
+struct Route {
+    matcher: Matcher,
+    sink: Arc<dyn Sink>,
+}
+
+#[derive(Clone)]
+enum Matcher {
+    Tag(String),
+    All,
+    Unmatched,
+}
+
+impl TagRoutingSink {
+    /// Constructs a builder of `TagRoutingSink`.
+    #[must_use]
+    pub fn builder() -> TagRoutingSinkBuilder {
+        TagRoutingSinkBuilder {
+            common_builder_impl: helper::CommonBuilderImpl::new(),
+            routes: vec![],
+        }
+    }
+
+    /// Gets an iterator over the sub-sinks that records with the specified
+    /// tags are dispatched to, in the order their routes were added.
+    ///
+    /// A sub-sink matched by multiple routes is yielded only once.
+    pub fn sinks_for<'a>(&'a self, tags: &'a [&'a str]) -> impl Iterator<Item = &'a Arc<dyn Sink>> {
+        let has_tag = move |tag: &String| tags.iter().any(|t| t == tag);
+        let matched = self
+            .routes
+            .iter()
+            .any(|route| matches!(&route.matcher, Matcher::Tag(tag) if has_tag(tag)));
+        let dispatch = move |route: &Route| match &route.matcher {
+            Matcher::Tag(tag) => has_tag(tag),
+            Matcher::All => true,
+            Matcher::Unmatched => !matched,
+        };
+
+        // Routes are few, so finding duplicates by scanning the previous routes is
+        // cheaper than collecting the yielded sinks
+        self.routes
+            .iter()
+            .enumerate()
+            .filter(move |(index, route)| {
+                dispatch(route)
+                    && !self.routes[..*index]
+                        .iter()
+                        .any(|prev| dispatch(prev) && Arc::ptr_eq(&prev.sink, &route.sink))
+            })
+            .map(|(_, route)| &route.sink)
+    }
+}
+
+impl Sink for TagRoutingSink {
+    fn log(&self, record: &Record) -> Result<()> {
+        if !self.should_log(record.level()) {
+            return Ok(());
+        }
+
+        let mut result = Ok(());
+        for sink in self.sinks_for(record.tags()) {
+            result = Error::push_result(result, sink.log(record));
+        }
+        result
+    }
+
+    fn write_formatted(
+        &self,
+        record: &Record,
+        formatted: &StringBuf,
+        extra_info: &FmtExtraInfo,
+    ) -> Result<()> {
+        if !self.should_log(record.level()) {
+            return Ok(());
+        }
+
+        let mut result = Ok(());
+        for sink in self.sinks_for(record.tags()) {
+            result =
+                Error::push_result(result, sink.write_formatted(record, formatted, extra_info));
+        }
+        result
+    }
+
+    fn flush(&self) -> Result<()> {
+        let mut result = Ok(());
+        for route in &self.routes {
+            result = Error::push_result(result, route.sink.flush());
+        }
+        result
+    }
+
+    fn prefers_sync(&self) -> bool {
+        self.routes.iter().any(|route| route.sink.prefers_sync())
+    }
+
+    fn clone_box(&self) -> Option<Box<dyn Sink>> {
+        Some(Box::new(TagRoutingSink {
+            common_impl: self.common_impl.clone_config(),
+            routes: self.routes.clone(),
+        }))
+    }
+
+    helper::common_impl!(@Sink: common_impl);
+}
+
+/// The builder of [`TagRoutingSink`].
+///
+/// # Examples
+///
+/// See the documentation of [`TagRoutingSink`].
+pub struct TagRoutingSinkBuilder {
+    common_builder_impl: helper::CommonBuilderImpl,
+    routes: Vec<Route>,
+}
+
+impl TagRoutingSinkBuilder {
+    /// Adds a route, dispatching records with the tag to the sub-sink.
+    #[must_use]
+    pub fn route<S>(mut self, tag: S, sink: Arc<dyn Sink>) -> Self
+    where
+        S: Into<String>,
+    {
+        self.routes.push(Route {
+            matcher: Matcher::Tag(tag.into()),
+            sink,
+        });
+        self
+    }
+
+    /// Adds a catch-all route, dispatching all records to the sub-sink,
+    /// regardless of their tags and the other routes.
+    #[must_use]
+    pub fn catch_all(mut self, sink: Arc<dyn Sink>) -> Self {
+        self.routes.push(Route {
+            matcher: Matcher::All,
+            sink,
+        });
+        self
+    }
+
+    /// Adds a fallback route, dispatching records that match none of the
+    /// routes added by [`TagRoutingSinkBuilder::route`] to the sub-sink,
+    /// including untagged records.
+    #[must_use]
+    pub fn unmatched(mut self, sink: Arc<dyn Sink>) -> Self {
+        self.routes.push(Route {
+            matcher: Matcher::Unmatched,
+            sink,
+        });
+        self
+    }
+
+    helper::common_impl!(@SinkBuilder: common_builder_impl);
+
+    /// Builds a [`TagRoutingSink`].
+    pub fn build(self) -> Result<TagRoutingSink> {
+        Ok(TagRoutingSink {
+            common_impl: helper::CommonImpl::from_builder(self.common_builder_impl),
+            routes: self.routes,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{prelude::*, test_utils::*};
+
+    #[test]
+    fn route_by_tag() {
+        let audit_sink = Arc::new(CounterSink::new());
+        let security_sink = Arc::new(CounterSink::new());
+        let unmatched_sink = Arc::new(CounterSink::new());
+        let catch_all_sink = Arc::new(CounterSink::new());
+        let sink = Arc::new(
+            TagRoutingSink::builder()
+                .route("audit", audit_sink.clone())
+                .route("security", security_sink.clone())
+                .route("compliance", audit_sink.clone())
+                .unmatched(unmatched_sink.clone())
+                .catch_all(catch_all_sink.clone())
+                .build()
+                .unwrap(),
+        );
+        let logger = build_test_logger(|b| b.sink(sink.clone()));
+
+        info!(logger: logger, "untagged");
+        info!(logger: logger, tags = ["audit"], "login");
+        warn!(logger: logger, tags = ["security", "audit", "compliance"], "denied");
+        info!(logger: logger, tags = ["metrics"], kv: { n = 1 }, "other tag");
+
+        assert_eq!(audit_sink.payloads(), ["login", "denied"]);
+        assert_eq!(security_sink.payloads(), ["denied"]);
+        assert_eq!(unmatched_sink.payloads(), ["untagged", "other tag"]);
+        assert_eq!(
+            catch_all_sink.payloads(),
+            ["untagged", "login", "denied", "other tag"]
+        );
+        assert!(audit_sink.records()[1].has_tag("security"));
+        assert_eq!(sink.sinks_for(&["compliance"]).count(), 2);
+    }
+}
@@ -0,0 +1,231 @@
+use std::sync::Arc;
+
+use crate::{
+    sink::{helper, Sink, Sinks},
+    Error, Record, Result, StringBuf,
+};
+
+/// A [combined sink], formats records once and writes the formatted text to
+/// all sub-sinks.
+///
+/// A [`Logger`] with multiple sinks calls [`Sink::log`] on each of them, and
+/// each sink formats the record by itself with its own formatter. If the sinks
+/// share the same format (e.g. console and file), this work is duplicated.
+/// `TeeSink` formats the record with its own formatter, then passes the
+/// formatted text to each sub-sink via [`Sink::write_formatted`], so the
+/// formatters of the sub-sinks are not used.
+///
+/// # Styles
+///
+/// Only the formatted text and its style range are shared. Each sub-sink still
+/// decides how to render the style range, e.g. [`StdStreamSink`] renders it in
+/// the color configured for the level of the record, while [`FileSink`] ignores
+/// it.
+///
+/// # Filtering
+///
+/// Records are filtered by the level filter of `TeeSink` first, and then by
+/// the level filters of sub-sinks.
+///
+/// # Note
+///
+/// Sub-sinks that do not override [`Sink::write_formatted`] (e.g. combined
+/// sinks) fall back to formatting the record by themselves.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// use spdlog::{
+///     prelude::*,
+///     sink::{FileSink, StdStream, StdStreamSink, TeeSink},
+/// };
+///
+/// # fn main() -> Result<(), spdlog::Error> {
+/// # let path = std::env::temp_dir().join("spdlog-rs-doctest-tee-sink.log");
+/// let console_sink = Arc::new(
+///     StdStreamSink::builder()
+///         .std_stream(StdStream::Stdout)
+///         .build()?,
+/// );
+/// let file_sink = Arc::new(FileSink::builder().path(path).build()?);
+///
+/// let sink = Arc::new(
+///     TeeSink::builder()
+///         .sink(console_sink)
+///         .sink(file_sink)
+///         .build()?,
+/// );
+/// let logger = Logger::builder().sink(sink).build()?;
+///
+/// info!(logger: logger, "formatted once, written twice");
+/// # Ok(()) }
+/// ```
+///
+/// [combined sink]: index.html#combined-sink
+/// [`Logger`]: crate::Logger
+/// [`StdStreamSink`]: crate::sink::StdStreamSink
+/// [`FileSink`]: crate::sink::FileSink
+pub struct TeeSink {
+    common_impl: helper::CommonImpl,
+    sinks: Sinks,
+}
+
+impl TeeSink {
+    /// Constructs a builder of `TeeSink`.
+    #[must_use]
+    pub fn builder() -> TeeSinkBuilder {
+        TeeSinkBuilder {
+            common_builder_impl: helper::CommonBuilderImpl::new(),
+            sinks: vec![],
+        }
+    }
+
+    /// Gets a reference to internal sinks in the combined sink.
+    #[must_use]
+    pub fn sinks(&self) -> &[Arc<dyn Sink>] {
+        &self.sinks
+    }
+}
+
+impl Sink for TeeSink {
+    fn log(&self, record: &Record) -> Result<()> {
+        if !self.should_log(record.level()) {
+            return Ok(());
+        }
+
+        let mut string_buf = StringBuf::new();
+        let extra_info = self
+            .common_impl
+            .formatter
+            .read()
+            .format(record, &mut string_buf)?;
+
+        let mut result = Ok(());
+        for sink in &self.sinks {
+            result = Error::push_result(
+                result,
+                sink.write_formatted(record, &string_buf, &extra_info),
+            );
+        }
+        result
+    }
+
+    fn flush(&self) -> Result<()> {
+        let mut result = Ok(());
+        for sink in &self.sinks {
+            result = Error::push_result(result, sink.flush());
+        }
+        result
+    }
+
+    fn prefers_sync(&self) -> bool {
+        self.sinks.iter().any(|sink| sink.prefers_sync())
+    }
+
+    fn clone_box(&self) -> Option<Box<dyn Sink>> {
+        Some(Box::new(TeeSink {
+            common_impl: self.common_impl.clone_config(),
+            sinks: self.sinks.clone(),
+        }))
+    }
+
+    helper::common_impl!(@Sink: common_impl);
+}
+
+/// The builder of [`TeeSink`].
+pub struct TeeSinkBuilder {
+    common_builder_impl: helper::CommonBuilderImpl,
+    sinks: Sinks,
+}
+
+impl TeeSinkBuilder {
+    /// Add a [`Sink`].
+    #[must_use]
+    pub fn sink(mut self, sink: Arc<dyn Sink>) -> Self {
+        self.sinks.push(sink);
+        self
+    }
+
+    /// Add multiple [`Sink`]s.
+    #[must_use]
+    pub fn sinks<I>(mut self, sinks: I) -> Self
+    where
+        I: IntoIterator<Item = Arc<dyn Sink>>,
+    {
+        self.sinks.append(&mut sinks.into_iter().collect());
+        self
+    }
+
+    helper::common_impl!(@SinkBuilder: common_builder_impl);
+
+    /// Builds a [`TeeSink`].
+    pub fn build(self) -> Result<TeeSink> {
+        Ok(TeeSink {
+            common_impl: helper::CommonImpl::from_builder(self.common_builder_impl),
+            sinks: self.sinks,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        formatter::{FmtExtraInfo, Formatter},
+        prelude::*,
+        sink::WriteSink,
+        sync::*,
+        test_utils::*,
+    };
+
+    #[test]
+    fn format_once() {
+        static FORMAT_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+        #[derive(Clone)]
+        struct CountingFormatter;
+
+        impl Formatter for CountingFormatter {
+            fn format(&self, record: &Record, dest: &mut StringBuf) -> Result<FmtExtraInfo> {
+                FORMAT_COUNT.fetch_add(1, Ordering::SeqCst);
+                dest.push_str(record.payload());
+                dest.push('\n');
+                Ok(FmtExtraInfo::new())
+            }
+
+            fn clone_box(&self) -> Box<dyn Formatter> {
+                Box::new(self.clone())
+            }
+        }
+
+        let write_sink = || {
+            Arc::new(
+                WriteSink::builder()
+                    .formatter(Box::new(NoModFormatter::new()))
+                    .target(Vec::new())
+                    .build()
+                    .unwrap(),
+            )
+        };
+        let (sink_1, sink_2) = (write_sink(), write_sink());
+        sink_2.set_level_filter(LevelFilter::MoreSevereEqual(Level::Warn));
+
+        let tee_sink = Arc::new(
+            TeeSink::builder()
+                .sinks([sink_1.clone() as Arc<dyn Sink>, sink_2.clone()])
+                .formatter(Box::new(CountingFormatter))
+                .build()
+                .unwrap(),
+        );
+        let logger = build_test_logger(|b| b.sink(tee_sink));
+
+        info!(logger: logger, "hello");
+        warn!(logger: logger, "world");
+
+        assert_eq!(FORMAT_COUNT.load(Ordering::SeqCst), 2);
+        assert_eq!(sink_1.clone_target(), b"hello\nworld\n");
+        assert_eq!(sink_2.clone_target(), b"world\n");
+    }
+}
@@ -0,0 +1,471 @@
+//! Provides a UDP sink.
+
+use std::{
+    convert::Infallible,
+    io,
+    net::{Ipv4Addr, Ipv6Addr, SocketAddr, UdpSocket},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    error::InvalidArgumentError,
+    formatter::FmtExtraInfo,
+    sink::{helper, Sink},
+    sync::*,
+    Error, Record, Result, StringBuf,
+};
+
+const GELF_CHUNK_MAGIC: [u8; 2] = [0x1e, 0x0f];
+const GELF_CHUNK_HEADER_SIZE: usize = 12;
+const GELF_MAX_CHUNKS: usize = 128;
+// The maximum payload size of a UDP datagram over IPv4
+const MAX_DATAGRAM_SIZE: usize = 65507;
+
+/// A sink that sends each log message as a UDP datagram to a server.
+///
+/// Sending never blocks. If a message cannot be sent, e.g. it's larger than
+/// the maximum datagram size (65507 bytes) without GELF chunking enabled, or
+/// the send buffer of the socket is full, the message is dropped and
+/// [`Error::WriteRecord`] is returned, which is passed to the error handler
+/// when logging via a [`Logger`].
+///
+/// # GELF
+///
+/// Combined with [`GelfFormatter`], it ships records to Graylog directly. GELF
+/// over UDP splits messages that exceed a datagram size limit into chunks,
+/// which is enabled by [`UdpSinkBuilder::gelf_chunk_size`].
+///
+/// The chunks of a message are sent one by one. If the send buffer becomes
+/// full partway through, the remaining chunks are dropped and an error is
+/// returned, so the server receives an incomplete message, which it discards
+/// after its chunk timeout. Use a larger chunk size or a larger send buffer of
+/// the system if this happens.
+///
+/// # Formatting
+///
+/// Records are formatted with [`Formatter::format_bytes`], so formatters with
+/// a binary output are sent as is, see [the documentation of
+/// `Formatter`](crate::formatter::Formatter#text-and-bytes).
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::sync::Arc;
+///
+/// use spdlog::{formatter::GelfFormatter, prelude::*, sink::UdpSink};
+///
+/// # fn main() -> Result<(), spdlog::Error> {
+/// let sink = UdpSink::builder()
+///     .server_addr("127.0.0.1:12201".parse().unwrap())
+///     .formatter(Box::new(GelfFormatter::new("my-host")))
+///     .gelf_chunk_size(8192)
+///     .build()?;
+/// let logger = Logger::builder().sink(Arc::new(sink)).build()?;
+///
+/// info!(logger: logger, "hello, Graylog");
+/// # Ok(()) }
+/// ```
+///
+/// [`Logger`]: crate::Logger
+/// [`GelfFormatter`]: crate::formatter::GelfFormatter
+/// [`Formatter::format_bytes`]: crate::formatter::Formatter::format_bytes
+pub struct UdpSink {
+    common_impl: helper::CommonImpl,
+    // Shared with clones, the message IDs of chunks must be unique per socket
+    socket: Arc<UdpSocket>,
+    gelf_chunk_size: Option<usize>,
+    next_message_id: Arc<Atomic<u64>>,
+}
+
+impl UdpSink {
+    /// Constructs a builder of `UdpSink`.
+    #[must_use]
+    pub fn builder() -> UdpSinkBuilder<()> {
+        UdpSinkBuilder {
+            common_builder_impl: helper::CommonBuilderImpl::new(),
+            server_addr: (),
+            gelf_chunk_size: None,
+        }
+    }
+
+    fn write(&self, message: &[u8]) -> Result<()> {
+        match self.gelf_chunk_size {
+            Some(chunk_size) if message.len() > chunk_size => {
+                self.send_gelf_chunks(message, chunk_size)
+            }
+            _ => self.send(message),
+        }
+        .map_err(Error::WriteRecord)
+    }
+
+    fn send(&self, datagram: &[u8]) -> io::Result<()> {
+        if datagram.len() > MAX_DATAGRAM_SIZE {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "message of {} bytes exceeds the maximum datagram size {}",
+                    datagram.len(),
+                    MAX_DATAGRAM_SIZE
+                ),
+            ));
+        }
+        self.socket.send(datagram).map(|_| ())
+    }
+
+    fn send_gelf_chunks(&self, message: &[u8], chunk_size: usize) -> io::Result<()> {
+        let chunk_data_size = chunk_size - GELF_CHUNK_HEADER_SIZE;
+        let count = (message.len() + chunk_data_size - 1) / chunk_data_size;
+        if count > GELF_MAX_CHUNKS {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "message of {} bytes needs {} GELF chunks, exceeds the maximum {}",
+                    message.len(),
+                    count,
+                    GELF_MAX_CHUNKS
+                ),
+            ));
+        }
+
+        let message_id = self.next_message_id.fetch_add(1, Ordering::Relaxed);
+        let mut datagram = Vec::with_capacity(chunk_size);
+        for (seq, chunk) in message.chunks(chunk_data_size).enumerate() {
+            datagram.clear();
+            datagram.extend_from_slice(&GELF_CHUNK_MAGIC);
+            datagram.extend_from_slice(&message_id.to_be_bytes());
+            datagram.push(seq as u8);
+            datagram.push(count as u8);
+            datagram.extend_from_slice(chunk);
+            self.socket.send(&datagram).map_err(|err| {
+                io::Error::new(
+                    err.kind(),
+                    format!("sent {} of {} GELF chunks: {}", seq, count, err),
+                )
+            })?;
+        }
+        Ok(())
+    }
+}
+
+impl Sink for UdpSink {
+    fn log(&self, record: &Record) -> Result<()> {
+        if !self.should_log(record.level()) {
+            return Ok(());
+        }
+
+        let mut message = Vec::new();
+        self.common_impl
+            .formatter
+            .read()
+            .format_bytes(record, &mut message)?;
+
+        self.write(&message)
+    }
+
+    fn write_formatted(
+        &self,
+        record: &Record,
+        formatted: &StringBuf,
+        _extra_info: &FmtExtraInfo,
+    ) -> Result<()> {
+        if !self.should_log(record.level()) {
+            return Ok(());
+        }
+
+        self.write(formatted.as_bytes())
+    }
+
+    fn flush(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn clone_box(&self) -> Option<Box<dyn Sink>> {
+        Some(Box::new(UdpSink {
+            common_impl: self.common_impl.clone_config(),
+            socket: self.socket.clone(),
+            gelf_chunk_size: self.gelf_chunk_size,
+            next_message_id: self.next_message_id.clone(),
+        }))
+    }
+
+    helper::common_impl!(@Sink: common_impl);
+}
+
+// --------------------------------------------------
+
+/// The builder of [`UdpSink`].
+#[doc = include_str!("../include/doc/generic-builder-note.md")]
+/// # Examples
+///
+/// - Building a [`UdpSink`].
+///
+///   ```no_run
+///   use spdlog::sink::UdpSink;
+///
+///   # fn main() -> Result<(), spdlog::Error> {
+///   let sink: UdpSink = UdpSink::builder()
+///       .server_addr("127.0.0.1:12201".parse().unwrap()) // required
+///       // .gelf_chunk_size(8192) // optional, defaults to no chunking
+///       .build()?;
+///   # Ok(()) }
+///   ```
+///
+/// - If any required parameters are missing, a compile-time error will be
+///   raised.
+///
+///   ```compile_fail,E0061
+///   use spdlog::sink::UdpSink;
+///
+///   # fn main() -> Result<(), spdlog::Error> {
+///   let sink: UdpSink = UdpSink::builder()
+///       // .server_addr("127.0.0.1:12201".parse().unwrap()) // required
+///       .gelf_chunk_size(8192) // optional, defaults to no chunking
+///       .build()?;
+///   # Ok(()) }
+///   ```
+pub struct UdpSinkBuilder<ArgAddr> {
+    common_builder_impl: helper::CommonBuilderImpl,
+    server_addr: ArgAddr,
+    gelf_chunk_size: Option<usize>,
+}
+
+impl<ArgAddr> UdpSinkBuilder<ArgAddr> {
+    /// The address of the server to send log messages to.
+    ///
+    /// This parameter is **required**.
+    #[must_use]
+    pub fn server_addr(self, server_addr: SocketAddr) -> UdpSinkBuilder<SocketAddr> {
+        UdpSinkBuilder {
+            common_builder_impl: self.common_builder_impl,
+            server_addr,
+            gelf_chunk_size: self.gelf_chunk_size,
+        }
+    }
+
+    /// Enables GELF chunking, splits messages larger than `chunk_size` bytes
+    /// into GELF chunks of at most `chunk_size` bytes each (including the
+    /// 12-byte chunk header).
+    ///
+    /// GELF allows at most 128 chunks per message, larger messages fail to be
+    /// sent with [`Error::WriteRecord`]. Graylog recommends a chunk size of
+    /// `8192`, or `1420` if the network path has a smaller MTU.
+    ///
+    /// `chunk_size` must be greater than 12, otherwise
+    /// [`Error::InvalidArgument`] will be returned when building.
+    ///
+    /// This parameter is **optional**, and defaults to no chunking, i.e. each
+    /// message is sent as a single datagram regardless of its size.
+    #[must_use]
+    pub fn gelf_chunk_size(mut self, chunk_size: usize) -> Self {
+        self.gelf_chunk_size = Some(chunk_size);
+        self
+    }
+
+    helper::common_impl!(@SinkBuilder: common_builder_impl);
+}
+
+impl UdpSinkBuilder<()> {
+    #[doc(hidden)]
+    #[deprecated(note = "\n\n\
+        builder compile-time error:\n\
+        - missing required field `server_addr`\n\n\
+    ")]
+    pub fn build(self, _: Infallible) {}
+}
+
+impl UdpSinkBuilder<SocketAddr> {
+    /// Builds a [`UdpSink`].
+    ///
+    /// # Errors
+    ///
+    /// If the GELF chunk size is invalid, [`Error::InvalidArgument`] will be
+    /// returned. If an error occurs binding or connecting the socket,
+    /// [`Error::ConnectSocket`] will be returned.
+    pub fn build(self) -> Result<UdpSink> {
+        if let Some(chunk_size) = self.gelf_chunk_size {
+            if chunk_size <= GELF_CHUNK_HEADER_SIZE {
+                return Err(Error::InvalidArgument(InvalidArgumentError::ChunkSize(
+                    format!(
+                        "chunk size must be greater than the header size {}, but got {}",
+                        GELF_CHUNK_HEADER_SIZE, chunk_size
+                    ),
+                )));
+            }
+        }
+
+        let local_addr = match self.server_addr {
+            SocketAddr::V4(_) => SocketAddr::from((Ipv4Addr::UNSPECIFIED, 0)),
+            SocketAddr::V6(_) => SocketAddr::from((Ipv6Addr::UNSPECIFIED, 0)),
+        };
+        let socket = UdpSocket::bind(local_addr).map_err(Error::ConnectSocket)?;
+        socket
+            .connect(self.server_addr)
+            .map_err(Error::ConnectSocket)?;
+        socket.set_nonblocking(true).map_err(Error::ConnectSocket)?;
+
+        // GELF requires message IDs to be unique, seed them with the current time to
+        // avoid collisions with other senders.
+        let seed = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos() as u64;
+
+        Ok(UdpSink {
+            common_impl: helper::CommonImpl::from_builder(self.common_builder_impl),
+            socket: Arc::new(socket),
+            gelf_chunk_size: self.gelf_chunk_size,
+            next_message_id: Arc::new(Atomic::new(seed)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::{formatter::Formatter, prelude::*, test_utils::*};
+
+    fn bind_server() -> UdpSocket {
+        let server = UdpSocket::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        server
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        server
+    }
+
+    fn recv(server: &UdpSocket) -> Vec<u8> {
+        let mut buf = [0; 1024];
+        let len = server.recv(&mut buf).unwrap();
+        buf[..len].to_vec()
+    }
+
+    #[test]
+    fn send() {
+        let server = bind_server();
+        let sink = UdpSink::builder()
+            .server_addr(server.local_addr().unwrap())
+            .formatter(Box::new(NoModFormatter::new()))
+            .build()
+            .unwrap();
+        let logger = build_test_logger(|b| b.sink(Arc::new(sink)));
+
+        info!(logger: logger, "hello");
+        assert_eq!(recv(&server), b"hello");
+    }
+
+    #[test]
+    fn format_bytes() {
+        #[derive(Clone)]
+        struct BinaryFormatter;
+
+        impl Formatter for BinaryFormatter {
+            fn format(&self, record: &Record, dest: &mut StringBuf) -> Result<FmtExtraInfo> {
+                dest.push_str(&format!("{:02x}", record.level() as u8));
+                Ok(FmtExtraInfo::new())
+            }
+
+            fn format_bytes(&self, record: &Record, dest: &mut Vec<u8>) -> Result<()> {
+                dest.extend_from_slice(&[0xff, record.level() as u8]);
+                dest.extend_from_slice(record.payload().as_bytes());
+                Ok(())
+            }
+
+            fn clone_box(&self) -> Box<dyn Formatter> {
+                Box::new(self.clone())
+            }
+        }
+
+        let server = bind_server();
+        let sink = UdpSink::builder()
+            .server_addr(server.local_addr().unwrap())
+            .formatter(Box::new(BinaryFormatter))
+            .build()
+            .unwrap();
+        let logger = build_test_logger(|b| b.sink(Arc::new(sink)));
+
+        warn!(logger: logger, "hi");
+        assert_eq!(
+            recv(&server),
+            [&[0xff, Level::Warn as u8], &b"hi"[..]].concat()
+        );
+    }
+
+    #[test]
+    fn gelf_chunks() {
+        let server = bind_server();
+        let sink = UdpSink::builder()
+            .server_addr(server.local_addr().unwrap())
+            .formatter(Box::new(NoModFormatter::new()))
+            .gelf_chunk_size(GELF_CHUNK_HEADER_SIZE + 4)
+            .build()
+            .unwrap();
+        let logger = build_test_logger(|b| b.sink(Arc::new(sink)));
+
+        // Not larger than the chunk size, sent as is
+        info!(logger: logger, "0123456789abcdef");
+        assert_eq!(recv(&server), b"0123456789abcdef");
+
+        info!(logger: logger, "0123456789abcdefg");
+        let chunks = (0..5).map(|_| recv(&server)).collect::<Vec<_>>();
+        let message_id = &chunks[0][2..10];
+        for (seq, (chunk, data)) in chunks
+            .iter()
+            .zip(["0123", "4567", "89ab", "cdef", "g"])
+            .enumerate()
+        {
+            assert_eq!(chunk[..2], GELF_CHUNK_MAGIC);
+            assert_eq!(&chunk[2..10], message_id);
+            assert_eq!(chunk[10], seq as u8);
+            assert_eq!(chunk[11], 5);
+            assert_eq!(&chunk[12..], data.as_bytes());
+        }
+
+        let too_large = "x".repeat(4 * GELF_MAX_CHUNKS + 1);
+        let sink = UdpSink::builder()
+            .server_addr(server.local_addr().unwrap())
+            .formatter(Box::new(NoModFormatter::new()))
+            .gelf_chunk_size(GELF_CHUNK_HEADER_SIZE + 4)
+            .build()
+            .unwrap();
+        assert!(matches!(
+            sink.log(&Record::new(Level::Info, too_large)),
+            Err(Error::WriteRecord(_))
+        ));
+
+        assert!(matches!(
+            UdpSink::builder()
+                .server_addr(server.local_addr().unwrap())
+                .gelf_chunk_size(GELF_CHUNK_HEADER_SIZE)
+                .build(),
+            Err(Error::InvalidArgument(InvalidArgumentError::ChunkSize(_)))
+        ));
+    }
+
+    #[test]
+    fn send_failure() {
+        static ERROR_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+        let server = bind_server();
+        let sink = UdpSink::builder()
+            .server_addr(server.local_addr().unwrap())
+            .formatter(Box::new(NoModFormatter::new()))
+            .build()
+            .unwrap();
+        let logger = build_test_logger(|b| {
+            b.sink(Arc::new(sink)).error_handler(|err| {
+                assert!(
+                    matches!(err, Error::Sink(err) if matches!(err.error(), Error::WriteRecord(_)))
+                );
+                ERROR_COUNT.fetch_add(1, Ordering::SeqCst);
+            })
+        });
+
+        info!(logger: logger, "{}", "x".repeat(MAX_DATAGRAM_SIZE + 1));
+        assert_eq!(ERROR_COUNT.load(Ordering::SeqCst), 1);
+
+        // The sink still works after a failure
+        info!(logger: logger, "hello");
+        assert_eq!(recv(&server), b"hello");
+    }
+}
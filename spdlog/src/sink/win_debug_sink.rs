@@ -61,6 +61,12 @@ impl Sink for WinDebugSink {
         Ok(())
     }
 
+    fn clone_box(&self) -> Option<Box<dyn Sink>> {
+        Some(Box::new(WinDebugSink {
+            common_impl: self.common_impl.clone_config(),
+        }))
+    }
+
     helper::common_impl!(@Sink: common_impl);
 }
 
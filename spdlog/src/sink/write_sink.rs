@@ -1,7 +1,8 @@
 use std::{convert::Infallible, io::Write, marker::PhantomData};
 
 use crate::{
-    sink::{helper, Sink},
+    formatter::FmtExtraInfo,
+    sink::{helper, FlushErrorPolicy, Sink},
     sync::*,
     Error, Record, Result, StringBuf,
 };
@@ -30,6 +31,7 @@ where
 {
     common_impl: helper::CommonImpl,
     target: Mutex<W>,
+    flush_error: helper::FlushErrorState,
 }
 
 impl<W> WriteSink<W>
@@ -42,6 +44,7 @@ where
         WriteSinkBuilder {
             common_builder_impl: helper::CommonBuilderImpl::new(),
             target: None,
+            flush_error_policy: FlushErrorPolicy::Propagate,
             _phantom: PhantomData,
         }
     }
@@ -64,9 +67,15 @@ where
         callback(&mut *self.lock_target())
     }
 
-    fn lock_target(&self) -> MutexGuard<W> {
+    fn lock_target(&self) -> MutexGuard<'_, W> {
         self.target.lock_expect()
     }
+
+    fn write(&self, string_buf: &StringBuf) -> Result<()> {
+        self.lock_target()
+            .write_all(string_buf.as_bytes())
+            .map_err(Error::WriteRecord)
+    }
 }
 
 impl<W> WriteSink<W>
@@ -95,15 +104,25 @@ where
             .read()
             .format(record, &mut string_buf)?;
 
-        self.lock_target()
-            .write_all(string_buf.as_bytes())
-            .map_err(Error::WriteRecord)?;
+        self.write(&string_buf)
+    }
 
-        Ok(())
+    fn write_formatted(
+        &self,
+        record: &Record,
+        formatted: &StringBuf,
+        _extra_info: &FmtExtraInfo,
+    ) -> Result<()> {
+        if !self.should_log(record.level()) {
+            return Ok(());
+        }
+
+        self.write(formatted)
     }
 
     fn flush(&self) -> Result<()> {
-        self.lock_target().flush().map_err(Error::FlushBuffer)
+        self.flush_error
+            .handle(self.lock_target().flush().map_err(Error::FlushBuffer))
     }
 
     helper::common_impl!(@Sink: common_impl);
@@ -115,7 +134,7 @@ where
 {
     fn drop(&mut self) {
         let flush_result = self.lock_target().flush().map_err(Error::FlushBuffer);
-        if let Err(err) = flush_result {
+        if let Err(err) = self.flush_error.handle(flush_result) {
             self.common_impl.non_returnable_error("WriteSink", err)
         }
     }
@@ -155,6 +174,7 @@ where
 pub struct WriteSinkBuilder<W, ArgW> {
     common_builder_impl: helper::CommonBuilderImpl,
     target: Option<W>,
+    flush_error_policy: FlushErrorPolicy,
     _phantom: PhantomData<ArgW>,
 }
 
@@ -171,10 +191,23 @@ where
         WriteSinkBuilder {
             common_builder_impl: self.common_builder_impl,
             target: Some(target),
+            flush_error_policy: self.flush_error_policy,
             _phantom: PhantomData,
         }
     }
 
+    /// Specifies the policy for handling errors flushing the buffer.
+    ///
+    /// See [`FlushErrorPolicy`] for details.
+    ///
+    /// This parameter is **optional**, and defaults to
+    /// [`FlushErrorPolicy::Propagate`].
+    #[must_use]
+    pub fn flush_error_policy(mut self, flush_error_policy: FlushErrorPolicy) -> Self {
+        self.flush_error_policy = flush_error_policy;
+        self
+    }
+
     helper::common_impl!(@SinkBuilder: common_builder_impl);
 }
 
@@ -199,6 +232,7 @@ where
         let sink = WriteSink {
             common_impl: helper::CommonImpl::from_builder(self.common_builder_impl),
             target: Mutex::new(self.target.unwrap()),
+            flush_error: helper::FlushErrorState::new(self.flush_error_policy),
         };
         Ok(sink)
     }
@@ -220,4 +254,61 @@ mod tests {
         let data = sink.clone_target();
         assert_eq!(data.as_slice(), b"hello WriteSink");
     }
+
+    #[test]
+    fn flush_error_policy() {
+        struct FailingFlush {
+            failing: Arc<AtomicBool>,
+        }
+
+        impl Write for FailingFlush {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                if self.failing.load(Ordering::Relaxed) {
+                    Err(std::io::Error::new(std::io::ErrorKind::Other, "disk full"))
+                } else {
+                    Ok(())
+                }
+            }
+        }
+
+        let flush_results = |policy| {
+            let failing = Arc::new(AtomicBool::new(true));
+            let sink = WriteSink::builder()
+                .target(FailingFlush {
+                    failing: failing.clone(),
+                })
+                .flush_error_policy(policy)
+                .build()
+                .unwrap();
+
+            let mut results = vec![];
+            for fail in [true, true, false, true, true] {
+                failing.store(fail, Ordering::Relaxed);
+                results.push(match sink.flush() {
+                    Ok(()) => "ok",
+                    Err(Error::FlushBuffer(_)) => "err",
+                    Err(err) => panic!("unexpected error: {}", err),
+                });
+            }
+            failing.store(false, Ordering::Relaxed);
+            results
+        };
+
+        assert_eq!(
+            flush_results(FlushErrorPolicy::Propagate),
+            ["err", "err", "ok", "err", "err"]
+        );
+        assert_eq!(
+            flush_results(FlushErrorPolicy::ReportOnce),
+            ["err", "ok", "ok", "err", "ok"]
+        );
+        assert_eq!(
+            flush_results(FlushErrorPolicy::Ignore),
+            ["ok", "ok", "ok", "ok", "ok"]
+        );
+    }
 }
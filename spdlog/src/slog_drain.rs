@@ -0,0 +1,162 @@
+use std::{
+    borrow::Cow,
+    fmt,
+    panic::{RefUnwindSafe, UnwindSafe},
+};
+
+use crate::{default_logger, sync::*, Logger, Record, SourceLocation};
+
+/// A [`slog::Drain`] forwarding records to a spdlog [`Logger`].
+///
+/// It eases migrating a codebase from `slog`: parts still logging via `slog`
+/// macros are output by the sinks of the spdlog logger, along with records
+/// logged via spdlog.
+///
+/// Records are forwarded to [`default_logger`] by default, or to a specified
+/// logger with [`SlogDrain::with_logger`]. Levels are mapped one-to-one, with
+/// [`slog::Level::Warning`] mapped to [`Level::Warn`]. The key-values of the
+/// record and those of the `slog` logger it was logged on are forwarded as
+/// [key-values of the record], those of the record first, in the order `slog`
+/// serializes them. The tag of the record is used as the logger name if the
+/// spdlog logger has no name and the tag is not empty.
+///
+/// # Examples
+///
+/// ```
+/// use slog::{info, o, Drain};
+/// use spdlog::SlogDrain;
+///
+/// let logger = slog::Logger::root(SlogDrain::new().fuse(), o!("app" => "demo"));
+///
+/// info!(logger, "hello"; "user" => "alice");
+/// ```
+///
+/// [`Level::Warn`]: crate::Level::Warn
+/// [key-values of the record]: crate::Record::key_values
+#[derive(Clone, Default)]
+pub struct SlogDrain {
+    logger: Option<Arc<Logger>>,
+}
+
+impl SlogDrain {
+    /// Constructs a `SlogDrain` forwarding to [`default_logger`].
+    ///
+    /// The default logger is obtained for each record, so replacing it takes
+    /// effect immediately.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Constructs a `SlogDrain` forwarding to the given logger.
+    #[must_use]
+    pub fn with_logger(logger: Arc<Logger>) -> Self {
+        Self {
+            logger: Some(logger),
+        }
+    }
+
+    #[must_use]
+    fn logger(&self) -> Arc<Logger> {
+        self.logger.clone().unwrap_or_else(default_logger)
+    }
+}
+
+// `slog::Logger::root` requires the drain to be unwind safe. A `Logger` is
+// shared across threads by design, and a panic in one thread (e.g. in a sink)
+// does not leave it in a state unusable by others.
+impl UnwindSafe for SlogDrain {}
+impl RefUnwindSafe for SlogDrain {}
+
+impl slog::Drain for SlogDrain {
+    type Ok = ();
+    type Err = slog::Never;
+
+    fn log(&self, record: &slog::Record, values: &slog::OwnedKVList) -> Result<(), slog::Never> {
+        let logger = self.logger();
+        if !logger.should_log(record.level().into()) {
+            return Ok(());
+        }
+
+        let mut key_values = KeyValueCollector(Vec::new());
+        // Serializing into a `Vec` never fails
+        let _ = slog::KV::serialize(&record.kv(), record, &mut key_values);
+        let _ = slog::KV::serialize(values, record, &mut key_values);
+
+        crate::record::with_formatted_payload(*record.msg(), |payload| {
+            let mut builder = Record::builder(record.level().into(), payload)
+                .key_values(key_values.0)
+                .source_location(Some(SourceLocation::__new(
+                    record.module(),
+                    record.file(),
+                    record.line(),
+                    record.column(),
+                )));
+            if logger.name().is_none() && !record.tag().is_empty() {
+                builder = builder.logger_name(record.tag());
+            }
+            logger.log(&builder.build());
+        });
+        Ok(())
+    }
+
+    fn is_enabled(&self, level: slog::Level) -> bool {
+        self.logger().should_log(level.into())
+    }
+}
+
+struct KeyValueCollector(Vec<(Cow<'static, str>, Cow<'static, str>)>);
+
+impl slog::Serializer for KeyValueCollector {
+    fn emit_arguments(&mut self, key: slog::Key, val: &fmt::Arguments) -> slog::Result {
+        let value = match val.as_str() {
+            Some(literal_str) => Cow::Borrowed(literal_str),
+            None => Cow::Owned(val.to_string()),
+        };
+        self.0.push((Cow::Owned(key.to_string()), value));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use slog::{o, Drain};
+
+    use super::*;
+    use crate::{test_utils::*, Level, LevelFilter};
+
+    #[test]
+    fn forward() {
+        let sink = Arc::new(CounterSink::new());
+        let logger = Arc::new(build_test_logger(|b| {
+            b.sink(sink.clone()).level_filter(LevelFilter::All)
+        }));
+        let slog_logger = slog::Logger::root(
+            SlogDrain::with_logger(logger.clone()).fuse(),
+            o!("app" => "demo"),
+        );
+
+        slog::info!(slog_logger, "hello {}", "world"; "user" => "alice", "attempt" => 3);
+        slog::warn!(slog_logger, #"net", "retrying");
+        logger.set_level_filter(LevelFilter::MoreSevereEqual(Level::Warn));
+        slog::debug!(slog_logger, "filtered");
+
+        let records = sink.records();
+        assert_eq!(records.len(), 2);
+
+        assert_eq!(records[0].level(), Level::Info);
+        assert_eq!(records[0].payload(), "hello world");
+        assert_eq!(
+            records[0].key_values().collect::<Vec<_>>(),
+            [("attempt", "3"), ("user", "alice"), ("app", "demo")]
+        );
+        let srcloc = records[0].source_location().unwrap();
+        assert_eq!(srcloc.file(), file!());
+        assert_eq!(srcloc.module_path(), module_path!());
+        assert_eq!(records[0].logger_name(), None);
+
+        assert_eq!(records[1].level(), Level::Warn);
+        assert_eq!(records[1].payload(), "retrying");
+        assert_eq!(records[1].logger_name(), Some("net"));
+    }
+}
@@ -2,7 +2,7 @@
 
 /// A string buffer type.
 ///
-/// Used at [`Formatter`].
+/// Used at [`Formatter`] and [`Sink::write_formatted`].
 ///
 /// By default, it is an alias for [`String`], if feature `flexible-string` is
 /// enabled, an internal type `FlexibleString` will be used.
@@ -12,6 +12,44 @@
 /// consistent as possible with [`String`], but some APIs are not yet
 /// implemented or cannot be implemented.
 ///
+/// # Stable API
+///
+/// As the underlying type depends on the feature, code that should compile
+/// regardless of it, e.g. third-party sinks and formatters, should only rely
+/// on the following API, which is guaranteed for both types:
+///
+/// - [`Deref<Target = str>`], for reading the text, e.g. `&*buf` and
+///   `buf.as_bytes()`.
+/// - [`fmt::Write`], for writing into it, e.g. with [`write!`].
+/// - `StringBuf::new()`, `push_str`, `push`, `clear` and `reserve`.
+///
+/// # Examples
+///
+/// A sink writing the formatted text to a writer:
+///
+/// ```
+/// use std::io::{self, Write};
+///
+/// use spdlog::StringBuf;
+///
+/// fn write_formatted(dest: &mut impl Write, formatted: &StringBuf) -> io::Result<()> {
+///     let text: &str = formatted;
+///     dest.write_all(text.as_bytes())
+/// }
+///
+/// let mut formatted = StringBuf::new();
+/// formatted.push_str("hello\n");
+///
+/// let mut dest = Vec::new();
+/// write_formatted(&mut dest, &formatted)?;
+/// assert_eq!(dest, b"hello\n");
+/// # Ok::<(), io::Error>(())
+/// ```
+///
+/// [`Sink::write_formatted`]: crate::sink::Sink::write_formatted
+/// [`Deref<Target = str>`]: std::ops::Deref
+/// [`fmt::Write`]: std::fmt::Write
+///
 /// # Warnings
 ///
 /// `FlexibleString` can improve performance as it avoids memory allocation when
@@ -41,3 +79,31 @@ cfg_if! {
 pub(crate) const STACK_SIZE: usize = 256;
 #[allow(dead_code)]
 pub(crate) const RESERVE_SIZE: usize = STACK_SIZE / 2;
+
+#[cfg(test)]
+mod tests {
+    use std::{fmt::Write, ops::Deref};
+
+    use super::*;
+
+    // Fails to compile if the documented stable API is broken.
+    #[test]
+    fn stable_api() {
+        fn assert_api<T>()
+        where
+            T: Deref<Target = str> + Write,
+        {
+        }
+        assert_api::<StringBuf>();
+
+        let mut buf = StringBuf::new();
+        buf.reserve(16);
+        buf.push_str("hello");
+        buf.push(',');
+        write!(buf, " {}", 42).unwrap();
+        assert_eq!(&*buf, "hello, 42");
+        assert_eq!(buf.as_bytes(), b"hello, 42");
+        buf.clear();
+        assert!(buf.is_empty());
+    }
+}
@@ -2,8 +2,13 @@ pub use std::sync::{
     Arc, Condvar, Mutex, MutexGuard, RwLock, RwLockReadGuard, RwLockWriteGuard, Weak,
 };
 
-pub use arc_swap::{ArcSwap, ArcSwapOption};
+pub use arc_swap::ArcSwap;
+#[cfg(feature = "log")]
+pub use arc_swap::ArcSwapOption;
 pub use once_cell::sync::{Lazy, OnceCell};
+// The guard types are re-exported along with the locks, though no module names
+// them yet.
+#[allow(unused_imports)]
 pub use spin::{
     Mutex as SpinMutex, MutexGuard as SpinMutexGuard, RwLock as SpinRwLock,
     RwLockReadGuard as SpinRwLockReadGuard, RwLockWriteGuard as SpinRwLockWriteGuard,
@@ -27,6 +32,7 @@ pub trait RwLockExtend<'a> {
     type ReadReturn;
     type WriteReturn;
 
+    #[allow(dead_code)]
     #[must_use]
     fn read_expect(&'a self) -> Self::ReadReturn;
 
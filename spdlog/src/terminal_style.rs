@@ -4,7 +4,13 @@
 //!
 //! [ANSI escape code]: https://en.wikipedia.org/wiki/ANSI_escape_code#SGR_(Select_Graphic_Rendition)_parameters
 
-use crate::Level;
+use std::{
+    borrow::Cow,
+    io::{self, Write},
+    ops::Range,
+};
+
+use crate::{formatter::StyleHint, Level};
 
 /// The terminal text color style.
 #[allow(missing_docs)]
@@ -291,8 +297,91 @@ impl LevelStyleCodes {
     {
         self.0[level as usize] = code.into();
     }
+
+    // Writes `text` into `dest`, with the styles rendered for the parts in
+    // `style_ranges`, the style of `level` for `StyleHint::Level` and the style
+    // in `hint_codes` for the others.
+    pub(crate) fn write_styled(
+        &self,
+        hint_codes: &HintStyleCodes,
+        dest: &mut impl Write,
+        level: Level,
+        text: &[u8],
+        style_ranges: impl Iterator<Item = (Range<usize>, StyleHint)>,
+    ) -> io::Result<()> {
+        let mut written = 0;
+        for (style_range, hint) in style_ranges {
+            // overlapping ranges are not rendered
+            if style_range.start < written {
+                continue;
+            }
+            let style_code = match hint {
+                StyleHint::Level => self.code(level),
+                hint => hint_codes.code(hint),
+            };
+            if style_code.start.is_empty() {
+                continue;
+            }
+
+            dest.write_all(&text[written..style_range.start])?;
+            dest.write_all(style_code.start.as_bytes())?;
+            dest.write_all(&text[style_range.start..style_range.end])?;
+            dest.write_all(style_code.end.as_bytes())?;
+            written = style_range.end;
+        }
+        dest.write_all(&text[written..])
+    }
+}
+
+// Style codes of `StyleHint`s other than `StyleHint::Level`.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub(crate) struct HintStyleCodes {
+    logger_name: StyleCode,
+    key: StyleCode,
+    value: StyleCode,
+}
+
+impl HintStyleCodes {
+    // Returns a code without escapes for `StyleHint::Level`, which is styled by
+    // `LevelStyleCodes`.
+    #[must_use]
+    pub(crate) fn code(&self, hint: StyleHint) -> &StyleCode {
+        match hint {
+            StyleHint::LoggerName => &self.logger_name,
+            StyleHint::Key => &self.key,
+            StyleHint::Value => &self.value,
+            StyleHint::Level => &NO_STYLE_CODE,
+        }
+    }
+
+    pub(crate) fn set_code<C>(&mut self, hint: StyleHint, code: C)
+    where
+        C: Into<StyleCode>,
+    {
+        match hint {
+            StyleHint::LoggerName => self.logger_name = code.into(),
+            StyleHint::Key => self.key = code.into(),
+            StyleHint::Value => self.value = code.into(),
+            StyleHint::Level => {}
+        }
+    }
 }
 
+impl Default for HintStyleCodes {
+    fn default() -> HintStyleCodes {
+        HintStyleCodes {
+            logger_name: NO_STYLE_CODE.clone(),
+            key: NO_STYLE_CODE.clone(),
+            value: NO_STYLE_CODE.clone(),
+        }
+    }
+}
+
+static NO_STYLE_CODE: StyleCode = StyleCode {
+    start: String::new(),
+    end: String::new(),
+};
+
 impl From<Style> for StyleCode {
     fn from(style: Style) -> StyleCode {
         style.code()
@@ -304,3 +393,300 @@ impl Default for LevelStyleCodes {
         LevelStyles::default().into()
     }
 }
+
+/// Removes SGR (Select Graphic Rendition) escape sequences from the text.
+///
+/// An SGR sequence is `ESC [`, followed by parameters consisting of digits,
+/// `;` and `:`, terminated by `m`, e.g. `\x1b[1;31m` and `\x1b[38:2::255:0:0m`.
+/// Other `ESC` characters, including those of other escape sequences, are kept
+/// as is.
+///
+/// The text is borrowed if it contains no SGR sequence.
+///
+/// # Examples
+///
+/// ```
+/// use spdlog::strip_ansi;
+///
+/// assert_eq!(strip_ansi("\x1b[1;31merror\x1b[0m: oops"), "error: oops");
+/// assert_eq!(strip_ansi("\x1b]0;title\x07"), "\x1b]0;title\x07");
+/// ```
+#[must_use]
+pub fn strip_ansi(text: &str) -> Cow<'_, str> {
+    let mut stripped = String::new();
+    let mut copied = 0;
+    let mut search = 0;
+
+    while let Some(pos) = text[search..].find('\x1b').map(|pos| pos + search) {
+        match sgr_len(&text[pos..]) {
+            Some(len) => {
+                stripped.push_str(&text[copied..pos]);
+                copied = pos + len;
+                search = copied;
+            }
+            None => search = pos + 1,
+        }
+    }
+
+    if copied == 0 {
+        Cow::Borrowed(text)
+    } else {
+        stripped.push_str(&text[copied..]);
+        Cow::Owned(stripped)
+    }
+}
+
+// Returns the length of the SGR sequence at the beginning of the text.
+fn sgr_len(text: &str) -> Option<usize> {
+    let params = text.strip_prefix("\x1b[")?;
+    let end = params.find(|c: char| !(c.is_ascii_digit() || c == ';' || c == ':'))?;
+    (params.as_bytes()[end] == b'm').then(|| end + 3)
+}
+
+// Returns the display width of the text in a terminal, see `wrap_points`.
+#[must_use]
+pub(crate) fn display_width(text: &str) -> usize {
+    let mut col = 0;
+    for_each_char(text, |_, ch| col = advance(col, ch));
+    col
+}
+
+// Returns the byte offsets where the text should be broken, so that no line is
+// wider than `max_width` columns when the continuation lines are indented by
+// `indent` columns.
+//
+// Escape sequences are zero-width, and so are control characters except for
+// tabs, which advance to the next multiple of 8. East Asian wide characters are
+// two columns wide. A line is never broken before its first visible character,
+// so a character wider than the remaining width is put on a line of its own.
+#[must_use]
+pub(crate) fn wrap_points(text: &str, max_width: usize, indent: usize) -> Vec<usize> {
+    let mut points = Vec::new();
+    let (mut col, mut line_start) = (0, 0);
+    for_each_char(text, |pos, ch| {
+        if ch == '\n' {
+            col = 0;
+            line_start = 0;
+            return;
+        }
+        let next = advance(col, ch);
+        if next > max_width && next > col && col > line_start {
+            points.push(pos);
+            col = advance(indent, ch);
+            line_start = indent;
+        } else {
+            col = next;
+        }
+    });
+    points
+}
+
+// Calls `f` with each character not in an escape sequence and its byte offset.
+fn for_each_char(text: &str, mut f: impl FnMut(usize, char)) {
+    let mut pos = 0;
+    while let Some(ch) = text[pos..].chars().next() {
+        if let Some(len) = escape_len(&text[pos..]) {
+            pos += len;
+            continue;
+        }
+        f(pos, ch);
+        pos += ch.len_utf8();
+    }
+}
+
+#[must_use]
+fn advance(col: usize, ch: char) -> usize {
+    match ch {
+        '\t' => (col / 8 + 1) * 8,
+        '\r' => 0,
+        _ => col + char_width(ch),
+    }
+}
+
+// Returns the length of the CSI (e.g. SGR) or OSC (e.g. hyperlink) sequence at
+// the beginning of the text.
+#[must_use]
+fn escape_len(text: &str) -> Option<usize> {
+    let bytes = text.as_bytes();
+    match bytes.get(..2)? {
+        b"\x1b[" => {
+            let end = bytes[2..].iter().position(|b| !(0x20..=0x3f).contains(b))? + 2;
+            (0x40..=0x7e).contains(&bytes[end]).then(|| end + 1)
+        }
+        b"\x1b]" => {
+            let end = bytes[2..]
+                .iter()
+                .position(|&b| b == b'\x07' || b == b'\x1b')?
+                + 2;
+            match bytes[end] {
+                b'\x07' => Some(end + 1),
+                _ => (bytes.get(end + 1) == Some(&b'\\')).then(|| end + 2),
+            }
+        }
+        _ => None,
+    }
+}
+
+#[must_use]
+fn char_width(ch: char) -> usize {
+    const ZERO_WIDTH: &[(u32, u32)] = &[
+        (0x0300, 0x036f), // Combining Diacritical Marks
+        (0x0483, 0x0489),
+        (0x0591, 0x05bd),
+        (0x0610, 0x061a),
+        (0x064b, 0x065f),
+        (0x1ab0, 0x1aff),
+        (0x1dc0, 0x1dff),
+        (0x200b, 0x200f), // Zero-width spaces and joiners
+        (0x20d0, 0x20ff),
+        (0xfe00, 0xfe0f), // Variation Selectors
+        (0xfe20, 0xfe2f),
+        (0xe0100, 0xe01ef),
+    ];
+    const WIDE: &[(u32, u32)] = &[
+        (0x1100, 0x115f), // Hangul Jamo
+        (0x2e80, 0x303e), // CJK Radicals, Symbols and Punctuation
+        (0x3041, 0x33ff), // Kana, Bopomofo, CJK Compatibility
+        (0x3400, 0x4dbf), // CJK Unified Ideographs Extension A
+        (0x4e00, 0x9fff), // CJK Unified Ideographs
+        (0xa000, 0xa4cf), // Yi
+        (0xa960, 0xa97f),
+        (0xac00, 0xd7a3), // Hangul Syllables
+        (0xf900, 0xfaff), // CJK Compatibility Ideographs
+        (0xfe30, 0xfe4f),
+        (0xff00, 0xff60), // Fullwidth Forms
+        (0xffe0, 0xffe6),
+        (0x1f300, 0x1f64f), // Emoji
+        (0x1f900, 0x1f9ff),
+        (0x20000, 0x2fffd),
+        (0x30000, 0x3fffd),
+    ];
+    let in_table = |table: &[(u32, u32)]| {
+        let code = ch as u32;
+        table
+            .iter()
+            .any(|&(first, last)| (first..=last).contains(&code))
+    };
+
+    if ch.is_control() || in_table(ZERO_WIDTH) {
+        0
+    } else if in_table(WIDE) {
+        2
+    } else {
+        1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strip_sgr() {
+        assert!(matches!(strip_ansi("plain text"), Cow::Borrowed(_)));
+        assert_eq!(strip_ansi("\x1b[31mred\x1b[m"), "red");
+        assert_eq!(strip_ansi("\x1b[1;4;38;5;196mstyled\x1b[0m"), "styled");
+        assert_eq!(
+            strip_ansi("\x1b[38:2::255:0:0mtrue color\x1b[39m"),
+            "true color"
+        );
+
+        // nested and adjacent
+        assert_eq!(
+            strip_ansi("\x1b[1m\x1b[31mbold red \x1b[32mbold green\x1b[0m\x1b[0m!"),
+            "bold red bold green!"
+        );
+        assert_eq!(strip_ansi("\x1b[m\x1b[m\x1b[m"), "");
+        let style_code = StyleCode::from(Style::builder().bold().color(Color::Red).build());
+        assert_eq!(
+            strip_ansi(&format!("[{}error{}]", style_code.start, style_code.end)),
+            "[error]"
+        );
+    }
+
+    #[test]
+    fn keep_invalid_sequences() {
+        for text in [
+            "\x1b",
+            "trailing \x1b[",
+            "\x1b[31",
+            "\x1b[31;Xm",
+            "\x1b[2J clear screen",
+            "\x1b]0;title\x07",
+            "a \x1b b",
+        ] {
+            assert!(matches!(strip_ansi(text), Cow::Borrowed(_)), "{:?}", text);
+        }
+
+        assert_eq!(strip_ansi("\x1b\x1b[31mred"), "\x1bred");
+        assert_eq!(strip_ansi("\x1b[2J\x1b[1mbold"), "\x1b[2Jbold");
+    }
+
+    #[test]
+    fn hint_styles_opt_in() {
+        let mut codes = HintStyleCodes::default();
+        for hint in [StyleHint::LoggerName, StyleHint::Key, StyleHint::Value] {
+            assert_eq!(codes.code(hint), &NO_STYLE_CODE);
+        }
+
+        codes.set_code(StyleHint::Key, Style::builder().bold().build());
+        assert_eq!(codes.code(StyleHint::Key).start, "\x1b[1m");
+        assert_eq!(codes.code(StyleHint::Value), &NO_STYLE_CODE);
+    }
+
+    #[test]
+    fn width() {
+        assert_eq!(display_width("hello"), 5);
+        assert_eq!(display_width("\x1b[1;31merror\x1b[0m"), 5);
+        assert_eq!(
+            display_width("\x1b]8;;https://example.com\x1b\\link\x1b]8;;\x07"),
+            4
+        );
+        assert_eq!(display_width("日本語"), 6);
+        assert_eq!(display_width("ｆｕｌｌ"), 8);
+        assert_eq!(display_width("e\u{301}\u{200d}"), 1);
+        assert_eq!(display_width("a\tb"), 9);
+        assert_eq!(display_width("\x1b"), 0);
+    }
+
+    #[test]
+    fn wrap() {
+        #[must_use]
+        fn wrap(text: &str, max_width: usize, indent: usize) -> Vec<&str> {
+            let mut lines = Vec::new();
+            let mut begin = 0;
+            for point in wrap_points(text, max_width, indent) {
+                lines.push(&text[begin..point]);
+                begin = point;
+            }
+            lines.push(&text[begin..]);
+            lines
+        }
+
+        assert_eq!(wrap("abcdef", 6, 0), ["abcdef"]);
+        assert_eq!(wrap("abcdefgh", 3, 0), ["abc", "def", "gh"]);
+        assert_eq!(wrap("abcdefgh", 5, 2), ["abcde", "fgh"]);
+        assert_eq!(wrap("abcdefghi", 4, 2), ["abcd", "ef", "gh", "i"]);
+        // Existing line breaks are kept, and reset the column
+        assert_eq!(wrap("abc\ndefgh\n", 4, 0), ["abc\ndefg", "h\n"]);
+
+        // Escape sequences are not counted, and never split
+        assert_eq!(
+            wrap("\x1b[31mabc\x1b[0mdef", 3, 0),
+            ["\x1b[31mabc\x1b[0m", "def"]
+        );
+        assert_eq!(
+            wrap("ab\x1b[38;5;196mcd\x1b[m", 2, 0),
+            ["ab\x1b[38;5;196m", "cd\x1b[m"]
+        );
+
+        // Wide characters are not split across lines
+        assert_eq!(wrap("日本語です", 4, 0), ["日本", "語で", "す"]);
+        assert_eq!(wrap("a日本", 4, 0), ["a日", "本"]);
+        assert_eq!(wrap("ab日本", 3, 1), ["ab", "日", "本"]);
+        // A character wider than the line is put on a line of its own
+        assert_eq!(wrap("a日b", 1, 0), ["a", "日", "b"]);
+        // Combining characters stay with their base characters
+        assert_eq!(wrap("abe\u{301}", 2, 0), ["ab", "e\u{301}"]);
+    }
+}
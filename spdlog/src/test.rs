@@ -0,0 +1,277 @@
+//! Provides helpers for testing code that logs.
+//!
+//! This module is only available with the `test-util` feature, which is
+//! intended to be enabled in `[dev-dependencies]`.
+//!
+//! # Examples
+//!
+//! ```
+//! use spdlog::{assert_logged, assert_not_logged, prelude::*};
+//!
+//! fn connect(retries: u32) {
+//!     for attempt in 1..=retries {
+//!         warn!("connection attempt {} failed", attempt);
+//!     }
+//!     error!("gave up connecting");
+//! }
+//!
+//! let logs = spdlog::test::capture(|| connect(2));
+//!
+//! assert_eq!(logs.len(), 3);
+//! assert!(logs.iter().any(|record| record.level() == Level::Error));
+//! assert_logged!(logs, Level::Warn, "attempt 2 failed");
+//! assert_not_logged!(logs, Level::Warn, "attempt 3");
+//! ```
+
+use std::{cell::Cell, ops::Deref};
+
+use crate::{
+    formatter::Formatter, sink::Sink, sync::*, ErrorHandler, Level, LevelFilter, Logger, Record,
+    RecordOwned, Result,
+};
+
+// Serializes captures, as they replace the global default logger
+static CAPTURE_LOCK: Lazy<Mutex<()>> = Lazy::new(Mutex::default);
+
+thread_local! {
+    static CAPTURING: Cell<bool> = Cell::new(false);
+}
+
+/// Runs the given closure with a capturing logger as the default logger, and
+/// returns the records logged to it.
+///
+/// The default logger is restored when the closure returns, even if it panics.
+/// All levels are captured, regardless of the level filter of the previous
+/// default logger, except for the levels disabled at compile time (see
+/// [`STATIC_LEVEL_FILTER`]).
+///
+/// # Note
+///
+/// Only records logged to the default logger are captured, i.e. by log macros
+/// without `logger:`. Records logged to other loggers are not.
+///
+/// The default logger is global, so captures are serialized, and a capture
+/// waits for the ongoing one in another thread to finish. Records logged to the
+/// default logger by other threads during a capture, e.g. by other tests that
+/// do not capture, are captured too.
+///
+/// # Panics
+///
+/// Panics if it's called inside the closure of another `capture`.
+///
+/// # Examples
+///
+/// See the documentation of [module `test`].
+///
+/// [`STATIC_LEVEL_FILTER`]: crate::STATIC_LEVEL_FILTER
+/// [module `test`]: crate::test
+pub fn capture<F>(f: F) -> CapturedLogs
+where
+    F: FnOnce(),
+{
+    struct CapturingGuard;
+
+    impl Drop for CapturingGuard {
+        fn drop(&mut self) {
+            CAPTURING.with(|capturing| capturing.set(false));
+        }
+    }
+
+    if CAPTURING.with(|capturing| capturing.replace(true)) {
+        panic!("`spdlog::test::capture` cannot be nested");
+    }
+    let _capturing = CapturingGuard;
+
+    // A panicking closure poisons the lock, which does not matter here
+    let _lock = CAPTURE_LOCK.lock().unwrap_or_else(|err| err.into_inner());
+
+    let sink = Arc::new(CaptureSink::default());
+    let logger = Arc::new(
+        Logger::builder()
+            .level_filter(LevelFilter::All)
+            .sink(sink.clone())
+            .build()
+            .expect("failed to build the capturing logger"),
+    );
+    crate::with_default_logger(logger, f);
+
+    let records = std::mem::take(&mut *sink.records.lock_expect());
+    CapturedLogs { records }
+}
+
+/// Records captured by [`capture`].
+///
+/// It dereferences to a slice of [`RecordOwned`] in the order they were
+/// logged.
+#[derive(Clone, Debug)]
+pub struct CapturedLogs {
+    records: Vec<RecordOwned>,
+}
+
+impl CapturedLogs {
+    /// Determines if a record at the given level whose payload contains the
+    /// given text has been captured.
+    #[must_use]
+    pub fn contains(&self, level: Level, text: &str) -> bool {
+        self.records
+            .iter()
+            .any(|record| record.level() == level && record.payload().contains(text))
+    }
+
+    /// Returns the number of captured records at the given level.
+    #[must_use]
+    pub fn count(&self, level: Level) -> usize {
+        self.records
+            .iter()
+            .filter(|record| record.level() == level)
+            .count()
+    }
+
+    /// Returns the payloads of the captured records.
+    #[must_use]
+    pub fn payloads(&self) -> Vec<&str> {
+        self.records.iter().map(|record| record.payload()).collect()
+    }
+
+    /// Converts into the captured records.
+    #[must_use]
+    pub fn into_records(self) -> Vec<RecordOwned> {
+        self.records
+    }
+
+    // Used by `assert_logged!` and `assert_not_logged!`
+    #[doc(hidden)]
+    #[must_use]
+    pub fn __dump(&self) -> String {
+        self.records
+            .iter()
+            .map(|record| format!("\n  [{}] {}", record.level(), record.payload()))
+            .collect()
+    }
+}
+
+impl Deref for CapturedLogs {
+    type Target = [RecordOwned];
+
+    fn deref(&self) -> &Self::Target {
+        &self.records
+    }
+}
+
+/// Asserts that a record at the given level whose payload contains the given
+/// text has been captured by [`test::capture`].
+///
+/// On failure, the panic message lists the captured records.
+///
+/// # Examples
+///
+/// See the documentation of [module `test`].
+///
+/// [`test::capture`]: crate::test::capture
+/// [module `test`]: crate::test
+#[macro_export]
+macro_rules! assert_logged {
+    ($logs:expr, $level:expr, $text:expr $(,)?) => {{
+        let (logs, level, text): (&$crate::test::CapturedLogs, $crate::Level, &str) =
+            (&$logs, $level, $text);
+        if !logs.contains(level, text) {
+            panic!(
+                "expected a `{}` record containing {:?}, captured:{}",
+                level,
+                text,
+                logs.__dump()
+            );
+        }
+    }};
+}
+
+/// Asserts that no record at the given level whose payload contains the given
+/// text has been captured by [`test::capture`].
+///
+/// On failure, the panic message lists the captured records.
+///
+/// # Examples
+///
+/// See the documentation of [module `test`].
+///
+/// [`test::capture`]: crate::test::capture
+/// [module `test`]: crate::test
+#[macro_export]
+macro_rules! assert_not_logged {
+    ($logs:expr, $level:expr, $text:expr $(,)?) => {{
+        let (logs, level, text): (&$crate::test::CapturedLogs, $crate::Level, &str) =
+            (&$logs, $level, $text);
+        if logs.contains(level, text) {
+            panic!(
+                "expected no `{}` record containing {:?}, captured:{}",
+                level,
+                text,
+                logs.__dump()
+            );
+        }
+    }};
+}
+
+#[derive(Default)]
+struct CaptureSink {
+    records: Mutex<Vec<RecordOwned>>,
+}
+
+impl Sink for CaptureSink {
+    fn log(&self, record: &Record) -> Result<()> {
+        self.records.lock_expect().push(record.to_owned());
+        Ok(())
+    }
+
+    fn flush(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn level_filter(&self) -> LevelFilter {
+        LevelFilter::All
+    }
+
+    fn set_level_filter(&self, _level_filter: LevelFilter) {}
+
+    fn set_formatter(&self, _formatter: Box<dyn Formatter>) {}
+
+    fn set_error_handler(&self, _handler: Option<ErrorHandler>) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn capture_and_assert() {
+        let logs = capture(|| {
+            trace!("verbose {}", 1);
+            error!("failed");
+            info!(logger: crate::default_logger(), "explicit default");
+        });
+
+        assert_eq!(
+            logs.payloads(),
+            vec!["verbose 1", "failed", "explicit default"]
+        );
+        assert_eq!(logs.count(Level::Error), 1);
+        assert_logged!(logs, Level::Trace, "verbose");
+        assert_not_logged!(logs, Level::Error, "verbose");
+
+        let result = std::panic::catch_unwind(|| {
+            assert_logged!(logs, Level::Warn, "failed");
+        });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn capture_after_panic() {
+        let result = std::panic::catch_unwind(|| capture(|| panic!("oops")));
+        assert!(result.is_err());
+
+        // Neither the lock nor the nesting check is left behind
+        let logs = capture(|| info!("after panic"));
+        assert_logged!(logs, Level::Info, "after panic");
+    }
+}
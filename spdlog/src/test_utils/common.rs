@@ -158,6 +158,15 @@ impl Sink for StringSink {
         self.underlying.log(record)
     }
 
+    fn write_formatted(
+        &self,
+        record: &Record,
+        formatted: &StringBuf,
+        extra_info: &FmtExtraInfo,
+    ) -> Result<()> {
+        self.underlying.write_formatted(record, formatted, extra_info)
+    }
+
     fn flush(&self) -> Result<()> {
         self.underlying.flush()
     }
@@ -5,7 +5,9 @@
 
 use std::{env, fs, path::PathBuf};
 
-use crate::sync::*;
+use crate::{
+    formatter::Formatter, sink::Sink, sync::*, Error, ErrorHandler, LevelFilter, Record, Result,
+};
 
 pub static TEST_LOGS_PATH: Lazy<PathBuf> = Lazy::new(|| {
     let path = env::current_exe()
@@ -16,3 +18,75 @@ pub static TEST_LOGS_PATH: Lazy<PathBuf> = Lazy::new(|| {
     fs::create_dir_all(&path).unwrap();
     path
 });
+
+//////////////////////////////////////////////////
+
+// A sink failing to log and flush while `failing` is set, with
+// `Error::__ForInternalTestsUseOnly(1)` and `(2)` respectively.
+pub struct FailingSink {
+    failing: AtomicBool,
+    attempts: AtomicUsize,
+    written: AtomicUsize,
+}
+
+impl FailingSink {
+    // Fails until `set_failing(false)` is called
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            failing: AtomicBool::new(true),
+            attempts: AtomicUsize::new(0),
+            written: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn set_failing(&self, failing: bool) {
+        self.failing.store(failing, Ordering::SeqCst);
+    }
+
+    // The number of calls to `log`
+    #[must_use]
+    pub fn attempts(&self) -> usize {
+        self.attempts.load(Ordering::SeqCst)
+    }
+
+    // The number of calls to `log` that succeeded
+    #[must_use]
+    pub fn written(&self) -> usize {
+        self.written.load(Ordering::SeqCst)
+    }
+}
+
+impl Sink for FailingSink {
+    fn log(&self, _record: &Record) -> Result<()> {
+        self.attempts.fetch_add(1, Ordering::SeqCst);
+        if self.failing.load(Ordering::SeqCst) {
+            return Err(Error::__ForInternalTestsUseOnly(1));
+        }
+        self.written.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+
+    fn flush(&self) -> Result<()> {
+        if self.failing.load(Ordering::SeqCst) {
+            return Err(Error::__ForInternalTestsUseOnly(2));
+        }
+        Ok(())
+    }
+
+    fn level_filter(&self) -> LevelFilter {
+        LevelFilter::All
+    }
+
+    fn set_level_filter(&self, _level_filter: LevelFilter) {}
+
+    fn set_formatter(&self, _formatter: Box<dyn Formatter>) {}
+
+    fn set_error_handler(&self, _handler: Option<ErrorHandler>) {}
+}
+
+impl Default for FailingSink {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -5,6 +5,7 @@ use once_cell::sync::Lazy;
 
 use crate::{
     error::{Error, InvalidArgumentError},
+    shutdown::{register_async_worker, AsyncWorker, ShutdownStage},
     sink::{OverflowPolicy, Task},
     sync::*,
     Result,
@@ -30,17 +31,31 @@ use crate::{
 /// # Ok(()) }
 /// ```
 ///
+/// # Shutdown
+///
+/// The threads are joined when the `ThreadPool` is dropped or
+/// [`spdlog::shutdown`] is called, after processing the remaining queued
+/// operations. Operations assigned after that are processed synchronously on
+/// the calling thread.
+///
 /// [`AsyncPoolSink`]: crate::sink::AsyncPoolSink
+/// [`spdlog::shutdown`]: crate::shutdown
 pub struct ThreadPool {
-    threads: Vec<Option<JoinHandle<()>>>,
-    sender: Option<Sender<Task>>,
+    inner: Arc<PoolInner>,
+    capacity: Option<usize>,
+}
+
+struct PoolInner {
+    // `None` after shutdown
+    sender: RwLock<Option<Sender<Task>>>,
+    threads: Mutex<Vec<JoinHandle<()>>>,
 }
 
 type Callback = Arc<dyn Fn() + Send + Sync + 'static>;
 
 /// The builder of [`ThreadPool`].
 pub struct ThreadPoolBuilder {
-    capacity: usize,
+    capacity: Option<usize>,
     threads: usize,
     on_thread_spawn: Option<Callback>,
     on_thread_finish: Option<Callback>,
@@ -55,7 +70,7 @@ impl ThreadPool {
     #[must_use]
     pub fn builder() -> ThreadPoolBuilder {
         ThreadPoolBuilder {
-            capacity: 8192,
+            capacity: Some(8192),
             threads: 1,
             on_thread_spawn: None,
             on_thread_finish: None,
@@ -67,8 +82,39 @@ impl ThreadPool {
         Self::builder().build()
     }
 
+    /// Gets the capacity of the operation channel.
+    ///
+    /// Returns `None` if the channel is unbounded.
+    ///
+    /// See [`ThreadPoolBuilder::capacity`] and
+    /// [`ThreadPoolBuilder::unbounded`].
+    #[must_use]
+    pub fn capacity(&self) -> Option<usize> {
+        self.capacity
+    }
+
+    /// Gets the number of operations currently queued in the channel, which
+    /// have not yet been taken by the threads.
+    #[must_use]
+    pub fn queue_len(&self) -> usize {
+        self.inner
+            .sender
+            .read_expect()
+            .as_ref()
+            .map_or(0, |sender| sender.len())
+    }
+
     pub(super) fn assign_task(&self, task: Task, overflow_policy: OverflowPolicy) -> Result<()> {
-        let sender = self.sender.as_ref().unwrap();
+        let sender = self.inner.sender.read_expect();
+        let sender = match sender.as_ref() {
+            Some(sender) => sender,
+            None => {
+                // The pool has been shut down, process it on the current thread
+                drop(sender);
+                task.exec();
+                return Ok(());
+            }
+        };
 
         match overflow_policy {
             OverflowPolicy::Block => sender.send(task).map_err(Error::from_crossbeam_send),
@@ -81,22 +127,25 @@ impl ThreadPool {
 
 impl Drop for ThreadPool {
     fn drop(&mut self) {
+        self.inner.shutdown();
+    }
+}
+
+impl AsyncWorker for PoolInner {
+    fn shutdown(&self) {
         // drop our sender, threads will break the loop after receiving and processing
         // the remaining tasks
-        self.sender.take();
+        self.sender.write_expect().take();
 
-        for thread in &mut self.threads {
-            thread
-                .take()
-                .unwrap()
-                .join()
-                .expect("failed to join a thread from pool");
+        for thread in self.threads.lock_expect().drain(..) {
+            thread.join().expect("failed to join a thread from pool");
         }
     }
 }
 
 impl ThreadPoolBuilder {
-    /// Specifies the capacity of the operation channel.
+    /// Specifies the capacity of the operation channel, i.e. makes the channel
+    /// bounded.
     ///
     /// This parameter is **optional**, and defaults to 8192 (The value may
     /// change in the future).
@@ -104,11 +153,35 @@ impl ThreadPoolBuilder {
     /// When a new operation is incoming, but the channel is full, it will be
     /// handled by sink according to the [`OverflowPolicy`] that has been set.
     ///
-    /// # Panics
+    /// # Memory footprint
+    ///
+    /// The slots of a bounded channel are allocated up front, each slot takes
+    /// about 150 bytes on 64-bit targets. In addition, a queued log operation
+    /// owns a copy of the payload and the logger name of its record, so a full
+    /// channel takes about `capacity × (150 + payload length + logger name
+    /// length)` bytes. For example, the default capacity takes about 1.1 MiB
+    /// for slots, plus about 1 MiB if payloads are 128 bytes on average.
     ///
-    /// Panics if the value is zero.
+    /// If [`ThreadPoolBuilder::build`] is called with a capacity of zero,
+    /// [`Error::InvalidArgument`] will be returned.
     pub fn capacity(&mut self, capacity: usize) -> &mut Self {
-        self.capacity = capacity;
+        self.capacity = Some(capacity);
+        self
+    }
+
+    /// Makes the operation channel unbounded.
+    ///
+    /// An unbounded channel never overflows, so the [`OverflowPolicy`] never
+    /// engages. Instead, if operations are incoming faster than the threads
+    /// can process them (e.g. a slow sink under a burst of logs), the channel
+    /// grows without limit and may eventually run out of memory. Prefer a
+    /// bounded channel, which is the default, unless the log rate is known to
+    /// be bounded.
+    ///
+    /// Calling [`ThreadPoolBuilder::capacity`] afterwards makes the channel
+    /// bounded again.
+    pub fn unbounded(&mut self) -> &mut Self {
+        self.capacity = None;
         self
     }
 
@@ -146,7 +219,7 @@ impl ThreadPoolBuilder {
 
     /// Builds a [`ThreadPool`].
     pub fn build(&self) -> Result<ThreadPool> {
-        if self.capacity < 1 {
+        if self.capacity == Some(0) {
             return Err(Error::InvalidArgument(
                 InvalidArgumentError::ThreadPoolCapacity("cannot be 0".to_string()),
             ));
@@ -158,7 +231,10 @@ impl ThreadPoolBuilder {
             panic!("threads of ThreadPool cannot be 0");
         }
 
-        let (sender, receiver) = mpmc::bounded(self.capacity);
+        let (sender, receiver) = match self.capacity {
+            Some(capacity) => mpmc::bounded(capacity),
+            None => mpmc::unbounded(),
+        };
 
         let mut threads = Vec::new();
         threads.resize_with(self.threads, || {
@@ -166,7 +242,7 @@ impl ThreadPoolBuilder {
             let on_thread_spawn = self.on_thread_spawn.clone();
             let on_thread_finish = self.on_thread_finish.clone();
 
-            Some(thread::spawn(move || {
+            thread::spawn(move || {
                 if let Some(f) = on_thread_spawn {
                     f();
                 }
@@ -176,12 +252,18 @@ impl ThreadPoolBuilder {
                 if let Some(f) = on_thread_finish {
                     f();
                 }
-            }))
+            })
         });
 
+        let inner = Arc::new(PoolInner {
+            sender: RwLock::new(Some(sender)),
+            threads: Mutex::new(threads),
+        });
+        register_async_worker(ShutdownStage::ThreadPool, inner.clone());
+
         Ok(ThreadPool {
-            threads,
-            sender: Some(sender),
+            inner,
+            capacity: self.capacity,
         })
     }
 }
@@ -224,6 +306,36 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn capacity() {
+        assert_eq!(ThreadPool::new().unwrap().capacity(), Some(8192));
+        assert_eq!(
+            ThreadPool::builder()
+                .capacity(1)
+                .build()
+                .unwrap()
+                .capacity(),
+            Some(1)
+        );
+        assert_eq!(
+            ThreadPool::builder()
+                .unbounded()
+                .build()
+                .unwrap()
+                .capacity(),
+            None
+        );
+        assert_eq!(
+            ThreadPool::builder()
+                .unbounded()
+                .capacity(2)
+                .build()
+                .unwrap()
+                .capacity(),
+            Some(2)
+        );
+    }
+
     #[test]
     #[should_panic]
     fn panic_thread_0() {
@@ -0,0 +1,119 @@
+//! Provides the guard used by macro [`trace_scope!`].
+//!
+//! [`trace_scope!`]: crate::trace_scope
+
+use std::time::Instant;
+
+use crate::{Level, Logger, Record, SourceLocation};
+
+// Logs the entry on construction and the exit with the elapsed time on drop.
+//
+// Used at macro `trace_scope!`.
+#[doc(hidden)]
+pub struct TraceScope<'a> {
+    logger: &'a Logger,
+    level: Level,
+    name: &'a str,
+    srcloc: Option<SourceLocation>,
+    start: Instant,
+}
+
+impl<'a> TraceScope<'a> {
+    #[doc(hidden)]
+    #[must_use]
+    pub fn __new(
+        logger: &'a Logger,
+        level: Level,
+        name: &'a str,
+        srcloc: Option<SourceLocation>,
+    ) -> Self {
+        let scope = Self {
+            logger,
+            level,
+            name,
+            srcloc,
+            start: Instant::now(),
+        };
+        if logger.should_log(level) {
+            scope.log(format!("enter {}", name));
+        }
+        scope
+    }
+
+    fn log(&self, payload: String) {
+        let mut builder = Record::builder(self.level, payload).source_location(self.srcloc.clone());
+        if let Some(logger_name) = self.logger.name() {
+            builder = builder.logger_name(logger_name);
+        }
+        self.logger.log(&builder.build());
+    }
+}
+
+impl Drop for TraceScope<'_> {
+    fn drop(&mut self) {
+        if self.logger.should_log(self.level) {
+            let elapsed = self.start.elapsed();
+            self.log(format!("exit {} ({}µs)", self.name, elapsed.as_micros()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::{prelude::*, test_utils::*};
+
+    #[test]
+    fn enter_exit() {
+        let test_sink = Arc::new(CounterSink::new());
+        let test_logger =
+            build_test_logger(|b| b.sink(test_sink.clone()).level_filter(LevelFilter::All));
+
+        {
+            crate::trace_scope!(logger: test_logger, "my_fn");
+            assert_eq!(test_sink.payloads(), ["enter my_fn"]);
+        }
+
+        let records = test_sink.records();
+        assert_eq!(records.len(), 2);
+        assert!(records.iter().all(|record| record.level() == Level::Trace));
+
+        let elapsed = records[1]
+            .payload()
+            .strip_prefix("exit my_fn (")
+            .and_then(|exit| exit.strip_suffix("µs)"))
+            .unwrap();
+        assert!(elapsed.parse::<u128>().is_ok());
+
+        #[cfg(feature = "source-location")]
+        assert_eq!(
+            records[0].source_location().unwrap().line(),
+            records[1].source_location().unwrap().line()
+        );
+    }
+
+    #[test]
+    fn level() {
+        let test_sink = Arc::new(CounterSink::new());
+        let test_logger = build_test_logger(|b| {
+            b.sink(test_sink.clone())
+                .level_filter(LevelFilter::MoreSevereEqual(Level::Debug))
+        });
+
+        {
+            crate::trace_scope!(logger: test_logger, "filtered");
+        }
+        assert_eq!(test_sink.log_count(), 0);
+
+        {
+            crate::trace_scope!(logger: test_logger, Level::Debug, "debug_fn");
+            crate::trace_scope!(logger: test_logger, "filtered");
+        }
+        let records = test_sink.records();
+        assert_eq!(records.len(), 2);
+        assert!(records.iter().all(|record| record.level() == Level::Debug));
+        assert_eq!(records[0].payload(), "enter debug_fn");
+        assert!(records[1].payload().starts_with("exit debug_fn ("));
+    }
+}
@@ -1,8 +1,11 @@
 use std::{
     fs::{self, File, OpenOptions},
+    io::{self, Read, Write},
     path::Path,
 };
 
+use cfg_if::cfg_if;
+
 use crate::{Error, Result};
 
 pub fn open_file(path: impl AsRef<Path>, truncate: bool) -> Result<File> {
@@ -26,6 +29,139 @@ pub fn open_file(path: impl AsRef<Path>, truncate: bool) -> Result<File> {
         .map_err(Error::OpenFile)
 }
 
+// Writes the UTF-8 BOM to the file if it's empty, returns whether it's written.
+//
+// It's regarded as a part of opening the file, so errors are reported as
+// `Error::OpenFile`.
+pub fn write_utf8_bom_if_empty(mut file: &File) -> Result<bool> {
+    if file.metadata().map_err(Error::OpenFile)?.len() != 0 {
+        return Ok(false);
+    }
+    file.write_all("\u{FEFF}".as_bytes())
+        .map_err(Error::OpenFile)?;
+    Ok(true)
+}
+
+pub const UTF8_BOM_LEN: u64 = "\u{FEFF}".len() as u64;
+
+// Returns whether the file at the path starts with the UTF-8 BOM.
+//
+// Like `write_utf8_bom_if_empty`, errors are reported as `Error::OpenFile`.
+pub fn starts_with_utf8_bom(path: impl AsRef<Path>) -> Result<bool> {
+    let mut head = Vec::with_capacity(UTF8_BOM_LEN as usize);
+    File::open(path)
+        .and_then(|file| file.take(UTF8_BOM_LEN).read_to_end(&mut head))
+        .map_err(Error::OpenFile)?;
+    Ok(head == "\u{FEFF}".as_bytes())
+}
+
+// Acquires an advisory exclusive lock on the file without blocking. The lock
+// is released when the file is closed or `unlock_file` is called.
+//
+// On Windows, locks are mandatory, so the lock is acquired on a byte far beyond
+// the end of the file, which does not prevent others from writing.
+pub fn try_lock_file_exclusive(file: &File) -> io::Result<()> {
+    cfg_if! {
+        if #[cfg(unix)] {
+            use std::os::unix::io::AsRawFd;
+
+            if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX | libc::LOCK_NB) } != 0 {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(())
+        } else if #[cfg(windows)] {
+            use std::os::windows::io::AsRawHandle;
+
+            use winapi::um::{
+                fileapi::LockFileEx,
+                minwinbase::{LOCKFILE_EXCLUSIVE_LOCK, LOCKFILE_FAIL_IMMEDIATELY},
+            };
+
+            let mut overlapped = windows_lock_overlapped();
+            let ret = unsafe {
+                LockFileEx(
+                    file.as_raw_handle() as _,
+                    LOCKFILE_EXCLUSIVE_LOCK | LOCKFILE_FAIL_IMMEDIATELY,
+                    0,
+                    1,
+                    0,
+                    &mut overlapped,
+                )
+            };
+            if ret == 0 {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(())
+        } else {
+            let _ = file;
+            Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "file locking is not supported on this platform",
+            ))
+        }
+    }
+}
+
+// Releases the lock acquired by `try_lock_file_exclusive`.
+pub fn unlock_file(file: &File) -> io::Result<()> {
+    cfg_if! {
+        if #[cfg(unix)] {
+            use std::os::unix::io::AsRawFd;
+
+            if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_UN) } != 0 {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(())
+        } else if #[cfg(windows)] {
+            use std::os::windows::io::AsRawHandle;
+
+            use winapi::um::fileapi::UnlockFileEx;
+
+            let mut overlapped = windows_lock_overlapped();
+            let ret = unsafe { UnlockFileEx(file.as_raw_handle() as _, 0, 1, 0, &mut overlapped) };
+            if ret == 0 {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(())
+        } else {
+            let _ = file;
+            Ok(())
+        }
+    }
+}
+
+#[cfg(windows)]
+fn windows_lock_overlapped() -> winapi::um::minwinbase::OVERLAPPED {
+    let mut overlapped: winapi::um::minwinbase::OVERLAPPED = unsafe { std::mem::zeroed() };
+    unsafe {
+        let offset = overlapped.u.s_mut();
+        offset.Offset = u32::MAX - 1;
+        offset.OffsetHigh = u32::MAX;
+    }
+    overlapped
+}
+
+// Syncs the parent directory of the given path, so that the creation, renaming
+// or removal of the file is durable.
+pub fn sync_parent_dir(path: impl AsRef<Path>) -> Result<()> {
+    cfg_if! {
+        if #[cfg(unix)] {
+            let parent = match path.as_ref().parent() {
+                Some(parent) if !parent.as_os_str().is_empty() => parent,
+                _ => Path::new("."),
+            };
+            File::open(parent)
+                .and_then(|dir| dir.sync_all())
+                .map_err(Error::SyncFile)
+        } else {
+            // Directories cannot be opened as files on Windows, and NTFS journals
+            // metadata changes anyway.
+            let _ = path;
+            Ok(())
+        }
+    }
+}
+
 // Credits `static_assertions` crate
 macro_rules! const_assert {
     ( $cond:expr $(,)? ) => {
@@ -0,0 +1,80 @@
+#![cfg(feature = "config")]
+
+use std::{
+    env, fs,
+    path::PathBuf,
+    thread,
+    time::{Duration, Instant},
+};
+
+use spdlog::{config, Level, LevelFilter};
+
+#[must_use]
+fn config_file_path() -> PathBuf {
+    let dir = env::current_exe()
+        .unwrap()
+        .parent()
+        .unwrap()
+        .join("dev/test_logs/config_watch");
+    fs::create_dir_all(&dir).unwrap();
+    dir.join("config.json")
+}
+
+fn write_config(path: &PathBuf, level: &str) {
+    fs::write(
+        path,
+        format!(
+            r#"{{ "default": {{ "level": "{}", "sinks": [{{ "type": "null" }}] }} }}"#,
+            level
+        ),
+    )
+    .unwrap();
+}
+
+fn wait_for_level(level_filter: LevelFilter) -> bool {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while Instant::now() < deadline {
+        if spdlog::default_logger().level_filter() == level_filter {
+            return true;
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+    false
+}
+
+// The default logger is global, so all steps are in a single test.
+#[test]
+fn reload_on_change() {
+    let path = config_file_path();
+    write_config(&path, "warn");
+
+    let watcher = config::watch(&path, Duration::from_millis(20)).unwrap();
+    assert_eq!(
+        spdlog::default_logger().level_filter(),
+        LevelFilter::MoreSevereEqual(Level::Warn)
+    );
+
+    // Make sure the modification time changes even on coarse-grained file systems.
+    thread::sleep(Duration::from_millis(1100));
+    write_config(&path, "debug");
+    assert!(wait_for_level(LevelFilter::MoreSevereEqual(Level::Debug)));
+
+    // An invalid configuration keeps the previous one in effect.
+    thread::sleep(Duration::from_millis(1100));
+    write_config(&path, "loud");
+    thread::sleep(Duration::from_millis(200));
+    assert_eq!(
+        spdlog::default_logger().level_filter(),
+        LevelFilter::MoreSevereEqual(Level::Debug)
+    );
+
+    // No longer reloads after the watcher is dropped.
+    drop(watcher);
+    thread::sleep(Duration::from_millis(1100));
+    write_config(&path, "error");
+    thread::sleep(Duration::from_millis(200));
+    assert_eq!(
+        spdlog::default_logger().level_filter(),
+        LevelFilter::MoreSevereEqual(Level::Debug)
+    );
+}
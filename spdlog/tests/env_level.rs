@@ -0,0 +1,37 @@
+use std::sync::Arc;
+
+use spdlog::prelude::*;
+
+// The environment variable level is global, so this test is in its own process.
+#[test]
+fn patterns_for_forked_loggers() {
+    std::env::set_var("SPDLOG_RS_TEST_ENV_LEVEL", "app.db.*=debug,*=error");
+    assert!(spdlog::init_env_level_from("SPDLOG_RS_TEST_ENV_LEVEL").unwrap());
+
+    let app = Arc::new(Logger::builder().name("app").build().unwrap());
+    assert_eq!(
+        app.level_filter(),
+        LevelFilter::MoreSevereEqual(Level::Error)
+    );
+
+    // Resolved for the new name
+    let pool = app.fork_with_name(Some("app.db.pool")).unwrap();
+    assert_eq!(
+        pool.level_filter(),
+        LevelFilter::MoreSevereEqual(Level::Debug)
+    );
+
+    let web = pool.fork_with_name(Some("web")).unwrap();
+    assert_eq!(
+        web.level_filter(),
+        LevelFilter::MoreSevereEqual(Level::Error)
+    );
+
+    // Not resolved when renaming
+    let mut renamed = Logger::builder().name("app").build().unwrap();
+    renamed.set_name(Some("app.db.pool")).unwrap();
+    assert_eq!(
+        renamed.level_filter(),
+        LevelFilter::MoreSevereEqual(Level::Error)
+    );
+}
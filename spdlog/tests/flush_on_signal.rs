@@ -0,0 +1,85 @@
+#![cfg(unix)]
+
+use std::{
+    env,
+    io::{BufRead, BufReader},
+    os::unix::process::ExitStatusExt,
+    path::PathBuf,
+    process::{Command, Stdio},
+    sync::Arc,
+    thread,
+    time::Duration,
+};
+
+use spdlog::{prelude::*, sink::FileSink, Signal};
+
+const CHILD_ENV: &str = "SPDLOG_RS_TEST_FLUSH_ON_SIGNAL_CHILD";
+const CHILD_READY: &str = "SPDLOG_RS_TEST_CHILD_READY";
+
+#[must_use]
+fn log_file_path() -> PathBuf {
+    env::current_exe()
+        .unwrap()
+        .parent()
+        .unwrap()
+        .join("dev/test_logs/flush_on_signal.log")
+}
+
+fn run_child() {
+    let sink = Arc::new(
+        FileSink::builder()
+            .path(log_file_path())
+            .truncate(true)
+            .build()
+            .unwrap(),
+    );
+    spdlog::set_default_logger(Arc::new(Logger::builder().sink(sink).build().unwrap()));
+    spdlog::install_flush_on_signal(&[Signal::Terminate]).unwrap();
+
+    info!("buffered before SIGTERM");
+    println!("{}", CHILD_READY);
+
+    loop {
+        thread::sleep(Duration::from_secs(1));
+    }
+}
+
+#[test]
+fn flush_on_sigterm() {
+    if env::var_os(CHILD_ENV).is_some() {
+        run_child();
+        return;
+    }
+
+    let mut child = Command::new(env::current_exe().unwrap())
+        .args([
+            "--exact",
+            "flush_on_sigterm",
+            "--nocapture",
+            "--test-threads=1",
+        ])
+        .env(CHILD_ENV, "1")
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    let stdout = BufReader::new(child.stdout.take().unwrap());
+    for line in stdout.lines() {
+        // the test harness may print something before it on the same line
+        if line.unwrap().ends_with(CHILD_READY) {
+            break;
+        }
+    }
+
+    let status = Command::new("kill")
+        .args(["-TERM", &child.id().to_string()])
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let status = child.wait().unwrap();
+    assert_eq!(status.signal(), Some(15));
+
+    let contents = std::fs::read_to_string(log_file_path()).unwrap();
+    assert!(contents.contains("buffered before SIGTERM"));
+}
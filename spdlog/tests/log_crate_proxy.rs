@@ -1,3 +1,5 @@
+#![cfg(feature = "log")]
+
 use std::sync::Arc;
 
 use spdlog::formatter::{pattern, PatternFormatter};
@@ -0,0 +1,70 @@
+// Run with `cargo test --features logging-off --test logging_off`
+#![cfg(feature = "logging-off")]
+
+use std::{
+    cell::Cell,
+    fmt,
+    sync::Arc,
+    time::{Duration, UNIX_EPOCH},
+};
+
+use spdlog::{
+    err_log, error_chain, log_at, log_bytes, prelude::*, trace_scope, warn_log, LevelFilter,
+    STATIC_LEVEL_FILTER,
+};
+
+struct Counted<'a>(&'a Cell<usize>);
+
+impl fmt::Display for Counted<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.set(self.0.get() + 1);
+        f.write_str("counted")
+    }
+}
+
+#[test]
+fn macros_evaluate_nothing() {
+    assert_eq!(STATIC_LEVEL_FILTER, LevelFilter::Off);
+
+    let evaluated = Cell::new(0);
+    let eval = |value: i32| {
+        evaluated.set(evaluated.get() + 1);
+        value
+    };
+    let logger = || {
+        evaluated.set(evaluated.get() + 1);
+        Arc::new(
+            Logger::builder()
+                .level_filter(LevelFilter::All)
+                .build()
+                .unwrap(),
+        )
+    };
+
+    critical!("{}", eval(1));
+    trace!(logger: logger(), "{}", eval(2));
+    log!(logger: logger(), Level::Error, kv: { key = eval(3) }, "{}", Counted(&evaluated));
+    info!(logger: logger(), |f| write!(f, "{}", eval(4)));
+    info!(logger: logger(), v = 1, tags = ["audit"], kv: { key = eval(8) }, "{}", eval(9));
+    log_at!(logger: logger(), UNIX_EPOCH + Duration::from_secs(eval(5) as u64), Level::Warn, "at");
+
+    let err = std::io::Error::new(std::io::ErrorKind::Other, Counted(&evaluated).to_string());
+    evaluated.set(0);
+    error_chain!(logger: logger(), Level::Error, err);
+    log_bytes!(logger: logger(), Level::Info, [eval(10) as u8]);
+
+    let failed = || Err::<(), _>(std::fmt::Error);
+    assert!(err_log!(logger: logger(), failed(), "{}", eval(6)).is_err());
+    assert!(warn_log!(logger: logger(), failed(), "{}", eval(7)).is_none());
+
+    let scope_logger = Logger::builder()
+        .level_filter(LevelFilter::All)
+        .build()
+        .unwrap();
+    {
+        trace_scope!(logger: scope_logger, "scope");
+        trace_scope!(Level::Critical, "default logger scope");
+    }
+
+    assert_eq!(evaluated.get(), 0);
+}
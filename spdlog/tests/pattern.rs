@@ -8,7 +8,8 @@ use cfg_if::cfg_if;
 use regex::Regex;
 use spdlog::{
     error,
-    formatter::{pattern, Formatter, Pattern, PatternFormatter},
+    formatter::{pattern, Formatter, LevelLabels, Pattern, PatternFormatter, Preset},
+    log_bytes, mdc,
     prelude::*,
     sink::Sink,
     StringBuf,
@@ -54,6 +55,197 @@ fn test_style_range() {
     );
 }
 
+#[test]
+fn test_short_level() {
+    let sink = Arc::new(MockSink::new());
+    sink.set_formatter(Box::new(PatternFormatter::new(pattern!(
+        "[{^{level_short}}] {payload}"
+    ))));
+    let logger = Logger::builder()
+        .sink(sink.clone())
+        .level_filter(LevelFilter::All)
+        .build()
+        .unwrap();
+
+    let check = |expected_short: &str| {
+        let (msg, style_range) = sink.get_last_msg().unwrap();
+        assert_eq!(msg, format!("[{}] msg", expected_short));
+        assert_eq!(style_range, Some(1..2));
+    };
+
+    critical!(logger: logger, "msg");
+    check("C");
+    error!(logger: logger, "msg");
+    check("E");
+    warn!(logger: logger, "msg");
+    check("W");
+    info!(logger: logger, "msg");
+    check("I");
+    debug!(logger: logger, "msg");
+    check("D");
+    trace!(logger: logger, "msg");
+    check("T");
+}
+
+#[test]
+fn test_level_labels() {
+    fn level_labels() -> LevelLabels {
+        LevelLabels::new()
+            .label(Level::Critical, "FATAL")
+            .label(Level::Error, "ERROR")
+            .label(Level::Warn, "WARNING")
+            .label(Level::Info, "INFO")
+            .label(Level::Debug, "DEBUG")
+            .label(Level::Trace, "TRACE")
+    }
+
+    let sink = Arc::new(MockSink::new());
+    sink.set_formatter(Box::new(PatternFormatter::new(pattern!(
+        "[{^{$level_label}}] {payload}",
+        {$level_label} => level_labels,
+    ))));
+    let logger = Logger::builder()
+        .sink(sink.clone())
+        .level_filter(LevelFilter::All)
+        .build()
+        .unwrap();
+
+    let check = |expected_label: &str| {
+        let (msg, style_range) = sink.get_last_msg().unwrap();
+        assert_eq!(msg, format!("[{}] msg", expected_label));
+        assert_eq!(style_range, Some(1..1 + expected_label.len()));
+    };
+
+    critical!(logger: logger, "msg");
+    check("FATAL");
+    error!(logger: logger, "msg");
+    check("ERROR");
+    warn!(logger: logger, "msg");
+    check("WARNING");
+    info!(logger: logger, "msg");
+    check("INFO");
+    debug!(logger: logger, "msg");
+    check("DEBUG");
+    trace!(logger: logger, "msg");
+    check("TRACE");
+}
+
+#[test]
+fn test_truncated_payload() {
+    #[track_caller]
+    fn fmt(pattern: impl Pattern + Clone + 'static, payload: &str) -> String {
+        let sink = Arc::new(MockSink::new());
+        sink.set_formatter(Box::new(PatternFormatter::new(pattern)));
+        let logger = Logger::builder().sink(sink.clone()).build().unwrap();
+
+        info!(logger: logger, "{}", payload);
+        sink.get_last_msg().unwrap().0
+    }
+
+    assert_eq!(fmt(pattern!("{payload:trunc(5)}"), "hello world"), "hell…");
+    assert_eq!(fmt(pattern!("{payload:trunc(5)}"), "hello"), "hello");
+    assert_eq!(fmt(pattern!("{payload:trunc(80)}"), "hello"), "hello");
+    assert_eq!(fmt(pattern!("{payload:trunc(5)}"), ""), "");
+    assert_eq!(fmt(pattern!("{payload:trunc(1)}"), "hello"), "…");
+    assert_eq!(fmt(pattern!("[{payload:trunc(0)}]"), "hello"), "[]");
+    assert_eq!(fmt(pattern!("[{payload:trunc(0)}]"), ""), "[]");
+
+    // Counted in characters, never splits a multibyte character
+    assert_eq!(fmt(pattern!("{payload:trunc(3)}"), "日本語"), "日本語");
+    assert_eq!(fmt(pattern!("{payload:trunc(3)}"), "日本語です"), "日本…");
+    assert_eq!(fmt(pattern!("{payload:trunc(2)}"), "a😀b"), "a…");
+
+    // Only affects the rendering of that placeholder
+    assert_eq!(
+        fmt(pattern!("{payload:trunc(4)} | {payload}"), "hello"),
+        "hel… | hello"
+    );
+}
+
+#[test]
+fn test_hex_payload() {
+    let sink = Arc::new(MockSink::new());
+    sink.set_formatter(Box::new(PatternFormatter::new(pattern!(
+        "{payload:hex} | {payload}"
+    ))));
+    let logger = Logger::builder().sink(sink.clone()).build().unwrap();
+
+    info!(logger: logger, "ok");
+    assert_eq!(sink.get_last_msg().unwrap().0, "6f6b | ok");
+
+    // Raw bytes are written losslessly, while `{payload}` is lossy
+    log_bytes!(logger: logger, Level::Info, b"ok\xff\x00");
+    assert_eq!(sink.get_last_msg().unwrap().0, "6f6bff00 | ok\u{fffd}\u{0}");
+
+    log_bytes!(logger: logger, Level::Info, [0u8; 0]);
+    assert_eq!(sink.get_last_msg().unwrap().0, " | ");
+}
+
+#[test]
+fn test_force_lf_eol() {
+    let sink = Arc::new(MockSink::new());
+    sink.set_formatter(Box::new(
+        PatternFormatter::new(pattern!("{payload}{eol}{eol}end")).force_lf_eol(true),
+    ));
+    let logger = Logger::builder().sink(sink.clone()).build().unwrap();
+
+    // The same on all platforms
+    info!(logger: logger, "msg");
+    assert_eq!(sink.get_last_msg().unwrap().0, "msg\n\nend");
+
+    sink.set_formatter(Box::new(
+        PatternFormatter::new(pattern!("{payload}{eol}")).force_lf_eol(false),
+    ));
+    info!(logger: logger, "msg");
+    assert_eq!(
+        sink.get_last_msg().unwrap().0,
+        if cfg!(windows) { "msg\r\n" } else { "msg\n" }
+    );
+}
+
+#[test]
+fn test_fields() {
+    #[track_caller]
+    fn fmt(pattern: impl Pattern + Clone + 'static) -> String {
+        let sink = Arc::new(MockSink::new());
+        sink.set_formatter(Box::new(PatternFormatter::new(pattern)));
+        let logger = Logger::builder()
+            .sink(sink.clone())
+            .with_field("service", "auth")
+            .with_field("user", "nobody")
+            .build()
+            .unwrap();
+
+        let _request = mdc::scoped("request_id", "42");
+        let _user = mdc::scoped("user", "bob");
+        info!(logger: logger, kv: { user = "alice", note = "a b" }, "login");
+        sink.get_last_msg().unwrap().0
+    }
+
+    assert_eq!(
+        fmt(pattern!("{fields}")),
+        r#"service=auth request_id=42 user=alice note="a b""#
+    );
+    assert_eq!(
+        fmt(pattern!("{fields:all,logfmt}")),
+        fmt(pattern!("{fields}"))
+    );
+    // The MDC value of `user` is overridden by the log macro
+    assert_eq!(fmt(pattern!("[{fields:mdc}]")), "[request_id=42]");
+    assert_eq!(
+        fmt(pattern!("{fields:json}")),
+        r#"{"service":"auth","request_id":"42","user":"alice","note":"a b"}"#
+    );
+    assert_eq!(
+        fmt(pattern!("{fields:mdc, json}")),
+        r#"{"request_id":"42"}"#
+    );
+
+    // The MDC is restored when the guards are dropped
+    assert_eq!(mdc::get("request_id"), None);
+    assert_eq!(fmt(pattern!("{payload}")), "login");
+}
+
 #[track_caller]
 fn test_pattern<P, F>(pat: P, expect_formatted: F, expect_style_range: Option<Range<usize>>)
 where
@@ -372,6 +564,16 @@ fn test_builtin_patterns() {
         None as Option<&str>,
         vec![0..=i32::MAX as u64],
     );
+    check(
+        pattern!("{unix_timestamp:ms}"),
+        None as Option<&str>,
+        vec![0..=u64::MAX],
+    );
+    check(
+        pattern!("{unix_timestamp:ns}"),
+        None as Option<&str>,
+        vec![0..=u64::MAX],
+    );
 
     cfg_if! {
         if #[cfg(feature = "source-location")] {
@@ -408,9 +610,14 @@ fn test_builtin_patterns() {
 
     check(pattern!("{level}"), Some("info"), vec![]);
     check(pattern!("{level_short}"), Some("I"), vec![]);
+    check(pattern!("{level:upper}"), Some("INFO"), vec![]);
+    check(pattern!("{level:lower}"), Some("info"), vec![]);
+    check(pattern!("{level:short}"), Some("I"), vec![]);
     cfg_if! {
         if #[cfg(feature = "source-location")] {
             check(pattern!("{source}"), Some(format!("{}:000", file!())), vec![SOURCE_RANGE]);
+            check(pattern!("{source:full}"), Some(format!("{}:000", file!())), vec![SOURCE_RANGE]);
+            check(pattern!("{source:basename}"), Some("pattern.rs:000"), vec![SOURCE_RANGE]);
             check(pattern!("{file_name}"), Some("pattern.rs"), vec![]);
             check(pattern!("{file}"), Some(file!()), vec![]);
             check(pattern!("{line}"), Some("000"), vec![SOURCE_RANGE]);
@@ -418,6 +625,7 @@ fn test_builtin_patterns() {
             check(pattern!("{module_path}"), Some(module_path!()), vec![]);
         } else {
             check(pattern!("{source}"), Some(""), vec![]);
+            check(pattern!("{source:basename}"), Some(""), vec![]);
             check(pattern!("{file_name}"), Some(""), vec![]);
             check(pattern!("{file}"), Some(""), vec![]);
             check(pattern!("{line}"), Some(""), vec![]);
@@ -426,12 +634,73 @@ fn test_builtin_patterns() {
         }
     }
     check(pattern!("{logger}"), Some("logger-name"), vec![]);
+    check(pattern!("{logger:abbrev}"), Some("logger-name"), vec![]);
+    check(pattern!("{logger:abbrev(5)}"), Some("logger-name"), vec![]);
+    check(pattern!("{logger:short(2)}"), Some("logger-name"), vec![]);
     check(pattern!("{payload}"), Some("test payload"), vec![]);
     check(pattern!("{pid}"), None as Option<&str>, vec![OS_ID_RANGE]);
     check(pattern!("{tid}"), None as Option<&str>, vec![OS_ID_RANGE]);
     check(pattern!("{eol}"), Some("{eol}"), vec![]);
 }
 
+#[test]
+fn test_presets() {
+    #[track_caller]
+    fn check(
+        preset: Preset,
+        expansion: impl Pattern + Clone + 'static,
+        expected_style_range: bool,
+        expected_regex: &str,
+    ) {
+        let preset_sink = Arc::new(MockSink::new());
+        preset_sink.set_formatter(Box::new(PatternFormatter::preset(preset)));
+        let expansion_sink = Arc::new(MockSink::new());
+        expansion_sink.set_formatter(Box::new(PatternFormatter::new(expansion)));
+        let logger = Logger::builder()
+            .sink(preset_sink.clone())
+            .sink(expansion_sink.clone())
+            .name("logger-name")
+            .build()
+            .unwrap();
+
+        info!(logger: logger, "test payload");
+
+        let (msg, style_range) = preset_sink.get_last_msg().unwrap();
+        assert_eq!(
+            Some((msg.clone(), style_range.clone())),
+            expansion_sink.get_last_msg()
+        );
+        assert_eq!(style_range.is_some(), expected_style_range);
+        let regex = Regex::new(&format!(r"^{}\r?\n$", expected_regex)).unwrap();
+        assert!(regex.is_match(&msg), "{:?} does not match {:?}", msg, regex);
+    }
+
+    check(
+        Preset::Syslog,
+        pattern!("{month_name} {day} {time} {logger}[{pid}]: [{level}] {payload}{eol}"),
+        false,
+        r"[A-Z][a-z]{2} \d{2} \d{2}:\d{2}:\d{2} logger-name\[\d+\]: \[info\] test payload",
+    );
+    check(
+        Preset::Apache,
+        pattern!("[{weekday_name} {month_name} {day} {time}.{microsecond} {year}] [{logger}:{level}] [pid {pid}:tid {tid}] {payload}{eol}"),
+        false,
+        r"\[[A-Z][a-z]{2} [A-Z][a-z]{2} \d{2} \d{2}:\d{2}:\d{2}\.\d{6} \d{4}\] \[logger-name:info\] \[pid \d+:tid \d+\] test payload",
+    );
+    check(
+        Preset::Compact,
+        pattern!("{time} {^{level_short}} {payload}{eol}"),
+        true,
+        r"\d{2}:\d{2}:\d{2} I test payload",
+    );
+    check(
+        Preset::Verbose,
+        pattern!("[{date} {time}.{microsecond} {tz_offset}] [{logger}] [{^{level}}] [pid {pid} tid {tid}] [{module_path}, {source}] {payload}{eol}"),
+        true,
+        r"\[\d{4}-\d{2}-\d{2} \d{2}:\d{2}:\d{2}\.\d{6} [+-]\d{2}:\d{2}\] \[logger-name\] \[info\] \[pid \d+ tid \d+\] \[.*, .*\] test payload",
+    );
+}
+
 #[cfg(feature = "multi-thread")]
 #[test]
 fn test_different_context_thread() {
@@ -0,0 +1,120 @@
+#![cfg(unix)]
+
+use std::{
+    env, fs,
+    io::{BufRead, BufReader},
+    os::unix::process::ExitStatusExt,
+    path::PathBuf,
+    process::{Command, Stdio},
+    sync::Arc,
+    thread,
+    time::Duration,
+};
+
+use spdlog::{prelude::*, sink::FileSink, Signal};
+
+#[must_use]
+fn log_dir_path() -> PathBuf {
+    env::current_exe()
+        .unwrap()
+        .parent()
+        .unwrap()
+        .join("dev/test_logs/reopen_on_signal")
+}
+
+#[test]
+fn reopen_on_sighup() {
+    let path = log_dir_path().join("app.log");
+    let rotated_path = log_dir_path().join("app.log.1");
+    let _ = fs::remove_file(&rotated_path);
+
+    let sink = Arc::new(
+        FileSink::builder()
+            .path(&path)
+            .truncate(true)
+            .build()
+            .unwrap(),
+    );
+    spdlog::install_reopen_on_signal(Signal::Hangup, std::slice::from_ref(&sink)).unwrap();
+    let logger = Logger::builder().sink(sink).build().unwrap();
+
+    info!(logger: logger, "before rotation");
+    fs::rename(&path, &rotated_path).unwrap();
+
+    // What `logrotate` does in `postrotate`
+    let status = Command::new("kill")
+        .args(["-HUP", &std::process::id().to_string()])
+        .status()
+        .unwrap();
+    assert!(status.success());
+    thread::sleep(Duration::from_millis(500));
+
+    info!(logger: logger, "after rotation");
+    logger.flush();
+
+    let rotated = fs::read_to_string(&rotated_path).unwrap();
+    assert!(rotated.contains("before rotation"));
+    assert!(!rotated.contains("after rotation"));
+    let new = fs::read_to_string(&path).unwrap();
+    assert!(new.contains("after rotation"));
+    assert!(!new.contains("before rotation"));
+}
+
+const CHILD_ENV: &str = "SPDLOG_RS_TEST_REOPEN_ON_SIGNAL_CHILD";
+const CHILD_READY: &str = "SPDLOG_RS_TEST_CHILD_READY";
+
+fn run_child() {
+    let sink = Arc::new(
+        FileSink::builder()
+            .path(log_dir_path().join("dropped.log"))
+            .truncate(true)
+            .build()
+            .unwrap(),
+    );
+    spdlog::install_reopen_on_signal(Signal::Hangup, &[sink]).unwrap();
+    // The sink is dropped here
+
+    println!("{}", CHILD_READY);
+
+    loop {
+        thread::sleep(Duration::from_secs(1));
+    }
+}
+
+#[test]
+fn default_action_after_sinks_dropped() {
+    if env::var_os(CHILD_ENV).is_some() {
+        run_child();
+        return;
+    }
+
+    let mut child = Command::new(env::current_exe().unwrap())
+        .args([
+            "--exact",
+            "default_action_after_sinks_dropped",
+            "--nocapture",
+            "--test-threads=1",
+        ])
+        .env(CHILD_ENV, "1")
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    let stdout = BufReader::new(child.stdout.take().unwrap());
+    for line in stdout.lines() {
+        // the test harness may print something before it on the same line
+        if line.unwrap().ends_with(CHILD_READY) {
+            break;
+        }
+    }
+
+    let status = Command::new("kill")
+        .args(["-HUP", &child.id().to_string()])
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    // Terminated by `SIGHUP` instead of being swallowed
+    let status = child.wait().unwrap();
+    assert_eq!(status.signal(), Some(1));
+}
@@ -0,0 +1,118 @@
+#![cfg(feature = "multi-thread")]
+
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+use spdlog::{
+    formatter::{FmtExtraInfo, Formatter},
+    prelude::*,
+    sink::{AsyncBatchSink, AsyncPoolSink, Sink},
+    ErrorHandler, Record, StringBuf,
+};
+
+// Counts written lines, slowly enough for records to be queued.
+#[derive(Default)]
+struct SlowSink {
+    lines: AtomicUsize,
+    flushes: AtomicUsize,
+}
+
+impl SlowSink {
+    fn lines(&self) -> usize {
+        self.lines.load(Ordering::SeqCst)
+    }
+
+    fn flushes(&self) -> usize {
+        self.flushes.load(Ordering::SeqCst)
+    }
+}
+
+impl Sink for SlowSink {
+    fn log(&self, _record: &Record) -> spdlog::Result<()> {
+        thread::sleep(Duration::from_millis(2));
+        self.lines.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+
+    fn write_formatted(
+        &self,
+        _record: &Record,
+        formatted: &StringBuf,
+        _extra_info: &FmtExtraInfo,
+    ) -> spdlog::Result<()> {
+        thread::sleep(Duration::from_millis(2));
+        self.lines
+            .fetch_add(formatted.lines().count(), Ordering::SeqCst);
+        Ok(())
+    }
+
+    fn flush(&self) -> spdlog::Result<()> {
+        self.flushes.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+
+    fn level_filter(&self) -> LevelFilter {
+        LevelFilter::All
+    }
+
+    fn set_level_filter(&self, _level_filter: LevelFilter) {}
+
+    fn set_formatter(&self, _formatter: Box<dyn Formatter>) {}
+
+    fn set_error_handler(&self, _handler: Option<ErrorHandler>) {}
+}
+
+#[test]
+fn shutdown_drains_async_sinks() {
+    // Safe to call when nothing asynchronous exists
+    spdlog::shutdown();
+
+    let pool_sub_sink = Arc::new(SlowSink::default());
+    let batch_sub_sink = Arc::new(SlowSink::default());
+
+    let pool_sink = AsyncPoolSink::builder()
+        .sink(pool_sub_sink.clone())
+        .build()
+        .unwrap();
+    let batch_sink = AsyncBatchSink::builder()
+        .sink(batch_sub_sink.clone())
+        .max_batch(10)
+        .max_delay(Duration::from_secs(60))
+        .build()
+        .unwrap();
+    spdlog::set_default_logger(Arc::new(
+        Logger::builder()
+            .sink(Arc::new(pool_sink))
+            .sink(Arc::new(batch_sink))
+            .build()
+            .unwrap(),
+    ));
+
+    for i in 0..105 {
+        info!("record {}", i);
+    }
+    assert!(pool_sub_sink.lines() < 105);
+    assert!(batch_sub_sink.lines() < 105);
+
+    spdlog::shutdown();
+    assert_eq!(pool_sub_sink.lines(), 105);
+    assert_eq!(batch_sub_sink.lines(), 105);
+    assert!(pool_sub_sink.flushes() >= 1);
+    assert!(batch_sub_sink.flushes() >= 1);
+
+    // Idempotent
+    spdlog::shutdown();
+    assert_eq!(pool_sub_sink.lines(), 105);
+    assert_eq!(batch_sub_sink.lines(), 105);
+
+    // Logging after shutdown is processed synchronously
+    info!("after shutdown");
+    assert_eq!(pool_sub_sink.lines(), 106);
+    assert_eq!(batch_sub_sink.lines(), 106);
+}